@@ -0,0 +1,13 @@
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+// x: scanline count, y: scanline darkness, z: vignette darkness
+@group(2) @binding(0) var<uniform> settings: vec4<f32>;
+
+// Only darkens what is under it, so the overlay never hides anything outright
+@fragment
+fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+    let scanline = step(0.5, fract(mesh.uv.y * settings.x)) * settings.y;
+    let from_center = length(mesh.uv - vec2<f32>(0.5, 0.5));
+    let vignette = smoothstep(0.35, 0.75, from_center) * settings.z;
+    return vec4<f32>(0.0, 0.0, 0.0, clamp(scanline + vignette, 0.0, 1.0));
+}
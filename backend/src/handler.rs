@@ -1,24 +1,134 @@
-use rocket::{futures::StreamExt, State};
+use rocket::{futures::StreamExt, http::ContentType, State};
 use rocket_ws::{Channel, WebSocket};
+use shooting_game_shared::{is_compatible, sanitize_name, Encoding};
 
-use crate::message::ClientMessageHandler;
+use crate::history::{SharedHistory, DEFAULT_MATCH_LIMIT};
+use crate::matchmaking::{Matchmaker, SharedMatchmaker};
+use crate::message::{ClientMessageHandler, RateLimit, RateLimiter, Receiver, Sender};
+use crate::metrics::SharedMetrics;
 use crate::state::SharedGameState;
 
-// TODO: need to handle user diconnect
-#[rocket::get("/game")]
-pub async fn ws_handler<'a>(ws: WebSocket, game_state: &'a State<SharedGameState>) -> Channel<'a> {
+#[rocket::get("/game?<session>&<room>&<protocol>&<name>")]
+pub async fn ws_handler<'a>(
+    ws: WebSocket,
+    session: Option<u64>,
+    room: Option<u32>,
+    protocol: Option<u8>,
+    name: Option<&'a str>,
+    matchmaker: &'a State<SharedMatchmaker>,
+    metrics: &'a State<SharedMetrics>,
+) -> Channel<'a> {
     ws.channel(move |stream| {
         Box::pin(async move {
-            let (sender, receiver) = stream.split();
+            let _connected_client = metrics.track_client();
+            let (sink, mut receiver) = stream.split();
+            // No version at all comes from clients older than the handshake itself
+            let client_version = protocol.unwrap_or_default();
+            let mut sender =
+                Sender::new(sink, Encoding::negotiate(protocol), metrics.inner().clone());
+            if !is_compatible(client_version) {
+                sender.reject_version().await;
+                return Ok(());
+            }
 
-            // Add Sender to ServerMessageHandler
-            let player_tag = game_state.write().await.new_player(sender).await;
+            let locked_matchmaker = matchmaker.write().await;
+            if let Some(session_token) = session {
+                match locked_matchmaker.resume_player(session_token, sender).await {
+                    Ok((game_state, player_tag, connection_id)) => {
+                        drop(locked_matchmaker);
+                        handle_player(
+                            player_tag,
+                            connection_id,
+                            receiver,
+                            game_state,
+                            metrics.inner().clone(),
+                        )
+                        .await;
+                        return Ok(());
+                    }
+                    // Unknown or expired session joins as a new connection
+                    Err(returned_sender) => sender = returned_sender,
+                }
+            }
 
-            // Add Receiver to ClientMessageHandler
-            let message_handler = ClientMessageHandler::new(player_tag, game_state.inner().clone());
-            message_handler.handle_messages(receiver).await;
+            // Asking for a room that is already playing watches it instead
+            if let Some(game_state) = room.and_then(|room_id| locked_matchmaker.room(room_id)) {
+                let mut locked_state = game_state.write().await;
+                if locked_state.is_full().await {
+                    drop(locked_matchmaker);
+                    let spectator_tag = locked_state.new_spectator(sender).await;
+                    drop(locked_state);
 
+                    // Spectators are read-only, ignore everything until they leave or flood
+                    let mut rate_limiter = RateLimiter::default();
+                    while receiver.next().await.is_some() {
+                        if rate_limiter.check() == RateLimit::Disconnect {
+                            log::warn!("Spectator {spectator_tag} disconnected for flooding");
+                            break;
+                        }
+                    }
+                    game_state
+                        .write()
+                        .await
+                        .remove_spectator(spectator_tag)
+                        .await;
+                    return Ok(());
+                }
+            }
+
+            // Whatever the client sent is cleaned up again, an empty name counts as none
+            let name = name.map(sanitize_name).filter(|name| !name.is_empty());
+            let matchmaker_clone = matchmaker.inner().clone();
+            let (game_state, player_tag, connection_id) =
+                Matchmaker::join(locked_matchmaker, sender, name, room, matchmaker_clone).await;
+
+            handle_player(
+                player_tag,
+                connection_id,
+                receiver,
+                game_state,
+                metrics.inner().clone(),
+            )
+            .await;
             Ok(())
         })
     })
 }
+
+async fn handle_player(
+    player_tag: u8,
+    connection_id: u32,
+    receiver: Receiver,
+    game_state: SharedGameState,
+    metrics: SharedMetrics,
+) {
+    // Add Receiver to ClientMessageHandler
+    let message_handler = ClientMessageHandler::new(player_tag, game_state.clone(), metrics);
+    message_handler.handle_messages(receiver).await;
+
+    game_state
+        .write()
+        .await
+        .player_left(player_tag, connection_id)
+        .await;
+}
+
+#[rocket::get("/")]
+pub async fn metrics_handler(
+    matchmaker: &State<SharedMatchmaker>,
+    metrics: &State<SharedMetrics>,
+) -> (ContentType, String) {
+    let active_rooms = matchmaker.read().await.room_count();
+    (ContentType::Plain, metrics.render(active_rooms))
+}
+
+// Newest first
+#[rocket::get("/?<limit>")]
+pub async fn matches_handler(
+    limit: Option<usize>,
+    history: &State<SharedHistory>,
+) -> (ContentType, String) {
+    let matches = history.recent(limit.unwrap_or(DEFAULT_MATCH_LIMIT)).await;
+    let body = serde_json::to_string(&matches).unwrap_or_else(|_| "[]".to_string());
+    (ContentType::JSON, body)
+}
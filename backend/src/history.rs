@@ -0,0 +1,235 @@
+use rocket::tokio::task::spawn_blocking;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use shooting_game_shared::RoomClosedReason;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub type SharedHistory = Arc<MatchHistory>;
+
+pub const DEFAULT_HISTORY_PATH: &str = "match_history.sqlite3";
+// Served on `/matches` when no limit is asked for
+pub const DEFAULT_MATCH_LIMIT: usize = 20;
+
+// Why a match stopped before anyone won or everyone was out
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DisconnectReason {
+    // A dropped player didn't come back within the grace period
+    ReconnectTimeout,
+    Idle,
+    ServerShutdown,
+}
+
+impl From<RoomClosedReason> for DisconnectReason {
+    fn from(reason: RoomClosedReason) -> Self {
+        match reason {
+            RoomClosedReason::Idle => DisconnectReason::Idle,
+            RoomClosedReason::ServerShutdown => DisconnectReason::ServerShutdown,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PlayerRecord {
+    pub tag: u8,
+    pub name: Option<String>,
+    pub score: u32,
+    pub bot: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MatchRecord {
+    // Seconds since the unix epoch
+    pub ended_at: u64,
+    pub duration_ms: u64,
+    pub players: Vec<PlayerRecord>,
+    // None for a match that was played to the end
+    pub disconnect_reason: Option<DisconnectReason>,
+}
+
+impl MatchRecord {
+    pub fn new(
+        players: Vec<PlayerRecord>,
+        duration: Duration,
+        disconnect_reason: Option<DisconnectReason>,
+    ) -> Self {
+        let ended_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since_epoch| since_epoch.as_secs());
+        Self {
+            ended_at,
+            duration_ms: duration.as_millis() as u64,
+            players,
+            disconnect_reason,
+        }
+    }
+}
+
+// Every finished match goes into a SQLite database, a `matches` row with one
+// `match_players` row per player, so the history outlives the server and can be queried
+pub struct MatchHistory {
+    connection: Arc<Mutex<Connection>>,
+}
+
+// Kept in memory only, gone with the server
+impl Default for MatchHistory {
+    fn default() -> Self {
+        let connection =
+            Connection::open_in_memory().expect("Failed to open match history in memory");
+        create_tables(&connection).expect("Failed to create match history tables");
+        Self {
+            connection: Arc::new(Mutex::new(connection)),
+        }
+    }
+}
+
+impl MatchHistory {
+    // Falls back to a database in memory when the file can't be opened
+    pub fn open(path: PathBuf) -> Self {
+        match Connection::open(&path)
+            .and_then(|connection| create_tables(&connection).map(|_| connection))
+        {
+            Ok(connection) => Self {
+                connection: Arc::new(Mutex::new(connection)),
+            },
+            Err(e) => {
+                println!("Failed to open match history {}: {e}", path.display());
+                Self::default()
+            }
+        }
+    }
+
+    // SQLite blocks on the disk, so it runs on the blocking pool and never stalls a room's tick
+    pub async fn record(&self, record: MatchRecord) {
+        let connection = self.connection.clone();
+        let result =
+            spawn_blocking(move || insert_match(&mut connection.lock().unwrap(), &record)).await;
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => println!("Failed to save match history: {e}"),
+            Err(e) => println!("Failed to save match history: {e}"),
+        }
+    }
+
+    // Newest first
+    pub async fn recent(&self, limit: usize) -> Vec<MatchRecord> {
+        let connection = self.connection.clone();
+        let result =
+            spawn_blocking(move || select_recent(&connection.lock().unwrap(), limit)).await;
+        match result {
+            Ok(Ok(records)) => records,
+            Ok(Err(e)) => {
+                println!("Failed to read match history: {e}");
+                Vec::new()
+            }
+            Err(e) => {
+                println!("Failed to read match history: {e}");
+                Vec::new()
+            }
+        }
+    }
+}
+
+impl DisconnectReason {
+    fn as_str(self) -> &'static str {
+        match self {
+            DisconnectReason::ReconnectTimeout => "reconnect_timeout",
+            DisconnectReason::Idle => "idle",
+            DisconnectReason::ServerShutdown => "server_shutdown",
+        }
+    }
+
+    fn from_str(reason: &str) -> Option<Self> {
+        match reason {
+            "reconnect_timeout" => Some(DisconnectReason::ReconnectTimeout),
+            "idle" => Some(DisconnectReason::Idle),
+            "server_shutdown" => Some(DisconnectReason::ServerShutdown),
+            _ => None,
+        }
+    }
+}
+
+fn create_tables(connection: &Connection) -> rusqlite::Result<()> {
+    connection.execute_batch(
+        "CREATE TABLE IF NOT EXISTS matches (
+            id INTEGER PRIMARY KEY,
+            ended_at INTEGER NOT NULL,
+            duration_ms INTEGER NOT NULL,
+            disconnect_reason TEXT
+        );
+        CREATE INDEX IF NOT EXISTS matches_ended_at ON matches (ended_at);
+        CREATE TABLE IF NOT EXISTS match_players (
+            match_id INTEGER NOT NULL REFERENCES matches (id),
+            tag INTEGER NOT NULL,
+            name TEXT,
+            score INTEGER NOT NULL,
+            bot INTEGER NOT NULL,
+            PRIMARY KEY (match_id, tag)
+        );",
+    )
+}
+
+fn insert_match(connection: &mut Connection, record: &MatchRecord) -> rusqlite::Result<()> {
+    let transaction = connection.transaction()?;
+    transaction.execute(
+        "INSERT INTO matches (ended_at, duration_ms, disconnect_reason) VALUES (?1, ?2, ?3)",
+        params![
+            record.ended_at as i64,
+            record.duration_ms as i64,
+            record.disconnect_reason.map(DisconnectReason::as_str),
+        ],
+    )?;
+    let match_id = transaction.last_insert_rowid();
+    for player in &record.players {
+        transaction.execute(
+            "INSERT INTO match_players (match_id, tag, name, score, bot) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![match_id, player.tag, player.name, player.score, player.bot],
+        )?;
+    }
+    transaction.commit()
+}
+
+fn select_recent(connection: &Connection, limit: usize) -> rusqlite::Result<Vec<MatchRecord>> {
+    let mut matches_statement = connection.prepare(
+        "SELECT id, ended_at, duration_ms, disconnect_reason FROM matches
+        ORDER BY ended_at DESC, id DESC LIMIT ?1",
+    )?;
+    let mut players_statement = connection.prepare(
+        "SELECT tag, name, score, bot FROM match_players WHERE match_id = ?1 ORDER BY tag",
+    )?;
+    // SQLite limits are signed, anything past that is everything anyway
+    let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+    let rows = matches_statement.query_map([limit], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, i64>(2)?,
+            row.get::<_, Option<String>>(3)?,
+        ))
+    })?;
+    let mut matches = Vec::new();
+    for row in rows {
+        let (match_id, ended_at, duration_ms, disconnect_reason) = row?;
+        let players = players_statement
+            .query_map([match_id], |row| {
+                Ok(PlayerRecord {
+                    tag: row.get(0)?,
+                    name: row.get(1)?,
+                    score: row.get(2)?,
+                    bot: row.get(3)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        matches.push(MatchRecord {
+            ended_at: ended_at as u64,
+            duration_ms: duration_ms as u64,
+            players,
+            disconnect_reason: disconnect_reason
+                .as_deref()
+                .and_then(DisconnectReason::from_str),
+        });
+    }
+    Ok(matches)
+}
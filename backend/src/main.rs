@@ -1,38 +1,61 @@
-use rocket::tokio::time::sleep;
-use rocket::tokio::{spawn, sync::RwLock};
-use state::{Cycle, SharedGameState};
+use history::{MatchHistory, SharedHistory, DEFAULT_HISTORY_PATH};
+use matchmaking::{Matchmaker, SharedMatchmaker, DEFAULT_TICK_RATE};
+use metrics::{Metrics, SharedMetrics};
+use rocket::fairing::AdHoc;
+use rocket::tokio::sync::RwLock;
+use rocket::{Build, Rocket};
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
 
 mod handler;
+mod history;
+mod matchmaking;
 mod message;
+mod metrics;
 mod state;
+#[cfg(test)]
+mod tests;
 
 #[rocket::main]
+#[allow(clippy::result_large_err)]
 async fn main() -> Result<(), rocket::Error> {
-    let game_state = Arc::new(RwLock::new(state::GameState::default()));
-
-    let game_state_clone = Arc::clone(&game_state);
-    spawn(game_loop(game_state_clone));
-
-    rocket::build()
-        .manage(game_state)
-        .mount("/ws", rocket::routes![handler::ws_handler])
-        .launch()
-        .await?;
-
+    server(rocket::build()).launch().await?;
     Ok(())
 }
 
-async fn game_loop(game_state: SharedGameState) {
-    loop {
-        let mut locked_state = game_state.write().await;
-        let cycle = locked_state.check_cycle().await;
-        drop(locked_state);
-        let sleep_millis = match cycle {
-            Cycle::Playing => 20,
-            _ => 500,
-        };
-        sleep(Duration::from_millis(sleep_millis)).await
-    }
+// Everything but the launch, so tests can run the same server on their own config
+fn server(rocket: Rocket<Build>) -> Rocket<Build> {
+    // Set with `tick_rate` in Rocket.toml or the ROCKET_TICK_RATE env var
+    let tick_rate = rocket
+        .figment()
+        .extract_inner::<u32>("tick_rate")
+        .unwrap_or(DEFAULT_TICK_RATE);
+    // Set with `history_path` in Rocket.toml or the ROCKET_HISTORY_PATH env var
+    let history_path = rocket
+        .figment()
+        .extract_inner::<PathBuf>("history_path")
+        .unwrap_or_else(|_| PathBuf::from(DEFAULT_HISTORY_PATH));
+    let metrics: SharedMetrics = Arc::new(Metrics::default());
+    let history: SharedHistory = Arc::new(MatchHistory::open(history_path));
+    let matchmaker = Arc::new(RwLock::new(Matchmaker::new(
+        tick_rate,
+        metrics.clone(),
+        history.clone(),
+    )));
+
+    rocket
+        .manage(matchmaker)
+        .manage(metrics)
+        .manage(history)
+        // Rocket starts shutting down on Ctrl-C and SIGTERM, rooms are closed before the connections go
+        .attach(AdHoc::on_shutdown("Close rooms", |rocket| {
+            Box::pin(async move {
+                if let Some(matchmaker) = rocket.state::<SharedMatchmaker>() {
+                    matchmaker.write().await.close_all_rooms().await;
+                }
+            })
+        }))
+        .mount("/ws", rocket::routes![handler::ws_handler])
+        .mount("/metrics", rocket::routes![handler::metrics_handler])
+        .mount("/matches", rocket::routes![handler::matches_handler])
 }
@@ -0,0 +1,204 @@
+use rocket::tokio::spawn;
+use rocket::tokio::sync::{RwLock, RwLockWriteGuard};
+use rocket::tokio::time::sleep;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use shooting_game_shared::RoomClosedReason;
+
+use crate::history::SharedHistory;
+use crate::message::Sender;
+use crate::metrics::SharedMetrics;
+use crate::state::{Cycle, GameState, SharedGameState};
+
+pub type SharedMatchmaker = Arc<RwLock<Matchmaker>>;
+
+pub const DEFAULT_TICK_RATE: u32 = 30;
+const IDLE_TICK: Duration = Duration::from_millis(500);
+
+pub struct Matchmaker {
+    rooms: HashMap<u32, SharedGameState>,
+    // Rooms still waiting for players, oldest first
+    queue: VecDeque<u32>,
+    next_room_id: u32,
+    tick: Duration,
+    metrics: SharedMetrics,
+    history: SharedHistory,
+}
+
+impl Matchmaker {
+    pub fn new(tick_rate: u32, metrics: SharedMetrics, history: SharedHistory) -> Self {
+        Self {
+            rooms: HashMap::new(),
+            queue: VecDeque::new(),
+            next_room_id: 0,
+            tick: Duration::from_secs(1) / tick_rate.max(1),
+            metrics,
+            history,
+        }
+    }
+
+    // Puts the player in the requested room, else the oldest waiting one or a new one
+    // The matchmaker is unlocked before anything is sent, a slow socket mustn't hold up other joins
+    pub async fn join(
+        mut locked_matchmaker: RwLockWriteGuard<'_, Matchmaker>,
+        sender: Sender,
+        name: Option<String>,
+        requested_room_id: Option<u32>,
+        matchmaker: SharedMatchmaker,
+    ) -> (SharedGameState, u8, u32) {
+        let requested_room_id =
+            requested_room_id.filter(|room_id| locked_matchmaker.rooms.contains_key(room_id));
+        let room_id = match requested_room_id {
+            Some(room_id) => room_id,
+            None => {
+                locked_matchmaker.prune_queue().await;
+                match locked_matchmaker.queue.front() {
+                    Some(room_id) => *room_id,
+                    None => locked_matchmaker.create_room(matchmaker.clone()),
+                }
+            }
+        };
+        let game_state = locked_matchmaker.rooms[&room_id].clone();
+        let mut locked_state = game_state.write().await;
+        let (player_tag, session_token, connection_id) = locked_state.new_player(name).await;
+        let is_full = locked_state.is_full().await;
+        if is_full {
+            locked_matchmaker
+                .queue
+                .retain(|queued_id| *queued_id != room_id);
+        }
+        // The room stays locked so it can't move on before the player hears about it
+        drop(locked_matchmaker);
+        locked_state
+            .connect_player(player_tag, session_token, sender)
+            .await;
+        if !is_full {
+            locked_state.room_created(player_tag, room_id).await;
+        }
+        // A player lost while connecting frees the slot again
+        let requeue = is_full && !locked_state.is_full().await;
+        drop(locked_state);
+        if requeue {
+            matchmaker.write().await.queue.push_front(room_id);
+        }
+        (game_state, player_tag, connection_id)
+    }
+
+    pub fn room_count(&self) -> usize {
+        self.rooms.len()
+    }
+
+    pub fn room(&self, room_id: u32) -> Option<SharedGameState> {
+        self.rooms.get(&room_id).cloned()
+    }
+
+    // Looks through every room for the session, the sender is returned when none knows it
+    pub async fn resume_player(
+        &self,
+        session_token: u64,
+        mut sender: Sender,
+    ) -> Result<(SharedGameState, u8, u32), Sender> {
+        for game_state in self.rooms.values() {
+            let mut locked_state = game_state.write().await;
+            match locked_state.resume_player(session_token, sender).await {
+                Ok((player_tag, connection_id)) => {
+                    drop(locked_state);
+                    return Ok((game_state.clone(), player_tag, connection_id));
+                }
+                Err(returned_sender) => sender = returned_sender,
+            }
+        }
+        Err(sender)
+    }
+
+    // Every room is told it is closing, their loops stop once they find it gone
+    pub async fn close_all_rooms(&mut self) {
+        for (room_id, game_state) in self.rooms.iter() {
+            game_state
+                .write()
+                .await
+                .close_room(RoomClosedReason::ServerShutdown)
+                .await;
+            self.metrics.room_closed(*room_id);
+        }
+        self.rooms.clear();
+        self.queue.clear();
+    }
+
+    // Private
+    fn create_room(&mut self, matchmaker: SharedMatchmaker) -> u32 {
+        let room_id = self.next_room_id;
+        self.next_room_id = self.next_room_id.wrapping_add(1);
+        let game_state = Arc::new(RwLock::new(GameState::new(self.history.clone())));
+        self.rooms.insert(room_id, game_state.clone());
+        self.queue.push_back(room_id);
+        spawn(room_loop(
+            room_id,
+            game_state,
+            matchmaker,
+            self.tick,
+            self.metrics.clone(),
+        ));
+        room_id
+    }
+
+    async fn prune_queue(&mut self) {
+        while let Some(room_id) = self.queue.front() {
+            let Some(game_state) = self.rooms.get(room_id) else {
+                self.queue.pop_front();
+                continue;
+            };
+            if !game_state.read().await.is_full().await {
+                return;
+            }
+            self.queue.pop_front();
+        }
+    }
+
+    async fn close_idle_room(&mut self, room_id: u32) -> bool {
+        let Some(game_state) = self.rooms.get(&room_id) else {
+            return true;
+        };
+        if !game_state.read().await.is_idle().await {
+            return false;
+        }
+        self.rooms.remove(&room_id);
+        self.queue.retain(|queued_id| *queued_id != room_id);
+        true
+    }
+}
+
+// Every room runs its own cycle until it is left empty
+async fn room_loop(
+    room_id: u32,
+    game_state: SharedGameState,
+    matchmaker: SharedMatchmaker,
+    tick: Duration,
+    metrics: SharedMetrics,
+) {
+    loop {
+        let tick_started = Instant::now();
+        let mut locked_state = game_state.write().await;
+        if locked_state.is_abandoned().await {
+            println!("Room {room_id} closed after being idle");
+            locked_state.close_room(RoomClosedReason::Idle).await;
+        }
+        let cycle = locked_state.check_cycle().await;
+        let is_idle = locked_state.is_idle().await;
+        drop(locked_state);
+        // Checked again under the matchmaker lock so no one joins between the check and the removal
+        if is_idle && matchmaker.write().await.close_idle_room(room_id).await {
+            metrics.room_closed(room_id);
+            return;
+        }
+        metrics.room_ticked(room_id, tick_started.elapsed());
+        // Simulation time spent in the tick comes out of the sleep to keep the rate steady
+        let interval = match cycle {
+            Cycle::Matching | Cycle::Result => IDLE_TICK,
+            Cycle::Ready | Cycle::Playing => tick,
+        };
+        sleep(interval.saturating_sub(tick_started.elapsed())).await
+    }
+}
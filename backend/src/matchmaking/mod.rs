@@ -0,0 +1,3 @@
+mod matchmaker;
+
+pub use matchmaker::{Matchmaker, SharedMatchmaker, DEFAULT_TICK_RATE};
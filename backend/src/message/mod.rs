@@ -1,5 +1,7 @@
+mod rate_limiter;
 mod receiver;
 mod sender;
 
-pub use receiver::ClientMessageHandler;
+pub use rate_limiter::{RateLimit, RateLimiter};
+pub use receiver::{ClientMessageHandler, Receiver};
 pub use sender::{Sender, ServerMessageHandler};
@@ -0,0 +1,47 @@
+use std::time::{Duration, Instant};
+
+const WINDOW: Duration = Duration::from_secs(1);
+// A client sends one update per fixed tick, 64 a second, plus the odd intent
+const MAX_MESSAGES_PER_WINDOW: u32 = 150;
+// Far past anything a real client sends, the connection is dropped
+const DISCONNECT_MESSAGES_PER_WINDOW: u32 = MAX_MESSAGES_PER_WINDOW * 4;
+
+#[derive(PartialEq)]
+pub enum RateLimit {
+    Allowed,
+    // First message over the limit in this window, worth logging once
+    Exceeded,
+    Dropped,
+    Disconnect,
+}
+
+// Counts messages in fixed one second windows for a single connection
+pub struct RateLimiter {
+    window_started: Instant,
+    count: u32,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self {
+            window_started: Instant::now(),
+            count: 0,
+        }
+    }
+}
+
+impl RateLimiter {
+    pub fn check(&mut self) -> RateLimit {
+        if self.window_started.elapsed() >= WINDOW {
+            self.window_started = Instant::now();
+            self.count = 0;
+        }
+        self.count += 1;
+        match self.count {
+            count if count > DISCONNECT_MESSAGES_PER_WINDOW => RateLimit::Disconnect,
+            count if count == MAX_MESSAGES_PER_WINDOW + 1 => RateLimit::Exceeded,
+            count if count > MAX_MESSAGES_PER_WINDOW => RateLimit::Dropped,
+            _ => RateLimit::Allowed,
+        }
+    }
+}
@@ -1,7 +1,11 @@
+use crate::metrics::SharedMetrics;
 use crate::state::SharedGameState;
+
+use super::{RateLimit, RateLimiter};
 use rocket::futures::stream::SplitStream;
 use rocket::futures::StreamExt;
 use rocket_ws::stream::DuplexStream;
+use rocket_ws::Message;
 use shooting_game_shared::ClientMessage;
 
 pub type Receiver = SplitStream<DuplexStream>;
@@ -9,45 +13,51 @@ pub type Receiver = SplitStream<DuplexStream>;
 pub struct ClientMessageHandler {
     player_tag: u8,
     shared_game_state: SharedGameState,
+    metrics: SharedMetrics,
 }
 
 impl ClientMessageHandler {
-    pub fn new(player_tag: u8, shared_game_state: SharedGameState) -> Self {
+    pub fn new(player_tag: u8, shared_game_state: SharedGameState, metrics: SharedMetrics) -> Self {
         Self {
             player_tag,
             shared_game_state,
+            metrics,
         }
     }
 
+    // Floods are dropped before they reach the shared state lock, the worst get disconnected
     pub async fn handle_messages(&self, mut receiver: Receiver) {
+        let mut rate_limiter = RateLimiter::default();
         while let Some(message) = receiver.next().await {
-            if let Ok(msg) = message {
-                if let Ok(client_msg) = serde_json::from_str::<ClientMessage>(&msg.to_string()) {
-                    self.handle_message(client_msg).await;
+            match rate_limiter.check() {
+                RateLimit::Allowed => {}
+                RateLimit::Exceeded => {
+                    log::warn!("Player {} is flooding, dropping messages", self.player_tag);
+                    continue;
+                }
+                RateLimit::Dropped => continue,
+                RateLimit::Disconnect => {
+                    log::warn!("Player {} disconnected for flooding", self.player_tag);
+                    return;
                 }
             }
+            let client_msg = match message {
+                Ok(Message::Binary(bytes)) => ClientMessage::from_binary(&bytes),
+                Ok(msg) => serde_json::from_str::<ClientMessage>(&msg.to_string()).ok(),
+                Err(_) => None,
+            };
+            if let Some(client_msg) = client_msg {
+                self.metrics.message_received(client_msg.kind());
+                self.handle_message(client_msg).await;
+            }
         }
     }
 
     async fn handle_message(&self, message: ClientMessage) {
         let mut game_state = self.shared_game_state.write().await;
-        match message {
-            ClientMessage::UpdatePlayerInfo { position, bullets } => {
-                game_state
-                    .update_player_info(self.player_tag, position, bullets)
-                    .await
-            }
-            ClientMessage::DamagedIntent { enemy_tag } => {
-                game_state.player_damaged(self.player_tag, enemy_tag).await;
-            }
-            ClientMessage::DestroyEnemyIntent {
-                bullet_tag,
-                enemy_tag,
-            } => {
-                game_state
-                    .destroy_enemy(self.player_tag, bullet_tag, enemy_tag)
-                    .await;
-            }
-        }
+        game_state.touch();
+        game_state
+            .handle_client_message(self.player_tag, message)
+            .await;
     }
 }
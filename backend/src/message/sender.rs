@@ -2,25 +2,170 @@ use rocket::{
     futures::{stream::SplitSink, SinkExt},
     tokio::sync::RwLock,
 };
+use rocket_ws::frame::{CloseCode, CloseFrame};
 use rocket_ws::{result::Error, stream::DuplexStream, Message};
-use shooting_game_shared::ServerMessage;
+use shooting_game_shared::{
+    incompatible_version_reason, BulletSnapshot, DownedSnapshot, EffectKind, Encoding,
+    EnemySnapshot, LobbyPlayer, OnlinePowerUp, Payload, PlayerSnapshot, RoomClosedReason,
+    ServerMessage, INCOMPATIBLE_VERSION_CLOSE_CODE,
+};
 use std::{collections::HashMap, sync::Arc};
 
-pub type Sender = SplitSink<DuplexStream, Message>;
+use crate::metrics::SharedMetrics;
+
+// A client sink which encodes every message the way that client negotiated
+pub struct Sender {
+    sink: SplitSink<DuplexStream, Message>,
+    encoding: Encoding,
+    metrics: SharedMetrics,
+}
+
+impl Sender {
+    pub fn new(
+        sink: SplitSink<DuplexStream, Message>,
+        encoding: Encoding,
+        metrics: SharedMetrics,
+    ) -> Self {
+        Self {
+            sink,
+            encoding,
+            metrics,
+        }
+    }
+
+    async fn send(&mut self, message: ServerMessage) -> Result<(), Error> {
+        self.metrics.message_sent(message.kind());
+        self.sink
+            .send(match message.encode(self.encoding) {
+                Payload::Text(text) => Message::Text(text),
+                Payload::Binary(bytes) => Message::Binary(bytes),
+            })
+            .await
+    }
+
+    // A close frame rather than a message, clients on other versions may not decode ours
+    pub async fn reject_version(mut self) {
+        let frame = CloseFrame {
+            code: CloseCode::Library(INCOMPATIBLE_VERSION_CLOSE_CODE),
+            reason: incompatible_version_reason().into(),
+        };
+        self.metrics.message_sent("IncompatibleVersion");
+        let _ = self.sink.send(Message::Close(Some(frame))).await;
+        let _ = self.close().await;
+    }
+
+    async fn close(&mut self) -> Result<(), Error> {
+        self.sink.close().await
+    }
+}
 
 #[derive(Default)]
-pub struct ServerMessageHandler(RwLock<HashMap<u8, Arc<RwLock<Sender>>>>);
+pub struct ServerMessageHandler {
+    senders: RwLock<HashMap<u8, Arc<RwLock<Sender>>>>,
+    spectators: RwLock<HashMap<u8, Arc<RwLock<Sender>>>>,
+    next_spectator_tag: RwLock<u8>,
+}
 
 impl ServerMessageHandler {
-    pub async fn add_sender(&self, player_tag: u8, sender: Sender) -> Result<(), (Error, u8)> {
-        let mut senders = self.0.write().await;
+    pub async fn add_sender(
+        &self,
+        player_tag: u8,
+        session_token: u64,
+        sender: Sender,
+    ) -> Result<(), (Error, u8)> {
+        let mut senders = self.senders.write().await;
         senders.insert(player_tag, Arc::new(RwLock::new(sender)));
         drop(senders);
 
-        self.send(player_tag, ServerMessage::Joined { player_tag })
+        self.send(
+            player_tag,
+            ServerMessage::Joined {
+                player_tag,
+                session_token,
+            },
+        )
+        .await
+    }
+
+    pub async fn room_created(&self, player_tag: u8, room_id: u32) -> Result<(), (Error, u8)> {
+        self.send(player_tag, ServerMessage::RoomCreated { room_id })
             .await
     }
 
+    // Keeps other senders open, used when a player drops mid-game
+    pub async fn remove_sender(&self, player_tag: u8) {
+        let mut senders = self.senders.write().await;
+        if let Some(sender) = senders.remove(&player_tag) {
+            let _ = sender.write().await.close().await;
+        }
+    }
+
+    pub async fn resume_state(
+        &self,
+        player_tag: u8,
+        score: u8,
+        health: u8,
+        enemies: Vec<EnemySnapshot>,
+        downed: Vec<DownedSnapshot>,
+        names: HashMap<u8, String>,
+    ) -> Result<(), (Error, u8)> {
+        self.send(player_tag, ServerMessage::PlayerNames { names })
+            .await?;
+        self.send(
+            player_tag,
+            ServerMessage::ResumeState {
+                score,
+                health,
+                enemies,
+                downed,
+            },
+        )
+        .await
+    }
+
+    pub async fn add_spectator(
+        &self,
+        sender: Sender,
+        game_started: bool,
+        names: HashMap<u8, String>,
+    ) -> u8 {
+        let mut next_spectator_tag = self.next_spectator_tag.write().await;
+        let spectator_tag = *next_spectator_tag;
+        *next_spectator_tag = next_spectator_tag.wrapping_add(1);
+        drop(next_spectator_tag);
+
+        let sender = Arc::new(RwLock::new(sender));
+        let mut locked_sender = sender.write().await;
+        let mut joined = locked_sender
+            .send(ServerMessage::SpectatorJoined { game_started })
+            .await;
+        if joined.is_ok() {
+            joined = locked_sender
+                .send(ServerMessage::PlayerNames { names })
+                .await;
+        }
+        drop(locked_sender);
+        if joined.is_ok() {
+            self.spectators.write().await.insert(spectator_tag, sender);
+        }
+        spectator_tag
+    }
+
+    pub async fn remove_spectator(&self, spectator_tag: u8) {
+        self.spectators.write().await.remove(&spectator_tag);
+    }
+
+    // Names go first so the roster can show them straight away, a sender that fails on them
+    // fails on the roster too so only the roster errors are returned
+    pub async fn lobby_state(
+        &self,
+        names: HashMap<u8, String>,
+        players: Vec<LobbyPlayer>,
+    ) -> Result<(), Vec<(Error, u8)>> {
+        let _ = self.send_all(ServerMessage::PlayerNames { names }).await;
+        self.send_all(ServerMessage::LobbyState { players }).await
+    }
+
     pub async fn game_ready(&self) -> Result<(), Vec<(Error, u8)>> {
         self.send_all(ServerMessage::GameReady).await
     }
@@ -29,41 +174,37 @@ impl ServerMessageHandler {
         self.send_all(ServerMessage::GameStart).await
     }
 
-    pub async fn game_over(&self) {
-        let _ = self.send_all(ServerMessage::GameOver).await;
+    pub async fn game_over(
+        &self,
+        scores: HashMap<u8, u32>,
+        winner: Option<u8>,
+    ) -> Result<(), Vec<(Error, u8)>> {
+        self.send_all(ServerMessage::GameOver { scores, winner })
+            .await
     }
 
     pub async fn game_interrupted(&self) {
         let _ = self.send_all(ServerMessage::GameInterrupted).await;
     }
 
-    pub async fn notice_others_position(
-        &self,
-        player_tag: u8,
-        position: (f32, f32),
-        bullets: Vec<(f32, f32)>,
-    ) -> Result<(), Vec<(Error, u8)>> {
-        self.send_all_except(
-            player_tag,
-            ServerMessage::UpdatePosition {
-                player_tag,
-                position,
-                bullets,
-            },
-        )
-        .await
+    pub async fn room_closed(&self, reason: RoomClosedReason) {
+        let _ = self.send_all(ServerMessage::RoomClosed { reason }).await;
     }
 
-    pub async fn enemy_spawn(
+    pub async fn snapshot(
         &self,
-        tag: u16,
-        position: (f32, f32),
-        velocity: (f32, f32),
+        tick: u32,
+        players: Vec<PlayerSnapshot>,
+        enemies: Vec<EnemySnapshot>,
+        bullets: Vec<BulletSnapshot>,
+        scores: HashMap<u8, u32>,
     ) -> Result<(), Vec<(Error, u8)>> {
-        self.send_all(ServerMessage::SpawnEnemy {
-            tag,
-            position,
-            velocity,
+        self.send_all(ServerMessage::Snapshot {
+            tick,
+            players,
+            enemies,
+            bullets,
+            scores,
         })
         .await
     }
@@ -98,16 +239,95 @@ impl ServerMessageHandler {
         .await
     }
 
+    pub async fn effect(
+        &self,
+        kind: EffectKind,
+        position: (f32, f32),
+    ) -> Result<(), Vec<(Error, u8)>> {
+        self.send_all(ServerMessage::Effect { kind, position })
+            .await
+    }
+
+    pub async fn player_downed(
+        &self,
+        player_tag: u8,
+        position: (f32, f32),
+    ) -> Result<(), Vec<(Error, u8)>> {
+        self.send_all(ServerMessage::PlayerDowned {
+            player_tag,
+            position,
+        })
+        .await
+    }
+
+    pub async fn player_revived(
+        &self,
+        player_tag: u8,
+        health: u8,
+        position: (f32, f32),
+    ) -> Result<(), Vec<(Error, u8)>> {
+        self.send_all(ServerMessage::PlayerRevived {
+            player_tag,
+            health,
+            position,
+        })
+        .await
+    }
+
+    pub async fn spawn_power_up(
+        &self,
+        tag: u16,
+        kind: OnlinePowerUp,
+        position: (f32, f32),
+        velocity: (f32, f32),
+    ) -> Result<(), Vec<(Error, u8)>> {
+        self.send_all(ServerMessage::SpawnPowerUp {
+            tag,
+            kind,
+            position,
+            velocity,
+        })
+        .await
+    }
+
+    pub async fn confirm_power_up(
+        &self,
+        player_tag: u8,
+        power_up_tag: u16,
+        kind: OnlinePowerUp,
+    ) -> Result<(), Vec<(Error, u8)>> {
+        self.send_all(ServerMessage::ConfirmPowerUp {
+            player_tag,
+            power_up_tag,
+            kind,
+        })
+        .await
+    }
+
+    pub async fn chat(&self, player_tag: u8, text: String) -> Result<(), Vec<(Error, u8)>> {
+        self.send_all(ServerMessage::Chat { player_tag, text })
+            .await
+    }
+
+    pub async fn pong(&self, player_tag: u8, sent_at: u64) -> Result<(), (Error, u8)> {
+        self.send(player_tag, ServerMessage::Pong { sent_at }).await
+    }
+
     pub async fn clear_senders(&self) {
-        let mut senders = self.0.write().await;
+        let mut senders = self.senders.write().await;
         for sender in senders.values_mut() {
             let _ = sender.write().await.close().await;
         }
         senders.clear();
+        let mut spectators = self.spectators.write().await;
+        for spectator in spectators.values_mut() {
+            let _ = spectator.write().await.close().await;
+        }
+        spectators.clear();
     }
 
     pub async fn clear_sender(&self, player_tag: u8) {
-        let mut senders = self.0.write().await;
+        let mut senders = self.senders.write().await;
         for sender in senders.values_mut() {
             let _ = sender.write().await.close().await;
         }
@@ -116,12 +336,12 @@ impl ServerMessageHandler {
 
     // Private
     async fn send(&self, tag: u8, message: ServerMessage) -> Result<(), (Error, u8)> {
-        let senders = self.0.read().await;
+        let senders = self.senders.read().await;
         if let Some(sender) = senders.get(&tag) {
             sender
                 .write()
                 .await
-                .send(message.clone().text())
+                .send(message.clone())
                 .await
                 .map_err(|e| (e, tag))?;
             Ok(())
@@ -130,42 +350,38 @@ impl ServerMessageHandler {
         }
     }
 
-    async fn send_all(&self, message: ServerMessage) -> Result<(), Vec<(Error, u8)>> {
-        let senders = self.0.read().await;
-        let sender_tags: Vec<u8> = senders.keys().cloned().collect();
-        drop(senders);
-
-        let mut errors = Vec::new();
-        for tag in sender_tags {
-            if let Err((e, _)) = self.send(tag, message.clone()).await {
-                errors.push((e, tag));
+    // Spectators never interrupt the game, the broken ones are just dropped
+    async fn send_spectators(&self, message: ServerMessage) {
+        let spectators = self.spectators.read().await;
+        let mut broken_tags = Vec::new();
+        for (tag, spectator) in spectators.iter() {
+            if spectator.write().await.send(message.clone()).await.is_err() {
+                broken_tags.push(*tag);
             }
         }
-        if errors.len() > 0 {
-            Err(errors)
-        } else {
-            Ok(())
+        drop(spectators);
+
+        if !broken_tags.is_empty() {
+            let mut spectators = self.spectators.write().await;
+            for tag in broken_tags {
+                spectators.remove(&tag);
+            }
         }
     }
 
-    async fn send_all_except(
-        &self,
-        except_tag: u8,
-        message: ServerMessage,
-    ) -> Result<(), Vec<(Error, u8)>> {
-        let senders = self.0.read().await;
+    async fn send_all(&self, message: ServerMessage) -> Result<(), Vec<(Error, u8)>> {
+        self.send_spectators(message.clone()).await;
+        let senders = self.senders.read().await;
         let sender_tags: Vec<u8> = senders.keys().cloned().collect();
         drop(senders);
 
         let mut errors = Vec::new();
         for tag in sender_tags {
-            if tag != except_tag {
-                if let Err((e, _)) = self.send(tag, message.clone()).await {
-                    errors.push((e, tag));
-                }
+            if let Err((e, _)) = self.send(tag, message.clone()).await {
+                errors.push((e, tag));
             }
         }
-        if errors.len() > 0 {
+        if !errors.is_empty() {
             Err(errors)
         } else {
             Ok(())
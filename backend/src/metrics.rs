@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub type SharedMetrics = Arc<Metrics>;
+
+// Served on `/metrics` in the Prometheus text format, rates are left to the scraper
+#[derive(Default)]
+pub struct Metrics {
+    connected_clients: AtomicUsize,
+    // Keyed by (direction, message type)
+    messages: Mutex<HashMap<(&'static str, &'static str), u64>>,
+    // How long the last tick of every open room took
+    room_ticks: Mutex<HashMap<u32, Duration>>,
+}
+
+impl Metrics {
+    // Counted for as long as the returned guard lives
+    pub fn track_client(self: &Arc<Self>) -> ConnectedClient {
+        self.connected_clients.fetch_add(1, Ordering::Relaxed);
+        ConnectedClient(self.clone())
+    }
+
+    pub fn message_received(&self, kind: &'static str) {
+        self.count_message("received", kind);
+    }
+
+    pub fn message_sent(&self, kind: &'static str) {
+        self.count_message("sent", kind);
+    }
+
+    pub fn room_ticked(&self, room_id: u32, duration: Duration) {
+        self.room_ticks.lock().unwrap().insert(room_id, duration);
+    }
+
+    pub fn room_closed(&self, room_id: u32) {
+        self.room_ticks.lock().unwrap().remove(&room_id);
+    }
+
+    pub fn render(&self, active_rooms: usize) -> String {
+        let mut output = String::new();
+        write_header(&mut output, "active_rooms", "gauge", "Rooms currently open");
+        writeln!(output, "shooting_game_active_rooms {active_rooms}").unwrap();
+
+        write_header(
+            &mut output,
+            "connected_clients",
+            "gauge",
+            "Open websocket connections, spectators included",
+        );
+        let connected_clients = self.connected_clients.load(Ordering::Relaxed);
+        writeln!(
+            output,
+            "shooting_game_connected_clients {connected_clients}"
+        )
+        .unwrap();
+
+        write_header(
+            &mut output,
+            "messages_total",
+            "counter",
+            "Messages by direction and type",
+        );
+        let mut messages: Vec<_> = self
+            .messages
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(key, count)| (*key, *count))
+            .collect();
+        messages.sort_unstable();
+        for ((direction, kind), count) in messages {
+            writeln!(
+                output,
+                "shooting_game_messages_total{{direction=\"{direction}\",type=\"{kind}\"}} {count}"
+            )
+            .unwrap();
+        }
+
+        write_header(
+            &mut output,
+            "room_tick_seconds",
+            "gauge",
+            "Time spent in the last tick of each room",
+        );
+        let mut room_ticks: Vec<_> = self
+            .room_ticks
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(room_id, duration)| (*room_id, *duration))
+            .collect();
+        room_ticks.sort_unstable();
+        for (room_id, duration) in room_ticks {
+            writeln!(
+                output,
+                "shooting_game_room_tick_seconds{{room=\"{room_id}\"}} {}",
+                duration.as_secs_f64()
+            )
+            .unwrap();
+        }
+        output
+    }
+
+    // Private
+    fn count_message(&self, direction: &'static str, kind: &'static str) {
+        *self
+            .messages
+            .lock()
+            .unwrap()
+            .entry((direction, kind))
+            .or_default() += 1;
+    }
+}
+
+pub struct ConnectedClient(SharedMetrics);
+
+impl Drop for ConnectedClient {
+    fn drop(&mut self) {
+        self.0.connected_clients.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+fn write_header(output: &mut String, name: &str, kind: &str, help: &str) {
+    writeln!(output, "# HELP shooting_game_{name} {help}").unwrap();
+    writeln!(output, "# TYPE shooting_game_{name} {kind}").unwrap();
+}
@@ -0,0 +1,156 @@
+use shooting_game_shared::game_related::apply_player_input;
+use shooting_game_shared::util::{EdgeUtil, MOBILE_WINDOW_SIZE, SPACESHIP_SIZE, UFO_SIZE};
+use shooting_game_shared::{ClientMessage, EnemySnapshot, PlayerInput};
+use std::time::{Duration, Instant};
+
+// Per second, stepped with the real time between room ticks so the tick rate doesn't matter
+const SPEED: f32 = 300.;
+const BULLET_SPEED: f32 = 900.;
+const SHOT_INTERVAL: Duration = Duration::from_millis(200);
+// Enemies closer than this above the bot are dodged rather than chased
+const DODGE_RANGE: f32 = 260.;
+
+// What a bot can see of the room on a tick
+pub struct BotView<'a> {
+    pub position: (f32, f32),
+    pub enemies: &'a [EnemySnapshot],
+    pub tick: u32,
+    pub playing: bool,
+}
+
+// Plays a slot like a client would, every tick turns into the messages a client would send
+#[derive(Default)]
+pub struct Bot {
+    sequence: u32,
+    next_bullet_tag: u16,
+    bullets: Vec<(u16, (f32, f32))>,
+    last_step: Option<Instant>,
+    last_shot: Option<Instant>,
+}
+
+impl Bot {
+    pub fn step(&mut self, view: BotView) -> Vec<ClientMessage> {
+        let now = Instant::now();
+        let delta = self
+            .last_step
+            .map_or(0., |last_step| (now - last_step).as_secs_f32());
+        self.last_step = Some(now);
+
+        let displacement = if view.playing {
+            self.steer(&view)
+        } else if EdgeUtil::spaceship().over_bottom_in(view.position.1) {
+            // Flies in from below like the client spaceship does before the start
+            (0., 1.)
+        } else {
+            (0., 0.)
+        };
+        let displacement = (
+            displacement.0 * SPEED * delta,
+            displacement.1 * SPEED * delta,
+        );
+        let position = apply_player_input(view.position, displacement);
+        self.sequence = self.sequence.wrapping_add(1);
+
+        for (_, bullet) in self.bullets.iter_mut() {
+            bullet.1 += BULLET_SPEED * delta;
+        }
+        self.bullets
+            .retain(|(_, bullet)| bullet.1 < MOBILE_WINDOW_SIZE.y / 2.);
+        if view.playing && self.should_shoot(position, view.enemies, now) {
+            self.last_shot = Some(now);
+            self.bullets.push((self.next_bullet_tag, position));
+            self.next_bullet_tag = self.next_bullet_tag.wrapping_add(1);
+        }
+
+        let mut messages = vec![ClientMessage::UpdatePlayerInfo {
+            input: Some(PlayerInput {
+                sequence: self.sequence,
+                displacement,
+            }),
+            bullets: self.bullets.iter().map(|(_, bullet)| *bullet).collect(),
+        }];
+        if !view.playing {
+            return messages;
+        }
+        for (enemy_tag, enemy_position, _) in view.enemies {
+            if overlaps(
+                position,
+                *enemy_position,
+                SPACESHIP_SIZE.x,
+                SPACESHIP_SIZE.y,
+            ) {
+                messages.push(ClientMessage::DamagedIntent {
+                    enemy_tag: *enemy_tag,
+                    tick: view.tick,
+                });
+                continue;
+            }
+            let Some(index) = self
+                .bullets
+                .iter()
+                .position(|(_, bullet)| overlaps(*bullet, *enemy_position, 0., 0.))
+            else {
+                continue;
+            };
+            let (bullet_tag, _) = self.bullets.remove(index);
+            messages.push(ClientMessage::DestroyEnemyIntent {
+                bullet_tag,
+                enemy_tag: *enemy_tag,
+                tick: view.tick,
+            });
+        }
+        messages
+    }
+
+    // Forgets the last match, the next one starts from a fresh spaceship
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    // Sidesteps whatever is about to hit it, otherwise lines up under the lowest enemy
+    fn steer(&self, view: &BotView) -> (f32, f32) {
+        let (x, y) = view.position;
+        let reach = (UFO_SIZE.x + SPACESHIP_SIZE.x) / 2.;
+        let edge = EdgeUtil::spaceship();
+        let threat = view
+            .enemies
+            .iter()
+            .map(|(_, position, _)| *position)
+            .filter(|(enemy_x, enemy_y)| {
+                *enemy_y > y && *enemy_y - y < DODGE_RANGE && (enemy_x - x).abs() < reach
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((enemy_x, _)) = threat {
+            let away = if x >= enemy_x { 1. } else { -1. };
+            // Cornered against a wall, the only way out is past it
+            let blocked = (away > 0. && x >= edge.right_in()) || (away < 0. && x <= edge.left_in());
+            return (if blocked { -away } else { away }, 0.);
+        }
+        let target = view
+            .enemies
+            .iter()
+            .map(|(_, position, _)| *position)
+            .filter(|(_, enemy_y)| *enemy_y > y)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        match target {
+            Some((enemy_x, _)) if (enemy_x - x).abs() > SPEED / 60. => ((enemy_x - x).signum(), 0.),
+            _ => (0., 0.),
+        }
+    }
+
+    fn should_shoot(&self, position: (f32, f32), enemies: &[EnemySnapshot], now: Instant) -> bool {
+        let cooled_down = self
+            .last_shot
+            .is_none_or(|last_shot| now - last_shot >= SHOT_INTERVAL);
+        cooled_down
+            && enemies.iter().any(|(_, (enemy_x, enemy_y), _)| {
+                *enemy_y > position.1 && (enemy_x - position.0).abs() < UFO_SIZE.x / 2.
+            })
+    }
+}
+
+// Box overlap between a point with the given size and a UFO
+fn overlaps(a: (f32, f32), ufo: (f32, f32), width: f32, height: f32) -> bool {
+    (a.0 - ufo.0).abs() < (width + UFO_SIZE.x) / 2.
+        && (a.1 - ufo.1).abs() < (height + UFO_SIZE.y) / 2.
+}
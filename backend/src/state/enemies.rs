@@ -0,0 +1,92 @@
+use rand::{rng, rngs::StdRng, Rng, SeedableRng};
+use shooting_game_shared::game_related::{
+    extrapolate_ufo, Stage, UFORandomGenerator, FIXED_TICKS_PER_SECOND,
+};
+use shooting_game_shared::util::EdgeUtil;
+use shooting_game_shared::EnemySnapshot;
+use std::time::Instant;
+
+struct EnemyInfo {
+    tag: u16,
+    position: (f32, f32),
+    velocity: (f32, f32),
+}
+
+// Steps enemies at the client FixedUpdate rate, a seeded rng keeps a run reproducible
+pub struct EnemySimulation {
+    rng: StdRng,
+    enemies: Vec<EnemyInfo>,
+    next_tag: u16,
+    started_at: Option<Instant>,
+    simulated_ticks: u32,
+}
+
+impl Default for EnemySimulation {
+    fn default() -> Self {
+        Self::new(rng().random())
+    }
+}
+
+impl EnemySimulation {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            enemies: Vec::new(),
+            next_tag: 0,
+            started_at: None,
+            simulated_ticks: 0,
+        }
+    }
+
+    // Catches up on every fixed tick due since the simulation started
+    pub fn step(&mut self, stage: &Stage) {
+        let started_at = *self.started_at.get_or_insert_with(Instant::now);
+        let due_ticks = (started_at.elapsed().as_secs_f32() * FIXED_TICKS_PER_SECOND) as u32;
+        while self.simulated_ticks < due_ticks {
+            self.simulated_ticks += 1;
+            self.integrate();
+            self.try_spawn(stage);
+        }
+    }
+
+    pub fn snapshot(&self) -> Vec<EnemySnapshot> {
+        self.enemies
+            .iter()
+            .map(|enemy| (enemy.tag, enemy.position, enemy.velocity))
+            .collect()
+    }
+
+    pub fn position(&self, tag: u16) -> Option<(f32, f32)> {
+        self.enemies
+            .iter()
+            .find(|enemy| enemy.tag == tag)
+            .map(|enemy| enemy.position)
+    }
+
+    pub fn remove(&mut self, tag: u16) {
+        self.enemies.retain(|enemy| enemy.tag != tag);
+    }
+
+    // Private
+    fn integrate(&mut self) {
+        let edge = EdgeUtil::ufo();
+        for enemy in self.enemies.iter_mut() {
+            (enemy.position, enemy.velocity) = extrapolate_ufo(enemy.position, enemy.velocity, 1);
+        }
+        self.enemies
+            .retain(|enemy| !edge.over_bottom_out(enemy.position.1));
+    }
+
+    fn try_spawn(&mut self, stage: &Stage) {
+        if !stage.random_generator(self.enemies.len() + 1, &mut self.rng) {
+            return;
+        }
+        let tag = self.next_tag;
+        self.next_tag = self.next_tag.wrapping_add(1);
+        self.enemies.push(EnemyInfo {
+            tag,
+            position: UFORandomGenerator::position(&mut self.rng),
+            velocity: stage.get_ufo_velocity_tuple(&mut self.rng),
+        });
+    }
+}
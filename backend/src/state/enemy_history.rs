@@ -0,0 +1,53 @@
+use shooting_game_shared::EnemySnapshot;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+// How far back a hit report can be rewound, covers the round trip of a laggy client
+const HISTORY_DURATION: Duration = Duration::from_millis(250);
+
+struct HistoryEntry {
+    tick: u32,
+    recorded_at: Instant,
+    positions: Vec<(u16, (f32, f32))>,
+}
+
+// Enemy positions as sent in each recent snapshot, keyed by the snapshot tick
+#[derive(Default)]
+pub struct EnemyHistory {
+    entries: VecDeque<HistoryEntry>,
+}
+
+impl EnemyHistory {
+    pub fn record(&mut self, tick: u32, enemies: &[EnemySnapshot]) {
+        self.entries.push_back(HistoryEntry {
+            tick,
+            recorded_at: Instant::now(),
+            positions: enemies
+                .iter()
+                .map(|(tag, position, _)| (*tag, *position))
+                .collect(),
+        });
+        while self
+            .entries
+            .front()
+            .is_some_and(|entry| entry.recorded_at.elapsed() > HISTORY_DURATION)
+        {
+            self.entries.pop_front();
+        }
+    }
+
+    // None when the tick is too old, not sent yet or the enemy wasn't in that snapshot
+    pub fn position_at(&self, tick: u32, tag: u16) -> Option<(f32, f32)> {
+        self.entries
+            .iter()
+            .find(|entry| entry.tick == tick)?
+            .positions
+            .iter()
+            .find(|(enemy_tag, _)| *enemy_tag == tag)
+            .map(|(_, position)| *position)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
@@ -1,20 +1,44 @@
 use rocket::tokio::sync::RwLock;
 use rocket_ws::result::Error;
-use shooting_game_shared::game_related::{Stage, UFORandomGenerator};
+use shooting_game_shared::game_related::Stage;
+use shooting_game_shared::util::{EdgeUtil, POWER_UP_SIZE, SPACESHIP_SIZE, UFO_SIZE};
+use shooting_game_shared::{
+    ClientMessage, EffectKind, EnemySnapshot, PlayerInput, RoomClosedReason, CHAT_MAX_LENGTH,
+};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use crate::history::{DisconnectReason, MatchRecord, PlayerRecord, SharedHistory};
 use crate::message::{Sender, ServerMessageHandler};
 
+use super::bot::{Bot, BotView};
+use super::enemies::EnemySimulation;
+use super::enemy_history::EnemyHistory;
 use super::players::Players;
+use super::power_ups::PowerUpSimulation;
+use super::revives::Revives;
 
 pub type SharedGameState = Arc<RwLock<GameState>>;
 
+const RECONNECT_GRACE_PERIOD: Duration = Duration::from_secs(30);
+// Slack on top of the touching distance, what the client saw is rewound so it only covers extrapolation
+const HIT_TOLERANCE: f32 = 40.;
+const CHAT_COOLDOWN: Duration = Duration::from_secs(1);
+const BOT_NAME: &str = "Bot";
+// Reaching it ends the match early for everyone
+const WIN_SCORE: u8 = 50;
+// A room nobody has sent anything to for this long is closed
+const ROOM_IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+
 #[derive(Default, Clone)]
 pub enum Cycle {
     #[default]
     Matching,
     Ready,
     Playing,
+    // Game over, players stay connected and can ask for a rematch
+    Result,
 }
 
 #[derive(Default)]
@@ -22,16 +46,45 @@ pub struct GameState {
     cycle: Cycle,
     players: Players,
     stage: RwLock<Stage>,
-    enemies: RwLock<Vec<u16>>,
+    enemies: RwLock<EnemySimulation>,
+    enemy_history: EnemyHistory,
+    power_ups: PowerUpSimulation,
+    revives: Revives,
+    disconnected: HashMap<u8, Instant>,
+    // Bumped on every (re)connection so a stale socket closing can't drop the new one
+    connection_ids: HashMap<u8, u32>,
+    tick: u32,
+    last_chat: HashMap<u8, Instant>,
+    rematch_requests: HashSet<u8>,
+    last_activity: Option<Instant>,
+    // Slots played by the server, they have no sender and never reconnect
+    bots: HashMap<u8, Bot>,
     server_message_handler: ServerMessageHandler,
+    // Set once GameStart goes out, a match stopped before that isn't recorded
+    match_started: Option<Instant>,
+    history: SharedHistory,
 }
 
 impl GameState {
-    pub async fn new_player(&mut self, sender: Sender) -> u8 {
-        let player_tag = self.players.new_player().await;
+    pub fn new(history: SharedHistory) -> Self {
+        Self {
+            history,
+            ..Self::default()
+        }
+    }
+
+    // Takes a slot, nothing is sent until the player is connected to it
+    pub async fn new_player(&mut self, name: Option<String>) -> (u8, u64, u32) {
+        self.touch();
+        let (player_tag, session_token) = self.players.new_player(name).await;
+        let connection_id = self.next_connection_id(player_tag);
+        (player_tag, session_token, connection_id)
+    }
+
+    pub async fn connect_player(&mut self, player_tag: u8, session_token: u64, sender: Sender) {
         if let Err((e, _)) = self
             .server_message_handler
-            .add_sender(player_tag, sender)
+            .add_sender(player_tag, session_token, sender)
             .await
         {
             match e {
@@ -39,23 +92,180 @@ impl GameState {
                 _ => println!("{}", e),
             }
         }
-        player_tag
+        self.broadcast_lobby().await;
+    }
+
+    // Gives the dropped player back its slot, the sender is returned when the token is unknown
+    pub async fn resume_player(
+        &mut self,
+        session_token: u64,
+        sender: Sender,
+    ) -> Result<(u8, u32), Sender> {
+        let Some(player_tag) = self.players.find_by_session(session_token).await else {
+            return Err(sender);
+        };
+        if self.disconnected.remove(&player_tag).is_none() {
+            return Err(sender);
+        }
+        self.touch();
+        let (score, health) = self
+            .players
+            .get_score_and_health(player_tag)
+            .await
+            .unwrap_or_default();
+        let enemies = self.current_enemies().await;
+        let downed = self.revives.snapshot();
+        let connection_id = self.next_connection_id(player_tag);
+        let resumed = match self
+            .server_message_handler
+            .add_sender(player_tag, session_token, sender)
+            .await
+        {
+            Ok(()) => {
+                let names = self.players.names().await;
+                self.server_message_handler
+                    .resume_state(player_tag, score, health, enemies, downed, names)
+                    .await
+            }
+            Err(error) => Err(error),
+        };
+        if let Err(error) = resumed {
+            self.handle_send_errors(vec![error]).await;
+        }
+        Ok((player_tag, connection_id))
+    }
+
+    pub async fn room_created(&mut self, player_tag: u8, room_id: u32) {
+        if let Err(error) = self
+            .server_message_handler
+            .room_created(player_tag, room_id)
+            .await
+        {
+            self.handle_send_errors(vec![error]).await;
+        }
+    }
+
+    pub async fn pong(&mut self, player_tag: u8, sent_at: u64) {
+        if let Err(error) = self.server_message_handler.pong(player_tag, sent_at).await {
+            self.handle_send_errors(vec![error]).await;
+        }
+    }
+
+    pub async fn player_left(&mut self, player_tag: u8, connection_id: u32) {
+        if self.connection_ids.get(&player_tag) != Some(&connection_id) {
+            return;
+        }
+        match self.cycle {
+            Cycle::Playing => self.disconnect_player(player_tag).await,
+            // Frees the slot so the room can be matched again or closed
+            Cycle::Matching => {
+                self.players.remove_player(player_tag).await;
+                self.server_message_handler.remove_sender(player_tag).await;
+                // A bot has nobody left to play with
+                for bot_tag in self.bots.drain().map(|(tag, _)| tag) {
+                    self.players.remove_player(bot_tag).await;
+                }
+                self.broadcast_lobby().await;
+            }
+            Cycle::Ready => {}
+            // No rematch without the opponent
+            Cycle::Result => self.interrupt_game().await,
+        }
+    }
+
+    pub async fn is_full(&self) -> bool {
+        self.players.matched().await
+    }
+
+    // Nobody is left waiting in or playing this room
+    pub async fn is_idle(&self) -> bool {
+        matches!(self.cycle, Cycle::Matching) && self.players.is_empty().await
+    }
+
+    pub fn touch(&mut self) {
+        self.last_activity = Some(Instant::now());
+    }
+
+    // Players are still in the room but none of them has done anything in a while
+    pub async fn is_abandoned(&self) -> bool {
+        !self.players.is_empty().await
+            && self
+                .last_activity
+                .is_some_and(|last_activity| last_activity.elapsed() > ROOM_IDLE_TIMEOUT)
+    }
+
+    // Tells everyone still connected why, then leaves the room empty to be removed
+    pub async fn close_room(&mut self, reason: RoomClosedReason) {
+        self.record_match(Some(reason.into())).await;
+        self.server_message_handler.room_closed(reason).await;
+        self.cleanup().await;
+    }
+
+    pub async fn new_spectator(&mut self, sender: Sender) -> u8 {
+        let game_started = matches!(self.cycle, Cycle::Playing);
+        let names = self.players.names().await;
+        self.server_message_handler
+            .add_spectator(sender, game_started, names)
+            .await
+    }
+
+    pub async fn remove_spectator(&mut self, spectator_tag: u8) {
+        self.server_message_handler
+            .remove_spectator(spectator_tag)
+            .await;
+    }
+
+    // Bots go through here too, so they are held to the same checks as a client
+    pub async fn handle_client_message(&mut self, player_tag: u8, message: ClientMessage) {
+        match message {
+            ClientMessage::UpdatePlayerInfo { input, bullets } => {
+                self.update_player_info(player_tag, input, bullets).await
+            }
+            ClientMessage::DamagedIntent { enemy_tag, tick } => {
+                self.player_damaged(player_tag, enemy_tag, tick).await;
+            }
+            ClientMessage::DestroyEnemyIntent {
+                bullet_tag,
+                enemy_tag,
+                tick,
+            } => {
+                self.destroy_enemy(player_tag, bullet_tag, enemy_tag, tick)
+                    .await;
+            }
+            ClientMessage::ClaimPowerUp { power_up_tag } => {
+                self.claim_power_up(player_tag, power_up_tag).await;
+            }
+            ClientMessage::Chat { text } => self.chat(player_tag, text).await,
+            ClientMessage::SetReady { ready } => self.set_ready(player_tag, ready).await,
+            ClientMessage::Rematch => self.request_rematch(player_tag),
+            ClientMessage::Ping { sent_at } => self.pong(player_tag, sent_at).await,
+            ClientMessage::AddBot => self.add_bot().await,
+        }
     }
 
     pub async fn update_player_info(
         &self,
         player_tag: u8,
-        position: Option<(f32, f32)>,
+        input: Option<PlayerInput>,
         bullets: Vec<(f32, f32)>,
     ) {
         self.players
-            .update_player_info(player_tag, position, bullets.clone())
+            .update_player_info(player_tag, input, bullets.clone())
             .await;
     }
 
-    pub async fn player_damaged(&mut self, player_tag: u8, enemy_tag: u16) {
+    pub async fn player_damaged(&mut self, player_tag: u8, enemy_tag: u16, tick: u32) {
         let mut enemies = self.enemies.write().await;
-        if enemies.contains(&enemy_tag) {
+        let Some(enemy_position) = self.rewound_position(&enemies, enemy_tag, tick) else {
+            return;
+        };
+        let reach = (UFO_SIZE + SPACESHIP_SIZE).length() / 2. + HIT_TOLERANCE;
+        let player_position = self
+            .players
+            .get_position(player_tag)
+            .await
+            .filter(|position| within(*position, enemy_position, reach));
+        if let Some(player_position) = player_position {
             let health = self.players.damaged(player_tag).await;
             match self
                 .server_message_handler
@@ -63,26 +273,49 @@ impl GameState {
                 .await
             {
                 Ok(()) => {
-                    enemies.retain(|&tag| tag != enemy_tag);
+                    let enemy_position = enemies.position(enemy_tag).unwrap_or(enemy_position);
+                    enemies.remove(enemy_tag);
                     drop(enemies);
+                    let player_effect = if health == 0 {
+                        EffectKind::PlayerDestroyed
+                    } else {
+                        EffectKind::PlayerDamaged
+                    };
+                    self.broadcast_effects(&[
+                        (EffectKind::EnemyDestroyed, enemy_position),
+                        (player_effect, player_position),
+                    ])
+                    .await;
+                    if health == 0 {
+                        self.player_downed(player_tag, player_position).await;
+                    }
                     self.check_game_over().await;
                 }
                 Err(errors) => {
-                    if errors
-                        .iter()
-                        .any(|(e, _)| matches!(e, Error::Io(_) | Error::ConnectionClosed))
-                    {
-                        drop(enemies);
-                        self.interrupt_game().await;
-                    }
+                    drop(enemies);
+                    self.handle_send_errors(errors).await;
                 }
             }
         }
     }
 
-    pub async fn destroy_enemy(&mut self, player_tag: u8, bullet_tag: u16, enemy_tag: u16) {
+    pub async fn destroy_enemy(
+        &mut self,
+        player_tag: u8,
+        bullet_tag: u16,
+        enemy_tag: u16,
+        tick: u32,
+    ) {
         let mut enemies = self.enemies.write().await;
-        if enemies.contains(&enemy_tag) {
+        let Some(enemy_position) = self.rewound_position(&enemies, enemy_tag, tick) else {
+            return;
+        };
+        let bullets = self.players.get_bullets(player_tag).await;
+        let reach = UFO_SIZE.length() / 2. + HIT_TOLERANCE;
+        if bullets
+            .iter()
+            .any(|bullet| within(*bullet, enemy_position, reach))
+        {
             let new_score = self.players.add_score(player_tag).await;
             match self
                 .server_message_handler
@@ -90,82 +323,265 @@ impl GameState {
                 .await
             {
                 Ok(_) => {
-                    enemies.retain(|&tag| tag != enemy_tag);
+                    let enemy_position = enemies.position(enemy_tag).unwrap_or(enemy_position);
+                    enemies.remove(enemy_tag);
                     drop(enemies);
+                    self.broadcast_effects(&[(EffectKind::EnemyDestroyed, enemy_position)])
+                        .await;
                     self.update_stage().await;
+                    self.check_game_over().await;
                 }
                 Err(errors) => {
-                    if errors
-                        .iter()
-                        .any(|(e, _)| matches!(e, Error::Io(_) | Error::ConnectionClosed))
-                    {
-                        drop(enemies);
-                        self.interrupt_game().await;
-                    }
+                    drop(enemies);
+                    self.handle_send_errors(errors).await;
                 }
             }
         }
     }
 
-    // Private
-    async fn notice_player_info(&mut self) -> Result<(), Vec<Error>> {
-        let players = self.players.get_players_info().await;
-        let mut errors = Vec::new();
-        for (player_tag, position, bullets) in players {
-            if let Err(new_errors) = self
+    // Claims are handled one at a time under the room lock, so simultaneous ones go to whoever came first
+    pub async fn claim_power_up(&mut self, player_tag: u8, power_up_tag: u16) {
+        if !matches!(self.cycle, Cycle::Playing) {
+            return;
+        }
+        let Some(power_up_position) = self.power_ups.position(power_up_tag) else {
+            return;
+        };
+        let player_position = self.players.get_position(player_tag).await;
+        let reach = (POWER_UP_SIZE + SPACESHIP_SIZE).length() / 2. + HIT_TOLERANCE;
+        if !player_position.is_some_and(|position| within(position, power_up_position, reach)) {
+            return;
+        }
+        let Some(power_up) = self.power_ups.take(power_up_tag) else {
+            return;
+        };
+        if let Err(errors) = self
+            .server_message_handler
+            .confirm_power_up(player_tag, power_up.tag, power_up.kind)
+            .await
+        {
+            self.handle_send_errors(errors).await;
+        }
+    }
+
+    async fn player_downed(&mut self, player_tag: u8, position: (f32, f32)) {
+        self.revives.down(player_tag, position);
+        if let Err(errors) = self
+            .server_message_handler
+            .player_downed(player_tag, position)
+            .await
+        {
+            self.handle_send_errors(errors).await;
+        }
+    }
+
+    // Disconnected players can't revive and aren't revived, they'd miss the ship coming back
+    async fn step_revives(&mut self) {
+        let away: Vec<u8> = self.disconnected.keys().copied().collect();
+        let rescuers: Vec<(u8, (f32, f32))> = self
+            .players
+            .alive_positions()
+            .await
+            .into_iter()
+            .filter(|(tag, _)| !away.contains(tag))
+            .collect();
+        for (player_tag, position) in self.revives.step(&rescuers, &away) {
+            let health = self.players.revive(player_tag, position).await;
+            if let Err(errors) = self
                 .server_message_handler
-                .notice_others_position(player_tag, position, bullets)
+                .player_revived(player_tag, health, position)
                 .await
             {
-                for (e, _) in new_errors {
-                    errors.push(e);
-                }
+                self.handle_send_errors(errors).await;
+                return;
             }
         }
-        if errors.len() > 0 {
-            Err(errors)
-        } else {
-            Ok(())
+    }
+
+    // Only counts in the lobby, the roster is sent back so everyone sees the change
+    pub async fn set_ready(&mut self, player_tag: u8, ready: bool) {
+        if !matches!(self.cycle, Cycle::Matching) {
+            return;
         }
+        self.players.set_ready(player_tag, ready).await;
+        self.broadcast_lobby().await;
     }
 
-    async fn spawn_enemy(&mut self) -> Result<(), Vec<(Error, u8)>> {
-        let mut enemies = self.enemies.write().await;
-        let stage = self.stage.read().await;
-        let ufo_numbers = enemies.len() + 1;
-        if !stage.random_generator(ufo_numbers) {
-            return Ok(());
+    // Only a lone player in the lobby can ask, the bot takes the other slot already readied
+    pub async fn add_bot(&mut self) {
+        if !matches!(self.cycle, Cycle::Matching) || self.players.tags().await.len() != 1 {
+            return;
+        }
+        let (bot_tag, _) = self.players.new_player(Some(BOT_NAME.to_string())).await;
+        self.players.set_ready(bot_tag, true).await;
+        self.bots.insert(bot_tag, Bot::default());
+        self.broadcast_lobby().await;
+    }
+
+    pub fn request_rematch(&mut self, player_tag: u8) {
+        if matches!(self.cycle, Cycle::Result) {
+            self.rematch_requests.insert(player_tag);
+        }
+    }
+
+    // Messages within the cooldown are dropped rather than queued
+    pub async fn chat(&mut self, player_tag: u8, text: String) {
+        let text: String = text.trim().chars().take(CHAT_MAX_LENGTH).collect();
+        if text.is_empty() {
+            return;
+        }
+        if self
+            .last_chat
+            .get(&player_tag)
+            .is_some_and(|sent_at| sent_at.elapsed() < CHAT_COOLDOWN)
+        {
+            return;
         }
-        let tag = UFORandomGenerator::tag();
-        let position = UFORandomGenerator::position();
-        let velocity = stage.get_ufo_velocity_tuple();
-        if enemies.contains(&tag) {
-            return Ok(());
+        self.last_chat.insert(player_tag, Instant::now());
+        if let Err(errors) = self.server_message_handler.chat(player_tag, text).await {
+            self.handle_send_errors(errors).await;
         }
-        enemies.push(tag);
+    }
+
+    // Private
+    async fn send_snapshot(&mut self) -> Result<(), Vec<(Error, u8)>> {
+        self.tick = self.tick.wrapping_add(1);
+        let (players, bullets) = self.players.get_snapshot().await;
+        // Leaving ones are skipped so clients don't respawn what they just cleaned up
+        let edge = EdgeUtil::ufo();
+        let enemies: Vec<EnemySnapshot> = self
+            .current_enemies()
+            .await
+            .into_iter()
+            .filter(|(_, position, _)| !edge.over_bottom_in(position.1))
+            .collect();
+        self.enemy_history.record(self.tick, &enemies);
+        let scores = self.players.get_scores().await;
         self.server_message_handler
-            .enemy_spawn(tag, position, velocity)
+            .snapshot(self.tick, players, enemies, bullets, scores)
             .await
     }
 
+    async fn simulate_enemies(&mut self) {
+        let stage = self.stage.read().await;
+        self.enemies.write().await.step(&stage);
+    }
+
+    // Each bot sees the room as a client would and its messages are handled the same way
+    async fn step_bots(&mut self, playing: bool) {
+        let enemies = self.current_enemies().await;
+        let mut bot_messages = Vec::new();
+        for (bot_tag, bot) in self.bots.iter_mut() {
+            let alive = self
+                .players
+                .get_score_and_health(*bot_tag)
+                .await
+                .is_some_and(|(_, health)| health > 0);
+            let Some(position) = self.players.get_position(*bot_tag).await else {
+                continue;
+            };
+            if !alive {
+                continue;
+            }
+            let view = BotView {
+                position,
+                enemies: &enemies,
+                tick: self.tick,
+                playing,
+            };
+            bot_messages.extend(
+                bot.step(view)
+                    .into_iter()
+                    .map(|message| (*bot_tag, message)),
+            );
+        }
+        for (bot_tag, message) in bot_messages {
+            self.handle_client_message(bot_tag, message).await;
+        }
+    }
+
+    async fn simulate_power_ups(&mut self) -> Result<(), Vec<(Error, u8)>> {
+        for power_up in self.power_ups.step() {
+            self.server_message_handler
+                .spawn_power_up(
+                    power_up.tag,
+                    power_up.kind,
+                    power_up.position,
+                    power_up.velocity,
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
     async fn check_game_over(&mut self) {
-        if self.players.all_players_dead().await {
-            self.server_message_handler.game_over().await;
-            self.cleanup().await;
+        if !self.players.all_players_dead().await && !self.players.any_reached(WIN_SCORE).await {
+            return;
+        }
+        self.record_match(None).await;
+        let scores = self.players.get_scores().await;
+        let winner = winner(&scores);
+        self.reset_match().await;
+        self.cycle = Cycle::Result;
+        if let Err(errors) = self.server_message_handler.game_over(scores, winner).await {
+            self.handle_send_errors(errors).await;
         }
     }
 
+    // Clears what one match left behind, the players and their connections stay
+    async fn reset_match(&mut self) {
+        *self.enemies.write().await = EnemySimulation::default();
+        self.disconnected.clear();
+        self.enemy_history.clear();
+        self.power_ups = PowerUpSimulation::default();
+        self.revives = Revives::default();
+        self.tick = 0;
+        self.rematch_requests.clear();
+        *self.stage.write().await = Stage::default();
+        self.bots.values_mut().for_each(Bot::reset);
+        self.match_started = None;
+    }
+
+    // Called before the scores are reset, bots are recorded like any other player
+    async fn record_match(&mut self, disconnect_reason: Option<DisconnectReason>) {
+        let Some(match_started) = self.match_started.take() else {
+            return;
+        };
+        let scores = self.players.get_scores().await;
+        let mut names = self.players.names().await;
+        let mut players: Vec<PlayerRecord> = scores
+            .into_iter()
+            .map(|(tag, score)| PlayerRecord {
+                tag,
+                name: names.remove(&tag),
+                score,
+                bot: self.bots.contains_key(&tag),
+            })
+            .collect();
+        players.sort_by_key(|player| player.tag);
+        self.history
+            .record(MatchRecord::new(
+                players,
+                match_started.elapsed(),
+                disconnect_reason,
+            ))
+            .await;
+    }
+
     async fn cleanup(&mut self) {
-        self.enemies.write().await.clear();
+        self.reset_match().await;
+        self.connection_ids.clear();
+        self.last_chat.clear();
+        self.bots.clear();
         self.players.clear_players().await;
-        *self.stage.write().await = Stage::default();
         self.server_message_handler.clear_senders().await;
+        self.last_activity = None;
         self.cycle = Cycle::Matching;
     }
 
     async fn update_stage(&self) {
         let total_score = self.players.get_total_score().await;
-        let new_stage = Stage::new(total_score);
+        let new_stage = Stage::new(total_score.into());
         let mut stage = self.stage.write().await;
         *stage = new_stage;
     }
@@ -180,18 +596,88 @@ impl GameState {
         self.cleanup().await;
     }
 
+    fn next_connection_id(&mut self, player_tag: u8) -> u32 {
+        let connection_id = self.connection_ids.entry(player_tag).or_default();
+        *connection_id = connection_id.wrapping_add(1);
+        *connection_id
+    }
+
+    async fn disconnect_player(&mut self, player_tag: u8) {
+        self.server_message_handler.remove_sender(player_tag).await;
+        self.disconnected
+            .entry(player_tag)
+            .or_insert_with(Instant::now);
+    }
+
+    // Where the server has things now rather than the rewound hit, so every screen agrees
+    async fn broadcast_effects(&mut self, effects: &[(EffectKind, (f32, f32))]) {
+        for (kind, position) in effects {
+            if let Err(errors) = self.server_message_handler.effect(*kind, *position).await {
+                self.handle_send_errors(errors).await;
+                return;
+            }
+        }
+    }
+
+    // Broken players get a grace period mid-game, any other cycle is interrupted
+    async fn handle_send_errors(&mut self, errors: Vec<(Error, u8)>) {
+        let broken_tags: Vec<u8> = errors
+            .iter()
+            .filter(|(e, _)| matches!(e, Error::Io(_) | Error::ConnectionClosed))
+            .map(|(_, tag)| *tag)
+            .collect();
+        if broken_tags.is_empty() {
+            return;
+        }
+        if !matches!(self.cycle, Cycle::Playing) {
+            self.interrupt_game().await;
+            return;
+        }
+        for tag in broken_tags {
+            self.disconnect_player(tag).await;
+        }
+    }
+
+    // Where the enemy was in the snapshot the client reported, enemies already removed can't be hit
+    fn rewound_position(
+        &self,
+        enemies: &EnemySimulation,
+        enemy_tag: u16,
+        tick: u32,
+    ) -> Option<(f32, f32)> {
+        let current = enemies.position(enemy_tag)?;
+        Some(
+            self.enemy_history
+                .position_at(tick, enemy_tag)
+                .unwrap_or(current),
+        )
+    }
+
+    async fn current_enemies(&self) -> Vec<EnemySnapshot> {
+        self.enemies.read().await.snapshot()
+    }
+
     // Cycle Related (Not run in the main thread)
     pub async fn check_cycle(&mut self) -> Cycle {
         match self.cycle {
             Cycle::Matching => self.handle_cycle_matching().await,
             Cycle::Ready => self.handle_cycle_ready().await,
             Cycle::Playing => self.handle_cycle_playing().await,
+            Cycle::Result => self.handle_cycle_result().await,
         }
         self.cycle.clone()
     }
 
+    async fn broadcast_lobby(&mut self) {
+        let names = self.players.names().await;
+        let lobby = self.players.lobby().await;
+        if let Err(errors) = self.server_message_handler.lobby_state(names, lobby).await {
+            self.handle_send_errors(errors).await;
+        }
+    }
+
     async fn handle_cycle_matching(&mut self) {
-        if self.players.matched().await {
+        if self.players.matched().await && self.players.all_ready().await {
             if let Err(errors) = self.server_message_handler.game_ready().await {
                 if errors
                     .iter()
@@ -206,13 +692,9 @@ impl GameState {
     }
 
     async fn handle_cycle_ready(&mut self) {
-        if let Err(errors) = self.notice_player_info().await {
-            if errors
-                .iter()
-                .any(|e| matches!(e, Error::Io(_) | Error::ConnectionClosed))
-            {
-                self.interrupt_game().await;
-            }
+        self.step_bots(false).await;
+        if let Err(errors) = self.send_snapshot().await {
+            self.handle_send_errors(errors).await;
         }
         if self.players.ready().await {
             if let Err(errors) = self.server_message_handler.game_start().await {
@@ -224,26 +706,66 @@ impl GameState {
                 }
             } else {
                 self.cycle = Cycle::Playing;
+                self.match_started = Some(Instant::now());
             }
         }
     }
 
     async fn handle_cycle_playing(&mut self) {
-        if let Err(errors) = self.notice_player_info().await {
-            if errors
-                .iter()
-                .any(|e| matches!(e, Error::Io(_) | Error::ConnectionClosed))
-            {
-                self.interrupt_game().await;
-            }
+        if self
+            .disconnected
+            .values()
+            .any(|since| since.elapsed() > RECONNECT_GRACE_PERIOD)
+        {
+            self.record_match(Some(DisconnectReason::ReconnectTimeout))
+                .await;
+            self.interrupt_game().await;
+            return;
         }
-        if let Err(errors) = self.spawn_enemy().await {
-            if errors
-                .iter()
-                .any(|(e, _)| matches!(e, Error::Io(_) | Error::ConnectionClosed))
-            {
-                self.interrupt_game().await;
-            }
+        self.simulate_enemies().await;
+        if let Err(errors) = self.simulate_power_ups().await {
+            self.handle_send_errors(errors).await;
+        }
+        self.step_bots(true).await;
+        self.step_revives().await;
+        // A bot's hit can end the match
+        if !matches!(self.cycle, Cycle::Playing) {
+            return;
+        }
+        if let Err(errors) = self.send_snapshot().await {
+            self.handle_send_errors(errors).await;
         }
     }
+
+    // Going back to Matching sends GameReady again on the next tick
+    async fn handle_cycle_result(&mut self) {
+        if !self.players.matched().await {
+            return;
+        }
+        let tags = self.players.tags().await;
+        // Bots are always up for another round
+        if tags
+            .iter()
+            .all(|tag| self.rematch_requests.contains(tag) || self.bots.contains_key(tag))
+        {
+            self.players.reset_for_rematch().await;
+            self.rematch_requests.clear();
+            self.cycle = Cycle::Matching;
+        }
+    }
+}
+
+// Highest score wins, a shared top score is a draw
+fn winner(scores: &HashMap<u8, u32>) -> Option<u8> {
+    let top_score = scores.values().max()?;
+    let mut top_tags = scores.iter().filter(|(_, score)| *score == top_score);
+    match (top_tags.next(), top_tags.next()) {
+        (Some((tag, _)), None) => Some(*tag),
+        _ => None,
+    }
+}
+
+pub(super) fn within(a: (f32, f32), b: (f32, f32), distance: f32) -> bool {
+    let (dx, dy) = (a.0 - b.0, a.1 - b.1);
+    dx * dx + dy * dy <= distance * distance
 }
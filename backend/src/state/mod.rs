@@ -1,4 +1,9 @@
+mod bot;
+mod enemies;
+mod enemy_history;
 mod game_state;
 mod players;
+mod power_ups;
+mod revives;
 
 pub use game_state::{Cycle, GameState, SharedGameState};
@@ -1,21 +1,80 @@
 use std::collections::HashMap;
 
 use rocket::tokio::sync::RwLock;
-use shooting_game_shared::util::EdgeUtil;
+use shooting_game_shared::{
+    game_related::{apply_player_input, spaceship_start_position, SessionRandomGenerator},
+    util::EdgeUtil,
+    BulletSnapshot, LobbyPlayer, PlayerInput, PlayerSnapshot,
+};
+
+const MAX_HEALTH: u8 = 3;
 
 #[derive(Default)]
 pub struct Players(RwLock<HashMap<u8, PlayerInfo>>);
 
 impl Players {
-    pub async fn new_player(&self) -> u8 {
+    pub async fn new_player(&self, name: Option<String>) -> (u8, u64) {
         let mut players = self.0.write().await;
         let mut player_tag = 1;
         while players.contains_key(&player_tag) {
             player_tag += 1;
         }
-        let player = PlayerInfo::default();
+        let player = PlayerInfo {
+            name,
+            ..PlayerInfo::new(player_tag)
+        };
+        let session_token = player.session_token;
         players.insert(player_tag, player);
-        player_tag
+        (player_tag, session_token)
+    }
+
+    pub async fn find_by_session(&self, session_token: u64) -> Option<u8> {
+        let players = self.0.read().await;
+        players
+            .iter()
+            .find(|(_, player)| player.session_token == session_token)
+            .map(|(tag, _)| *tag)
+    }
+
+    pub async fn get_score_and_health(&self, player_tag: u8) -> Option<(u8, u8)> {
+        let players = self.0.read().await;
+        players
+            .get(&player_tag)
+            .map(|player| (player.score, player.health))
+    }
+
+    pub async fn get_position(&self, player_tag: u8) -> Option<(f32, f32)> {
+        let players = self.0.read().await;
+        players.get(&player_tag).map(|player| player.position)
+    }
+
+    pub async fn get_bullets(&self, player_tag: u8) -> Vec<(f32, f32)> {
+        let players = self.0.read().await;
+        players
+            .get(&player_tag)
+            .map(|player| player.bullets.clone())
+            .unwrap_or_default()
+    }
+
+    // Where the players still in the fight are, they are the ones who can revive
+    pub async fn alive_positions(&self) -> Vec<(u8, (f32, f32))> {
+        let players = self.0.read().await;
+        players
+            .iter()
+            .filter(|(_, player)| player.health > 0)
+            .map(|(tag, player)| (*tag, player.position))
+            .collect()
+    }
+
+    // Back at the beacon with half the max health, returns the new health
+    pub async fn revive(&self, player_tag: u8, position: (f32, f32)) -> u8 {
+        let mut players = self.0.write().await;
+        let Some(player) = players.get_mut(&player_tag) else {
+            return 0;
+        };
+        player.health = MAX_HEALTH.div_ceil(2);
+        player.position = position;
+        player.health
     }
 
     pub async fn remove_player(&self, player_tag: u8) {
@@ -33,20 +92,75 @@ impl Players {
         players.values().map(|player| player.score).sum()
     }
 
-    pub async fn get_players_info(&self) -> Vec<(u8, (f32, f32), Vec<(f32, f32)>)> {
-        self.0
-            .read()
-            .await
+    pub async fn get_scores(&self) -> HashMap<u8, u32> {
+        let players = self.0.read().await;
+        players
+            .iter()
+            .map(|(tag, player)| (*tag, player.score.into()))
+            .collect()
+    }
+
+    pub async fn any_reached(&self, score: u8) -> bool {
+        let players = self.0.read().await;
+        players.values().any(|player| player.score >= score)
+    }
+
+    pub async fn tags(&self) -> Vec<u8> {
+        self.0.read().await.keys().cloned().collect()
+    }
+
+    pub async fn get_snapshot(&self) -> (Vec<PlayerSnapshot>, Vec<BulletSnapshot>) {
+        let players = self.0.read().await;
+        let player_snapshots = players
+            .iter()
+            .map(|(tag, player)| (*tag, player.position, player.last_sequence))
+            .collect();
+        let bullet_snapshots = players
+            .iter()
+            .flat_map(|(tag, player)| player.bullets.iter().map(|bullet| (*tag, *bullet)))
+            .collect();
+        (player_snapshots, bullet_snapshots)
+    }
+
+    pub async fn lobby(&self) -> Vec<LobbyPlayer> {
+        let players = self.0.read().await;
+        let mut lobby: Vec<LobbyPlayer> = players
+            .iter()
+            .map(|(tag, player)| (*tag, player.ready))
+            .collect();
+        lobby.sort_by_key(|(tag, _)| *tag);
+        lobby
+    }
+
+    pub async fn names(&self) -> HashMap<u8, String> {
+        let players = self.0.read().await;
+        players
             .iter()
-            .map(|(tag, player)| (*tag, player.position, player.bullets.clone()))
+            .filter_map(|(tag, player)| Some((*tag, player.name.clone()?)))
             .collect()
     }
 
+    pub async fn set_ready(&self, player_tag: u8, ready: bool) {
+        let mut players = self.0.write().await;
+        if let Some(player) = players.get_mut(&player_tag) {
+            player.ready = ready;
+        }
+    }
+
+    pub async fn all_ready(&self) -> bool {
+        let players = self.0.read().await;
+        players.values().all(|player| player.ready)
+    }
+
     pub async fn matched(&self) -> bool {
         let players = self.0.read().await;
         players.len() == 2
     }
 
+    pub async fn is_empty(&self) -> bool {
+        self.0.read().await.is_empty()
+    }
+
     pub async fn ready(&self) -> bool {
         let players = self.0.read().await;
         let mut ready_count = 0;
@@ -62,13 +176,15 @@ impl Players {
     pub async fn update_player_info(
         &self,
         player_tag: u8,
-        position: Option<(f32, f32)>,
+        input: Option<PlayerInput>,
         bullets: Vec<(f32, f32)>,
     ) {
         let mut players = self.0.write().await;
         players.entry(player_tag).and_modify(|player| {
-            if let Some(position) = position {
-                player.position = position;
+            // Taken as is rather than the highest seen, a reconnected client counts from zero again
+            if let Some(input) = input {
+                player.position = apply_player_input(player.position, input.displacement);
+                player.last_sequence = input.sequence;
             }
             player.bullets = bullets;
         });
@@ -88,6 +204,19 @@ impl Players {
         player.score
     }
 
+    // Keeps the tags and sessions so the same players can go again, asking for the rematch counts as ready
+    pub async fn reset_for_rematch(&self) {
+        let mut players = self.0.write().await;
+        for (tag, player) in players.iter_mut() {
+            *player = PlayerInfo {
+                session_token: player.session_token,
+                name: player.name.take(),
+                ready: true,
+                ..PlayerInfo::new(*tag)
+            };
+        }
+    }
+
     pub async fn clear_players(&self) {
         let mut players = self.0.write().await;
         players.clear();
@@ -96,18 +225,29 @@ impl Players {
 
 #[derive(Debug)]
 struct PlayerInfo {
+    session_token: u64,
+    // Picked by the client on join, kept across reconnects and rematches
+    name: Option<String>,
+    // Set from the lobby before the game starts
+    ready: bool,
     score: u8,
     health: u8,
+    // Moved by the client inputs, starting where the client spawns the spaceship
     position: (f32, f32),
+    last_sequence: u32,
     bullets: Vec<(f32, f32)>,
 }
 
-impl Default for PlayerInfo {
-    fn default() -> Self {
+impl PlayerInfo {
+    fn new(player_tag: u8) -> Self {
         Self {
+            session_token: SessionRandomGenerator::token(),
+            name: None,
+            ready: false,
             score: 0,
-            health: 3,
-            position: (0.0, 0.0),
+            health: MAX_HEALTH,
+            position: spaceship_start_position(player_tag),
+            last_sequence: 0,
             bullets: Vec::new(),
         }
     }
@@ -0,0 +1,100 @@
+use rand::{rng, rngs::StdRng, Rng, SeedableRng};
+use shooting_game_shared::game_related::FIXED_TICKS_PER_SECOND;
+use shooting_game_shared::util::{EdgeUtil, POWER_UP_SIZE};
+use shooting_game_shared::OnlinePowerUp;
+use std::time::Instant;
+
+// Same pace as the single player game
+const SPAWN_INTERVAL_TICKS: u32 = 12 * FIXED_TICKS_PER_SECOND as u32;
+const POWER_UP_VELOCITY: (f32, f32) = (0., -2.);
+const KINDS: [OnlinePowerUp; 2] = [OnlinePowerUp::SpreadShot, OnlinePowerUp::RapidFire];
+
+#[derive(Clone, Copy)]
+pub struct PowerUpInfo {
+    pub tag: u16,
+    pub kind: OnlinePowerUp,
+    pub position: (f32, f32),
+    pub velocity: (f32, f32),
+}
+
+// Falls at the client FixedUpdate rate so a claim can be checked against where it is
+pub struct PowerUpSimulation {
+    rng: StdRng,
+    power_ups: Vec<PowerUpInfo>,
+    next_tag: u16,
+    started_at: Option<Instant>,
+    simulated_ticks: u32,
+}
+
+impl Default for PowerUpSimulation {
+    fn default() -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(rng().random()),
+            power_ups: Vec::new(),
+            next_tag: 0,
+            started_at: None,
+            simulated_ticks: 0,
+        }
+    }
+}
+
+impl PowerUpSimulation {
+    // Catches up on every fixed tick due, returns the power-ups spawned on the way
+    pub fn step(&mut self) -> Vec<PowerUpInfo> {
+        let started_at = *self.started_at.get_or_insert_with(Instant::now);
+        let due_ticks = (started_at.elapsed().as_secs_f32() * FIXED_TICKS_PER_SECOND) as u32;
+        let mut spawned = Vec::new();
+        while self.simulated_ticks < due_ticks {
+            self.simulated_ticks += 1;
+            self.integrate();
+            if self.simulated_ticks.is_multiple_of(SPAWN_INTERVAL_TICKS) {
+                spawned.push(self.spawn());
+            }
+        }
+        spawned
+    }
+
+    pub fn position(&self, tag: u16) -> Option<(f32, f32)> {
+        self.power_ups
+            .iter()
+            .find(|power_up| power_up.tag == tag)
+            .map(|power_up| power_up.position)
+    }
+
+    // Gone once claimed, so a second claim for the same tag finds nothing
+    pub fn take(&mut self, tag: u16) -> Option<PowerUpInfo> {
+        let index = self
+            .power_ups
+            .iter()
+            .position(|power_up| power_up.tag == tag)?;
+        Some(self.power_ups.swap_remove(index))
+    }
+
+    // Private
+    fn integrate(&mut self) {
+        let edge = EdgeUtil::new(POWER_UP_SIZE);
+        for power_up in self.power_ups.iter_mut() {
+            power_up.position.0 += power_up.velocity.0;
+            power_up.position.1 += power_up.velocity.1;
+        }
+        self.power_ups
+            .retain(|power_up| !edge.over_bottom_out(power_up.position.1));
+    }
+
+    fn spawn(&mut self) -> PowerUpInfo {
+        let edge = EdgeUtil::new(POWER_UP_SIZE);
+        let tag = self.next_tag;
+        self.next_tag = self.next_tag.wrapping_add(1);
+        let power_up = PowerUpInfo {
+            tag,
+            kind: KINDS[self.rng.random_range(0..KINDS.len())],
+            position: (
+                self.rng.random_range(edge.left_in()..edge.right_in()),
+                edge.top_out(),
+            ),
+            velocity: POWER_UP_VELOCITY,
+        };
+        self.power_ups.push(power_up);
+        power_up
+    }
+}
@@ -0,0 +1,59 @@
+use shooting_game_shared::game_related::{REVIVE_DURATION, REVIVE_RADIUS};
+use shooting_game_shared::DownedSnapshot;
+use std::collections::HashMap;
+use std::time::Instant;
+
+use super::game_state::within;
+
+struct Beacon {
+    position: (f32, f32),
+    // Since when a partner has been standing on it without leaving
+    channel_started: Option<Instant>,
+}
+
+// The beacons of players out of health, a partner standing on one revives its player
+#[derive(Default)]
+pub struct Revives(HashMap<u8, Beacon>);
+
+impl Revives {
+    pub fn down(&mut self, player_tag: u8, position: (f32, f32)) {
+        self.0.insert(
+            player_tag,
+            Beacon {
+                position,
+                channel_started: None,
+            },
+        );
+    }
+
+    // `rescuers` are the players able to revive, a beacon of a player in `away` waits for them to be back
+    pub fn step(&mut self, rescuers: &[(u8, (f32, f32))], away: &[u8]) -> Vec<DownedSnapshot> {
+        let now = Instant::now();
+        let mut revived = Vec::new();
+        for (player_tag, beacon) in self.0.iter_mut() {
+            let hovered = !away.contains(player_tag)
+                && rescuers.iter().any(|(rescuer_tag, position)| {
+                    rescuer_tag != player_tag && within(*position, beacon.position, REVIVE_RADIUS)
+                });
+            if !hovered {
+                beacon.channel_started = None;
+                continue;
+            }
+            let channel_started = *beacon.channel_started.get_or_insert(now);
+            if now.duration_since(channel_started) >= REVIVE_DURATION {
+                revived.push((*player_tag, beacon.position));
+            }
+        }
+        for (player_tag, _) in revived.iter() {
+            self.0.remove(player_tag);
+        }
+        revived
+    }
+
+    pub fn snapshot(&self) -> Vec<DownedSnapshot> {
+        self.0
+            .iter()
+            .map(|(player_tag, beacon)| (*player_tag, beacon.position))
+            .collect()
+    }
+}
@@ -0,0 +1,188 @@
+use shooting_game_shared::{
+    server_version_from_reason, ClientMessage, EffectKind, ServerMessage,
+    INCOMPATIBLE_VERSION_CLOSE_CODE, PROTOCOL_VERSION,
+};
+use std::collections::HashMap;
+
+use crate::history::{DisconnectReason, MatchRecord, PlayerRecord};
+
+use super::support::{TestClient, TestServer};
+
+// Two inputs already get a spaceship from below the screen into the play area
+const FLY_IN_INPUTS: u32 = 3;
+const FLY_IN_STEP: (f32, f32) = (0., 60.);
+
+// Fills a room and plays it up to GameStart
+async fn start_game(server: &TestServer) -> (TestClient, TestClient) {
+    let mut first = TestClient::join(server, None).await;
+    let mut second = TestClient::join(server, None).await;
+    for client in [&mut first, &mut second] {
+        client.send(ClientMessage::SetReady { ready: true }).await;
+    }
+    for client in [&mut first, &mut second] {
+        client.expect_message(ServerMessage::GameReady).await;
+    }
+    for client in [&mut first, &mut second] {
+        for sequence in 1..=FLY_IN_INPUTS {
+            client.send_input(sequence, FLY_IN_STEP, Vec::new()).await;
+        }
+    }
+    for client in [&mut first, &mut second] {
+        client.expect_message(ServerMessage::GameStart).await;
+    }
+    (first, second)
+}
+
+#[rocket::async_test]
+async fn old_protocol_is_rejected() {
+    let server = TestServer::launch().await;
+    let mut client = TestClient::connect(&server, PROTOCOL_VERSION - 1, None).await;
+    let (code, reason) = client.expect_close().await;
+    assert_eq!(code, INCOMPATIBLE_VERSION_CLOSE_CODE);
+    assert_eq!(server_version_from_reason(&reason), Some(PROTOCOL_VERSION));
+}
+
+#[rocket::async_test]
+async fn two_clients_share_a_room() {
+    let server = TestServer::launch().await;
+    let mut first = TestClient::join(&server, None).await;
+    let room_id = first
+        .expect("RoomCreated", |message| match message {
+            ServerMessage::RoomCreated { room_id } => Some(room_id),
+            _ => None,
+        })
+        .await;
+    let mut second = TestClient::join(&server, Some(room_id)).await;
+    assert_ne!(first.player_tag, second.player_tag);
+
+    let lobby = vec![(first.player_tag, false), (second.player_tag, false)];
+    for client in [&mut first, &mut second] {
+        client
+            .expect_message(ServerMessage::LobbyState {
+                players: lobby.clone(),
+            })
+            .await;
+    }
+
+    second.send(ClientMessage::SetReady { ready: true }).await;
+    let lobby = vec![(first.player_tag, false), (second.player_tag, true)];
+    for client in [&mut first, &mut second] {
+        client
+            .expect_message(ServerMessage::LobbyState {
+                players: lobby.clone(),
+            })
+            .await;
+    }
+}
+
+#[rocket::async_test]
+async fn nicknames_reach_both_players() {
+    let server = TestServer::launch().await;
+    let mut first = TestClient::join_named(&server, "Alice").await;
+    // Anything a nickname can't hold is dropped by the server
+    let mut second = TestClient::join_named(&server, "Bob%21%20").await;
+    let names = HashMap::from([
+        (first.player_tag, "Alice".to_string()),
+        (second.player_tag, "Bob".to_string()),
+    ]);
+    for client in [&mut first, &mut second] {
+        client
+            .expect_message(ServerMessage::PlayerNames {
+                names: names.clone(),
+            })
+            .await;
+    }
+}
+
+#[rocket::async_test]
+async fn game_starts_once_everyone_flew_in() {
+    let server = TestServer::launch().await;
+    let (mut first, _second) = start_game(&server).await;
+    let players = first
+        .expect("Snapshot", |message| match message {
+            ServerMessage::Snapshot { players, .. } => Some(players),
+            _ => None,
+        })
+        .await;
+    assert_eq!(players.len(), 2);
+    assert!(players
+        .iter()
+        .all(|(_, _, last_sequence)| *last_sequence == FLY_IN_INPUTS));
+}
+
+#[rocket::async_test]
+async fn destroyed_enemy_is_broadcast() {
+    let server = TestServer::launch().await;
+    let (mut first, mut second) = start_game(&server).await;
+    let (tick, (enemy_tag, enemy_position, _)) = first
+        .expect("an enemy", |message| match message {
+            ServerMessage::Snapshot { tick, enemies, .. } => {
+                enemies.first().map(|enemy| (tick, *enemy))
+            }
+            _ => None,
+        })
+        .await;
+
+    // A bullet right on the enemy as of that snapshot, the server rewinds the enemy to check it
+    first
+        .send(ClientMessage::UpdatePlayerInfo {
+            input: None,
+            bullets: vec![enemy_position],
+        })
+        .await;
+    first
+        .send(ClientMessage::DestroyEnemyIntent {
+            bullet_tag: 0,
+            enemy_tag,
+            tick,
+        })
+        .await;
+
+    let confirmation = ServerMessage::ConfirmDestroyEnemy {
+        player_tag: first.player_tag,
+        bullet_tag: 0,
+        enemy_tag,
+        new_score: 1,
+    };
+    for client in [&mut first, &mut second] {
+        client.expect_message(confirmation.clone()).await;
+        client
+            .expect("the explosion", |message| match message {
+                ServerMessage::Effect {
+                    kind: EffectKind::EnemyDestroyed,
+                    ..
+                } => Some(()),
+                _ => None,
+            })
+            .await;
+    }
+}
+
+#[rocket::async_test]
+async fn match_history_is_served_newest_first() {
+    let player = |tag, name: Option<&str>, score| PlayerRecord {
+        tag,
+        name: name.map(str::to_string),
+        score,
+        bot: false,
+    };
+    let older = MatchRecord {
+        ended_at: 1,
+        duration_ms: 60_000,
+        players: vec![player(1, Some("Ann"), 12), player(2, None, 8)],
+        disconnect_reason: None,
+    };
+    let newer = MatchRecord {
+        ended_at: 2,
+        duration_ms: 5_000,
+        players: vec![player(1, None, 0), player(2, Some("Bob"), 1)],
+        disconnect_reason: Some(DisconnectReason::ReconnectTimeout),
+    };
+    let server = TestServer::launch_with_history(&[older.clone(), newer.clone()]).await;
+
+    let matches: Vec<MatchRecord> = serde_json::from_str(&server.get("/matches").await).unwrap();
+    assert_eq!(matches, vec![newer.clone(), older]);
+    let matches: Vec<MatchRecord> =
+        serde_json::from_str(&server.get("/matches?limit=1").await).unwrap();
+    assert_eq!(matches, vec![newer]);
+}
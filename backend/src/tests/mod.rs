@@ -0,0 +1,3 @@
+// Runs the real server on a free port and plays it through simulated clients
+mod flows;
+mod support;
@@ -0,0 +1,264 @@
+use rocket::config::{Config, LogLevel, Shutdown as ShutdownConfig};
+use rocket::fairing::AdHoc;
+use rocket::figment::Figment;
+use rocket::futures::{SinkExt, StreamExt};
+use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+use rocket::tokio::net::TcpStream;
+use rocket::tokio::sync::oneshot;
+use rocket::tokio::time::{timeout, Instant};
+use rocket::Shutdown;
+use shooting_game_shared::{ClientMessage, PlayerInput, ServerMessage, PROTOCOL_VERSION};
+use std::net::Ipv4Addr;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+use crate::history::{MatchHistory, MatchRecord};
+
+// Generous as the server ticks in real time, a hung flow still fails instead of blocking
+const EXPECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Tells apart the history databases of tests running in the same process
+static NEXT_HISTORY_FILE: AtomicU32 = AtomicU32::new(0);
+
+// The server for a single test, shut down when dropped
+pub struct TestServer {
+    port: u16,
+    shutdown: Shutdown,
+    history_path: PathBuf,
+}
+
+impl TestServer {
+    pub async fn launch() -> Self {
+        Self::launch_with_history(&[]).await
+    }
+
+    // Starts as if an earlier run had already recorded `records`, oldest first
+    pub async fn launch_with_history(records: &[MatchRecord]) -> Self {
+        let history_file = NEXT_HISTORY_FILE.fetch_add(1, Ordering::Relaxed);
+        let history_path = std::env::temp_dir().join(format!(
+            "shooting_game_history_{}_{history_file}.sqlite3",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&history_path);
+        let history = MatchHistory::open(history_path.clone());
+        for record in records {
+            history.record(record.clone()).await;
+        }
+        drop(history);
+        let config = Config {
+            address: Ipv4Addr::LOCALHOST.into(),
+            // Picked by the OS so tests can run side by side
+            port: 0,
+            log_level: LogLevel::Off,
+            shutdown: ShutdownConfig {
+                ctrlc: false,
+                ..ShutdownConfig::default()
+            },
+            ..Config::debug_default()
+        };
+        let (port_sender, port_receiver) = oneshot::channel();
+        let figment = Figment::from(config).merge(("history_path", &history_path));
+        let rocket = crate::server(rocket::custom(figment))
+            .attach(AdHoc::on_liftoff("Report port", |rocket| {
+                Box::pin(async move {
+                    let _ = port_sender.send(rocket.config().port);
+                })
+            }))
+            .ignite()
+            .await
+            .expect("Test server failed to ignite");
+        let shutdown = rocket.shutdown();
+        rocket::tokio::spawn(rocket.launch());
+        let port = port_receiver.await.expect("Test server failed to launch");
+        Self {
+            port,
+            shutdown,
+            history_path,
+        }
+    }
+
+    // A plain HTTP GET, only the body of the response is returned
+    pub async fn get(&self, path: &str) -> String {
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, self.port))
+            .await
+            .expect("Test client failed to connect");
+        let request =
+            format!("GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+        stream
+            .write_all(request.as_bytes())
+            .await
+            .expect("Test client failed to send");
+        let mut response = String::new();
+        timeout(EXPECT_TIMEOUT, stream.read_to_string(&mut response))
+            .await
+            .expect("Timed out waiting for a response")
+            .expect("Test client failed to read");
+        match response.split_once("\r\n\r\n") {
+            Some((_, body)) => body.to_string(),
+            None => panic!("Response without a body: {response}"),
+        }
+    }
+
+    fn game_url(&self, protocol: u8, room: Option<u32>) -> String {
+        let room = room.map_or(String::new(), |room_id| format!("&room={room_id}"));
+        format!(
+            "ws://127.0.0.1:{}/ws/game?protocol={protocol}{room}",
+            self.port
+        )
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        self.shutdown.clone().notify();
+        let _ = std::fs::remove_file(&self.history_path);
+    }
+}
+
+// Speaks the websocket protocol like the game client, sending JSON and reading whatever the
+// server negotiated
+pub struct TestClient {
+    stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    pub player_tag: u8,
+    pub session_token: u64,
+}
+
+impl TestClient {
+    pub async fn connect(server: &TestServer, protocol: u8, room: Option<u32>) -> Self {
+        Self::connect_to(server.game_url(protocol, room)).await
+    }
+
+    async fn connect_to(url: String) -> Self {
+        let (stream, _) = connect_async(url)
+            .await
+            .expect("Test client failed to connect");
+        Self {
+            stream,
+            player_tag: 0,
+            session_token: 0,
+        }
+    }
+
+    // Connects on the current protocol and waits until the server hands out a player tag
+    pub async fn join(server: &TestServer, room: Option<u32>) -> Self {
+        Self::connect(server, PROTOCOL_VERSION, room)
+            .await
+            .wait_joined()
+            .await
+    }
+
+    // Joins like the game client does with a nickname set
+    pub async fn join_named(server: &TestServer, name: &str) -> Self {
+        let url = format!("{}&name={name}", server.game_url(PROTOCOL_VERSION, None));
+        Self::connect_to(url).await.wait_joined().await
+    }
+
+    async fn wait_joined(mut self) -> Self {
+        let (player_tag, session_token) = self
+            .expect("Joined", |message| match message {
+                ServerMessage::Joined {
+                    player_tag,
+                    session_token,
+                } => Some((player_tag, session_token)),
+                _ => None,
+            })
+            .await;
+        self.player_tag = player_tag;
+        self.session_token = session_token;
+        self
+    }
+
+    pub async fn send(&mut self, message: ClientMessage) {
+        let text = serde_json::to_string(&message).unwrap();
+        self.stream
+            .send(Message::Text(text))
+            .await
+            .expect("Test client failed to send");
+    }
+
+    // Moves the spaceship by `displacement` and reports the bullets it has in flight
+    pub async fn send_input(
+        &mut self,
+        sequence: u32,
+        displacement: (f32, f32),
+        bullets: Vec<(f32, f32)>,
+    ) {
+        self.send(ClientMessage::UpdatePlayerInfo {
+            input: Some(PlayerInput {
+                sequence,
+                displacement,
+            }),
+            bullets,
+        })
+        .await;
+    }
+
+    // The next message from the server, None if nothing came in time
+    pub async fn recv(&mut self, wait: Duration) -> Option<ServerMessage> {
+        let deadline = Instant::now() + wait;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let frame = match timeout(remaining, self.stream.next()).await {
+                Ok(Some(Ok(frame))) => frame,
+                Ok(_) => panic!("Connection closed while waiting for a message"),
+                Err(_) => return None,
+            };
+            let message = match frame {
+                Message::Binary(bytes) => ServerMessage::from_binary(&bytes),
+                Message::Text(text) => serde_json::from_str(&text).ok(),
+                // Control frames are answered by tungstenite itself
+                _ => continue,
+            };
+            match message {
+                Some(message) => return Some(message),
+                None => panic!("Server sent a message the client can't decode"),
+            }
+        }
+    }
+
+    // The close frame the server ends the connection with, as code and reason
+    pub async fn expect_close(&mut self) -> (u16, String) {
+        loop {
+            let frame = match timeout(EXPECT_TIMEOUT, self.stream.next()).await {
+                Ok(Some(Ok(frame))) => frame,
+                Ok(_) => panic!("Connection closed without a close frame"),
+                Err(_) => panic!("Timed out waiting for the connection to close"),
+            };
+            if let Message::Close(frame) = frame {
+                return frame.map_or((0, String::new()), |frame| {
+                    (frame.code.into(), frame.reason.into_owned())
+                });
+            }
+        }
+    }
+
+    // Skips everything else, the Snapshots in particular, until `matcher` picks a message out
+    pub async fn expect<T>(
+        &mut self,
+        description: &str,
+        mut matcher: impl FnMut(ServerMessage) -> Option<T>,
+    ) -> T {
+        let deadline = Instant::now() + EXPECT_TIMEOUT;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let Some(message) = self.recv(remaining).await else {
+                panic!(
+                    "Player {} timed out waiting for {description}",
+                    self.player_tag
+                );
+            };
+            if let Some(matched) = matcher(message) {
+                return matched;
+            }
+        }
+    }
+
+    pub async fn expect_message(&mut self, expected: ServerMessage) {
+        let description = format!("{expected:?}");
+        self.expect(&description, |message| (message == expected).then_some(()))
+            .await;
+    }
+}
@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use bevy::state::state::StateTransitionSteps;
+
+use crate::components::{
+    Asteroid, Boss, Bullet, Downed, Drone, EnemyBullet, Explosion, FloatingText, Missile, Player,
+    PowerUp, Spaceship, UFO,
+};
+use crate::states::{AppState, GameState, OnlineGameState};
+
+// Despawned the moment the app leaves this state, so nothing outlives its screen
+#[derive(Component)]
+pub struct DespawnOnExit(pub AppState);
+
+pub struct CleanupPlugin;
+
+impl Plugin for CleanupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            StateTransition,
+            despawn_on_exit.in_set(StateTransitionSteps::ExitSchedules),
+        )
+        // Retrying starts the next run from Ready without leaving the game
+        .add_systems(
+            OnExit(GameState::GameOver),
+            despawn_scoped_to(AppState::Game),
+        )
+        // A rematch starts the next match from Ready without leaving the room
+        .add_systems(
+            OnTransition {
+                exited: OnlineGameState::Result,
+                entered: OnlineGameState::Ready,
+            },
+            despawn_scoped_to(AppState::OnlineGame),
+        )
+        .add_observer(scope_to_current_state::<Player>)
+        .add_observer(scope_to_current_state::<Spaceship>)
+        .add_observer(scope_to_current_state::<UFO>)
+        .add_observer(scope_to_current_state::<Boss>)
+        .add_observer(scope_to_current_state::<Bullet>)
+        .add_observer(scope_to_current_state::<Missile>)
+        .add_observer(scope_to_current_state::<Drone>)
+        .add_observer(scope_to_current_state::<EnemyBullet>)
+        .add_observer(scope_to_current_state::<PowerUp>)
+        .add_observer(scope_to_current_state::<Asteroid>)
+        .add_observer(scope_to_current_state::<Explosion>)
+        .add_observer(scope_to_current_state::<FloatingText>)
+        .add_observer(scope_to_current_state::<Downed>);
+    }
+}
+
+// Game entities are shared by the single player and online game, so they take whichever one spawned them
+fn scope_to_current_state<T: Component>(
+    ev: Trigger<OnAdd, T>,
+    mut commands: Commands,
+    app_state: Res<State<AppState>>,
+) {
+    if let Ok(mut entity_commands) = commands.get_entity(ev.target()) {
+        entity_commands.insert(DespawnOnExit(app_state.get().clone()));
+    }
+}
+
+fn despawn_on_exit(
+    mut commands: Commands,
+    mut transitions: EventReader<StateTransitionEvent<AppState>>,
+    entity_q: Query<(Entity, &DespawnOnExit)>,
+) {
+    let Some(transition) = transitions.read().last() else {
+        return;
+    };
+    let Some(exited) = &transition.exited else {
+        return;
+    };
+    if transition.entered.as_ref() == Some(exited) {
+        return;
+    }
+    for (entity, despawn_on_exit) in entity_q.iter() {
+        if despawn_on_exit.0 == *exited {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn despawn_scoped_to(app_state: AppState) -> impl Fn(Commands, Query<(Entity, &DespawnOnExit)>) {
+    move |mut commands, entity_q| {
+        for (entity, despawn_on_exit) in entity_q.iter() {
+            if despawn_on_exit.0 == app_state {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+
+use crate::constant::ZIndex;
+use crate::res::GameSpeed;
+
+use super::{collisable::CollisionLayer, Velocity};
+
+const ASTEROID_COLOR: Color = Color::srgb(0.55, 0.45, 0.35);
+
+#[derive(Clone, Copy)]
+pub enum AsteroidSize {
+    Large,
+    Medium,
+    Small,
+}
+
+impl AsteroidSize {
+    pub fn size(&self) -> Vec2 {
+        match self {
+            AsteroidSize::Large => Vec2::splat(80.),
+            AsteroidSize::Medium => Vec2::splat(50.),
+            AsteroidSize::Small => Vec2::splat(30.),
+        }
+    }
+
+    // What a shot breaks it into, small ones just crumble
+    pub fn split(&self) -> Option<AsteroidSize> {
+        match self {
+            AsteroidSize::Large => Some(AsteroidSize::Medium),
+            AsteroidSize::Medium => Some(AsteroidSize::Small),
+            AsteroidSize::Small => None,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Asteroid {
+    position: Vec2,
+    velocity: Vec2,
+    // Radians per second
+    spin: f32,
+    size: AsteroidSize,
+}
+
+impl Asteroid {
+    pub fn new(position: Vec2, velocity: Vec2, spin: f32, size: AsteroidSize) -> Self {
+        Self {
+            position,
+            velocity,
+            spin,
+            size,
+        }
+    }
+
+    pub fn size(&self) -> AsteroidSize {
+        self.size
+    }
+
+    pub fn spin(&self) -> f32 {
+        self.spin
+    }
+}
+
+pub struct AsteroidPlugin;
+
+impl Plugin for AsteroidPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, handle_asteroid_rotation)
+            .add_observer(asteroid_on_added);
+    }
+}
+
+fn asteroid_on_added(
+    ev: Trigger<OnAdd, Asteroid>,
+    mut commands: Commands,
+    asteroid_q: Query<&Asteroid>,
+) {
+    let Ok(asteroid) = asteroid_q.get(ev.target()) else {
+        warn!("Asteroid not found in asteroid_on_added");
+        return;
+    };
+    if let Ok(mut entity_commands) = commands.get_entity(ev.target()) {
+        entity_commands.insert((
+            Velocity::from_vec2(asteroid.velocity),
+            Transform::from_translation(asteroid.position.extend(ZIndex::UFO.z_value())),
+            Sprite {
+                color: ASTEROID_COLOR,
+                custom_size: Some(asteroid.size.size()),
+                ..default()
+            },
+            CollisionLayer::Enemy,
+        ));
+    }
+}
+
+fn handle_asteroid_rotation(
+    mut asteroid_q: Query<(&Asteroid, &mut Transform)>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    for (asteroid, mut transform) in asteroid_q.iter_mut() {
+        transform.rotate_z(asteroid.spin * game_speed.delta_secs(&time));
+    }
+}
@@ -0,0 +1,135 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::prelude::*;
+use shooting_game_shared::util::MOBILE_WINDOW_SIZE;
+
+use crate::constant::ZIndex;
+use crate::res::{AccessibilityOption, GameSpeed};
+
+const BOMBS_PER_LIFE: u8 = 2;
+const SHOCKWAVE_DURATION: Duration = Duration::from_millis(600);
+const SHOCKWAVE_THICKNESS: f32 = 12.;
+
+// Clears the screen once per charge, refilled with every new life
+#[derive(Component)]
+pub struct BombCharges(u8);
+
+impl Default for BombCharges {
+    fn default() -> Self {
+        Self(BOMBS_PER_LIFE)
+    }
+}
+
+impl BombCharges {
+    pub fn count(&self) -> u8 {
+        self.0
+    }
+
+    // False when there is nothing left to set off
+    pub fn take(&mut self) -> bool {
+        let Some(left) = self.0.checked_sub(1) else {
+            return false;
+        };
+        self.0 = left;
+        true
+    }
+
+    // Never goes past what a new life starts with
+    pub fn refill(&mut self) -> bool {
+        if self.0 >= BOMBS_PER_LIFE {
+            return false;
+        }
+        self.0 += 1;
+        true
+    }
+}
+
+// Ring growing out of a bomb, everything hostile inside it is cleared
+#[derive(Component)]
+#[require(Transform)]
+pub struct Shockwave {
+    player: u8,
+    position: Vec2,
+    timer: Timer,
+}
+
+impl Shockwave {
+    pub fn by_player(player: u8, position: Vec2) -> Self {
+        Self {
+            player,
+            position,
+            timer: Timer::new(SHOCKWAVE_DURATION, TimerMode::Once),
+        }
+    }
+
+    pub fn get_player(&self) -> u8 {
+        self.player
+    }
+
+    // Ends as wide as the window diagonal so it covers the screen from anywhere
+    pub fn radius(&self) -> f32 {
+        MOBILE_WINDOW_SIZE.length() * self.timer.fraction()
+    }
+
+    pub fn reaches(&self, position: Vec2) -> bool {
+        self.position.distance(position) <= self.radius()
+    }
+}
+
+pub struct BombPlugin;
+
+impl Plugin for BombPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, expand_shockwaves)
+            .add_observer(shockwave_on_added);
+    }
+}
+
+fn shockwave_on_added(
+    ev: Trigger<OnAdd, Shockwave>,
+    mut commands: Commands,
+    shockwave_q: Query<&Shockwave>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    accessibility: Res<AccessibilityOption>,
+) {
+    let Ok(shockwave) = shockwave_q.get(ev.target()) else {
+        warn!("Shockwave not found in shockwave_on_added");
+        return;
+    };
+    let position = shockwave.position;
+    // The ring is drawn at full size and scaled down, so it starts as a dot
+    let outer_radius = MOBILE_WINDOW_SIZE.length();
+    let alpha = if accessibility.disable_flashing {
+        0.4
+    } else {
+        0.8
+    };
+    if let Ok(mut entity_commands) = commands.get_entity(ev.target()) {
+        entity_commands.insert((
+            Mesh2d(meshes.add(Annulus::new(
+                outer_radius - SHOCKWAVE_THICKNESS,
+                outer_radius,
+            ))),
+            MeshMaterial2d(materials.add(Color::WHITE.with_alpha(alpha))),
+            Transform::from_translation(position.extend(ZIndex::EXPLOSION.z_value()))
+                .with_scale(Vec3::ZERO),
+        ));
+    }
+}
+
+fn expand_shockwaves(
+    mut commands: Commands,
+    mut shockwave_q: Query<(Entity, &mut Shockwave, &mut Transform)>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    for (entity, mut shockwave, mut transform) in shockwave_q.iter_mut() {
+        shockwave.timer.tick(game_speed.delta(&time));
+        transform.scale = Vec3::splat(shockwave.timer.fraction());
+        if shockwave.timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+
+use crate::constant::{ZIndex, BOSS_SIZE};
+use crate::res::ImageHandles;
+use crate::util::{listen_position, Position};
+
+use super::collisable::CollisionLayer;
+
+pub const BOSS_COLOR: Color = Color::srgb(1., 0.6, 0.6);
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum BossPhase {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(Component)]
+pub struct Boss {
+    position: Vec2,
+    health: u8,
+    max_health: u8,
+}
+
+impl Position for Boss {
+    fn get_position(&self) -> Vec2 {
+        self.position
+    }
+    fn set_position(&mut self, position: Vec2) {
+        self.position = position;
+    }
+}
+
+impl Boss {
+    pub fn new(position: Vec2, max_health: u8) -> Self {
+        Self {
+            position,
+            health: max_health,
+            max_health,
+        }
+    }
+
+    pub fn damage(&mut self, amount: u8) {
+        self.health = self.health.saturating_sub(amount);
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health == 0
+    }
+
+    pub fn health_ratio(&self) -> f32 {
+        self.health as f32 / self.max_health as f32
+    }
+
+    pub fn phase(&self) -> BossPhase {
+        match self.health_ratio() {
+            r if r > 2. / 3. => BossPhase::One,
+            r if r > 1. / 3. => BossPhase::Two,
+            _ => BossPhase::Three,
+        }
+    }
+}
+
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, listen_position::<Boss>)
+            .add_observer(handle_boss_on_added);
+    }
+}
+
+fn handle_boss_on_added(
+    ev: Trigger<OnAdd, Boss>,
+    mut commands: Commands,
+    image_handles: Res<ImageHandles>,
+    boss_query: Query<&Boss>,
+) {
+    let Ok(boss) = boss_query.get(ev.target()) else {
+        warn!("Boss not found in handle_boss_on_added");
+        return;
+    };
+    if let Ok(mut entity_commands) = commands.get_entity(ev.target()) {
+        entity_commands.insert((
+            Sprite {
+                image: image_handles.ufo.clone(),
+                custom_size: Some(BOSS_SIZE),
+                color: BOSS_COLOR,
+                ..default()
+            },
+            Transform::from_translation(boss.position.extend(ZIndex::UFO.z_value())),
+            CollisionLayer::Enemy,
+        ));
+    }
+}
@@ -1,14 +1,84 @@
-use bevy::color::palettes::css::YELLOW;
+use bevy::color::palettes::css::{ORANGE, VIOLET, YELLOW};
 use bevy::prelude::*;
-use rand::{rng, Rng};
+use rand::Rng;
 
 use crate::{
-    constant::{ZIndex, BULLET_SIZE},
-    res::PlayerTag,
-    util::{listen_position, Position},
+    constant::{
+        ZIndex, BULLET_SIZE, CHARGED_BULLET_SIZE, COLOR_BLIND_BLUE, COLOR_BLIND_SKY_BLUE,
+        DRONE_BULLET_SIZE,
+    },
+    res::{AccessibilityOption, EffectOption, GameRng, LocalCoop, PlayerTag, RngStream},
+    util::{angle_to_radian, listen_position, Position},
 };
 
-use super::{collisable::Collisable, Player, Velocity};
+use super::particle::ParticleEmitter;
+use super::pool::Poolable;
+use super::{
+    collisable::{CollisionLayer, Pierced},
+    Player, Velocity,
+};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BulletKind {
+    Normal,
+    // Fired after holding shoot, goes through everything in its way
+    Charged,
+    // Smaller and slower, fired by a Drone
+    Drone,
+}
+
+impl BulletKind {
+    pub fn damage(&self) -> u8 {
+        match self {
+            BulletKind::Normal | BulletKind::Drone => 1,
+            BulletKind::Charged => 3,
+        }
+    }
+
+    fn speed(&self) -> f32 {
+        match self {
+            BulletKind::Normal => 10.,
+            BulletKind::Charged => 14.,
+            BulletKind::Drone => 8.,
+        }
+    }
+
+    fn size(&self) -> Vec2 {
+        match self {
+            BulletKind::Normal => BULLET_SIZE,
+            BulletKind::Charged => CHARGED_BULLET_SIZE,
+            BulletKind::Drone => DRONE_BULLET_SIZE,
+        }
+    }
+
+    // Other players' bullets are dimmed
+    fn color(&self, is_local: bool, color_blind: bool) -> Color {
+        if color_blind {
+            let color = match self {
+                BulletKind::Normal => COLOR_BLIND_SKY_BLUE,
+                BulletKind::Charged => Color::WHITE,
+                BulletKind::Drone => COLOR_BLIND_BLUE,
+            };
+            return if is_local {
+                color
+            } else {
+                color.mix(&Color::BLACK, 0.5)
+            };
+        }
+        match (self, is_local) {
+            (BulletKind::Normal, true) => Color::from(YELLOW),
+            (BulletKind::Normal, false) => Color::srgb(0.5, 0.5, 0.),
+            (BulletKind::Charged, true) => Color::from(ORANGE),
+            (BulletKind::Charged, false) => Color::srgb(0.5, 0.32, 0.),
+            (BulletKind::Drone, true) => Color::from(VIOLET),
+            (BulletKind::Drone, false) => Color::srgb(0.47, 0.25, 0.47),
+        }
+    }
+
+    pub fn is_piercing(&self) -> bool {
+        matches!(self, BulletKind::Charged)
+    }
+}
 
 // Only SelfPlayer can have this component
 #[derive(Component)]
@@ -17,7 +87,9 @@ pub struct BulletTag(pub u16);
 #[derive(Component)]
 pub struct Bullet {
     player: u8,
+    kind: BulletKind,
     position: Vec2,
+    velocity: Vec2,
 }
 
 impl Position for Bullet {
@@ -31,16 +103,73 @@ impl Position for Bullet {
 
 impl Bullet {
     pub fn by_player(player: u8, position: Vec2) -> Self {
-        Self { player, position }
+        Self {
+            player,
+            kind: BulletKind::Normal,
+            position,
+            velocity: Vec2::new(0., BulletKind::Normal.speed()),
+        }
+    }
+
+    // Angle in degree, positive value tilts the bullet to the left
+    pub fn by_player_with_angle(player: u8, position: Vec2, angle: f32) -> Self {
+        let velocity = Vec2::new(0., BulletKind::Normal.speed());
+        Self {
+            player,
+            kind: BulletKind::Normal,
+            position,
+            velocity: Vec2::from_angle(angle_to_radian(angle)).rotate(velocity),
+        }
+    }
+
+    pub fn charged(player: u8, position: Vec2) -> Self {
+        Self {
+            player,
+            kind: BulletKind::Charged,
+            position,
+            velocity: Vec2::new(0., BulletKind::Charged.speed()),
+        }
+    }
+
+    // Goes wherever the drone aimed, `direction` must be normalized
+    pub fn by_drone(player: u8, position: Vec2, direction: Vec2) -> Self {
+        Self {
+            player,
+            kind: BulletKind::Drone,
+            position,
+            velocity: direction * BulletKind::Drone.speed(),
+        }
+    }
+
+    // Turns the bullet along with an aiming spaceship
+    pub fn rotated(mut self, rotation: Rot2) -> Self {
+        self.velocity = rotation * self.velocity;
+        self
     }
+
     pub fn get_player(&self) -> u8 {
         self.player
     }
+
+    pub fn kind(&self) -> BulletKind {
+        self.kind
+    }
     pub fn get_position_tuple(&self) -> (f32, f32) {
         (self.position.x, self.position.y)
     }
 }
 
+impl Poolable for Bullet {
+    type Attached = (
+        Velocity,
+        CollisionLayer,
+        BulletTag,
+        Player,
+        ParticleEmitter,
+        Pierced,
+    );
+}
+
 pub struct BulletPlugin;
 
 impl Plugin for BulletPlugin {
@@ -50,33 +179,42 @@ impl Plugin for BulletPlugin {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn bullet_on_added(
     ev: Trigger<OnAdd, Bullet>,
     mut commands: Commands,
     bullet_q: Query<&Bullet>,
     player_tag: Res<PlayerTag>,
+    local_coop: Option<Res<LocalCoop>>,
+    accessibility: Res<AccessibilityOption>,
+    effect_option: Res<EffectOption>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     let bullet = bullet_q.get(ev.target()).unwrap();
-    let color = if bullet.get_player() == player_tag.0 {
-        Color::from(YELLOW)
-    } else {
-        Color::srgb(0.5, 0.5, 0.)
-    };
+    let player = Player(bullet.get_player());
+    let is_local = player.is_local(&player_tag, local_coop.is_some());
 
     if let Ok(mut entity_commands) = commands.get_entity(ev.target()) {
         entity_commands.insert((
-            Velocity { x: 0., y: 10. },
-            Transform::from_translation(bullet.get_position().extend(ZIndex::BULLET.z_value())),
+            Velocity::from_vec2(bullet.velocity),
+            Transform::from_translation(bullet.get_position().extend(ZIndex::BULLET.z_value()))
+                .with_rotation(Quat::from_rotation_z(Vec2::Y.angle_to(bullet.velocity))),
             Sprite {
-                color,
-                custom_size: Some(BULLET_SIZE),
+                color: effect_option.glow(bullet.kind.color(is_local, accessibility.color_blind)),
+                custom_size: Some(bullet.kind.size()),
                 ..default()
             },
-            Player(bullet.get_player()),
+            Visibility::Inherited,
+            player,
         ));
-        if bullet.get_player() == player_tag.0 {
-            let bullet_tag = rng().random_range(u16::MIN..u16::MAX);
-            entity_commands.insert((Collisable::Player, BulletTag(bullet_tag)));
+        if is_local {
+            let bullet_tag = game_rng
+                .stream(RngStream::BulletTag)
+                .random_range(u16::MIN..u16::MAX);
+            entity_commands.insert((CollisionLayer::PlayerBullet, BulletTag(bullet_tag)));
+        }
+        if bullet.kind.is_piercing() {
+            entity_commands.insert(Pierced::default());
         }
     }
 }
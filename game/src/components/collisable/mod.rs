@@ -0,0 +1,327 @@
+mod spatial_hash;
+
+use bevy::{
+    math::bounding::{Aabb2d, Bounded2d, BoundingVolume, IntersectsVolume},
+    prelude::*,
+};
+
+use super::{
+    graze::{Grazed, NearMiss},
+    invisible::{BulletInvisible, Invisible},
+    Bullet, Player, Spaceship,
+};
+use crate::res::{CollisionMatrix, FriendlyFireOption, LocalCoop};
+use spatial_hash::SpatialHash;
+
+// How far past the hitbox a hostile can pass and still count as a near miss
+const GRAZE_MARGIN: f32 = 24.;
+
+// What an entity collides as, CollisionMatrix decides which layers hit each other
+#[derive(Component, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[require(Sprite)]
+pub enum CollisionLayer {
+    // Spaceships and drones
+    Player,
+    PlayerBullet,
+    Enemy,
+    EnemyBullet,
+    Pickup,
+    // Bonus stage ore, only there to be shot so it can't be grazed or crashed into
+    Ore,
+}
+
+impl CollisionLayer {
+    pub const COUNT: usize = 6;
+}
+
+// Entities this one already went through, they never collide with it again
+#[derive(Component, Default)]
+pub struct Pierced(Vec<Entity>);
+
+impl Pierced {
+    pub fn add(&mut self, entity: Entity) {
+        self.0.push(entity);
+    }
+
+    fn contains(&self, entity: Entity) -> bool {
+        self.0.contains(&entity)
+    }
+}
+
+#[derive(Event)]
+pub struct CollidedEvent {
+    pub player: Entity,
+    pub enemy: Entity,
+}
+
+// A hostile went through the sensor around a spaceship and left it without touching the hitbox
+#[derive(Event)]
+pub struct GrazeEvent {
+    pub spaceship: Entity,
+}
+
+// A co-op player's bullet hit the other player's spaceship
+#[derive(Event)]
+pub struct FriendlyFireEvent {
+    pub bullet: Entity,
+    pub spaceship: Entity,
+}
+
+#[derive(Event)]
+pub struct PowerUpCollidedEvent {
+    pub spaceship: Entity,
+    pub power_up: Entity,
+}
+
+// The box around a sprite after its rotation, an upright sprite keeps its own size
+pub fn rotated_bounds(transform: &Transform, size: Vec2) -> Aabb2d {
+    let (_, _, angle) = transform.rotation.to_euler(EulerRot::XYZ);
+    let isometry = Isometry2d::new(transform.translation.truncate(), Rot2::radians(angle));
+    Rectangle::from_size(size).aabb_2d(isometry)
+}
+
+pub struct CollisablePlugin;
+
+impl Plugin for CollisablePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<CollidedEvent>()
+            .add_event::<GrazeEvent>()
+            .add_event::<PowerUpCollidedEvent>()
+            .add_event::<FriendlyFireEvent>()
+            .add_systems(
+                Update,
+                (
+                    check_collision,
+                    check_graze,
+                    check_power_up_collision,
+                    check_friendly_fire
+                        .run_if(resource_exists::<LocalCoop>.and(friendly_fire_enabled)),
+                ),
+            );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn check_collision(
+    mut event_writer: EventWriter<CollidedEvent>,
+    matrix: Res<CollisionMatrix>,
+    collisable_query: Query<(
+        Entity,
+        &Transform,
+        &Sprite,
+        &CollisionLayer,
+        Has<Invisible>,
+        Has<BulletInvisible>,
+        Option<&Pierced>,
+    )>,
+) {
+    // (entity, aabb, layer, invisible, bullet invisible, already pierced)
+    let mut colliders: Vec<(Entity, Aabb2d, CollisionLayer, bool, bool, Option<&Pierced>)> =
+        collisable_query
+            .iter()
+            .map(
+                |(entity, transform, sprite, layer, invisible, bullet_invisible, pierced)| {
+                    let aabb = rotated_bounds(transform, sprite.custom_size.unwrap());
+                    (entity, aabb, *layer, invisible, bullet_invisible, pierced)
+                },
+            )
+            .collect();
+    // Candidates come back in layer order, so enemies are checked before enemy bullets
+    colliders.sort_by_key(|(_, _, layer, ..)| *layer);
+
+    let mut spatial_hash = SpatialHash::default();
+    for (index, (_, aabb, ..)) in colliders.iter().enumerate() {
+        spatial_hash.insert(index, aabb);
+    }
+
+    for (entity, aabb, layer, invisible, bullet_invisible, pierced) in colliders.iter() {
+        if !matrix.hits_anything(*layer) {
+            continue;
+        }
+        for index in spatial_hash.query(aabb) {
+            let (other_entity, other_aabb, other_layer, ..) = &colliders[index];
+            if !matrix.collides(*layer, *other_layer) {
+                continue;
+            }
+            if pierced.is_some_and(|pierced| pierced.contains(*other_entity)) {
+                continue;
+            }
+            let immune = match other_layer {
+                CollisionLayer::Enemy => *invisible,
+                CollisionLayer::EnemyBullet => *bullet_invisible,
+                _ => false,
+            };
+            if !immune && aabb.intersects(other_aabb) {
+                event_writer.write(CollidedEvent {
+                    player: *entity,
+                    enemy: *other_entity,
+                });
+                return;
+            }
+        }
+    }
+}
+
+// Spaceships with i-frames can't graze, hiding in them would be free score
+#[allow(clippy::type_complexity)]
+fn check_graze(
+    mut commands: Commands,
+    mut event_writer: EventWriter<GrazeEvent>,
+    spaceship_query: Query<
+        (Entity, &Transform, &Sprite, Has<Invisible>),
+        (With<Spaceship>, With<CollisionLayer>),
+    >,
+    hostile_query: Query<
+        (
+            Entity,
+            &Transform,
+            &Sprite,
+            &CollisionLayer,
+            Option<&NearMiss>,
+        ),
+        Without<Grazed>,
+    >,
+) {
+    let spaceships: Vec<(Entity, Aabb2d, bool)> = spaceship_query
+        .iter()
+        .map(|(entity, transform, sprite, invisible)| {
+            let aabb = rotated_bounds(transform, sprite.custom_size.unwrap());
+            (entity, aabb, invisible)
+        })
+        .collect();
+    let hostiles: Vec<(Entity, Aabb2d, Option<Entity>)> = hostile_query
+        .iter()
+        .filter(|(_, _, _, layer, _)| {
+            matches!(layer, CollisionLayer::Enemy | CollisionLayer::EnemyBullet)
+        })
+        .map(|(entity, transform, sprite, _, near_miss)| {
+            let aabb = rotated_bounds(transform, sprite.custom_size.unwrap());
+            (entity, aabb, near_miss.map(|near_miss| near_miss.0))
+        })
+        .collect();
+
+    let mut spatial_hash = SpatialHash::default();
+    for (index, (_, aabb, _)) in hostiles.iter().enumerate() {
+        spatial_hash.insert(index, aabb);
+    }
+
+    // Touching a hitbox spoils the graze, even through i-frames
+    let mut spoiled: Vec<Entity> = Vec::new();
+    for (spaceship_entity, spaceship_aabb, invisible) in spaceships.iter() {
+        let sensor = spaceship_aabb.grow(Vec2::splat(GRAZE_MARGIN));
+        for index in spatial_hash.query(&sensor) {
+            let (enemy_entity, enemy_aabb, near_miss) = &hostiles[index];
+            if spoiled.contains(enemy_entity) || !sensor.intersects(enemy_aabb) {
+                continue;
+            }
+            let Ok(mut entity_commands) = commands.get_entity(*enemy_entity) else {
+                continue;
+            };
+            if spaceship_aabb.intersects(enemy_aabb) {
+                spoiled.push(*enemy_entity);
+                entity_commands.insert(Grazed).remove::<NearMiss>();
+            } else if near_miss.is_none() && !invisible {
+                entity_commands.insert(NearMiss(*spaceship_entity));
+            }
+        }
+    }
+
+    for (enemy_entity, enemy_aabb, near_miss) in hostiles.iter() {
+        let Some(spaceship_entity) = near_miss else {
+            continue;
+        };
+        if spoiled.contains(enemy_entity) {
+            continue;
+        }
+        let spaceship = spaceships
+            .iter()
+            .find(|(entity, ..)| entity == spaceship_entity);
+        let still_near = spaceship.is_some_and(|(_, spaceship_aabb, _)| {
+            spaceship_aabb
+                .grow(Vec2::splat(GRAZE_MARGIN))
+                .intersects(enemy_aabb)
+        });
+        if still_near {
+            continue;
+        }
+        let Ok(mut entity_commands) = commands.get_entity(*enemy_entity) else {
+            continue;
+        };
+        entity_commands.remove::<NearMiss>();
+        // A spaceship that went down or started blinking meanwhile gets nothing
+        if spaceship.is_some_and(|(_, _, invisible)| !invisible) {
+            entity_commands.insert(Grazed);
+            event_writer.write(GrazeEvent {
+                spaceship: *spaceship_entity,
+            });
+        }
+    }
+}
+
+// Power ups can still be collected while the spaceship is Invisible
+fn check_power_up_collision(
+    mut event_writer: EventWriter<PowerUpCollidedEvent>,
+    collisable_query: Query<(Entity, &Transform, &Sprite, &CollisionLayer, Has<Spaceship>)>,
+) {
+    let mut spaceships: Vec<(Entity, Aabb2d)> = Vec::new();
+    let mut power_ups: Vec<(Entity, Aabb2d)> = Vec::new();
+
+    for (entity, transform, sprite, collisable, is_spaceship) in collisable_query.iter() {
+        let aabb = rotated_bounds(transform, sprite.custom_size.unwrap());
+
+        match collisable {
+            CollisionLayer::Player if is_spaceship => spaceships.push((entity, aabb)),
+            CollisionLayer::Pickup => power_ups.push((entity, aabb)),
+            _ => {}
+        }
+    }
+
+    for (spaceship_entity, spaceship_aabb) in spaceships.iter() {
+        for (power_up_entity, power_up_aabb) in power_ups.iter() {
+            if spaceship_aabb.intersects(power_up_aabb) {
+                event_writer.write(PowerUpCollidedEvent {
+                    spaceship: *spaceship_entity,
+                    power_up: *power_up_entity,
+                });
+            }
+        }
+    }
+}
+
+fn friendly_fire_enabled(friendly_fire_option: Res<FriendlyFireOption>) -> bool {
+    friendly_fire_option.enabled
+}
+
+// Own bullets start inside the spaceship, so only the other player's count
+#[allow(clippy::type_complexity)]
+fn check_friendly_fire(
+    mut event_writer: EventWriter<FriendlyFireEvent>,
+    spaceship_query: Query<
+        (Entity, &Transform, &Sprite, &Player),
+        (With<Spaceship>, Without<Invisible>),
+    >,
+    bullet_query: Query<(Entity, &Transform, &Sprite, &Player, Option<&Pierced>), With<Bullet>>,
+) {
+    for (spaceship_entity, spaceship_transform, spaceship_sprite, spaceship_player) in
+        spaceship_query.iter()
+    {
+        let spaceship_aabb =
+            rotated_bounds(spaceship_transform, spaceship_sprite.custom_size.unwrap());
+        for (bullet_entity, bullet_transform, bullet_sprite, bullet_player, pierced) in
+            bullet_query.iter()
+        {
+            if bullet_player.0 == spaceship_player.0
+                || pierced.is_some_and(|pierced| pierced.contains(spaceship_entity))
+            {
+                continue;
+            }
+            let bullet_aabb = rotated_bounds(bullet_transform, bullet_sprite.custom_size.unwrap());
+            if spaceship_aabb.intersects(&bullet_aabb) {
+                event_writer.write(FriendlyFireEvent {
+                    bullet: bullet_entity,
+                    spaceship: spaceship_entity,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use bevy::{math::bounding::Aabb2d, platform::collections::HashMap, prelude::*};
+
+// Roughly the size of a UFO, so most entities only touch a few cells
+const CELL_SIZE: f32 = 100.;
+
+// Uniform grid broadphase, only items sharing a cell are worth an exact check
+#[derive(Default)]
+pub struct SpatialHash {
+    cells: HashMap<IVec2, Vec<usize>>,
+}
+
+impl SpatialHash {
+    pub fn insert(&mut self, index: usize, aabb: &Aabb2d) {
+        for cell in cells_of(aabb) {
+            self.cells.entry(cell).or_default().push(index);
+        }
+    }
+
+    // Candidates come back sorted by insertion index without duplicates
+    pub fn query(&self, aabb: &Aabb2d) -> Vec<usize> {
+        let mut candidates: Vec<usize> = cells_of(aabb)
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates
+    }
+}
+
+fn cells_of(aabb: &Aabb2d) -> impl Iterator<Item = IVec2> {
+    let min = (aabb.min / CELL_SIZE).floor().as_ivec2();
+    let max = (aabb.max / CELL_SIZE).floor().as_ivec2();
+    (min.x..=max.x).flat_map(move |x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+}
@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::math::bounding::BoundingVolume;
+use bevy::prelude::*;
+use shooting_game_shared::util::{EdgeUtil, SPACESHIP_SIZE};
+
+use crate::res::{AccessibilityOption, EdgeMode, GameSpeed};
+
+use super::pool::PoolCommandsExt;
+use super::{rotated_bounds, Particle};
+
+const DASH_COOLDOWN: Duration = Duration::from_secs(3);
+const DASH_DISTANCE: f32 = 180.;
+// Fixed ticks it takes to cover the distance
+const DASH_TICKS: f32 = 8.;
+pub const DASH_INVINCIBILITY: Duration = Duration::from_millis(300);
+const TRAIL_PER_TICK: f32 = 3.;
+const TRAIL_SIZE: f32 = 10.;
+const TRAIL_LIFETIME: Duration = Duration::from_millis(250);
+const TRAIL_COLOR: Color = Color::srgba(0.4, 0.9, 1., 0.5);
+
+// Bursts the spaceship a fixed distance, usable again once the cooldown is over
+#[derive(Component)]
+pub struct Dash {
+    cooldown: Timer,
+    // Direction and distance left to cover while dashing
+    active: Option<(Vec2, f32)>,
+}
+
+impl Default for Dash {
+    // Every new life starts with the dash ready
+    fn default() -> Self {
+        let mut cooldown = Timer::new(DASH_COOLDOWN, TimerMode::Once);
+        cooldown.tick(DASH_COOLDOWN);
+        Self {
+            cooldown,
+            active: None,
+        }
+    }
+}
+
+impl Dash {
+    pub fn is_ready(&self) -> bool {
+        self.cooldown.finished() && self.active.is_none()
+    }
+
+    // False while recharging or without a direction to go
+    pub fn start(&mut self, direction: Vec2) -> bool {
+        if !self.is_ready() {
+            return false;
+        }
+        let Some(direction) = direction.try_normalize() else {
+            return false;
+        };
+        self.cooldown.reset();
+        self.active = Some((direction, DASH_DISTANCE));
+        true
+    }
+
+    // From 0 right after a dash up to 1 once it is ready again
+    pub fn charge(&self) -> f32 {
+        self.cooldown.fraction()
+    }
+}
+
+pub struct DashPlugin;
+
+impl Plugin for DashPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, recharge_dash)
+            .add_systems(FixedUpdate, apply_dash);
+    }
+}
+
+fn recharge_dash(mut dash_q: Query<&mut Dash>, time: Res<Time>, game_speed: Res<GameSpeed>) {
+    for mut dash in dash_q.iter_mut() {
+        if dash.active.is_none() {
+            dash.cooldown.tick(game_speed.delta(&time));
+        }
+    }
+}
+
+// Moved alongside velocity so the dash is interpolated like any other movement
+fn apply_dash(
+    mut commands: Commands,
+    mut dash_q: Query<(&mut Dash, &mut Transform)>,
+    edge_mode: Res<EdgeMode>,
+    game_speed: Res<GameSpeed>,
+    accessibility: Res<AccessibilityOption>,
+) {
+    for (mut dash, mut transform) in dash_q.iter_mut() {
+        let Some((direction, remaining)) = dash.active else {
+            continue;
+        };
+        let step = (DASH_DISTANCE / DASH_TICKS * game_speed.factor()).min(remaining);
+        let from = transform.translation.truncate();
+        let target = from + direction * step;
+        // A turned ship sticks out further, so it stops sooner
+        let bounds = rotated_bounds(&transform, SPACESHIP_SIZE);
+        let edge = EdgeUtil::new(bounds.half_size() * 2.);
+        let mut to = Vec2::new(
+            if edge_mode.is_wrap() {
+                target.x
+            } else {
+                target.x.clamp(edge.left_in(), edge.right_in())
+            },
+            target.y.clamp(edge.bottom_in(), edge.top_in()),
+        );
+
+        let count = (TRAIL_PER_TICK * accessibility.particle_density()).ceil() as u32;
+        for index in 0..count {
+            commands.spawn_pooled(Particle::new(
+                from.lerp(to, index as f32 / count as f32),
+                Vec2::ZERO,
+                TRAIL_SIZE,
+                TRAIL_COLOR,
+                TRAIL_LIFETIME,
+            ));
+        }
+
+        // Running into an edge ends the dash there instead of pushing against it
+        let blocked = to != target;
+        if edge_mode.is_wrap() {
+            to.x = EdgeUtil::wrap_x(to.x);
+        }
+        transform.translation = to.extend(transform.translation.z);
+        let remaining = remaining - step;
+        dash.active = (remaining > 0. && !blocked).then_some((direction, remaining));
+    }
+}
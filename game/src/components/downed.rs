@@ -0,0 +1,108 @@
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use bevy::color::palettes::css::AQUA;
+use bevy::prelude::*;
+use shooting_game_shared::game_related::{REVIVE_DURATION, REVIVE_RADIUS};
+
+use crate::constant::ZIndex;
+use crate::util::Position;
+
+use super::{Player, Spaceship};
+
+const BEACON_COLOR: Color = Color::srgba(0.3, 0.9, 1., 0.8);
+const BEACON_SIZE: Vec2 = Vec2::splat(24.);
+const RING_RADIUS: f32 = BEACON_SIZE.x;
+const RING_TRACK_COLOR: Color = Color::srgba(1., 1., 1., 0.2);
+
+// Beacon left where a co-op player went down for good, a partner hovering it brings them back
+#[derive(Component)]
+pub struct Downed {
+    player: u8,
+    position: Vec2,
+    revive: Timer,
+}
+
+impl Downed {
+    pub fn new(player: u8, position: Vec2) -> Self {
+        Self {
+            player,
+            position,
+            revive: Timer::new(REVIVE_DURATION, TimerMode::Once),
+        }
+    }
+
+    pub fn player(&self) -> u8 {
+        self.player
+    }
+
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    // Any spaceship but the downed player's own close enough to the beacon
+    pub fn hovered<'a>(
+        &self,
+        spaceships: impl IntoIterator<Item = (&'a Spaceship, &'a Player)>,
+    ) -> bool {
+        spaceships.into_iter().any(|(spaceship, player)| {
+            player.0 != self.player
+                && spaceship.get_position().distance(self.position) <= REVIVE_RADIUS
+        })
+    }
+
+    // Returns true once the partner has hovered for the whole duration
+    pub fn channel(&mut self, delta: Duration) -> bool {
+        self.revive.tick(delta).finished()
+    }
+
+    // Leaving the beacon throws the progress away
+    pub fn interrupt(&mut self) {
+        self.revive.reset();
+    }
+
+    pub fn progress(&self) -> f32 {
+        self.revive.fraction()
+    }
+}
+
+pub struct DownedPlugin;
+
+impl Plugin for DownedPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, draw_revive_rings)
+            .add_observer(downed_on_added);
+    }
+}
+
+fn downed_on_added(ev: Trigger<OnAdd, Downed>, mut commands: Commands, downed_q: Query<&Downed>) {
+    let Ok(downed) = downed_q.get(ev.target()) else {
+        warn!("Downed not found in downed_on_added");
+        return;
+    };
+    if let Ok(mut entity_commands) = commands.get_entity(ev.target()) {
+        entity_commands.insert((
+            Transform::from_translation(downed.position.extend(ZIndex::POWERUP.z_value()))
+                .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+            Sprite {
+                color: BEACON_COLOR,
+                custom_size: Some(BEACON_SIZE),
+                ..default()
+            },
+        ));
+    }
+}
+
+// A faint full ring as the track, filled clockwise from the top as the revive goes on
+fn draw_revive_rings(mut gizmos: Gizmos, downed_q: Query<&Downed>) {
+    for downed in downed_q.iter() {
+        gizmos.circle_2d(downed.position, RING_RADIUS, RING_TRACK_COLOR);
+        let progress = downed.progress();
+        if progress <= 0. {
+            continue;
+        }
+        let arc_angle = TAU * progress;
+        let isometry = Isometry2d::new(downed.position, Rot2::radians(-arc_angle / 2.));
+        gizmos.arc_2d(isometry, arc_angle, RING_RADIUS, AQUA);
+    }
+}
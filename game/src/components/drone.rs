@@ -0,0 +1,106 @@
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use bevy::color::palettes::css::VIOLET;
+use bevy::prelude::*;
+
+use crate::constant::{ZIndex, DRONE_SIZE};
+use crate::res::GameSpeed;
+use crate::util::Position;
+
+use super::collisable::CollisionLayer;
+use super::{Player, Spaceship};
+
+const ORBIT_RADIUS: f32 = 80.;
+// Radians per second
+const ORBIT_SPEED: f32 = 2.5;
+const DRONE_FIRE_INTERVAL: Duration = Duration::from_millis(500);
+
+// Circles its owner's spaceship and shoots on its own, one hit destroys it
+#[derive(Component)]
+pub struct Drone {
+    player: u8,
+    angle: f32,
+    position: Vec2,
+    fire_timer: Timer,
+}
+
+impl Position for Drone {
+    fn get_position(&self) -> Vec2 {
+        self.position
+    }
+    fn set_position(&mut self, position: Vec2) {
+        self.position = position;
+    }
+}
+
+impl Drone {
+    pub fn by_player(player: u8, position: Vec2) -> Self {
+        Self {
+            player,
+            angle: 0.,
+            position: position + Vec2::new(ORBIT_RADIUS, 0.),
+            fire_timer: Timer::new(DRONE_FIRE_INTERVAL, TimerMode::Repeating),
+        }
+    }
+
+    pub fn get_player(&self) -> u8 {
+        self.player
+    }
+
+    // True once every fire interval
+    pub fn tick_fire(&mut self, delta: Duration) -> bool {
+        self.fire_timer.tick(delta).just_finished()
+    }
+}
+
+pub struct DronePlugin;
+
+impl Plugin for DronePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, orbit_drones)
+            .add_observer(drone_on_added);
+    }
+}
+
+fn drone_on_added(ev: Trigger<OnAdd, Drone>, mut commands: Commands, drone_q: Query<&Drone>) {
+    let Ok(drone) = drone_q.get(ev.target()) else {
+        warn!("Drone not found in drone_on_added");
+        return;
+    };
+    if let Ok(mut entity_commands) = commands.get_entity(ev.target()) {
+        entity_commands.insert((
+            Sprite {
+                color: Color::from(VIOLET),
+                custom_size: Some(DRONE_SIZE),
+                ..default()
+            },
+            Transform::from_translation(drone.position.extend(ZIndex::SPACESHIP.z_value())),
+            CollisionLayer::Player,
+            Player(drone.player),
+        ));
+    }
+}
+
+// A drone without its spaceship has nothing to orbit and goes with it
+fn orbit_drones(
+    mut commands: Commands,
+    mut drone_q: Query<(Entity, &mut Drone, &mut Transform)>,
+    spaceship_q: Query<(&Spaceship, &Player)>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    for (entity, mut drone, mut transform) in drone_q.iter_mut() {
+        let Some((spaceship, _)) = spaceship_q
+            .iter()
+            .find(|(_, player)| player.0 == drone.player)
+        else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        drone.angle = (drone.angle + ORBIT_SPEED * game_speed.delta_secs(&time)) % TAU;
+        let position = spaceship.get_position() + Vec2::from_angle(drone.angle) * ORBIT_RADIUS;
+        drone.set_position(position);
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
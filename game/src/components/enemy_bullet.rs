@@ -0,0 +1,55 @@
+use bevy::color::palettes::css::RED;
+use bevy::prelude::*;
+
+use crate::constant::{ZIndex, COLOR_BLIND_VERMILLION, ENEMY_BULLET_SIZE};
+use crate::res::AccessibilityOption;
+
+use super::{collisable::CollisionLayer, Velocity};
+
+#[derive(Component)]
+pub struct EnemyBullet {
+    position: Vec2,
+    velocity: Vec2,
+}
+
+impl EnemyBullet {
+    pub fn new(position: Vec2, velocity: Vec2) -> Self {
+        Self { position, velocity }
+    }
+}
+
+pub struct EnemyBulletPlugin;
+
+impl Plugin for EnemyBulletPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(enemy_bullet_on_added);
+    }
+}
+
+fn enemy_bullet_on_added(
+    ev: Trigger<OnAdd, EnemyBullet>,
+    mut commands: Commands,
+    enemy_bullet_q: Query<&EnemyBullet>,
+    accessibility: Res<AccessibilityOption>,
+) {
+    let Ok(enemy_bullet) = enemy_bullet_q.get(ev.target()) else {
+        warn!("EnemyBullet not found in enemy_bullet_on_added");
+        return;
+    };
+    if let Ok(mut entity_commands) = commands.get_entity(ev.target()) {
+        entity_commands.insert((
+            Velocity::from_vec2(enemy_bullet.velocity),
+            Transform::from_translation(enemy_bullet.position.extend(ZIndex::BULLET.z_value())),
+            Sprite {
+                color: if accessibility.color_blind {
+                    COLOR_BLIND_VERMILLION
+                } else {
+                    Color::from(RED)
+                },
+                custom_size: Some(ENEMY_BULLET_SIZE),
+                ..default()
+            },
+            CollisionLayer::EnemyBullet,
+        ));
+    }
+}
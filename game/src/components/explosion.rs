@@ -1,30 +1,82 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 use bevy::time::Timer;
 
 use crate::constant::{ZIndex::EXPLOSION, EXPLOSION_SIZE};
-use crate::res::ImageHandles;
+use crate::res::{EffectOption, GameSpeed, ImageHandles};
+
+use super::pool::{PoolCommandsExt, Poolable};
+
+// Frames laid out left to right in explosion.png
+pub const EXPLOSION_FRAMES: usize = 8;
+pub const EXPLOSION_FRAME_SIZE: UVec2 = UVec2::splat(64);
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ExplosionKind {
+    BulletImpact,
+    UfoDeath,
+    PlayerDeath,
+}
+
+impl ExplosionKind {
+    fn size(&self) -> Vec2 {
+        match self {
+            ExplosionKind::BulletImpact => EXPLOSION_SIZE / 4.,
+            ExplosionKind::UfoDeath => EXPLOSION_SIZE,
+            ExplosionKind::PlayerDeath => EXPLOSION_SIZE * 1.25,
+        }
+    }
+
+    fn frame_duration(&self) -> Duration {
+        match self {
+            ExplosionKind::BulletImpact => Duration::from_millis(30),
+            ExplosionKind::UfoDeath => Duration::from_millis(60),
+            ExplosionKind::PlayerDeath => Duration::from_millis(90),
+        }
+    }
+}
 
 #[derive(Component)]
 #[require(Transform)]
 pub struct Explosion {
     position: Vec2,
-    timer: Timer,
+    size: Vec2,
+    frame_timer: Timer,
 }
 
 impl Explosion {
-    pub fn new(position: Vec2) -> Self {
+    pub fn new(position: Vec2, kind: ExplosionKind) -> Self {
         Self {
             position,
-            timer: Timer::from_seconds(0.5, TimerMode::Once),
+            size: kind.size(),
+            frame_timer: Timer::new(kind.frame_duration(), TimerMode::Repeating),
         }
     }
+
+    pub fn with_size(mut self, size: Vec2) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+}
+
+impl Poolable for Explosion {
+    type Attached = ();
 }
 
 pub struct ExplosionPlugin;
 
 impl Plugin for ExplosionPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, apply_explosion)
+        app.add_systems(Update, animate_explosion)
             .add_observer(handle_explosion_on_added);
     }
 }
@@ -34,33 +86,42 @@ fn handle_explosion_on_added(
     mut commands: Commands,
     explosion_query: Query<&Explosion>,
     image_handles: Res<ImageHandles>,
+    effect_option: Res<EffectOption>,
 ) {
-    let explosion = explosion_query.get(ev.target()).unwrap();
+    let Ok(explosion) = explosion_query.get(ev.target()) else {
+        warn!("Explosion not found in handle_explosion_on_added");
+        return;
+    };
     if let Ok(mut entity_commands) = commands.get_entity(ev.target()) {
         entity_commands.insert((
             Sprite {
                 image: image_handles.explosion.clone(),
-                custom_size: Some(EXPLOSION_SIZE),
+                texture_atlas: Some(TextureAtlas::from(image_handles.explosion_layout.clone())),
+                custom_size: Some(explosion.size),
+                color: effect_option.glow(Color::WHITE),
                 ..default()
             },
             Transform::from_translation(explosion.position.extend(EXPLOSION.z_value())),
+            Visibility::Inherited,
         ));
     }
 }
 
-fn apply_explosion(
+// Steps through the sheet one frame per tick and goes back to the pool after the last one
+fn animate_explosion(
     mut commands: Commands,
-    mut explosion_queries: Query<(Entity, &mut Explosion, &mut Transform)>,
+    mut explosion_queries: Query<(Entity, &mut Explosion, &mut Sprite)>,
     time: Res<Time>,
+    game_speed: Res<GameSpeed>,
 ) {
-    for (entity, mut explosion, mut transform) in explosion_queries.iter_mut() {
-        explosion.timer.tick(time.delta());
-        transform.scale.x += 0.01;
-        transform.scale.y += 0.01;
-        if explosion.timer.finished() {
-            if let Ok(mut entity_commands) = commands.get_entity(entity) {
-                entity_commands.despawn();
-            }
+    for (entity, mut explosion, mut sprite) in explosion_queries.iter_mut() {
+        explosion.frame_timer.tick(game_speed.delta(&time));
+        let Some(atlas) = sprite.texture_atlas.as_mut() else {
+            continue;
+        };
+        atlas.index += explosion.frame_timer.times_finished_this_tick() as usize;
+        if atlas.index >= EXPLOSION_FRAMES {
+            commands.release_pooled::<Explosion>(entity);
         }
     }
 }
@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+
+use crate::constant::ZIndex;
+use crate::res::GameSpeed;
+
+const FLOATING_TEXT_SECS: f32 = 0.5;
+const RISE_SPEED: f32 = 80.;
+const FONT_SIZE: f32 = 24.;
+
+// Short-lived feedback text shown at a world position, rises and fades out
+#[derive(Component)]
+#[require(Transform)]
+pub struct FloatingText {
+    position: Vec2,
+    text: String,
+    color: Color,
+    timer: Timer,
+}
+
+impl FloatingText {
+    pub fn new(position: Vec2, text: impl Into<String>) -> Self {
+        Self {
+            position,
+            text: text.into(),
+            color: Color::WHITE,
+            timer: Timer::from_seconds(FLOATING_TEXT_SECS, TimerMode::Once),
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+pub struct FloatingTextPlugin;
+
+impl Plugin for FloatingTextPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, apply_floating_text)
+            .add_observer(floating_text_on_added);
+    }
+}
+
+fn floating_text_on_added(
+    ev: Trigger<OnAdd, FloatingText>,
+    mut commands: Commands,
+    floating_text_q: Query<&FloatingText>,
+) {
+    let Ok(floating_text) = floating_text_q.get(ev.target()) else {
+        warn!("FloatingText not found in floating_text_on_added");
+        return;
+    };
+    if let Ok(mut entity_commands) = commands.get_entity(ev.target()) {
+        entity_commands.insert((
+            Text2d::new(floating_text.text.clone()),
+            TextFont::from_font_size(FONT_SIZE),
+            TextColor(floating_text.color),
+            Transform::from_translation(floating_text.position.extend(ZIndex::TEXT.z_value())),
+        ));
+    }
+}
+
+fn apply_floating_text(
+    mut commands: Commands,
+    mut floating_text_q: Query<(Entity, &mut FloatingText, &mut Transform, &mut TextColor)>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    for (entity, mut floating_text, mut transform, mut text_color) in floating_text_q.iter_mut() {
+        floating_text.timer.tick(game_speed.delta(&time));
+        if floating_text.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        transform.translation.y += RISE_SPEED * game_speed.delta_secs(&time);
+        text_color.0 = floating_text
+            .color
+            .with_alpha(1. - floating_text.timer.fraction());
+    }
+}
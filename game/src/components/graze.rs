@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+const METER_PER_GRAZE: f32 = 0.1;
+
+// Near misses over the whole game, the meter fills up with every one of them
+#[derive(Component, Default)]
+pub struct Graze {
+    count: u32,
+    meter: f32,
+}
+
+impl Graze {
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    pub fn meter(&self) -> f32 {
+        self.meter
+    }
+
+    // True when this graze filled the meter, which then starts over
+    pub fn add(&mut self) -> bool {
+        self.count += 1;
+        self.meter += METER_PER_GRAZE;
+        if self.meter < 1. - f32::EPSILON {
+            return false;
+        }
+        self.meter = 0.;
+        true
+    }
+}
+
+// Each hostile only counts as a graze once, a hostile that touched a hitbox never does
+#[derive(Component)]
+pub struct Grazed;
+
+// A hostile inside the sensor around this spaceship, it only counts once it leaves untouched
+#[derive(Component)]
+pub struct NearMiss(pub Entity);
@@ -1,16 +1,55 @@
 use bevy::prelude::*;
 
+use crate::res::Difficulty;
+
 const INITIAL_HEALTH: u8 = 3;
+// Max health upgrades stop here, more pips wouldn't fit the HUD
+const HEALTH_CAP: u8 = 8;
 
 #[derive(Component)]
-pub struct Health(pub u8);
+pub struct Health {
+    pub current: u8,
+    // Starts at the difficulty's starting health, upgrades raise it for the rest of the run
+    pub max: u8,
+}
 
 impl Health {
     pub fn new() -> Self {
-        Self(INITIAL_HEALTH)
+        Self::full(INITIAL_HEALTH)
+    }
+
+    pub fn from_difficulty(difficulty: &Difficulty) -> Self {
+        Self::full(difficulty.starting_health())
+    }
+
+    fn full(max: u8) -> Self {
+        Self { current: max, max }
     }
 
     pub fn reduce(&mut self) {
-        self.0 -= 1;
+        self.current -= 1;
+    }
+
+    // Never goes past the max health
+    pub fn heal(&mut self) -> bool {
+        if self.current >= self.max {
+            return false;
+        }
+        self.current += 1;
+        true
+    }
+
+    pub fn refill(&mut self) {
+        self.current = self.max;
+    }
+
+    // The new pip comes filled
+    pub fn raise_max(&mut self) -> bool {
+        if self.max >= HEALTH_CAP {
+            return false;
+        }
+        self.max += 1;
+        self.current += 1;
+        true
     }
 }
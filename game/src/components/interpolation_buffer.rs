@@ -0,0 +1,77 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::prelude::*;
+
+// Rendering this far in the past keeps a snapshot on each side of the render time
+const RENDER_DELAY: Duration = Duration::from_millis(100);
+const MAX_EXTRAPOLATION: Duration = Duration::from_millis(200);
+const BUFFER_SIZE: usize = 8;
+
+// Smooths remote entities which only move when a snapshot arrives from the server
+#[derive(Component, Default)]
+#[require(Transform)]
+pub struct InterpolationBuffer {
+    snapshots: VecDeque<(Duration, Vec2)>,
+}
+
+impl InterpolationBuffer {
+    pub fn push(&mut self, received_at: Duration, position: Vec2) {
+        if let Some(last) = self.snapshots.back_mut() {
+            // Several messages read in one frame share a timestamp, the last one wins
+            if last.0 == received_at {
+                last.1 = position;
+                return;
+            }
+        }
+        if self.snapshots.len() == BUFFER_SIZE {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back((received_at, position));
+    }
+
+    fn sample(&self, now: Duration) -> Option<Vec2> {
+        let render_time = now.saturating_sub(RENDER_DELAY);
+        let &(first_time, first_position) = self.snapshots.front()?;
+        if render_time <= first_time {
+            return Some(first_position);
+        }
+        for (from, to) in self.snapshots.iter().zip(self.snapshots.iter().skip(1)) {
+            if render_time < to.0 {
+                let t = (render_time - from.0).as_secs_f32() / (to.0 - from.0).as_secs_f32();
+                return Some(from.1.lerp(to.1, t));
+            }
+        }
+        // Packets are late, keep the last known velocity for a short while
+        let &(last_time, last_position) = self.snapshots.back()?;
+        let Some(&(previous_time, previous_position)) = self.snapshots.iter().rev().nth(1) else {
+            return Some(last_position);
+        };
+        let velocity =
+            (last_position - previous_position) / (last_time - previous_time).as_secs_f32();
+        let ahead = (render_time - last_time).min(MAX_EXTRAPOLATION);
+        Some(last_position + velocity * ahead.as_secs_f32())
+    }
+}
+
+pub struct InterpolationBufferPlugin;
+
+impl Plugin for InterpolationBufferPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, apply_interpolation);
+    }
+}
+
+fn apply_interpolation(
+    time: Res<Time>,
+    mut buffer_query: Query<(&InterpolationBuffer, &mut Transform)>,
+) {
+    for (buffer, mut transform) in buffer_query.iter_mut() {
+        let Some(position) = buffer.sample(time.elapsed()) else {
+            continue;
+        };
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+}
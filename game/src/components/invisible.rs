@@ -3,7 +3,13 @@ use std::time::Duration;
 use bevy::app::App;
 use bevy::prelude::*;
 
-use crate::ui_components::Blink;
+use crate::res::{AccessibilityOption, GameSpeed};
+
+// Full on/off cycles per second while invincible
+const BLINK_FREQUENCY: f32 = 10.;
+const BLINK_ALPHA: f32 = 0.2;
+// Held instead of blinking when flashing is disabled
+const STEADY_ALPHA: f32 = 0.5;
 
 #[derive(Component)]
 #[require(Sprite)]
@@ -13,8 +19,31 @@ pub struct Invisible {
 
 impl Invisible {
     pub fn new() -> Self {
+        Self::with_duration(Duration::from_secs(1))
+    }
+
+    pub fn with_duration(duration: Duration) -> Self {
+        Self {
+            timer: Timer::new(duration, TimerMode::Once),
+        }
+    }
+}
+
+// Invincibility frames against enemy bullets, kept apart from body collisions
+#[derive(Component)]
+#[require(Sprite)]
+pub struct BulletInvisible {
+    timer: Timer,
+}
+
+impl BulletInvisible {
+    pub fn new() -> Self {
+        Self::with_duration(Duration::from_millis(500))
+    }
+
+    pub fn with_duration(duration: Duration) -> Self {
         Self {
-            timer: Timer::new(Duration::from_secs(1), TimerMode::Once),
+            timer: Timer::new(duration, TimerMode::Once),
         }
     }
 }
@@ -23,28 +52,74 @@ pub struct InvisiblePlugin;
 
 impl Plugin for InvisiblePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, handle_invisible_timer)
-            .add_observer(invisible_on_add);
+        app.add_systems(
+            Update,
+            (
+                blink_invincible,
+                handle_invisible_timer,
+                handle_bullet_invisible_timer,
+            )
+                .chain(),
+        );
     }
 }
 
-fn invisible_on_add(ev: Trigger<OnAdd, Invisible>, mut commands: Commands) {
-    commands
-        .entity(ev.target())
-        .insert(Blink::new_with_speed(1.1));
+// Alpha follows the elapsed time so the blink rate does not depend on the frame rate
+#[allow(clippy::type_complexity)]
+fn blink_invincible(
+    mut invincible_query: Query<
+        (&mut Sprite, Option<&Invisible>, Option<&BulletInvisible>),
+        Or<(With<Invisible>, With<BulletInvisible>)>,
+    >,
+    accessibility: Res<AccessibilityOption>,
+) {
+    for (mut sprite, invisible, bullet_invisible) in invincible_query.iter_mut() {
+        if accessibility.disable_flashing {
+            sprite.color.set_alpha(STEADY_ALPHA);
+            continue;
+        }
+        let elapsed = invisible
+            .map(|invisible| invisible.timer.elapsed_secs())
+            .or(bullet_invisible.map(|invisible| invisible.timer.elapsed_secs()))
+            .unwrap_or_default();
+        let shown = (elapsed * BLINK_FREQUENCY).fract() < 0.5;
+        sprite.color.set_alpha(if shown { 1. } else { BLINK_ALPHA });
+    }
 }
 
 fn handle_invisible_timer(
     mut commands: Commands,
-    mut invisible_query: Query<(Entity, &mut Invisible)>,
+    mut invisible_query: Query<(Entity, &mut Invisible, &mut Sprite, Has<BulletInvisible>)>,
     time: Res<Time>,
+    game_speed: Res<GameSpeed>,
 ) {
-    for (entity, mut invisible) in invisible_query.iter_mut() {
-        invisible.timer.tick(time.delta());
+    for (entity, mut invisible, mut sprite, bullet_invisible) in invisible_query.iter_mut() {
+        invisible.timer.tick(game_speed.delta(&time));
         if invisible.timer.finished() {
+            if !bullet_invisible {
+                sprite.color.set_alpha(1.);
+            }
             if let Ok(mut entity_commands) = commands.get_entity(entity) {
                 entity_commands.remove::<Invisible>();
-                entity_commands.remove::<Blink>();
+            }
+        }
+    }
+}
+
+fn handle_bullet_invisible_timer(
+    mut commands: Commands,
+    mut invisible_query: Query<(Entity, &mut BulletInvisible, &mut Sprite, Has<Invisible>)>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    for (entity, mut invisible, mut sprite, body_invisible) in invisible_query.iter_mut() {
+        invisible.timer.tick(game_speed.delta(&time));
+        if invisible.timer.finished() {
+            if !body_invisible {
+                sprite.color.set_alpha(1.);
+            }
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.remove::<BulletInvisible>();
             }
         }
     }
@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+// Spaceships left including the one in play
+#[derive(Component)]
+pub struct Lives(pub u8);
+
+impl Lives {
+    pub fn new(lives: u8) -> Self {
+        Self(lives)
+    }
+
+    pub fn lose(&mut self) {
+        self.0 = self.0.saturating_sub(1);
+    }
+}
@@ -0,0 +1,131 @@
+use bevy::color::palettes::css::ORANGE_RED;
+use bevy::prelude::*;
+
+use crate::constant::{ZIndex, MISSILE_SIZE};
+use crate::res::GameSpeed;
+use crate::util::{closest_position, listen_position, Position};
+
+use super::collisable::CollisionLayer;
+use super::{Player, Velocity, UFO};
+
+const MISSILE_SPEED: f32 = 7.;
+// Radians a missile can turn every fixed tick
+const MISSILE_TURN_RATE: f32 = 0.06;
+const MISSILE_DAMAGE: u8 = 2;
+const STARTING_MISSILE_AMMO: u8 = 3;
+const MISSILE_PICKUP_AMMO: u8 = 3;
+const MAX_MISSILE_AMMO: u8 = 6;
+
+#[derive(Component)]
+pub struct Missile {
+    player: u8,
+    position: Vec2,
+}
+
+impl Position for Missile {
+    fn get_position(&self) -> Vec2 {
+        self.position
+    }
+    fn set_position(&mut self, position: Vec2) {
+        self.position = position;
+    }
+}
+
+impl Missile {
+    pub fn by_player(player: u8, position: Vec2) -> Self {
+        Self { player, position }
+    }
+
+    pub fn get_player(&self) -> u8 {
+        self.player
+    }
+
+    pub fn damage(&self) -> u8 {
+        MISSILE_DAMAGE
+    }
+}
+
+// Secondary weapon ammo, only spaceships of the single player game carry it
+#[derive(Component)]
+pub struct MissileAmmo(u8);
+
+impl Default for MissileAmmo {
+    fn default() -> Self {
+        Self(STARTING_MISSILE_AMMO)
+    }
+}
+
+impl MissileAmmo {
+    pub fn count(&self) -> u8 {
+        self.0
+    }
+
+    // False when there is nothing left to fire
+    pub fn take(&mut self) -> bool {
+        let Some(left) = self.0.checked_sub(1) else {
+            return false;
+        };
+        self.0 = left;
+        true
+    }
+
+    pub fn refill(&mut self) {
+        self.0 = (self.0 + MISSILE_PICKUP_AMMO).min(MAX_MISSILE_AMMO);
+    }
+}
+
+pub struct MissilePlugin;
+
+impl Plugin for MissilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, listen_position::<Missile>)
+            .add_systems(FixedUpdate, steer_missiles)
+            .add_observer(missile_on_added);
+    }
+}
+
+fn missile_on_added(
+    ev: Trigger<OnAdd, Missile>,
+    mut commands: Commands,
+    missile_q: Query<&Missile>,
+) {
+    let Ok(missile) = missile_q.get(ev.target()) else {
+        warn!("Missile not found in missile_on_added");
+        return;
+    };
+    if let Ok(mut entity_commands) = commands.get_entity(ev.target()) {
+        entity_commands.insert((
+            Sprite {
+                color: Color::from(ORANGE_RED),
+                custom_size: Some(MISSILE_SIZE),
+                ..default()
+            },
+            Transform::from_translation(missile.position.extend(ZIndex::BULLET.z_value())),
+            Velocity::from_vec2(Vec2::new(0., MISSILE_SPEED)),
+            CollisionLayer::PlayerBullet,
+            Player(missile.player),
+        ));
+    }
+}
+
+// Turns toward the nearest UFO a little at a time so fast enemies can still outrun it
+fn steer_missiles(
+    mut missile_q: Query<(&mut Velocity, &mut Transform), With<Missile>>,
+    ufo_q: Query<&UFO>,
+    game_speed: Res<GameSpeed>,
+) {
+    for (mut velocity, mut transform) in missile_q.iter_mut() {
+        let position = transform.translation.truncate();
+        let mut heading = Vec2::new(velocity.x, velocity.y);
+        let to_target = closest_position(position, ufo_q.iter())
+            .map(|target| target - position)
+            .filter(|to_target| *to_target != Vec2::ZERO);
+        if let Some(to_target) = to_target {
+            let max_turn = MISSILE_TURN_RATE * game_speed.factor();
+            let turn = heading.angle_to(to_target).clamp(-max_turn, max_turn);
+            heading = Vec2::from_angle(turn).rotate(heading);
+            *velocity = Velocity::from_vec2(heading);
+        }
+        transform.rotation = Quat::from_rotation_z(Vec2::Y.angle_to(heading));
+    }
+}
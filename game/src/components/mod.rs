@@ -1,25 +1,68 @@
+mod asteroid;
+mod bomb;
+mod boss;
 mod bullet;
 mod collisable;
+mod dash;
+mod downed;
+mod drone;
+mod enemy_bullet;
 mod explosion;
+mod floating_text;
+mod graze;
 mod health;
+mod interpolation_buffer;
 mod invisible;
+mod lives;
+mod missile;
+mod ore;
+mod particle;
 mod player;
+mod pool;
+mod power_up;
 mod score;
+mod shield;
 mod spaceship;
+mod transform_interpolation;
 mod ufo;
 mod velocity;
+mod weapon_heat;
+mod weapon_level;
 
+pub use asteroid::{Asteroid, AsteroidSize};
 use bevy::prelude::{App, Plugin};
+pub use bomb::{BombCharges, Shockwave};
+pub use boss::{Boss, BossPhase, BOSS_COLOR};
 pub use bullet::{Bullet, BulletTag};
-pub use collisable::CollidedEvent;
-pub use explosion::Explosion;
+pub use collisable::{
+    rotated_bounds, CollidedEvent, CollisionLayer, FriendlyFireEvent, GrazeEvent, Pierced,
+    PowerUpCollidedEvent,
+};
+pub use dash::{Dash, DASH_INVINCIBILITY};
+pub use downed::Downed;
+pub use drone::Drone;
+pub use enemy_bullet::EnemyBullet;
+pub use explosion::{Explosion, ExplosionKind, EXPLOSION_FRAMES, EXPLOSION_FRAME_SIZE};
+pub use floating_text::FloatingText;
+pub use graze::Graze;
 pub use health::Health;
-pub use invisible::Invisible;
+pub use interpolation_buffer::InterpolationBuffer;
+pub use invisible::{BulletInvisible, Invisible};
+pub use lives::Lives;
+pub use missile::{Missile, MissileAmmo};
+pub use ore::{Ore, ORE_SIZE};
+pub use particle::Particle;
 pub use player::{Player, SelfPlayer};
+pub use pool::PoolCommandsExt;
+pub use power_up::{Buff, Drift, PowerUp, PowerUpKind, PowerUpTag, POWER_UP_VELOCITY};
 pub use score::Score;
+pub use shield::{Shield, ShieldBreak};
 pub use spaceship::Spaceship;
-pub use ufo::{EnemyTag, UFO};
+pub use transform_interpolation::TransformInterpolation;
+pub use ufo::{EnemyTag, UFOKind, UFO};
 pub use velocity::Velocity;
+pub use weapon_heat::{WeaponHeat, HEAT_FIRE_INTERVAL};
+pub use weapon_level::WeaponLevel;
 pub struct ComponentPlugin;
 
 impl Plugin for ComponentPlugin {
@@ -27,12 +70,31 @@ impl Plugin for ComponentPlugin {
         app.add_plugins((
             spaceship::SpaceshipPlugin,
             ufo::UFOPlugin,
+            boss::BossPlugin,
+            enemy_bullet::EnemyBulletPlugin,
             collisable::CollisablePlugin,
             explosion::ExplosionPlugin,
             velocity::VelocityPlugin,
             invisible::InvisiblePlugin,
             bullet::BulletPlugin,
             player::PlayerPlugin,
+            power_up::PowerUpPlugin,
+            interpolation_buffer::InterpolationBufferPlugin,
+            shield::ShieldPlugin,
+            pool::PoolPlugin,
+            (
+                asteroid::AsteroidPlugin,
+                particle::ParticlePlugin,
+                floating_text::FloatingTextPlugin,
+                missile::MissilePlugin,
+                drone::DronePlugin,
+                weapon_heat::WeaponHeatPlugin,
+                bomb::BombPlugin,
+                ore::OrePlugin,
+                transform_interpolation::TransformInterpolationPlugin,
+                downed::DownedPlugin,
+                dash::DashPlugin,
+            ),
         ));
     }
 }
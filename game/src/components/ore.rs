@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+use crate::constant::ZIndex;
+
+use super::{collisable::CollisionLayer, Velocity};
+
+const ORE_COLOR: Color = Color::srgb(0.95, 0.75, 0.2);
+pub const ORE_SIZE: Vec2 = Vec2::splat(36.);
+
+// Harmless chunk floating through the bonus stage, worth score when shot
+#[derive(Component)]
+pub struct Ore {
+    position: Vec2,
+    velocity: Vec2,
+}
+
+impl Ore {
+    pub fn new(position: Vec2, velocity: Vec2) -> Self {
+        Self { position, velocity }
+    }
+}
+
+pub struct OrePlugin;
+
+impl Plugin for OrePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(ore_on_added);
+    }
+}
+
+fn ore_on_added(ev: Trigger<OnAdd, Ore>, mut commands: Commands, ore_q: Query<&Ore>) {
+    let Ok(ore) = ore_q.get(ev.target()) else {
+        warn!("Ore not found in ore_on_added");
+        return;
+    };
+    if let Ok(mut entity_commands) = commands.get_entity(ev.target()) {
+        entity_commands.insert((
+            Velocity::from_vec2(ore.velocity),
+            Transform::from_translation(ore.position.extend(ZIndex::UFO.z_value()))
+                .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+            Sprite {
+                color: ORE_COLOR,
+                custom_size: Some(ORE_SIZE),
+                ..default()
+            },
+            CollisionLayer::Ore,
+        ));
+    }
+}
@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+use bevy::color::palettes::css::{ORANGE, YELLOW};
+use bevy::prelude::*;
+use rand::Rng;
+use shooting_game_shared::util::SPACESHIP_SIZE;
+
+use crate::constant::{ZIndex, EXPLOSION_SIZE};
+use crate::res::{AccessibilityOption, GameRng, GameSpeed, RngStream};
+
+use super::pool::{PoolCommandsExt, Poolable};
+use super::{Bullet, Explosion, Spaceship, Velocity};
+
+// Spaceship speed (per fixed tick) at which the exhaust is strongest
+const MAX_THRUST_SPEED: f32 = 10.;
+const EXHAUST_RATE: (f32, f32) = (15., 90.);
+const BULLET_TRAIL_RATE: f32 = 40.;
+const DEBRIS_COUNT: f32 = 16.;
+
+// Plain untextured sprites, so all of them are drawn in a single batch
+#[derive(Component)]
+pub struct Particle {
+    position: Vec2,
+    velocity: Vec2,
+    size: f32,
+    color: Color,
+    lifetime: Timer,
+}
+
+impl Particle {
+    pub fn new(
+        position: Vec2,
+        velocity: Vec2,
+        size: f32,
+        color: Color,
+        lifetime: Duration,
+    ) -> Self {
+        Self {
+            position,
+            velocity,
+            size,
+            color,
+            lifetime: Timer::new(lifetime, TimerMode::Once),
+        }
+    }
+}
+
+impl Poolable for Particle {
+    type Attached = ();
+}
+
+#[derive(Clone, Copy)]
+enum EmitterKind {
+    EngineExhaust,
+    BulletTrail,
+}
+
+#[derive(Component)]
+pub struct ParticleEmitter {
+    kind: EmitterKind,
+    // Fractional particles carried over to the next frame
+    pending: f32,
+}
+
+impl ParticleEmitter {
+    fn new(kind: EmitterKind) -> Self {
+        Self { kind, pending: 0. }
+    }
+}
+
+pub struct ParticlePlugin;
+
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (emit_particles, apply_particles))
+            .add_observer(particle_on_added)
+            .add_observer(add_engine_exhaust)
+            .add_observer(add_bullet_trail)
+            .add_observer(spawn_explosion_debris);
+    }
+}
+
+fn particle_on_added(
+    ev: Trigger<OnAdd, Particle>,
+    mut commands: Commands,
+    particle_q: Query<&Particle>,
+) {
+    let Ok(particle) = particle_q.get(ev.target()) else {
+        warn!("Particle not found in particle_on_added");
+        return;
+    };
+    if let Ok(mut entity_commands) = commands.get_entity(ev.target()) {
+        entity_commands.insert((
+            Sprite {
+                color: particle.color,
+                custom_size: Some(Vec2::splat(particle.size)),
+                ..default()
+            },
+            Transform::from_translation(particle.position.extend(ZIndex::EXPLOSION.z_value())),
+            Visibility::Inherited,
+        ));
+    }
+}
+
+fn add_engine_exhaust(ev: Trigger<OnAdd, Spaceship>, mut commands: Commands) {
+    commands
+        .entity(ev.target())
+        .insert(ParticleEmitter::new(EmitterKind::EngineExhaust));
+}
+
+fn add_bullet_trail(ev: Trigger<OnAdd, Bullet>, mut commands: Commands) {
+    commands
+        .entity(ev.target())
+        .insert(ParticleEmitter::new(EmitterKind::BulletTrail));
+}
+
+fn spawn_explosion_debris(
+    ev: Trigger<OnAdd, Explosion>,
+    mut commands: Commands,
+    explosion_q: Query<&Explosion>,
+    accessibility: Res<AccessibilityOption>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let Ok(explosion) = explosion_q.get(ev.target()) else {
+        warn!("Explosion not found in spawn_explosion_debris");
+        return;
+    };
+    let rng = game_rng.stream(RngStream::Cosmetic);
+    let position = explosion.position();
+    let scale = explosion.size().x / EXPLOSION_SIZE.x;
+    let count = DEBRIS_COUNT * scale * accessibility.particle_density();
+    for _ in 0..count.ceil() as u32 {
+        let direction = Vec2::from_angle(rng.random_range(0.0..std::f32::consts::TAU));
+        let speed = rng.random_range(60.0..240.0) * scale.max(0.5);
+        commands.spawn_pooled(Particle::new(
+            position,
+            direction * speed,
+            rng.random_range(3.0..7.0),
+            Color::from(ORANGE),
+            Duration::from_millis(rng.random_range(300..700)),
+        ));
+    }
+}
+
+fn emit_particles(
+    mut commands: Commands,
+    mut emitter_q: Query<(&mut ParticleEmitter, &Transform, Option<&Velocity>)>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+    accessibility: Res<AccessibilityOption>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let rng = game_rng.stream(RngStream::Cosmetic);
+    for (mut emitter, transform, velocity) in emitter_q.iter_mut() {
+        let speed = velocity.map_or(0., |velocity| Vec2::new(velocity.x, velocity.y).length());
+        let rate = match emitter.kind {
+            EmitterKind::EngineExhaust => {
+                let intensity = (speed / MAX_THRUST_SPEED).clamp(0., 1.);
+                EXHAUST_RATE.0 + (EXHAUST_RATE.1 - EXHAUST_RATE.0) * intensity
+            }
+            EmitterKind::BulletTrail => BULLET_TRAIL_RATE,
+        };
+        emitter.pending += rate * accessibility.particle_density() * game_speed.delta_secs(&time);
+        let position = transform.translation.truncate();
+        while emitter.pending >= 1. {
+            emitter.pending -= 1.;
+            let particle = match emitter.kind {
+                EmitterKind::EngineExhaust => Particle::new(
+                    position - Vec2::new(rng.random_range(-8.0..8.0), SPACESHIP_SIZE.y / 2.),
+                    Vec2::new(
+                        rng.random_range(-20.0..20.0),
+                        -rng.random_range(80.0..160.0),
+                    ),
+                    rng.random_range(3.0..6.0),
+                    Color::srgba(1., 0.6, 0.2, 0.8),
+                    Duration::from_millis(rng.random_range(150..300)),
+                ),
+                EmitterKind::BulletTrail => Particle::new(
+                    position,
+                    Vec2::ZERO,
+                    2.,
+                    Color::from(YELLOW).with_alpha(0.6),
+                    Duration::from_millis(150),
+                ),
+            };
+            commands.spawn_pooled(particle);
+        }
+    }
+}
+
+fn apply_particles(
+    mut commands: Commands,
+    mut particle_q: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    for (entity, mut particle, mut transform, mut sprite) in particle_q.iter_mut() {
+        particle.lifetime.tick(game_speed.delta(&time));
+        if particle.lifetime.finished() {
+            commands.release_pooled::<Particle>(entity);
+            continue;
+        }
+        let remaining = 1. - particle.lifetime.fraction();
+        transform.translation += (particle.velocity * game_speed.delta_secs(&time)).extend(0.);
+        transform.scale = Vec3::splat(remaining);
+        sprite.color = particle
+            .color
+            .with_alpha(particle.color.alpha() * remaining);
+    }
+}
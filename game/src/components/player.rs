@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::res::PlayerTag;
+use crate::res::{LocalCoop, PlayerTag};
 
 #[derive(Component, Clone)]
 pub struct Player(pub u8);
@@ -9,6 +9,11 @@ impl Player {
     pub fn new_from_res(player_tag: &Res<PlayerTag>) -> Self {
         Self(player_tag.0)
     }
+
+    // Every player is controlled from this client in local co-op
+    pub fn is_local(&self, player_tag: &PlayerTag, local_coop: bool) -> bool {
+        local_coop || self.0 == player_tag.0
+    }
 }
 
 #[derive(Component)]
@@ -26,13 +31,14 @@ fn on_player_added(
     ev: Trigger<OnAdd, Player>,
     mut commands: Commands,
     player_tag: Res<PlayerTag>,
+    local_coop: Option<Res<LocalCoop>>,
     player_q: Query<&Player>,
 ) {
     let Ok(player) = player_q.get(ev.target()) else {
         warn!("Player not found in on_player_added");
         return;
     };
-    if player.0 == player_tag.0 {
+    if player.is_local(&player_tag, local_coop.is_some()) {
         commands.entity(ev.target()).insert(SelfPlayer);
     }
 }
@@ -0,0 +1,119 @@
+use std::marker::PhantomData;
+
+use bevy::app::App;
+use bevy::prelude::*;
+
+use crate::states::{GameState, OnlineGameState};
+
+use super::particle::Particle;
+use super::{Bullet, Explosion};
+
+const BULLET_POOL_SIZE: usize = 64;
+const EXPLOSION_POOL_SIZE: usize = 16;
+const PARTICLE_POOL_SIZE: usize = 256;
+
+// Components whose entities are recycled instead of despawned,
+// `Attached` is what the on-added observer inserts and releasing takes off again
+pub trait Poolable: Component {
+    type Attached: Bundle;
+}
+
+// Hidden entities waiting to have T inserted again, which reruns its on-added setup
+#[derive(Resource)]
+pub struct Pool<T: Poolable> {
+    free: Vec<Entity>,
+    marker: PhantomData<T>,
+}
+
+impl<T: Poolable> Default for Pool<T> {
+    fn default() -> Self {
+        Self {
+            free: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+}
+
+pub type BulletPool = Pool<Bullet>;
+pub type ExplosionPool = Pool<Explosion>;
+pub type ParticlePool = Pool<Particle>;
+
+pub trait PoolCommandsExt {
+    fn spawn_pooled<T: Poolable>(&mut self, component: T);
+    fn release_pooled<T: Poolable>(&mut self, entity: Entity);
+}
+
+impl PoolCommandsExt for Commands<'_, '_> {
+    fn spawn_pooled<T: Poolable>(&mut self, component: T) {
+        self.queue(move |world: &mut World| {
+            let free = world
+                .get_resource_mut::<Pool<T>>()
+                .and_then(|mut pool| pool.free.pop());
+            match free.and_then(|entity| world.get_entity_mut(entity).ok()) {
+                Some(mut entity) => {
+                    entity.insert(component);
+                }
+                None => {
+                    world.spawn(component);
+                }
+            }
+        });
+    }
+
+    fn release_pooled<T: Poolable>(&mut self, entity: Entity) {
+        self.queue(move |world: &mut World| {
+            let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+                return;
+            };
+            // Already released earlier this frame
+            if !entity_mut.contains::<T>() {
+                return;
+            }
+            entity_mut
+                .remove::<(T, T::Attached)>()
+                .insert(Visibility::Hidden);
+            if let Some(mut pool) = world.get_resource_mut::<Pool<T>>() {
+                pool.free.push(entity);
+            }
+        });
+    }
+}
+
+pub struct PoolPlugin;
+
+impl Plugin for PoolPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BulletPool>()
+            .init_resource::<ExplosionPool>()
+            .init_resource::<ParticlePool>()
+            .add_systems(
+                OnEnter(GameState::Ready),
+                (
+                    prewarm_pool::<Bullet, BULLET_POOL_SIZE>,
+                    prewarm_pool::<Explosion, EXPLOSION_POOL_SIZE>,
+                    prewarm_pool::<Particle, PARTICLE_POOL_SIZE>,
+                ),
+            )
+            .add_systems(
+                OnEnter(OnlineGameState::Ready),
+                (
+                    prewarm_pool::<Bullet, BULLET_POOL_SIZE>,
+                    prewarm_pool::<Explosion, EXPLOSION_POOL_SIZE>,
+                    prewarm_pool::<Particle, PARTICLE_POOL_SIZE>,
+                ),
+            );
+    }
+}
+
+// Tops the pool up so the first shots of a run don't allocate
+fn prewarm_pool<T: Poolable, const SIZE: usize>(mut commands: Commands, mut pool: ResMut<Pool<T>>) {
+    // Released entities still tagged for the last game are despawned when it ends
+    pool.free
+        .retain(|entity| commands.get_entity(*entity).is_ok());
+    while pool.free.len() < SIZE {
+        let entity = commands
+            .spawn((Sprite::default(), Transform::default(), Visibility::Hidden))
+            .id();
+        pool.free.push(entity);
+    }
+}
@@ -0,0 +1,252 @@
+use std::time::Duration;
+
+use bevy::color::palettes::css::{AQUA, GOLD, HOT_PINK, LIME, ORANGE, ORANGE_RED, RED, VIOLET};
+use bevy::prelude::*;
+use serde::Deserialize;
+use shooting_game_shared::util::{EdgeUtil, POWER_UP_SIZE};
+use shooting_game_shared::OnlinePowerUp;
+
+use crate::constant::{
+    ZIndex, COLOR_BLIND_BLUE, COLOR_BLIND_BLUISH_GREEN, COLOR_BLIND_ORANGE,
+    COLOR_BLIND_REDDISH_PURPLE, COLOR_BLIND_SKY_BLUE, COLOR_BLIND_VERMILLION, COLOR_BLIND_YELLOW,
+};
+use crate::res::{AccessibilityOption, GameSpeed};
+
+use super::collisable::CollisionLayer;
+use super::Velocity;
+
+const BUFF_DURATION: Duration = Duration::from_secs(8);
+pub const POWER_UP_VELOCITY: Vec2 = Vec2::new(0., -2.);
+// Share of the gap to the usual fall closed every fixed step
+const DRIFT_DAMPING: f32 = 0.04;
+
+#[derive(Deserialize, Clone, Copy, Eq, PartialEq, Debug)]
+pub enum PowerUpKind {
+    SpreadShot,
+    RapidFire,
+    Shield,
+    // Refills the secondary weapon instead of applying a Buff
+    Missiles,
+    // Spawns a Drone around the spaceship instead of applying a Buff
+    Drone,
+    // Gives back one health point instead of applying a Buff
+    Health,
+    // Raises the WeaponLevel for the rest of the run instead of applying a Buff
+    WeaponUp,
+    // Adds a health pip for the rest of the run, only rare UFO drops give it
+    MaxHealth,
+}
+
+impl PowerUpKind {
+    pub const ALL: [PowerUpKind; 8] = [
+        PowerUpKind::SpreadShot,
+        PowerUpKind::RapidFire,
+        PowerUpKind::Shield,
+        PowerUpKind::Missiles,
+        PowerUpKind::Drone,
+        PowerUpKind::Health,
+        PowerUpKind::WeaponUp,
+        PowerUpKind::MaxHealth,
+    ];
+
+    // Left out of the timed spawns
+    pub fn is_drop_only(&self) -> bool {
+        matches!(self, PowerUpKind::MaxHealth)
+    }
+
+    pub fn color(&self, color_blind: bool) -> Color {
+        match (self, color_blind) {
+            (PowerUpKind::SpreadShot, false) => Color::from(ORANGE),
+            (PowerUpKind::RapidFire, false) => Color::from(LIME),
+            (PowerUpKind::Shield, false) => Color::from(AQUA),
+            (PowerUpKind::Missiles, false) => Color::from(ORANGE_RED),
+            (PowerUpKind::Drone, false) => Color::from(VIOLET),
+            (PowerUpKind::Health, false) => Color::from(RED),
+            (PowerUpKind::WeaponUp, false) => Color::from(GOLD),
+            (PowerUpKind::MaxHealth, false) => Color::from(HOT_PINK),
+            (PowerUpKind::SpreadShot, true) => COLOR_BLIND_ORANGE,
+            (PowerUpKind::RapidFire, true) => COLOR_BLIND_YELLOW,
+            (PowerUpKind::Shield, true) => COLOR_BLIND_SKY_BLUE,
+            (PowerUpKind::Missiles, true) => COLOR_BLIND_VERMILLION,
+            (PowerUpKind::Drone, true) => COLOR_BLIND_REDDISH_PURPLE,
+            (PowerUpKind::Health, true) => COLOR_BLIND_BLUE,
+            (PowerUpKind::WeaponUp, true) => COLOR_BLIND_BLUISH_GREEN,
+            (PowerUpKind::MaxHealth, true) => Color::WHITE,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PowerUpKind::SpreadShot => "SPREAD SHOT",
+            PowerUpKind::RapidFire => "RAPID FIRE",
+            PowerUpKind::Shield => "SHIELD",
+            PowerUpKind::Missiles => "MISSILES",
+            PowerUpKind::Drone => "DRONE",
+            PowerUpKind::Health => "HEALTH",
+            PowerUpKind::WeaponUp => "WEAPON UP",
+            PowerUpKind::MaxHealth => "MAX HEALTH UP",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            PowerUpKind::SpreadShot => "SP",
+            PowerUpKind::RapidFire => "RF",
+            PowerUpKind::Shield => "SH",
+            PowerUpKind::Missiles => "MS",
+            PowerUpKind::Drone => "DR",
+            PowerUpKind::Health => "HP",
+            PowerUpKind::WeaponUp => "W+",
+            PowerUpKind::MaxHealth => "H+",
+        }
+    }
+}
+
+impl From<OnlinePowerUp> for PowerUpKind {
+    fn from(kind: OnlinePowerUp) -> Self {
+        match kind {
+            OnlinePowerUp::SpreadShot => PowerUpKind::SpreadShot,
+            OnlinePowerUp::RapidFire => PowerUpKind::RapidFire,
+        }
+    }
+}
+
+// Server side id of a power-up in the online game
+#[derive(Component)]
+pub struct PowerUpTag(pub u16);
+
+#[derive(Component)]
+pub struct PowerUp {
+    kind: PowerUpKind,
+    position: Vec2,
+}
+
+impl PowerUp {
+    pub fn new(kind: PowerUpKind, position: Vec2) -> Self {
+        Self { kind, position }
+    }
+
+    pub fn kind(&self) -> PowerUpKind {
+        self.kind
+    }
+}
+
+// Dropped by a UFO, bursts out and eases into the usual fall while bouncing off the sides
+#[derive(Component)]
+pub struct Drift;
+
+// Timed effect applied to the spaceship after collecting a PowerUp
+#[derive(Component)]
+#[require(Sprite)]
+pub struct Buff {
+    kind: PowerUpKind,
+    timer: Timer,
+}
+
+impl Buff {
+    pub fn new(kind: PowerUpKind) -> Self {
+        Self {
+            kind,
+            timer: Timer::new(BUFF_DURATION, TimerMode::Once),
+        }
+    }
+
+    pub fn kind(&self) -> PowerUpKind {
+        self.kind
+    }
+}
+
+pub struct PowerUpPlugin;
+
+impl Plugin for PowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, handle_buff_timer)
+            .add_systems(FixedUpdate, apply_drift)
+            .add_observer(power_up_on_added)
+            .add_observer(buff_on_insert)
+            .add_observer(buff_on_remove);
+    }
+}
+
+fn power_up_on_added(
+    ev: Trigger<OnAdd, PowerUp>,
+    mut commands: Commands,
+    power_up_q: Query<&PowerUp>,
+    accessibility: Res<AccessibilityOption>,
+) {
+    let Ok(power_up) = power_up_q.get(ev.target()) else {
+        warn!("PowerUp not found in power_up_on_added");
+        return;
+    };
+    if let Ok(mut entity_commands) = commands.get_entity(ev.target()) {
+        entity_commands
+            .insert((
+                Sprite {
+                    color: power_up.kind.color(accessibility.color_blind),
+                    custom_size: Some(POWER_UP_SIZE),
+                    ..default()
+                },
+                Transform::from_translation(power_up.position.extend(ZIndex::POWERUP.z_value())),
+                CollisionLayer::Pickup,
+            ))
+            .with_child((
+                Text2d::new(power_up.kind.label()),
+                TextFont::from_font_size(14.),
+                TextColor(Color::BLACK),
+                Transform::from_xyz(0., 0., 0.1),
+            ));
+    }
+}
+
+fn buff_on_insert(
+    ev: Trigger<OnInsert, Buff>,
+    mut buff_q: Query<(&Buff, &mut Sprite)>,
+    accessibility: Res<AccessibilityOption>,
+) {
+    let Ok((buff, mut sprite)) = buff_q.get_mut(ev.target()) else {
+        return;
+    };
+    let alpha = sprite.color.alpha();
+    sprite.color = buff.kind.color(accessibility.color_blind).with_alpha(alpha);
+}
+
+fn buff_on_remove(ev: Trigger<OnRemove, Buff>, mut sprite_q: Query<&mut Sprite>) {
+    let Ok(mut sprite) = sprite_q.get_mut(ev.target()) else {
+        return;
+    };
+    let alpha = sprite.color.alpha();
+    sprite.color = Color::WHITE.with_alpha(alpha);
+}
+
+fn handle_buff_timer(
+    mut commands: Commands,
+    mut buff_query: Query<(Entity, &mut Buff)>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    for (entity, mut buff) in buff_query.iter_mut() {
+        buff.timer.tick(game_speed.delta(&time));
+        if buff.timer.finished() {
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.remove::<Buff>();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn apply_drift(
+    mut drift_q: Query<(&mut Velocity, &Transform), (With<Drift>, With<PowerUp>)>,
+    game_speed: Res<GameSpeed>,
+) {
+    let edge = EdgeUtil::new(POWER_UP_SIZE);
+    let damping = (DRIFT_DAMPING * game_speed.factor()).min(1.);
+    for (mut velocity, transform) in drift_q.iter_mut() {
+        velocity.x += (POWER_UP_VELOCITY.x - velocity.x) * damping;
+        velocity.y += (POWER_UP_VELOCITY.y - velocity.y) * damping;
+        let x = transform.translation.x;
+        if (x < edge.left_in() && velocity.x < 0.) || (x > edge.right_in() && velocity.x > 0.) {
+            velocity.x = -velocity.x;
+        }
+    }
+}
@@ -1,14 +1,14 @@
 use bevy::prelude::*;
 
 #[derive(Component)]
-pub struct Score(pub u8);
+pub struct Score(pub u32);
 
 impl Score {
     pub fn new() -> Self {
         Self(0)
     }
 
-    pub fn add(&mut self, amount: u8) {
+    pub fn add(&mut self, amount: u32) {
         self.0 += amount;
     }
 }
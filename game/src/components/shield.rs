@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::color::palettes::css::AQUA;
+use bevy::prelude::*;
+
+use crate::constant::ZIndex;
+use crate::res::GameSpeed;
+
+const RING_INNER_RADIUS: f32 = 58.;
+const RING_OUTER_RADIUS: f32 = 64.;
+const BREAK_DURATION: Duration = Duration::from_millis(300);
+
+// Absorbs the next hit on the spaceship, shown as a ring around it
+#[derive(Component)]
+pub struct Shield;
+
+#[derive(Component)]
+struct ShieldRing;
+
+// Expanding and fading ring left behind where a shield was broken
+#[derive(Component)]
+#[require(Transform)]
+pub struct ShieldBreak {
+    position: Vec2,
+    timer: Timer,
+}
+
+impl ShieldBreak {
+    pub fn new(position: Vec2) -> Self {
+        Self {
+            position,
+            timer: Timer::new(BREAK_DURATION, TimerMode::Once),
+        }
+    }
+}
+
+pub struct ShieldPlugin;
+
+impl Plugin for ShieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, apply_shield_break)
+            .add_observer(shield_on_added)
+            .add_observer(shield_on_remove)
+            .add_observer(shield_break_on_added);
+    }
+}
+
+fn ring_bundle(
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+) -> (Mesh2d, MeshMaterial2d<ColorMaterial>) {
+    (
+        Mesh2d(meshes.add(Annulus::new(RING_INNER_RADIUS, RING_OUTER_RADIUS))),
+        MeshMaterial2d(materials.add(Color::from(AQUA).with_alpha(0.8))),
+    )
+}
+
+fn shield_on_added(
+    ev: Trigger<OnAdd, Shield>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if let Ok(mut entity_commands) = commands.get_entity(ev.target()) {
+        entity_commands.with_child((
+            ShieldRing,
+            ring_bundle(&mut meshes, &mut materials),
+            Transform::from_xyz(0., 0., 0.1),
+        ));
+    }
+}
+
+fn shield_on_remove(
+    ev: Trigger<OnRemove, Shield>,
+    mut commands: Commands,
+    children_q: Query<&Children>,
+    ring_q: Query<(), With<ShieldRing>>,
+) {
+    let Ok(children) = children_q.get(ev.target()) else {
+        return;
+    };
+    for child in children.iter() {
+        if ring_q.contains(child) {
+            commands.entity(child).despawn();
+        }
+    }
+}
+
+fn shield_break_on_added(
+    ev: Trigger<OnAdd, ShieldBreak>,
+    mut commands: Commands,
+    shield_break_q: Query<&ShieldBreak>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let Ok(shield_break) = shield_break_q.get(ev.target()) else {
+        warn!("ShieldBreak not found in shield_break_on_added");
+        return;
+    };
+    let position = shield_break.position;
+    if let Ok(mut entity_commands) = commands.get_entity(ev.target()) {
+        entity_commands.insert((
+            ring_bundle(&mut meshes, &mut materials),
+            Transform::from_translation(position.extend(ZIndex::EXPLOSION.z_value())),
+        ));
+    }
+}
+
+fn apply_shield_break(
+    mut commands: Commands,
+    mut shield_break_q: Query<(
+        Entity,
+        &mut ShieldBreak,
+        &mut Transform,
+        &MeshMaterial2d<ColorMaterial>,
+    )>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    for (entity, mut shield_break, mut transform, material) in shield_break_q.iter_mut() {
+        shield_break.timer.tick(game_speed.delta(&time));
+        let progress = shield_break.timer.fraction();
+        transform.scale = Vec3::splat(1. + progress);
+        if let Some(material) = materials.get_mut(material) {
+            material.color.set_alpha(0.8 * (1. - progress));
+        }
+        if shield_break.timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
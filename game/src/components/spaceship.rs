@@ -1,3 +1,4 @@
+use std::f32::consts::TAU;
 use std::time::Duration;
 
 use bevy::prelude::*;
@@ -5,17 +6,24 @@ use shooting_game_shared::util::SPACESHIP_SIZE;
 
 use crate::constant::ZIndex;
 use crate::res::ImageHandles;
-use crate::res::PlayerTag;
+use crate::res::{GameSpeed, LocalCoop, PlayerTag};
 use crate::util::listen_position;
 use crate::util::Position;
 
-use super::collisable::Collisable;
+use super::collisable::CollisionLayer;
 use super::Player;
 
+// Aim snaps to this many directions, so a replay can store it and play it back exactly
+const AIM_STEPS: u16 = 4096;
+
 #[derive(Component)]
 pub struct Spaceship {
     position: Vec2,
     cooldown: Option<Timer>,
+    // How long shoot has been held for a charged shot
+    charge: Duration,
+    // Where the nose points, straight up unless the ship aims at the mouse
+    aim: Vec2,
 }
 
 impl Position for Spaceship {
@@ -32,28 +40,70 @@ impl Spaceship {
         Self {
             position,
             cooldown: None,
+            charge: Duration::ZERO,
+            aim: Vec2::Y,
+        }
+    }
+
+    // `direction` doesn't need to be normalized, zero keeps the last aim
+    pub fn set_aim(&mut self, direction: Vec2) {
+        if direction.try_normalize().is_some() {
+            self.set_aim_step(aim_step(direction));
         }
     }
 
-    pub fn get_position_tuple(&self) -> (f32, f32) {
-        (self.position.x, self.position.y)
+    pub fn aim_step(&self) -> u16 {
+        aim_step(self.aim)
+    }
+
+    pub fn set_aim_step(&mut self, step: u16) {
+        self.aim = Vec2::from_angle(f32::from(step % AIM_STEPS) * TAU / AIM_STEPS as f32);
+    }
+
+    pub fn aim(&self) -> Vec2 {
+        self.aim
+    }
+
+    // Turns anything fired straight up to where the ship aims
+    pub fn aim_rotation(&self) -> Rot2 {
+        Rot2::radians(Vec2::Y.angle_to(self.aim))
     }
 
     pub fn can_shoot(&self) -> bool {
         self.cooldown.is_none()
     }
 
-    pub fn start_cd(&mut self) {
-        self.cooldown = Some(Timer::new(Duration::from_millis(100), TimerMode::Once));
+    pub fn start_cd(&mut self, cooldown: Duration) {
+        self.cooldown = Some(Timer::new(cooldown, TimerMode::Once));
+    }
+
+    pub fn charge(&mut self, delta: Duration) {
+        self.charge += delta;
+    }
+
+    pub fn take_charge(&mut self) -> Duration {
+        std::mem::take(&mut self.charge)
     }
 }
 
+fn aim_step(direction: Vec2) -> u16 {
+    let step = (direction.to_angle() / (TAU / AIM_STEPS as f32)).round() as i32;
+    step.rem_euclid(AIM_STEPS.into()) as u16
+}
+
 pub struct SpaceshipPlugin;
 
 impl Plugin for SpaceshipPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (listen_position::<Spaceship>, handle_cooldown))
-            .add_observer(handle_spaceship_on_added);
+        app.add_systems(
+            Update,
+            (
+                listen_position::<Spaceship>,
+                handle_cooldown,
+                handle_aim_rotation,
+            ),
+        )
+        .add_observer(handle_spaceship_on_added);
     }
 }
 
@@ -63,12 +113,14 @@ fn handle_spaceship_on_added(
     image_handles: Res<ImageHandles>,
     spaceship_query: Query<(&Player, &Spaceship)>,
     player_tag: Res<PlayerTag>,
+    local_coop: Option<Res<LocalCoop>>,
 ) {
     let Ok((player, spaceship)) = spaceship_query.get(ev.target()) else {
         warn!("Player not found in handle_spaceship_on_added");
         return;
     };
-    let (z, color) = if player_tag.0 == player.0 {
+    let is_local = player.is_local(&player_tag, local_coop.is_some());
+    let (z, color) = if is_local {
         (ZIndex::SELFSPACESHIP.z_value(), Color::WHITE)
     } else {
         (ZIndex::SPACESHIP.z_value(), Color::srgb(0.5, 0.5, 0.5))
@@ -83,19 +135,38 @@ fn handle_spaceship_on_added(
             },
             Transform::from_translation(spaceship.position.extend(z)),
         ));
-        if player_tag.0 == player.0 {
-            entity_commands.insert(Collisable::Player);
+        if is_local {
+            entity_commands.insert(CollisionLayer::Player);
+        }
+        if local_coop.is_some() {
+            entity_commands.with_child((
+                Text2d::new(format!("P{}", player.0)),
+                TextFont::from_font_size(16.),
+                Transform::from_xyz(0., -SPACESHIP_SIZE.y / 2. - 10., 0.1),
+            ));
         }
     }
 }
 
-fn handle_cooldown(mut spaceship_query: Query<&mut Spaceship>, time: Res<Time>) {
+fn handle_cooldown(
+    mut spaceship_query: Query<&mut Spaceship>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
     for mut spaceship in spaceship_query.iter_mut() {
         if let Some(timer) = &mut spaceship.cooldown {
-            timer.tick(time.delta());
+            timer.tick(game_speed.delta(&time));
             if timer.finished() {
                 spaceship.cooldown = None;
             }
         };
     }
 }
+
+fn handle_aim_rotation(
+    mut spaceship_query: Query<(&Spaceship, &mut Transform), Changed<Spaceship>>,
+) {
+    for (spaceship, mut transform) in spaceship_query.iter_mut() {
+        transform.rotation = Quat::from_rotation_z(spaceship.aim_rotation().as_radians());
+    }
+}
@@ -0,0 +1,83 @@
+use bevy::app::{App, RunFixedMainLoop, RunFixedMainLoopSystem};
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+
+// Movement steps in FixedUpdate, so drawing the raw translation stutters whenever the frame
+// rate and the fixed rate drift apart. Rendering lerps between the last two fixed positions
+// and the real translation is put back before anything else reads it.
+#[derive(Component, Default)]
+#[require(Transform)]
+pub struct TransformInterpolation {
+    previous: Vec3,
+    current: Vec3,
+    // False until the first position is seen, nothing to lerp from before that
+    ready: bool,
+}
+
+impl TransformInterpolation {
+    fn snap(&mut self, translation: Vec3) {
+        self.previous = translation;
+        self.current = translation;
+        self.ready = true;
+    }
+}
+
+pub struct TransformInterpolationPlugin;
+
+impl Plugin for TransformInterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            RunFixedMainLoop,
+            restore_translation.in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop),
+        )
+        .add_systems(FixedFirst, store_previous_translation)
+        .add_systems(FixedLast, store_current_translation)
+        .add_systems(
+            PostUpdate,
+            interpolate_translation.before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+fn restore_translation(mut interpolation_q: Query<(&TransformInterpolation, &mut Transform)>) {
+    for (interpolation, mut transform) in interpolation_q.iter_mut() {
+        if interpolation.ready {
+            transform.translation = interpolation.current;
+        }
+    }
+}
+
+fn store_previous_translation(
+    mut interpolation_q: Query<(&mut TransformInterpolation, &Transform)>,
+) {
+    for (mut interpolation, transform) in interpolation_q.iter_mut() {
+        interpolation.previous = transform.translation;
+    }
+}
+
+fn store_current_translation(
+    mut interpolation_q: Query<(&mut TransformInterpolation, &Transform)>,
+) {
+    for (mut interpolation, transform) in interpolation_q.iter_mut() {
+        if interpolation.ready {
+            interpolation.current = transform.translation;
+        } else {
+            interpolation.snap(transform.translation);
+        }
+    }
+}
+
+// Anything that moved the entity outside the fixed steps is a teleport and is not smoothed
+fn interpolate_translation(
+    mut interpolation_q: Query<(&mut TransformInterpolation, &mut Transform)>,
+    fixed_time: Res<Time<Fixed>>,
+) {
+    let overstep = fixed_time.overstep_fraction();
+    for (mut interpolation, mut transform) in interpolation_q.iter_mut() {
+        if !interpolation.ready || transform.translation != interpolation.current {
+            interpolation.snap(transform.translation);
+            continue;
+        }
+        transform.translation = interpolation.previous.lerp(interpolation.current, overstep);
+    }
+}
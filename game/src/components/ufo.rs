@@ -1,17 +1,35 @@
 use crate::constant::ZIndex;
-use crate::res::ImageHandles;
+use crate::res::{AccessibilityOption, EnemyCatalog};
 use crate::util::{listen_position, Position};
 use bevy::prelude::*;
-use shooting_game_shared::util::UFO_SIZE;
 
-use super::collisable::Collisable;
+use super::collisable::CollisionLayer;
 
 #[derive(Component)]
 pub struct EnemyTag(pub u16);
 
+// Index of the UFO's definition in the EnemyCatalog
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct UFOKind(usize);
+
+impl UFOKind {
+    pub const BASIC: UFOKind = UFOKind(0);
+
+    pub fn from_index(index: usize) -> Self {
+        Self(index)
+    }
+
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
 #[derive(Component)]
+#[allow(clippy::upper_case_acronyms)]
 pub struct UFO {
     position: Vec2,
+    kind: UFOKind,
+    health: u8,
 }
 
 impl Position for UFO {
@@ -24,8 +42,28 @@ impl Position for UFO {
 }
 
 impl UFO {
-    pub fn new(position: Vec2) -> Self {
-        Self { position }
+    pub fn new(position: Vec2, catalog: &EnemyCatalog) -> Self {
+        Self::with_kind(position, UFOKind::BASIC, catalog)
+    }
+
+    pub fn with_kind(position: Vec2, kind: UFOKind, catalog: &EnemyCatalog) -> Self {
+        Self {
+            position,
+            kind,
+            health: catalog.get(kind).health,
+        }
+    }
+
+    pub fn kind(&self) -> UFOKind {
+        self.kind
+    }
+
+    pub fn damage(&mut self, amount: u8) {
+        self.health = self.health.saturating_sub(amount);
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health == 0
     }
 }
 
@@ -41,19 +79,22 @@ impl Plugin for UFOPlugin {
 fn handle_ufo_on_added(
     ev: Trigger<OnAdd, UFO>,
     mut commands: Commands,
-    image_handles: Res<ImageHandles>,
     ufo_query: Query<&UFO>,
+    catalog: Res<EnemyCatalog>,
+    accessibility: Res<AccessibilityOption>,
 ) {
     let ufo = ufo_query.get(ev.target()).unwrap();
+    let definition = catalog.get(ufo.kind);
     if let Ok(mut entity_commands) = commands.get_entity(ev.target()) {
         entity_commands.insert((
             Sprite {
-                image: image_handles.ufo.clone(),
-                custom_size: Some(UFO_SIZE),
+                image: definition.sprite.clone(),
+                color: definition.color(accessibility.color_blind),
+                custom_size: Some(definition.size),
                 ..default()
             },
             Transform::from_translation(ufo.position.extend(ZIndex::UFO.z_value())),
-            Collisable::Enemy,
+            CollisionLayer::Enemy,
         ));
     }
 }
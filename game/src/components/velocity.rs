@@ -1,7 +1,13 @@
 use bevy::app::App;
 use bevy::prelude::*;
 
+use crate::res::GameSpeed;
+
+use super::TransformInterpolation;
+
+// Applied in FixedUpdate, drawn smoothly in between
 #[derive(Component)]
+#[require(TransformInterpolation)]
 pub struct Velocity {
     pub x: f32,
     pub y: f32,
@@ -24,10 +30,10 @@ impl Plugin for VelocityPlugin {
     }
 }
 
-fn apply_velocity(mut items: Query<(&Velocity, &mut Transform)>) {
+fn apply_velocity(mut items: Query<(&Velocity, &mut Transform)>, game_speed: Res<GameSpeed>) {
     for (velocity, mut transform) in items.iter_mut() {
         let origin_translation = transform.translation;
-        transform.translation.x = origin_translation.x + velocity.x;
-        transform.translation.y = origin_translation.y + velocity.y;
+        transform.translation.x = origin_translation.x + velocity.x * game_speed.factor();
+        transform.translation.y = origin_translation.y + velocity.y * game_speed.factor();
     }
 }
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::res::GameSpeed;
+
+// Heat goes from 0 to 1, a full gauge locks the weapon
+const HEAT_PER_SHOT: f32 = 0.08;
+const COOL_RATE: f32 = 0.6;
+// Cooling only starts once shoot has been let go for a moment
+const COOL_DELAY: Duration = Duration::from_millis(300);
+const OVERHEAT_DURATION: Duration = Duration::from_secs(2);
+// Faster than the classic cooldown, the heat is what holds the fire rate back
+pub const HEAT_FIRE_INTERVAL: Duration = Duration::from_millis(60);
+
+// Only spaceships of a single player game in WeaponMode::Heat carry it
+#[derive(Component, Default)]
+pub struct WeaponHeat {
+    heat: f32,
+    since_shot: Duration,
+    overheat: Option<Timer>,
+}
+
+impl WeaponHeat {
+    pub fn heat(&self) -> f32 {
+        self.heat
+    }
+
+    pub fn is_overheated(&self) -> bool {
+        self.overheat.is_some()
+    }
+
+    pub fn add_shot(&mut self) {
+        self.since_shot = Duration::ZERO;
+        self.heat = (self.heat + HEAT_PER_SHOT).min(1.);
+        if self.heat >= 1. {
+            self.overheat = Some(Timer::new(OVERHEAT_DURATION, TimerMode::Once));
+        }
+    }
+}
+
+pub struct WeaponHeatPlugin;
+
+impl Plugin for WeaponHeatPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, cool_weapons);
+    }
+}
+
+// The gauge stays full while locked and is emptied once the lock is over
+fn cool_weapons(
+    mut weapon_heat_q: Query<&mut WeaponHeat>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    let delta = game_speed.delta(&time);
+    for mut weapon_heat in weapon_heat_q.iter_mut() {
+        if let Some(overheat) = weapon_heat.overheat.as_mut() {
+            if overheat.tick(delta).finished() {
+                weapon_heat.overheat = None;
+                weapon_heat.heat = 0.;
+            }
+            continue;
+        }
+        weapon_heat.since_shot += delta;
+        if weapon_heat.since_shot >= COOL_DELAY {
+            weapon_heat.heat = (weapon_heat.heat - COOL_RATE * delta.as_secs_f32()).max(0.);
+        }
+    }
+}
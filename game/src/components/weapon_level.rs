@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+
+const MAX_LEVEL: u8 = 5;
+// Distance between parallel streams
+const STREAM_GAP: f32 = 10.;
+// Extra tilt in degree for every stream away from the middle
+const STREAM_SPREAD: f32 = 3.;
+
+// Kept apart from the spaceship so it lasts through respawns for the whole run
+#[derive(Component)]
+pub struct WeaponLevel(u8);
+
+impl Default for WeaponLevel {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+impl WeaponLevel {
+    pub fn from_level(level: u8) -> Self {
+        Self(level.clamp(1, MAX_LEVEL))
+    }
+
+    pub fn level(&self) -> u8 {
+        self.0
+    }
+
+    pub fn upgrade(&mut self) {
+        self.0 = (self.0 + 1).min(MAX_LEVEL);
+    }
+
+    pub fn downgrade(&mut self) {
+        self.0 = self.0.saturating_sub(1).max(1);
+    }
+
+    // One (x offset, angle) per stream, positive angles tilt to the left like Bullet
+    pub fn pattern(&self) -> impl Iterator<Item = (f32, f32)> {
+        let middle = (self.0 - 1) as f32 / 2.;
+        (0..self.0).map(move |stream| {
+            let from_middle = stream as f32 - middle;
+            (from_middle * STREAM_GAP, -from_middle * STREAM_SPREAD)
+        })
+    }
+}
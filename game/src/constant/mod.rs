@@ -1,5 +1,7 @@
+mod palette;
 mod size;
 mod z_index;
 
+pub use palette::*;
 pub use size::*;
 pub use z_index::*;
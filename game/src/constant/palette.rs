@@ -0,0 +1,10 @@
+use bevy::color::Color;
+
+// Okabe-Ito hues, they stay apart with every common kind of color blindness
+pub const COLOR_BLIND_ORANGE: Color = Color::srgb(0.9, 0.6, 0.);
+pub const COLOR_BLIND_SKY_BLUE: Color = Color::srgb(0.34, 0.71, 0.91);
+pub const COLOR_BLIND_YELLOW: Color = Color::srgb(0.94, 0.89, 0.26);
+pub const COLOR_BLIND_BLUE: Color = Color::srgb(0., 0.45, 0.7);
+pub const COLOR_BLIND_VERMILLION: Color = Color::srgb(0.84, 0.37, 0.);
+pub const COLOR_BLIND_REDDISH_PURPLE: Color = Color::srgb(0.8, 0.47, 0.65);
+pub const COLOR_BLIND_BLUISH_GREEN: Color = Color::srgb(0., 0.62, 0.45);
@@ -2,4 +2,14 @@ use bevy::math::Vec2;
 
 pub const STAR_SIZE: Vec2 = Vec2::new(400., 290.);
 pub const BULLET_SIZE: Vec2 = Vec2::new(5., 10.);
+pub const CHARGED_BULLET_SIZE: Vec2 = Vec2::new(14., 28.);
+pub const MISSILE_SIZE: Vec2 = Vec2::new(8., 18.);
+pub const DRONE_BULLET_SIZE: Vec2 = Vec2::new(4., 8.);
+pub const DRONE_SIZE: Vec2 = Vec2::new(22., 22.);
 pub const EXPLOSION_SIZE: Vec2 = Vec2::new(100., 100.);
+pub const ENEMY_BULLET_SIZE: Vec2 = Vec2::new(8., 8.);
+pub const BOSS_SIZE: Vec2 = Vec2::new(240., 162.);
+pub const HEALTH_PIP_SIZE: Vec2 = Vec2::new(18., 10.);
+pub const HEAT_GAUGE_SIZE: Vec2 = Vec2::new(60., 10.);
+// Gap between the HUD and the window edge
+pub const HUD_MARGIN: Vec2 = Vec2::new(5., 5.);
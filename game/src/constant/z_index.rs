@@ -1,13 +1,17 @@
 use bevy::ui::ZIndex as BevyZIndex;
 
+#[allow(clippy::upper_case_acronyms)]
 pub enum ZIndex {
     BACKGROUND,
     STARS,
     EXPLOSION,
     SPACESHIP,
     SELFSPACESHIP,
+    // Over the whole game view, still under the UI
+    CRT,
     UFO,
     BULLET,
+    POWERUP,
     MAINCONTAINER,
     TEXT,
 }
@@ -18,8 +22,9 @@ impl ZIndex {
             ZIndex::BACKGROUND => 0.,
             ZIndex::STARS => 1.,
             ZIndex::EXPLOSION => 2.,
-            ZIndex::SPACESHIP | ZIndex::UFO | ZIndex::BULLET => 3.,
+            ZIndex::SPACESHIP | ZIndex::UFO | ZIndex::BULLET | ZIndex::POWERUP => 3.,
             ZIndex::SELFSPACESHIP => 4.,
+            ZIndex::CRT => 4.5,
             ZIndex::MAINCONTAINER => 5.,
             ZIndex::TEXT => 6.,
         }
@@ -0,0 +1,248 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use bevy::tasks::{block_on, futures_lite::future::poll_once, AsyncComputeTaskPool, Task};
+use chrono::Local;
+
+use crate::constant::ZIndex;
+use crate::persistence::save_path;
+
+const SCREENSHOT_KEY: KeyCode = KeyCode::F12;
+const GIF_KEY: KeyCode = KeyCode::F11;
+const CAPTURES_DIR: &str = "captures";
+const GIF_FRAME_INTERVAL: Duration = Duration::from_millis(100);
+// About the last 5 seconds at the frame interval above
+const GIF_MAX_FRAMES: usize = 50;
+// Frames are shrunk to fit, a full size clip would take far too long to encode
+const GIF_MAX_SIZE: UVec2 = UVec2::new(270, 480);
+// From 1 (best colors) to 30 (fastest), a short clip doesn't need the best
+const GIF_QUANTIZE_SPEED: i32 = 20;
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+
+pub struct CapturePlugin;
+
+impl Plugin for CapturePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GifRecorder>().add_systems(
+            Update,
+            (
+                take_screenshot,
+                record_gif,
+                handle_capture_task,
+                expire_capture_toast,
+            ),
+        );
+    }
+}
+
+// Keeps only the most recent frames while the key is held
+#[derive(Resource)]
+struct GifRecorder {
+    frames: VecDeque<RgbFrame>,
+    timer: Timer,
+}
+
+impl Default for GifRecorder {
+    fn default() -> Self {
+        Self {
+            frames: VecDeque::new(),
+            timer: Timer::new(GIF_FRAME_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+struct RgbFrame {
+    size: UVec2,
+    pixels: Vec<u8>,
+}
+
+// Resolves to the written file so the toast can show where it went
+#[derive(Component)]
+struct CaptureTask(Task<Result<PathBuf, String>>);
+
+#[derive(Component)]
+struct CaptureToast(Timer);
+
+fn take_screenshot(mut commands: Commands, keys: Res<ButtonInput<KeyCode>>) {
+    if !keys.just_pressed(SCREENSHOT_KEY) {
+        return;
+    }
+    commands
+        .spawn(Screenshot::primary_window())
+        .observe(save_screenshot);
+}
+
+fn save_screenshot(trigger: Trigger<ScreenshotCaptured>, mut commands: Commands) {
+    let Some(path) = capture_path("png") else {
+        warn!("No data directory to save the screenshot to");
+        return;
+    };
+    let image = trigger.event().0.clone();
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        let image = image
+            .try_into_dynamic()
+            .map_err(|e| format!("Failed to read the screenshot: {e}"))?;
+        create_parent_dir(&path)?;
+        image
+            .to_rgb8()
+            .save(&path)
+            .map_err(|e| format!("Failed to save {}: {e}", path.display()))?;
+        Ok(path)
+    });
+    commands.spawn(CaptureTask(task));
+}
+
+fn record_gif(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut recorder: ResMut<GifRecorder>,
+    time: Res<Time<Real>>,
+) {
+    if keys.just_pressed(GIF_KEY) {
+        recorder.frames.clear();
+        recorder.timer.reset();
+        commands
+            .spawn(Screenshot::primary_window())
+            .observe(push_gif_frame);
+    }
+    if keys.pressed(GIF_KEY) {
+        recorder.timer.tick(time.delta());
+        if recorder.timer.just_finished() {
+            commands
+                .spawn(Screenshot::primary_window())
+                .observe(push_gif_frame);
+        }
+    }
+    if !keys.just_released(GIF_KEY) || recorder.frames.is_empty() {
+        return;
+    }
+    let Some(path) = capture_path("gif") else {
+        warn!("No data directory to save the GIF to");
+        return;
+    };
+    let frames: Vec<RgbFrame> = recorder.frames.drain(..).collect();
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        // A resize mid-recording would change the size, those frames are left out
+        let size = frames[0].size;
+        let pixels: Vec<Vec<u8>> = frames
+            .into_iter()
+            .filter(|frame| frame.size == size)
+            .map(|frame| frame.pixels)
+            .collect();
+        let delay = (GIF_FRAME_INTERVAL.as_millis() / 10) as u16;
+        create_parent_dir(&path)?;
+        save_gif(&path, size, &pixels, delay)
+            .map_err(|e| format!("Failed to save {}: {e}", path.display()))?;
+        Ok(path)
+    });
+    commands.spawn(CaptureTask(task));
+}
+
+// Frames can still arrive a moment after the key is released, they go to the next recording
+fn push_gif_frame(trigger: Trigger<ScreenshotCaptured>, mut recorder: ResMut<GifRecorder>) {
+    let image = match trigger.event().0.clone().try_into_dynamic() {
+        Ok(image) => image,
+        Err(e) => {
+            warn!("Failed to read a GIF frame: {e}");
+            return;
+        }
+    };
+    let frame = image.thumbnail(GIF_MAX_SIZE.x, GIF_MAX_SIZE.y).to_rgb8();
+    recorder.frames.push_back(RgbFrame {
+        size: UVec2::new(frame.width(), frame.height()),
+        pixels: frame.into_raw(),
+    });
+    while recorder.frames.len() > GIF_MAX_FRAMES {
+        recorder.frames.pop_front();
+    }
+}
+
+fn handle_capture_task(
+    mut commands: Commands,
+    mut task_q: Query<(Entity, &mut CaptureTask)>,
+    toast_q: Query<Entity, With<CaptureToast>>,
+) {
+    for (entity, mut task) in task_q.iter_mut() {
+        let Some(result) = block_on(poll_once(&mut task.0)) else {
+            continue;
+        };
+        commands.entity(entity).despawn();
+        let path = match result {
+            Ok(path) => path,
+            Err(e) => {
+                warn!("Capture failed with: {e}");
+                continue;
+            }
+        };
+        // Only the latest capture is shown
+        for toast in toast_q.iter() {
+            commands.entity(toast).despawn();
+        }
+        commands.spawn((
+            CaptureToast(Timer::new(TOAST_DURATION, TimerMode::Once)),
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(10.),
+                right: Val::Px(10.),
+                bottom: Val::Px(10.),
+                padding: UiRect::all(Val::Px(5.)),
+                ..default()
+            },
+            BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+            ZIndex::TEXT.component(),
+            children![(
+                Text::new(format!("Saved {}", path.display())),
+                TextFont::from_font_size(14.),
+            )],
+        ));
+    }
+}
+
+fn expire_capture_toast(
+    mut commands: Commands,
+    mut toast_q: Query<(Entity, &mut CaptureToast)>,
+    time: Res<Time<Real>>,
+) {
+    for (entity, mut toast) in toast_q.iter_mut() {
+        if toast.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn capture_path(extension: &str) -> Option<PathBuf> {
+    let timestamp = Local::now().format("%Y%m%d-%H%M%S%.3f");
+    save_path(&format!("{CAPTURES_DIR}/{timestamp}.{extension}"))
+}
+
+// Frames are tightly packed RGB, each one gets its own palette
+fn save_gif(path: &Path, size: UVec2, frames: &[Vec<u8>], delay: u16) -> Result<(), String> {
+    let (width, height) = (size.x as u16, size.y as u16);
+    let file = File::create(path).map_err(|e| e.to_string())?;
+    let mut encoder =
+        gif::Encoder::new(BufWriter::new(file), width, height, &[]).map_err(|e| e.to_string())?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(|e| e.to_string())?;
+    for pixels in frames {
+        let mut frame = gif::Frame::from_rgb_speed(width, height, pixels, GIF_QUANTIZE_SPEED);
+        frame.delay = delay;
+        encoder.write_frame(&frame).map_err(|e| e.to_string())?;
+    }
+    encoder
+        .into_inner()
+        .and_then(|mut writer| writer.flush())
+        .map_err(|e| e.to_string())
+}
+
+fn create_parent_dir(path: &Path) -> Result<(), String> {
+    let Some(dir) = path.parent() else {
+        return Ok(());
+    };
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {e}", dir.display()))
+}
@@ -0,0 +1,143 @@
+use std::time::Duration;
+
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::platform::time::Instant;
+use bevy::prelude::*;
+
+use crate::components::{Bullet, EnemyBullet, Particle, UFO};
+use crate::constant::ZIndex;
+use crate::res::{AdaptiveDifficulty, ControlOption, GameRng};
+use crate::states::{AppState, GameState, OnlineGameState};
+
+const TOGGLE_KEY: KeyCode = KeyCode::F3;
+
+pub struct DebugOverlayPlugin;
+
+impl Plugin for DebugOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+        }
+        app.init_resource::<FixedTickTiming>()
+            .add_systems(FixedFirst, start_fixed_tick)
+            .add_systems(FixedLast, end_fixed_tick)
+            .add_systems(
+                Update,
+                (
+                    toggle_debug_overlay,
+                    update_debug_overlay.run_if(any_with_component::<DebugOverlay>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Component)]
+struct DebugOverlay;
+
+#[derive(Component)]
+struct DebugOverlayText;
+
+// Wall clock time the last FixedUpdate took to run
+#[derive(Resource, Default)]
+struct FixedTickTiming {
+    started: Option<Instant>,
+    last: Duration,
+}
+
+fn start_fixed_tick(mut timing: ResMut<FixedTickTiming>) {
+    timing.started = Some(Instant::now());
+}
+
+fn end_fixed_tick(mut timing: ResMut<FixedTickTiming>) {
+    if let Some(started) = timing.started.take() {
+        timing.last = started.elapsed();
+    }
+}
+
+fn toggle_debug_overlay(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    overlay_q: Query<Entity, With<DebugOverlay>>,
+) {
+    if !keys.just_pressed(TOGGLE_KEY) {
+        return;
+    }
+    if let Ok(entity) = overlay_q.single() {
+        commands.entity(entity).despawn();
+        return;
+    }
+    commands.spawn((
+        DebugOverlay,
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(10.),
+            top: Val::Px(10.),
+            padding: UiRect::all(Val::Px(5.)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0., 0., 0., 0.6)),
+        GlobalZIndex(ZIndex::TEXT.z_value() as i32),
+        children![(
+            DebugOverlayText,
+            Text::new(""),
+            TextFont::from_font_size(14.)
+        )],
+    ));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_debug_overlay(
+    mut text_q: Query<&mut Text, With<DebugOverlayText>>,
+    diagnostics: Res<DiagnosticsStore>,
+    timing: Res<FixedTickTiming>,
+    entity_q: Query<()>,
+    bullet_q: Query<(), With<Bullet>>,
+    enemy_bullet_q: Query<(), With<EnemyBullet>>,
+    ufo_q: Query<(), With<UFO>>,
+    particle_q: Query<(), With<Particle>>,
+    app_state: Res<State<AppState>>,
+    game_state: Option<Res<State<GameState>>>,
+    online_game_state: Option<Res<State<OnlineGameState>>>,
+    control_option: Res<ControlOption>,
+    game_rng: Res<GameRng>,
+    adaptive_difficulty: Option<Res<AdaptiveDifficulty>>,
+) {
+    let Ok(mut text) = text_q.single_mut() else {
+        warn!("Debug overlay text not found in update_debug_overlay");
+        return;
+    };
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or_default();
+    let state = match (game_state, online_game_state) {
+        (Some(game_state), _) => format!("{:?}", game_state.get()),
+        (_, Some(online_game_state)) => format!("{:?}", online_game_state.get()),
+        _ => format!("{:?}", app_state.get()),
+    };
+    let adaptive = match adaptive_difficulty {
+        Some(adaptive_difficulty) => {
+            let totals = adaptive_difficulty.totals();
+            format!(
+                "{:+.0}% (deaths {}, accuracy {:.0}%, grazes {})",
+                adaptive_difficulty.adjustment() * 100.,
+                totals.deaths,
+                totals.accuracy() * 100.,
+                totals.grazes,
+            )
+        }
+        None => "Off".to_string(),
+    };
+    text.0 = format!(
+        "FPS: {fps:.0}\nFixed tick: {:.2} ms\nEntities: {}\nBullets: {}\nEnemy bullets: {}\nUFOs: {}\nParticles: {}\nState: {state}\nControl: {:?}\nSeed: {}\nAdaptive: {adaptive}",
+        timing.last.as_secs_f64() * 1000.,
+        entity_q.iter().len(),
+        bullet_q.iter().len(),
+        enemy_bullet_q.iter().len(),
+        ufo_q.iter().len(),
+        particle_q.iter().len(),
+        control_option.mode,
+        game_rng.seed(),
+    );
+}
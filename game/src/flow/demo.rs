@@ -0,0 +1,173 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use shooting_game_shared::util::EdgeUtil;
+
+use crate::components::{Asteroid, Boss, EnemyBullet, SelfPlayer, Spaceship, UFO};
+use crate::flow::shared::game_trigger::{
+    ShootBulletEvent, SpaceShipMovement, SpaceShipMovementEvent,
+};
+use crate::res::DemoMode;
+use crate::states::{AppState, GameState};
+use crate::ui_components::Blink;
+use crate::util::{any_input_just_pressed, cleanup_components};
+
+const IDLE_BEFORE_DEMO: Duration = Duration::from_secs(30);
+// Threats closer than this are dodged before anything else
+const DODGE_RADIUS: f32 = 140.;
+// Close enough under a target to hit it
+const ALIGN_TOLERANCE: f32 = 20.;
+// How far above its starting height the spaceship wanders before heading back down
+const HOME_RANGE: f32 = 120.;
+
+pub struct DemoPlugin;
+
+impl Plugin for DemoPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::MainMenu), reset_idle_timer)
+            .add_systems(
+                Update,
+                start_demo_when_idle.run_if(in_state(AppState::MainMenu)),
+            )
+            .add_systems(
+                OnEnter(AppState::Game),
+                spawn_demo_banner.run_if(resource_exists::<DemoMode>),
+            )
+            .add_systems(
+                Update,
+                (
+                    end_demo_on_input.run_if(in_state(AppState::Game)),
+                    drive_demo_spaceship.run_if(in_state(GameState::InPlay)),
+                )
+                    .run_if(resource_exists::<DemoMode>),
+            )
+            // The demo skips the game over screen and goes straight back to the menu
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                end_demo.run_if(resource_exists::<DemoMode>),
+            )
+            .add_systems(OnExit(AppState::Game), cleanup_components::<DemoBanner>);
+    }
+}
+
+#[derive(Resource)]
+struct IdleTimer(Timer);
+
+#[derive(Component)]
+struct DemoBanner;
+
+fn reset_idle_timer(mut commands: Commands) {
+    commands.insert_resource(IdleTimer(Timer::new(IDLE_BEFORE_DEMO, TimerMode::Once)));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn start_demo_when_idle(
+    mut commands: Commands,
+    mut idle_timer: ResMut<IdleTimer>,
+    mut cursor_events: EventReader<CursorMoved>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    gamepad_q: Query<&Gamepad>,
+    mut next_state: ResMut<NextState<AppState>>,
+    time: Res<Time>,
+) {
+    let moved = cursor_events.read().count() > 0;
+    if moved || any_input_just_pressed(&keys, &mouse, &touches, &gamepad_q) {
+        idle_timer.0.reset();
+        return;
+    }
+    idle_timer.0.tick(time.delta());
+    if idle_timer.0.just_finished() {
+        commands.insert_resource(DemoMode);
+        next_state.set(AppState::Game);
+    }
+}
+
+fn spawn_demo_banner(mut commands: Commands) {
+    commands.spawn((
+        DemoBanner,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.),
+            top: Val::Percent(40.),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        children![(
+            Text::new("DEMO"),
+            TextFont::from_font_size(40.),
+            Blink::new_with_speed(0.02)
+        )],
+    ));
+}
+
+fn end_demo_on_input(
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    gamepad_q: Query<&Gamepad>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if any_input_just_pressed(&keys, &mouse, &touches, &gamepad_q) {
+        next_state.set(AppState::MainMenu);
+    }
+}
+
+fn end_demo(mut next_state: ResMut<NextState<AppState>>) {
+    next_state.set(AppState::MainMenu);
+}
+
+// Dodges the closest threat, otherwise lines up under the closest target and shoots
+#[allow(clippy::type_complexity)]
+fn drive_demo_spaceship(
+    mut commands: Commands,
+    spaceship_q: Query<&Transform, (With<Spaceship>, With<SelfPlayer>)>,
+    threat_q: Query<&Transform, Or<(With<UFO>, With<EnemyBullet>, With<Asteroid>)>>,
+    target_q: Query<&Transform, Or<(With<UFO>, With<Boss>, With<Asteroid>)>>,
+) {
+    let Ok(spaceship_transform) = spaceship_q.single() else {
+        return;
+    };
+    let position = spaceship_transform.translation.truncate();
+    let threat = threat_q
+        .iter()
+        .map(|transform| transform.translation.truncate())
+        .filter(|threat| threat.distance(position) < DODGE_RADIUS)
+        .min_by(|a, b| {
+            position
+                .distance_squared(*a)
+                .total_cmp(&position.distance_squared(*b))
+        });
+    let target = target_q
+        .iter()
+        .map(|transform| transform.translation.truncate())
+        .filter(|target| target.y > position.y)
+        .min_by(|a, b| {
+            (a.x - position.x)
+                .abs()
+                .total_cmp(&(b.x - position.x).abs())
+        });
+
+    let mut direction = Vec2::ZERO;
+    if let Some(threat) = threat {
+        direction = (position - threat).normalize_or_zero();
+    } else {
+        if let Some(target) = target {
+            let offset = target.x - position.x;
+            if offset.abs() > ALIGN_TOLERANCE {
+                direction.x = offset.signum();
+            }
+        }
+        if position.y > EdgeUtil::spaceship().bottom_in() + HOME_RANGE {
+            direction.y = -1.;
+        }
+    }
+    commands.trigger(SpaceShipMovementEvent(SpaceShipMovement::from_direction(
+        direction,
+    )));
+
+    if target.is_some_and(|target| (target.x - position.x).abs() <= ALIGN_TOLERANCE) {
+        commands.trigger(ShootBulletEvent);
+    }
+}
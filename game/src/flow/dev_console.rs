@@ -0,0 +1,258 @@
+use std::collections::VecDeque;
+
+use bevy::ecs::system::SystemId;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::{ButtonState, InputSystem};
+use bevy::prelude::*;
+
+use crate::components::{Health, Player};
+use crate::constant::ZIndex;
+
+const TOGGLE_KEY: KeyCode = KeyCode::Backquote;
+const LOG_LINES: usize = 8;
+
+pub type ConsoleResult = Result<String, String>;
+
+// Words typed after the command name
+pub type ConsoleArgs = Vec<String>;
+
+// Every command the console knows, modules add their own with `add_console_command`
+#[derive(Resource, Default)]
+struct ConsoleCommands(Vec<ConsoleCommand>);
+
+struct ConsoleCommand {
+    // One or more words, the longest match wins
+    name: &'static str,
+    usage: &'static str,
+    system: SystemId<In<ConsoleArgs>, ConsoleResult>,
+}
+
+impl ConsoleCommands {
+    fn find(&self, words: &[&str]) -> Option<(&ConsoleCommand, usize)> {
+        self.0
+            .iter()
+            .filter_map(|command| {
+                let length = command.name.split(' ').count();
+                let matches = words.len() >= length
+                    && command.name.split(' ').zip(words).all(|(a, b)| a == *b);
+                matches.then_some((command, length))
+            })
+            .max_by_key(|(_, length)| *length)
+    }
+}
+
+pub trait ConsoleCommandAppExt {
+    fn add_console_command<M>(
+        &mut self,
+        name: &'static str,
+        usage: &'static str,
+        system: impl IntoSystem<In<ConsoleArgs>, ConsoleResult, M> + 'static,
+    ) -> &mut Self;
+}
+
+impl ConsoleCommandAppExt for App {
+    fn add_console_command<M>(
+        &mut self,
+        name: &'static str,
+        usage: &'static str,
+        system: impl IntoSystem<In<ConsoleArgs>, ConsoleResult, M> + 'static,
+    ) -> &mut Self {
+        let system = self.world_mut().register_system(system);
+        self.world_mut()
+            .get_resource_or_init::<ConsoleCommands>()
+            .0
+            .push(ConsoleCommand {
+                name,
+                usage,
+                system,
+            });
+        self
+    }
+}
+
+pub struct DevConsolePlugin;
+
+impl Plugin for DevConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConsoleCommands>()
+            .init_resource::<Console>()
+            // Ahead of everything reading the keys, the game shouldn't react to typing
+            .add_systems(PreUpdate, handle_console_input.after(InputSystem))
+            .add_systems(Update, (run_console_line, update_console_text).chain())
+            .add_console_command("set health", "set health <amount>", set_health)
+            .add_console_command("timescale", "timescale <scale>", set_timescale);
+    }
+}
+
+#[derive(Resource, Default)]
+struct Console {
+    input: String,
+    submitted: Option<String>,
+    log: VecDeque<String>,
+}
+
+impl Console {
+    fn print(&mut self, line: String) {
+        self.log.push_back(line);
+        while self.log.len() > LOG_LINES {
+            self.log.pop_front();
+        }
+    }
+}
+
+#[derive(Component)]
+struct ConsolePanel;
+
+#[derive(Component)]
+struct ConsoleText;
+
+fn handle_console_input(
+    mut commands: Commands,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut console: ResMut<Console>,
+    panel_q: Query<Entity, With<ConsolePanel>>,
+) {
+    let toggled = keys.just_pressed(TOGGLE_KEY);
+    let panel = panel_q.single().ok();
+    if toggled {
+        match panel {
+            Some(panel) => commands.entity(panel).despawn(),
+            None => spawn_console_panel(commands.reborrow()),
+        }
+    }
+    // Nothing is typed on the frame it opens or closes, so the backquote stays out
+    if panel.is_none() || toggled {
+        keyboard_events.clear();
+        if panel.is_some() {
+            keys.reset_all();
+        }
+        return;
+    }
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Enter => {
+                let line = std::mem::take(&mut console.input);
+                console.submitted = Some(line);
+            }
+            Key::Backspace => {
+                console.input.pop();
+            }
+            Key::Escape => {
+                if let Some(panel) = panel {
+                    commands.entity(panel).despawn();
+                }
+            }
+            Key::Space => console.input.push(' '),
+            Key::Character(text) => console.input.push_str(text),
+            _ => {}
+        }
+    }
+    keys.reset_all();
+}
+
+fn spawn_console_panel(mut commands: Commands) {
+    commands.spawn((
+        ConsolePanel,
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(0.),
+            right: Val::Px(0.),
+            top: Val::Px(0.),
+            padding: UiRect::all(Val::Px(8.)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0., 0., 0., 0.8)),
+        GlobalZIndex(ZIndex::TEXT.z_value() as i32 + 1),
+        children![(ConsoleText, Text::new(""), TextFont::from_font_size(14.))],
+    ));
+}
+
+// Exclusive so each command can run as its own system with whatever it needs
+fn run_console_line(world: &mut World) {
+    let Some(line) = world.resource_mut::<Console>().submitted.take() else {
+        return;
+    };
+    let words: Vec<&str> = line.split_whitespace().collect();
+    if words.is_empty() {
+        return;
+    }
+    world.resource_mut::<Console>().print(format!("> {line}"));
+    if words == ["help"] {
+        let usages: Vec<&str> = world
+            .resource::<ConsoleCommands>()
+            .0
+            .iter()
+            .map(|command| command.usage)
+            .collect();
+        let help = usages.join("\n");
+        world.resource_mut::<Console>().print(help);
+        return;
+    }
+    let found = world
+        .resource::<ConsoleCommands>()
+        .find(&words)
+        .map(|(command, length)| (command.system, length));
+    let Some((system, length)) = found else {
+        world
+            .resource_mut::<Console>()
+            .print("Unknown command, try help".to_string());
+        return;
+    };
+    let args = words[length..]
+        .iter()
+        .map(|word| word.to_string())
+        .collect();
+    let output = match world.run_system_with(system, args) {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => e,
+        Err(e) => format!("Command failed: {e}"),
+    };
+    world.resource_mut::<Console>().print(output);
+}
+
+fn update_console_text(console: Res<Console>, mut text_q: Query<&mut Text, With<ConsoleText>>) {
+    let Ok(mut text) = text_q.single_mut() else {
+        return;
+    };
+    let mut lines: Vec<&str> = console.log.iter().map(String::as_str).collect();
+    let prompt = format!("> {}_", console.input);
+    lines.push(&prompt);
+    text.0 = lines.join("\n");
+}
+
+// Parses the one number a command takes, rejecting anything after it
+pub fn parse_arg<T: std::str::FromStr>(args: &[String], usage: &str) -> Result<T, String> {
+    match args {
+        [arg] => arg.parse().map_err(|_| format!("Usage: {usage}")),
+        _ => Err(format!("Usage: {usage}")),
+    }
+}
+
+fn set_health(
+    In(args): In<ConsoleArgs>,
+    mut health_q: Query<&mut Health, With<Player>>,
+) -> ConsoleResult {
+    let amount: u8 = parse_arg(&args, "set health <amount>")?;
+    if health_q.is_empty() {
+        return Err("No players to heal".to_string());
+    }
+    for mut health in health_q.iter_mut() {
+        health.current = amount;
+        health.max = health.max.max(amount);
+    }
+    Ok(format!("Health set to {amount}"))
+}
+
+// Scales the whole virtual clock, fixed ticks included
+fn set_timescale(In(args): In<ConsoleArgs>, mut time: ResMut<Time<Virtual>>) -> ConsoleResult {
+    let scale: f32 = parse_arg(&args, "timescale <scale>")?;
+    if scale <= 0. || !scale.is_finite() {
+        return Err("The scale has to be above 0".to_string());
+    }
+    time.set_relative_speed(scale);
+    Ok(format!("Time scale set to {scale}"))
+}
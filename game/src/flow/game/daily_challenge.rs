@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+
+use crate::components::{Player, Score};
+use crate::persistence::{load_json, save_json};
+use crate::res::{
+    DailyChallenge, DailyRecord, GameRng, PlayerRunSettings, RunSettings, RunSettingsParam,
+    StageScripts,
+};
+use crate::states::{AppState, GameState};
+
+const DAILY_FILE: &str = "daily.json";
+
+pub struct DailyChallengePlugin;
+
+impl Plugin for DailyChallengePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_daily_record)
+            .add_systems(
+                Update,
+                save_daily_record.run_if(
+                    resource_changed::<DailyRecord>.and(not(resource_added::<DailyRecord>)),
+                ),
+            )
+            .add_systems(
+                // Before the game starts, so everything set up on entering it sees the defaults
+                OnExit(AppState::MainMenu),
+                apply_daily_settings.run_if(resource_exists::<DailyChallenge>),
+            )
+            .add_systems(
+                OnEnter(AppState::Game),
+                start_daily_challenge.run_if(resource_exists::<DailyChallenge>),
+            )
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                record_daily_score.run_if(resource_exists::<DailyChallenge>),
+            )
+            .add_systems(
+                OnExit(AppState::Game),
+                restore_stage_scripts.run_if(resource_exists::<DailyChallenge>),
+            );
+    }
+}
+
+fn load_daily_record(mut commands: Commands) {
+    commands.insert_resource(load_json::<DailyRecord>(DAILY_FILE).unwrap_or_default());
+}
+
+fn save_daily_record(daily_record: Res<DailyRecord>) {
+    save_json(DAILY_FILE, daily_record.as_ref());
+}
+
+// Every run of the day plays with the default run settings, so scores can be compared
+fn apply_daily_settings(mut commands: Commands, mut run_settings: RunSettingsParam) {
+    let player_settings = run_settings.replace(RunSettings::default());
+    commands.insert_resource(PlayerRunSettings(player_settings));
+}
+
+// and the same script from the same seed
+fn start_daily_challenge(
+    mut commands: Commands,
+    daily_challenge: Res<DailyChallenge>,
+    mut daily_record: ResMut<DailyRecord>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    commands.insert_resource(StageScripts::daily());
+    game_rng.set_seed(daily_challenge.seed());
+    // Counted as soon as it starts, quitting halfway doesn't earn a second try
+    *daily_record = DailyRecord {
+        date: Some(daily_challenge.date),
+        score: None,
+    };
+}
+
+fn record_daily_score(score_q: Query<(&Score, &Player)>, mut daily_record: ResMut<DailyRecord>) {
+    let Some((score, _)) = score_q.iter().min_by_key(|(_, player)| player.0) else {
+        warn!("Score not found in record_daily_score");
+        return;
+    };
+    daily_record.score = Some(score.0);
+}
+
+// The player's run settings come back through RunSettingsPlugin
+fn restore_stage_scripts(mut commands: Commands) {
+    commands.insert_resource(StageScripts::default());
+}
@@ -0,0 +1,294 @@
+use bevy::app::App;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use chrono::Local;
+
+use crate::components::{Player, Score};
+use crate::flow::leaderboard::SubmitScoreEvent;
+use crate::flow::replay::ReplayPlayback;
+use crate::res::{
+    AdaptiveDifficultyOption, ContinueRun, ControlOption, DailyChallenge, DemoMode, Difficulty,
+    GameStats, HighScoreEntry, HighScores, LeaderboardOption, LeaderboardPartition,
+};
+use crate::states::{AppState, GameState};
+use crate::ui_components::{Blink, FocusBack, InteractionUI, MainContainer};
+
+pub struct GameOverPlugin;
+
+impl Plugin for GameOverPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(GameState::GameOver),
+            show_game_over.run_if(not(resource_exists::<DemoMode>)),
+        )
+        .add_systems(
+            Update,
+            (handle_initials_input, handle_game_over_button_interaction)
+                .run_if(in_state(GameState::GameOver)),
+        );
+    }
+}
+
+#[derive(Component)]
+struct GameOver;
+
+#[derive(Component, Clone, Copy)]
+enum GameOverButton {
+    Retry,
+    MainMenu,
+}
+
+const MAX_INITIALS: usize = 3;
+
+#[derive(Component, Default)]
+struct InitialsInput(String);
+
+impl InitialsInput {
+    fn display(&self) -> String {
+        format!("{:_<width$}", self.0, width = MAX_INITIALS)
+    }
+}
+
+fn stats_text(game_stats: &GameStats) -> String {
+    let seconds = game_stats.time_survived.as_secs();
+    format!(
+        "UFOs Destroyed: {}\nBullets Fired: {}\nAccuracy: {:.1}%\nTime Survived: {:02}:{:02}",
+        game_stats.ufos_destroyed,
+        game_stats.bullets_fired,
+        game_stats.accuracy(),
+        seconds / 60,
+        seconds % 60,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn show_game_over(
+    mut commands: Commands,
+    score_query: Query<(&Score, &Player)>,
+    high_scores: Res<HighScores>,
+    game_stats: Res<GameStats>,
+    playback: Option<Res<ReplayPlayback>>,
+    leaderboard_option: Res<LeaderboardOption>,
+    daily_challenge: Option<Res<DailyChallenge>>,
+    continue_run: Option<Res<ContinueRun>>,
+    difficulty: Res<Difficulty>,
+    control_option: Res<ControlOption>,
+    adaptive_option: Res<AdaptiveDifficultyOption>,
+) {
+    let mut scores: Vec<(&Score, &Player)> = score_query.iter().collect();
+    scores.sort_by_key(|(_, player)| player.0);
+    let Some((score, _)) = scores.first() else {
+        warn!("Score not found in show_game_over");
+        return;
+    };
+    let is_local_coop = scores.len() > 1;
+    // A replayed run was already scored when it was recorded, local co-op scores are not
+    // comparable with the single player leaderboard and a daily run is shared by its code instead,
+    // a continued run skipped the stages before its save and an adaptive run didn't play the
+    // difficulty its table is for
+    let ranked = playback.is_none()
+        && !is_local_coop
+        && daily_challenge.is_none()
+        && continue_run.is_none()
+        && !adaptive_option.enabled;
+    let partition = LeaderboardPartition::current(&difficulty, &control_option);
+    let new_high_score = ranked && high_scores.qualifies(score.0, &partition);
+    // Any recorded single player score can go online, not only ones that beat the local table
+    let submit_online = ranked && score.0 > 0 && leaderboard_option.is_online();
+    // The replayed input is over, retrying would leave nothing to play back,
+    // and the daily challenge only allows one attempt
+    let buttons: &[GameOverButton] = if playback.is_some() || daily_challenge.is_some() {
+        &[GameOverButton::MainMenu]
+    } else {
+        &[GameOverButton::Retry, GameOverButton::MainMenu]
+    };
+    commands
+        .spawn((GameOver, MainContainer))
+        .with_children(|game_over_background| {
+            game_over_background.spawn((
+                Text::new("Game Over"),
+                TextFont::from_font_size(40.),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+            if is_local_coop {
+                for (score, player) in scores.iter() {
+                    game_over_background
+                        .spawn(Text::new(format!("P{} Final Score: {}", player.0, score.0)));
+                }
+            } else {
+                game_over_background.spawn(Text::new(format!("Final Score: {}", score.0)));
+            }
+            if let Some(daily_challenge) = daily_challenge.as_ref() {
+                game_over_background.spawn((
+                    Node {
+                        margin: UiRect::top(Val::Px(20.)),
+                        ..default()
+                    },
+                    Text::new(format!(
+                        "Daily Challenge {}\nShare code: {}",
+                        daily_challenge.date,
+                        daily_challenge.share_code(score.0)
+                    )),
+                    TextLayout::new_with_justify(JustifyText::Center),
+                    TextColor(Color::srgba(1., 0.8, 0., 1.)),
+                ));
+            }
+            game_over_background.spawn((
+                Node {
+                    margin: UiRect::top(Val::Px(20.)),
+                    padding: UiRect::all(Val::Px(10.)),
+                    border: UiRect::all(Val::Px(2.)),
+                    ..default()
+                },
+                BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+                BorderColor::from(Color::BLACK),
+                children![Text::new(stats_text(&game_stats))],
+            ));
+            if new_high_score || submit_online {
+                let prompt = if new_high_score {
+                    "New High Score!\nType your initials before leaving to save"
+                } else {
+                    "Type your initials before leaving to submit online"
+                };
+                game_over_background.spawn((
+                    Node {
+                        margin: UiRect::top(Val::Px(30.)),
+                        ..default()
+                    },
+                    Text::new(prompt),
+                    TextColor(Color::srgba(1., 0.8, 0., 1.)),
+                ));
+                let initials_input = InitialsInput::default();
+                game_over_background.spawn((
+                    Text::new(initials_input.display()),
+                    TextFont::from_font_size(40.),
+                    TextLayout::new_with_justify(JustifyText::Center),
+                    initials_input,
+                ));
+            }
+            game_over_background
+                .spawn(Node {
+                    height: Val::Percent(100.),
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::FlexEnd,
+                    row_gap: Val::Px(10.),
+                    ..default()
+                })
+                .with_children(|button_container| {
+                    button_container.spawn((
+                        Blink::new_with_speed(0.02),
+                        Text::new("Retry or return to main menu"),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                    ));
+                    for button in buttons {
+                        let label = match button {
+                            GameOverButton::Retry => "Retry",
+                            GameOverButton::MainMenu => "Main Menu",
+                        };
+                        let mut button_commands = button_container.spawn((
+                            *button,
+                            InteractionUI,
+                            Node {
+                                align_self: AlignSelf::FlexEnd,
+                                width: Val::Px(160.),
+                                height: Val::Px(50.),
+                                border: UiRect::all(Val::Px(2.)),
+                                display: Display::Flex,
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                            BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
+                            BorderColor::from(Color::BLACK),
+                        ));
+                        button_commands.with_child(Text::new(label));
+                        // Escape leaves for the main menu
+                        if matches!(button, GameOverButton::MainMenu) {
+                            button_commands.insert(FocusBack);
+                        }
+                    }
+                });
+        });
+}
+
+fn handle_initials_input(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut initials_query: Query<(&mut InitialsInput, &mut Text)>,
+) {
+    let Ok((mut initials_input, mut text)) = initials_query.single_mut() else {
+        return;
+    };
+    for keyboard_event in keyboard_events.read() {
+        if keyboard_event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &keyboard_event.logical_key {
+            Key::Backspace => {
+                initials_input.0.pop();
+            }
+            Key::Character(character) => {
+                for c in character.chars().filter(char::is_ascii_alphanumeric) {
+                    if initials_input.0.len() < MAX_INITIALS {
+                        initials_input.0.push(c.to_ascii_uppercase());
+                    }
+                }
+            }
+            _ => {}
+        }
+        text.0 = initials_input.display();
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_game_over_button_interaction(
+    mut commands: Commands,
+    button_query: Query<(&Interaction, &GameOverButton), Changed<Interaction>>,
+    game_over_query: Query<Entity, With<GameOver>>,
+    initials_query: Query<&InitialsInput>,
+    score_query: Query<&Score>,
+    mut high_scores: ResMut<HighScores>,
+    leaderboard_option: Res<LeaderboardOption>,
+    difficulty: Res<Difficulty>,
+    control_option: Res<ControlOption>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Ok(game_over) = game_over_query.single() else {
+            panic!("GameOver not found in handle_game_over_button_interaction");
+        };
+        if let (Ok(initials_input), Ok(score)) = (initials_query.single(), score_query.single()) {
+            let name = if initials_input.0.is_empty() {
+                "???".to_string()
+            } else {
+                initials_input.0.clone()
+            };
+            let partition = LeaderboardPartition::current(&difficulty, &control_option);
+            // The initials may only be asked for the online leaderboard
+            if high_scores.qualifies(score.0, &partition) {
+                high_scores.insert(HighScoreEntry {
+                    name: name.clone(),
+                    score: score.0,
+                    date: Local::now().date_naive(),
+                    partition: partition.clone(),
+                });
+            }
+            if leaderboard_option.is_online() {
+                commands.trigger(SubmitScoreEvent::new(name, score.0, partition));
+            }
+        }
+        if let Ok(mut entity_commands) = commands.get_entity(game_over) {
+            entity_commands.despawn();
+        }
+        match button {
+            GameOverButton::Retry => next_game_state.set(GameState::Ready),
+            GameOverButton::MainMenu => next_app_state.set(AppState::MainMenu),
+        }
+        return;
+    }
+}
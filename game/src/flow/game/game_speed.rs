@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+use crate::res::{GameSpeed, GameSpeedOption};
+use crate::states::AppState;
+
+pub struct GameSpeedPlugin;
+
+impl Plugin for GameSpeedPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Game), apply_game_speed_option)
+            .add_systems(OnExit(AppState::Game), reset_game_speed_setting);
+    }
+}
+
+fn apply_game_speed_option(
+    mut game_speed: ResMut<GameSpeed>,
+    game_speed_option: Res<GameSpeedOption>,
+) {
+    game_speed.setting = game_speed_option.scale();
+}
+
+fn reset_game_speed_setting(mut game_speed: ResMut<GameSpeed>) {
+    game_speed.setting = 1.;
+}
@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+
+use crate::components::{Bullet, Downed, GrazeEvent};
+use crate::flow::game::triggers::{DamageBossEvent, DamageUFOEvent};
+use crate::res::{AdaptiveDifficulty, AdaptiveDifficultyOption, GameSpeed};
+use crate::states::{AppState, GameState};
+
+use super::respawn::RespawnCountdown;
+
+pub struct AdaptiveDifficultyPlugin;
+
+impl Plugin for AdaptiveDifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Ready), reset_adaptive_difficulty)
+            .add_systems(
+                Update,
+                (tick_adaptive_difficulty, track_grazes)
+                    .run_if(in_state(GameState::InPlay))
+                    .run_if(resource_exists::<AdaptiveDifficulty>),
+            )
+            .add_systems(OnExit(AppState::Game), remove_adaptive_difficulty)
+            .add_observer(track_shot)
+            .add_observer(track_ufo_hit)
+            .add_observer(track_boss_hit)
+            .add_observer(track_respawn)
+            .add_observer(track_downed);
+    }
+}
+
+// Every run starts unadjusted
+fn reset_adaptive_difficulty(mut commands: Commands, option: Res<AdaptiveDifficultyOption>) {
+    if option.enabled {
+        commands.insert_resource(AdaptiveDifficulty::default());
+    } else {
+        commands.remove_resource::<AdaptiveDifficulty>();
+    }
+}
+
+fn remove_adaptive_difficulty(mut commands: Commands) {
+    commands.remove_resource::<AdaptiveDifficulty>();
+}
+
+fn tick_adaptive_difficulty(
+    mut adaptive_difficulty: ResMut<AdaptiveDifficulty>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    adaptive_difficulty.tick(game_speed.delta(&time));
+}
+
+// Online bullets are spawned too, only count them while playing offline
+fn track_shot(
+    _trigger: Trigger<OnAdd, Bullet>,
+    game_state: Option<Res<State<GameState>>>,
+    adaptive_difficulty: Option<ResMut<AdaptiveDifficulty>>,
+) {
+    let (Some(game_state), Some(mut adaptive_difficulty)) = (game_state, adaptive_difficulty)
+    else {
+        return;
+    };
+    if *game_state.get() == GameState::InPlay {
+        adaptive_difficulty.record_shot();
+    }
+}
+
+fn track_ufo_hit(
+    _trigger: Trigger<DamageUFOEvent>,
+    adaptive_difficulty: Option<ResMut<AdaptiveDifficulty>>,
+) {
+    if let Some(mut adaptive_difficulty) = adaptive_difficulty {
+        adaptive_difficulty.record_hit();
+    }
+}
+
+fn track_boss_hit(
+    _trigger: Trigger<DamageBossEvent>,
+    adaptive_difficulty: Option<ResMut<AdaptiveDifficulty>>,
+) {
+    if let Some(mut adaptive_difficulty) = adaptive_difficulty {
+        adaptive_difficulty.record_hit();
+    }
+}
+
+fn track_grazes(
+    mut graze_events: EventReader<GrazeEvent>,
+    mut adaptive_difficulty: ResMut<AdaptiveDifficulty>,
+) {
+    for _ in graze_events.read() {
+        adaptive_difficulty.record_graze();
+    }
+}
+
+// A life lost leaves a countdown behind, or a beacon once a co-op player is out of lives
+fn track_respawn(
+    _trigger: Trigger<OnAdd, RespawnCountdown>,
+    adaptive_difficulty: Option<ResMut<AdaptiveDifficulty>>,
+) {
+    if let Some(mut adaptive_difficulty) = adaptive_difficulty {
+        adaptive_difficulty.record_death();
+    }
+}
+
+fn track_downed(
+    _trigger: Trigger<OnAdd, Downed>,
+    adaptive_difficulty: Option<ResMut<AdaptiveDifficulty>>,
+) {
+    if let Some(mut adaptive_difficulty) = adaptive_difficulty {
+        adaptive_difficulty.record_death();
+    }
+}
@@ -0,0 +1,190 @@
+use std::ops::Range;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use rand::Rng;
+use shooting_game_shared::util::EdgeUtil;
+
+use crate::components::{
+    Asteroid, AsteroidSize, Bullet, CollidedEvent, Drone, Explosion, ExplosionKind, Invisible,
+    Missile, Pierced, Player, PoolCommandsExt, Shield, Spaceship, Velocity,
+};
+use crate::constant::EXPLOSION_SIZE;
+use crate::res::{GameRng, GameSpeed, RngStream};
+use crate::states::GameState;
+use crate::util::{angle_to_radian, Position};
+
+use super::collision::{damage_spaceship, destroy_drone, explode_missile, spend_bullet};
+use super::wave::WaveManager;
+
+const ASTEROID_SPAWN_INTERVAL: Duration = Duration::from_millis(2500);
+const ASTEROID_SPEED: Range<f32> = 1.5..3.;
+const ASTEROID_DRIFT: Range<f32> = -1.5..1.5;
+const ASTEROID_SPIN: Range<f32> = -2.0..2.0;
+const SPLIT_ANGLE: f32 = 30.;
+const SPLIT_SPEED_RATIO: f32 = 1.3;
+
+pub struct AsteroidPlugin;
+
+impl Plugin for AsteroidPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::InPlay), setup_asteroid_field)
+            .add_systems(
+                Update,
+                (
+                    check_and_spawn_asteroid,
+                    handle_asteroid_collisions,
+                    cleanup_on_out_screen,
+                )
+                    .run_if(in_state(GameState::InPlay)),
+            )
+            .add_systems(OnExit(GameState::InPlay), remove_asteroid_field);
+    }
+}
+
+#[derive(Resource)]
+struct AsteroidField {
+    spawn_timer: Timer,
+}
+
+fn setup_asteroid_field(mut commands: Commands) {
+    commands.insert_resource(AsteroidField {
+        spawn_timer: Timer::new(ASTEROID_SPAWN_INTERVAL, TimerMode::Repeating),
+    });
+}
+
+fn remove_asteroid_field(mut commands: Commands) {
+    commands.remove_resource::<AsteroidField>();
+}
+
+// Waves scripted with asteroids drift a field of rocks through to vary the pacing
+fn check_and_spawn_asteroid(
+    mut commands: Commands,
+    mut asteroid_field: ResMut<AsteroidField>,
+    wave_manager: Res<WaveManager>,
+    mut game_rng: ResMut<GameRng>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    if !wave_manager.has_asteroids() {
+        return;
+    }
+    asteroid_field.spawn_timer.tick(game_speed.delta(&time));
+    if !asteroid_field.spawn_timer.just_finished() {
+        return;
+    }
+    let rng = game_rng.stream(RngStream::Asteroid);
+    let size = AsteroidSize::Large;
+    let edge = EdgeUtil::new(size.size());
+    let position = Vec2::new(
+        rng.random_range(edge.left_in()..edge.right_in()),
+        edge.top_out(),
+    );
+    let velocity = Vec2::new(
+        rng.random_range(ASTEROID_DRIFT),
+        -rng.random_range(ASTEROID_SPEED),
+    );
+    let spin = rng.random_range(ASTEROID_SPIN);
+    commands.spawn(Asteroid::new(position, velocity, spin, size));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_asteroid_collisions(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollidedEvent>,
+    asteroid_q: Query<(&Asteroid, &Transform, &Velocity)>,
+    spaceship_q: Query<(&Player, &Spaceship, Has<Shield>, Has<Invisible>)>,
+    bullet_q: Query<&Bullet>,
+    missile_q: Query<&Missile>,
+    drone_q: Query<&Drone>,
+    mut pierced_q: Query<&mut Pierced>,
+) {
+    for collision in collision_events.read() {
+        let Ok((asteroid, transform, velocity)) = asteroid_q.get(collision.enemy) else {
+            continue;
+        };
+        // Rocks shrug off spaceships and only break apart when shot
+        if let Ok((player, spaceship, shielded, invisible)) = spaceship_q.get(collision.player) {
+            if invisible {
+                continue;
+            }
+            damage_spaceship(
+                commands.reborrow(),
+                player,
+                shielded.then(|| spaceship.get_position()),
+                collision.player,
+                Invisible::new(),
+            );
+            continue;
+        }
+        if let Ok(drone) = drone_q.get(collision.player) {
+            destroy_drone(commands.reborrow(), drone, collision.player);
+            continue;
+        }
+        if let Ok(missile) = missile_q.get(collision.player) {
+            explode_missile(commands.reborrow(), missile, collision.player);
+        } else if bullet_q.contains(collision.player) {
+            spend_bullet(
+                commands.reborrow(),
+                &mut pierced_q,
+                collision.player,
+                collision.enemy,
+            );
+        } else {
+            continue;
+        }
+        split_asteroid(
+            commands.reborrow(),
+            asteroid,
+            transform.translation.truncate(),
+            Vec2::new(velocity.x, velocity.y),
+            collision.enemy,
+        );
+    }
+}
+
+fn split_asteroid(
+    mut commands: Commands,
+    asteroid: &Asteroid,
+    position: Vec2,
+    velocity: Vec2,
+    entity: Entity,
+) {
+    if let Ok(mut entity_commands) = commands.get_entity(entity) {
+        entity_commands.despawn();
+    }
+    let Some(size) = asteroid.size().split() else {
+        commands.spawn_pooled(
+            Explosion::new(position, ExplosionKind::BulletImpact).with_size(EXPLOSION_SIZE / 2.),
+        );
+        return;
+    };
+    for angle in [-SPLIT_ANGLE, SPLIT_ANGLE] {
+        let piece_velocity =
+            Vec2::from_angle(angle_to_radian(angle)).rotate(velocity) * SPLIT_SPEED_RATIO;
+        commands.spawn(Asteroid::new(
+            position,
+            piece_velocity,
+            -asteroid.spin() * SPLIT_SPEED_RATIO,
+            size,
+        ));
+    }
+}
+
+fn cleanup_on_out_screen(
+    mut commands: Commands,
+    asteroid_q: Query<(Entity, &Asteroid, &Transform)>,
+) {
+    for (entity, asteroid, transform) in asteroid_q.iter() {
+        let edge = EdgeUtil::new(asteroid.size().size());
+        let position = transform.translation;
+        if edge.over_bottom_out(position.y)
+            || edge.over_left_out(position.x)
+            || edge.over_right_out(position.x)
+        {
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.despawn();
+            }
+        }
+    }
+}
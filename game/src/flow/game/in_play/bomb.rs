@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+
+use crate::components::{EnemyBullet, Explosion, ExplosionKind, PoolCommandsExt, Shockwave, UFO};
+use crate::flow::game::triggers::RemoveUFOEvent;
+use crate::states::GameState;
+use crate::util::Position;
+
+pub struct BombPlugin;
+
+impl Plugin for BombPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            clear_with_shockwaves.run_if(in_state(GameState::InPlay)),
+        );
+    }
+}
+
+// The boss shrugs the shockwave off, only regular UFOs and enemy bullets are cleared
+fn clear_with_shockwaves(
+    mut commands: Commands,
+    shockwave_q: Query<&Shockwave>,
+    ufo_q: Query<(Entity, &UFO)>,
+    enemy_bullet_q: Query<(Entity, &Transform), With<EnemyBullet>>,
+) {
+    if shockwave_q.is_empty() {
+        return;
+    }
+    for (entity, ufo) in ufo_q.iter() {
+        let position = ufo.get_position();
+        // Only the first shockwave gets the kill when co-op bombs overlap
+        let Some(shockwave) = shockwave_q
+            .iter()
+            .find(|shockwave| shockwave.reaches(position))
+        else {
+            continue;
+        };
+        commands.spawn_pooled(Explosion::new(position, ExplosionKind::UfoDeath));
+        commands.trigger(RemoveUFOEvent::by_bomb(entity, shockwave.get_player()));
+    }
+    for (entity, transform) in enemy_bullet_q.iter() {
+        let position = transform.translation.truncate();
+        if shockwave_q
+            .iter()
+            .any(|shockwave| shockwave.reaches(position))
+        {
+            commands.entity(entity).despawn();
+        }
+    }
+}
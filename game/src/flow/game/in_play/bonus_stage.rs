@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use rand::Rng;
+use shooting_game_shared::util::EdgeUtil;
+
+use crate::components::{
+    Bullet, CollidedEvent, Explosion, ExplosionKind, FloatingText, Missile, Ore, Pierced, Player,
+    PoolCommandsExt, Score, ORE_SIZE,
+};
+use crate::constant::EXPLOSION_SIZE;
+use crate::flow::game::triggers::AddScoreEvent;
+use crate::res::{GameRng, GameSpeed, RngStream};
+use crate::states::InPlayState;
+use crate::util::cleanup_components;
+
+use super::collision::{explode_missile, spend_bullet};
+
+const MINING_DURATION: Duration = Duration::from_secs(20);
+const TALLY_DURATION: Duration = Duration::from_secs(4);
+const ORE_SPAWN_INTERVAL: Duration = Duration::from_millis(400);
+const ORE_SPEED: Range<f32> = 1.5..3.5;
+const ORE_DRIFT: Range<f32> = -0.8..0.8;
+const ORE_SCORE: u32 = 50;
+
+pub struct BonusStagePlugin;
+
+impl Plugin for BonusStagePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(InPlayState::BonusStage), setup_bonus_stage)
+            .add_systems(
+                Update,
+                (
+                    handle_bonus_stage,
+                    spawn_ore,
+                    handle_ore_collisions,
+                    cleanup_ore_on_out_screen,
+                    update_countdown_text,
+                )
+                    .run_if(in_state(InPlayState::BonusStage)),
+            )
+            .add_systems(
+                OnExit(InPlayState::BonusStage),
+                (
+                    remove_bonus_stage,
+                    cleanup_components::<Ore>,
+                    cleanup_components::<BonusStageBanner>,
+                ),
+            );
+    }
+}
+
+enum BonusPhase {
+    Mining(Timer),
+    Tally(Timer),
+}
+
+#[derive(Resource)]
+struct BonusStage {
+    phase: BonusPhase,
+    spawn_timer: Timer,
+    // Ore mined by player tag
+    mined: HashMap<u8, u32>,
+}
+
+#[derive(Component)]
+struct BonusStageBanner;
+
+#[derive(Component)]
+struct CountdownText;
+
+fn setup_bonus_stage(mut commands: Commands) {
+    commands.insert_resource(BonusStage {
+        phase: BonusPhase::Mining(Timer::new(MINING_DURATION, TimerMode::Once)),
+        spawn_timer: Timer::new(ORE_SPAWN_INTERVAL, TimerMode::Repeating),
+        mined: HashMap::new(),
+    });
+    commands.spawn((
+        BonusStageBanner,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.),
+            top: Val::Percent(10.),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        children![(
+            CountdownText,
+            Text::new(countdown_text(MINING_DURATION)),
+            TextFont::from_font_size(32.),
+            TextLayout::new_with_justify(JustifyText::Center),
+        )],
+    ));
+}
+
+fn remove_bonus_stage(mut commands: Commands) {
+    commands.remove_resource::<BonusStage>();
+}
+
+fn countdown_text(remaining: Duration) -> String {
+    format!(
+        "Bonus Stage\nMine the ore! {}",
+        remaining.as_secs_f32().ceil()
+    )
+}
+
+fn update_countdown_text(
+    bonus_stage: Res<BonusStage>,
+    mut text_q: Query<&mut Text, With<CountdownText>>,
+) {
+    let BonusPhase::Mining(timer) = &bonus_stage.phase else {
+        return;
+    };
+    let Ok(mut text) = text_q.single_mut() else {
+        return;
+    };
+    text.0 = countdown_text(timer.remaining());
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_bonus_stage(
+    mut commands: Commands,
+    mut bonus_stage: ResMut<BonusStage>,
+    ore_q: Query<Entity, With<Ore>>,
+    banner_q: Query<Entity, With<BonusStageBanner>>,
+    player_q: Query<&Player, With<Score>>,
+    mut next_state: ResMut<NextState<InPlayState>>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    let delta = game_speed.delta(&time);
+    match &mut bonus_stage.phase {
+        BonusPhase::Mining(timer) => {
+            timer.tick(delta);
+            if !timer.finished() {
+                return;
+            }
+            cleanup_components::<Ore>(commands.reborrow(), ore_q);
+            cleanup_components::<BonusStageBanner>(commands.reborrow(), banner_q);
+            let mut players: Vec<u8> = player_q.iter().map(|player| player.0).collect();
+            players.sort();
+            spawn_tally(commands.reborrow(), &bonus_stage.mined, &players);
+            bonus_stage.phase = BonusPhase::Tally(Timer::new(TALLY_DURATION, TimerMode::Once));
+        }
+        BonusPhase::Tally(timer) => {
+            timer.tick(delta);
+            if timer.finished() {
+                next_state.set(InPlayState::Waves);
+            }
+        }
+    }
+}
+
+fn spawn_tally(mut commands: Commands, mined: &HashMap<u8, u32>, players: &[u8]) {
+    let mut lines = vec!["Bonus Stage Clear!".to_string()];
+    for player in players {
+        let count = mined.get(player).copied().unwrap_or_default();
+        let label = if players.len() > 1 {
+            format!("P{player} Ore")
+        } else {
+            "Ore".to_string()
+        };
+        lines.push(format!("{label}: {count} ({})", count * ORE_SCORE));
+    }
+    commands.spawn((
+        BonusStageBanner,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.),
+            top: Val::Percent(35.),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        children![(
+            Text::new(lines.join("\n")),
+            TextFont::from_font_size(36.),
+            TextLayout::new_with_justify(JustifyText::Center),
+            TextColor(Color::srgb(1., 0.8, 0.)),
+        )],
+    ));
+}
+
+fn spawn_ore(
+    mut commands: Commands,
+    mut bonus_stage: ResMut<BonusStage>,
+    mut game_rng: ResMut<GameRng>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    if !matches!(bonus_stage.phase, BonusPhase::Mining(_)) {
+        return;
+    }
+    bonus_stage.spawn_timer.tick(game_speed.delta(&time));
+    if !bonus_stage.spawn_timer.just_finished() {
+        return;
+    }
+    let rng = game_rng.stream(RngStream::Ore);
+    let edge = EdgeUtil::new(ORE_SIZE);
+    let position = Vec2::new(
+        rng.random_range(edge.left_in()..edge.right_in()),
+        edge.top_out(),
+    );
+    let velocity = Vec2::new(rng.random_range(ORE_DRIFT), -rng.random_range(ORE_SPEED));
+    commands.spawn(Ore::new(position, velocity));
+}
+
+// Score goes straight to the shooter, the tally only repeats what was already earned
+fn handle_ore_collisions(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollidedEvent>,
+    mut bonus_stage: ResMut<BonusStage>,
+    ore_q: Query<&Transform, With<Ore>>,
+    bullet_q: Query<&Bullet>,
+    missile_q: Query<&Missile>,
+    mut pierced_q: Query<&mut Pierced>,
+) {
+    // A piercing shot can report the same chunk twice in one frame
+    let mut mined = Vec::new();
+    for collision in collision_events.read() {
+        if mined.contains(&collision.enemy) {
+            continue;
+        }
+        let Ok(transform) = ore_q.get(collision.enemy) else {
+            continue;
+        };
+        let player = if let Ok(missile) = missile_q.get(collision.player) {
+            explode_missile(commands.reborrow(), missile, collision.player);
+            missile.get_player()
+        } else if let Ok(bullet) = bullet_q.get(collision.player) {
+            spend_bullet(
+                commands.reborrow(),
+                &mut pierced_q,
+                collision.player,
+                collision.enemy,
+            );
+            bullet.get_player()
+        } else {
+            continue;
+        };
+        let Ok(mut entity_commands) = commands.get_entity(collision.enemy) else {
+            continue;
+        };
+        entity_commands.despawn();
+        mined.push(collision.enemy);
+        let position = transform.translation.truncate();
+        commands.spawn_pooled(
+            Explosion::new(position, ExplosionKind::BulletImpact).with_size(EXPLOSION_SIZE / 2.),
+        );
+        commands.spawn(FloatingText::new(position, format!("+{ORE_SCORE}")));
+        commands.trigger(AddScoreEvent::new(player, ORE_SCORE));
+        *bonus_stage.mined.entry(player).or_default() += 1;
+    }
+}
+
+fn cleanup_ore_on_out_screen(
+    mut commands: Commands,
+    ore_q: Query<(Entity, &Transform), With<Ore>>,
+) {
+    let edge = EdgeUtil::new(ORE_SIZE);
+    for (entity, transform) in ore_q.iter() {
+        let position = transform.translation;
+        if edge.over_bottom_out(position.y)
+            || edge.over_left_out(position.x)
+            || edge.over_right_out(position.x)
+        {
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.despawn();
+            }
+        }
+    }
+}
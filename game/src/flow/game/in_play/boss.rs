@@ -0,0 +1,268 @@
+use std::time::Duration;
+
+use bevy::color::palettes::css::RED;
+use bevy::prelude::*;
+use shooting_game_shared::util::EdgeUtil;
+
+use crate::components::{
+    Boss, BossPhase, EnemyBullet, Explosion, ExplosionKind, PoolCommandsExt, Spaceship, Velocity,
+    BOSS_COLOR,
+};
+use crate::constant::BOSS_SIZE;
+use crate::res::{AccessibilityOption, GameSpeed};
+use crate::states::GameState;
+use crate::ui_components::BossHealthBar;
+use crate::util::{angle_to_radian, cleanup_components, closest_position, Position};
+
+use super::wave::WaveManager;
+
+const BOSS_HEALTH: u8 = 30;
+const BOSS_NAME: &str = "MOTHERSHIP";
+// Where Two and Three start, matching Boss::phase
+const BOSS_PHASE_MARKERS: [f32; 2] = [1. / 3., 2. / 3.];
+const BOSS_HOVER_Y: f32 = 250.;
+const BOSS_ENTER_SPEED: f32 = 2.;
+const BOSS_SWAY_SPEED: f32 = 2.;
+const BOSS_CHARGE_SPEED: f32 = 12.;
+const BOSS_BULLET_SPEED: f32 = 5.;
+const BOSS_SPREAD_ANGLE: f32 = 30.;
+const BOSS_FLASH_DURATION: Duration = Duration::from_millis(300);
+
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                check_and_spawn_boss,
+                handle_boss_phase,
+                handle_boss_movement,
+                handle_boss_attack,
+                sync_boss_health_bar,
+            )
+                .run_if(in_state(GameState::InPlay)),
+        )
+        .add_systems(
+            OnExit(GameState::InPlay),
+            cleanup_components::<BossHealthBar>,
+        );
+    }
+}
+
+enum BossAction {
+    Entering,
+    Hovering,
+    Charging(Vec2),
+    Returning,
+}
+
+#[derive(Component)]
+struct BossBehaviour {
+    action: BossAction,
+    phase: BossPhase,
+    attack_timer: Timer,
+    charge_timer: Timer,
+    flash_timer: Option<Timer>,
+}
+
+impl BossBehaviour {
+    fn new() -> Self {
+        let phase = BossPhase::One;
+        Self {
+            action: BossAction::Entering,
+            phase,
+            attack_timer: Timer::new(attack_interval(phase), TimerMode::Repeating),
+            charge_timer: Timer::new(charge_interval(phase), TimerMode::Repeating),
+            flash_timer: None,
+        }
+    }
+
+    fn enter_phase(&mut self, phase: BossPhase) {
+        self.phase = phase;
+        self.attack_timer.set_duration(attack_interval(phase));
+        self.attack_timer.reset();
+        self.charge_timer.set_duration(charge_interval(phase));
+        self.charge_timer.reset();
+        self.flash_timer = Some(Timer::new(BOSS_FLASH_DURATION, TimerMode::Once));
+    }
+}
+
+fn attack_interval(phase: BossPhase) -> Duration {
+    match phase {
+        BossPhase::One => Duration::from_millis(2000),
+        BossPhase::Two => Duration::from_millis(1500),
+        BossPhase::Three => Duration::from_millis(1000),
+    }
+}
+
+// Phase One never charges, the timer is simply not ticked
+fn charge_interval(phase: BossPhase) -> Duration {
+    match phase {
+        BossPhase::One | BossPhase::Two => Duration::from_secs(6),
+        BossPhase::Three => Duration::from_secs(4),
+    }
+}
+
+fn spread_count(phase: BossPhase) -> usize {
+    match phase {
+        BossPhase::One | BossPhase::Two => 5,
+        BossPhase::Three => 7,
+    }
+}
+
+fn check_and_spawn_boss(
+    mut commands: Commands,
+    mut wave_manager: ResMut<WaveManager>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    if !wave_manager.is_boss_wave() || !wave_manager.tick_spawn(game_speed.delta(&time)) {
+        return;
+    }
+    let edge = EdgeUtil::new(BOSS_SIZE);
+    commands.spawn((
+        Boss::new(Vec2::new(0., edge.top_out()), BOSS_HEALTH),
+        BossBehaviour::new(),
+        Velocity::from_vec2(Vec2::new(0., -BOSS_ENTER_SPEED)),
+    ));
+    commands.spawn(
+        BossHealthBar::new(BOSS_NAME, BOSS_HEALTH).with_phase_markers(BOSS_PHASE_MARKERS.to_vec()),
+    );
+}
+
+// The bar goes away with the last boss
+fn sync_boss_health_bar(
+    mut commands: Commands,
+    boss_query: Query<Ref<Boss>>,
+    mut bar_query: Query<(Entity, &mut BossHealthBar)>,
+) {
+    let Ok((entity, mut bar)) = bar_query.single_mut() else {
+        return;
+    };
+    let Ok(boss) = boss_query.single() else {
+        commands.entity(entity).despawn();
+        return;
+    };
+    if boss.is_changed() {
+        bar.set_ratio(boss.health_ratio());
+    }
+}
+
+fn handle_boss_phase(
+    mut commands: Commands,
+    mut boss_query: Query<(&Boss, &mut BossBehaviour, &mut Sprite)>,
+    mut bar_query: Query<&mut BossHealthBar>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+    accessibility: Res<AccessibilityOption>,
+) {
+    for (boss, mut behaviour, mut sprite) in boss_query.iter_mut() {
+        let phase = boss.phase();
+        if phase != behaviour.phase {
+            behaviour.enter_phase(phase);
+            for mut bar in bar_query.iter_mut() {
+                bar.flash();
+            }
+            if !accessibility.disable_flashing {
+                sprite.color = Color::from(RED);
+            }
+            let position = boss.get_position();
+            for offset in [-BOSS_SIZE.x / 4., BOSS_SIZE.x / 4.] {
+                commands.spawn_pooled(Explosion::new(
+                    position + Vec2::new(offset, 0.),
+                    ExplosionKind::UfoDeath,
+                ));
+            }
+        }
+        let Some(flash_timer) = behaviour.flash_timer.as_mut() else {
+            continue;
+        };
+        flash_timer.tick(game_speed.delta(&time));
+        if flash_timer.finished() {
+            sprite.color = BOSS_COLOR;
+            behaviour.flash_timer = None;
+        }
+    }
+}
+
+fn handle_boss_movement(
+    mut boss_query: Query<(&mut BossBehaviour, &mut Velocity, &Transform), With<Boss>>,
+    spaceship_query: Query<&Spaceship>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    let edge = EdgeUtil::new(BOSS_SIZE);
+    for (mut behaviour, mut velocity, transform) in boss_query.iter_mut() {
+        let position = transform.translation.truncate();
+        match behaviour.action {
+            BossAction::Entering | BossAction::Returning => {
+                let arrived = match behaviour.action {
+                    BossAction::Entering => position.y <= BOSS_HOVER_Y,
+                    _ => position.y >= BOSS_HOVER_Y,
+                };
+                if arrived {
+                    behaviour.action = BossAction::Hovering;
+                    *velocity = Velocity::from_vec2(Vec2::new(BOSS_SWAY_SPEED, 0.));
+                }
+            }
+            BossAction::Hovering => {
+                if edge.over_left_in(position.x) {
+                    velocity.x = BOSS_SWAY_SPEED;
+                } else if edge.over_right_in(position.x) {
+                    velocity.x = -BOSS_SWAY_SPEED;
+                }
+                if behaviour.phase == BossPhase::One {
+                    continue;
+                }
+                behaviour.charge_timer.tick(game_speed.delta(&time));
+                if !behaviour.charge_timer.just_finished() {
+                    continue;
+                }
+                let Some(target) = closest_position(position, spaceship_query.iter()) else {
+                    continue;
+                };
+                let target = target.clamp(
+                    Vec2::new(edge.left_in(), edge.bottom_in()),
+                    Vec2::new(edge.right_in(), BOSS_HOVER_Y),
+                );
+                behaviour.action = BossAction::Charging(target);
+                *velocity = Velocity::from_vec2(
+                    (target - position).normalize_or_zero() * BOSS_CHARGE_SPEED,
+                );
+            }
+            BossAction::Charging(target) => {
+                if position.distance(target) <= BOSS_CHARGE_SPEED || edge.over_bottom_in(position.y)
+                {
+                    behaviour.action = BossAction::Returning;
+                    *velocity = Velocity::from_vec2(Vec2::new(0., BOSS_ENTER_SPEED * 2.));
+                }
+            }
+        }
+    }
+}
+
+fn handle_boss_attack(
+    mut commands: Commands,
+    mut boss_query: Query<(&Boss, &mut BossBehaviour)>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    for (boss, mut behaviour) in boss_query.iter_mut() {
+        if !matches!(behaviour.action, BossAction::Hovering) {
+            continue;
+        }
+        behaviour.attack_timer.tick(game_speed.delta(&time));
+        if !behaviour.attack_timer.just_finished() {
+            continue;
+        }
+        let count = spread_count(behaviour.phase);
+        let origin = boss.get_position() - Vec2::new(0., BOSS_SIZE.y / 2.);
+        for i in 0..count {
+            let angle = -BOSS_SPREAD_ANGLE + 2. * BOSS_SPREAD_ANGLE * i as f32 / (count - 1) as f32;
+            let radian = angle_to_radian(angle);
+            let direction = Vec2::new(radian.sin(), -radian.cos());
+            commands.spawn(EnemyBullet::new(origin, direction * BOSS_BULLET_SPEED));
+        }
+    }
+}
@@ -1,9 +1,15 @@
 use crate::{
-    components::{Bullet, CollidedEvent, Explosion, Invisible, Player, Spaceship, UFO},
-    flow::game::triggers::{HealthReduceEvent, RemoveUFOEvent},
+    components::{
+        Boss, Bullet, BulletInvisible, CollidedEvent, Drone, EnemyBullet, Explosion, ExplosionKind,
+        FloatingText, FriendlyFireEvent, Invisible, Missile, Pierced, Player, PoolCommandsExt,
+        Shield, ShieldBreak, Spaceship, UFO,
+    },
+    constant::EXPLOSION_SIZE,
+    flow::game::triggers::{DamageBossEvent, DamageUFOEvent, HealthReduceEvent, RemoveUFOEvent},
     states::GameState,
     util::Position,
 };
+use bevy::color::palettes::css::AQUA;
 use bevy::prelude::*;
 
 pub struct CollisionPlugin;
@@ -12,72 +18,261 @@ impl Plugin for CollisionPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            handle_collisions.run_if(in_state(GameState::InPlay)),
+            (handle_collisions, handle_friendly_fire)
+                .run_if(in_state(GameState::InPlay).or(in_state(GameState::Tutorial))),
         );
     }
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn handle_collisions(
     mut commands: Commands,
     mut collision_events: EventReader<CollidedEvent>,
     ufo_q: Query<&UFO>,
-    spaceship_q: Query<&Player, With<Spaceship>>,
+    boss_q: Query<(), With<Boss>>,
+    enemy_bullet_q: Query<(), With<EnemyBullet>>,
+    spaceship_q: Query<(
+        &Player,
+        &Spaceship,
+        Has<Shield>,
+        Has<Invisible>,
+        Has<BulletInvisible>,
+    )>,
     bullet_q: Query<&Bullet>,
+    missile_q: Query<&Missile>,
+    drone_q: Query<&Drone>,
+    mut pierced_q: Query<&mut Pierced>,
 ) {
     for collision in collision_events.read() {
-        let Ok(ufo) = ufo_q.get(collision.enemy) else {
-            continue;
-        };
         let player_entity = collision.player;
+        let enemy_entity = collision.enemy;
+
+        if let Ok(drone) = drone_q.get(player_entity) {
+            // Crashing UFOs go down with the drone, the boss does not notice
+            if let Ok(ufo) = ufo_q.get(enemy_entity) {
+                commands.spawn_pooled(Explosion::new(ufo.get_position(), ExplosionKind::UfoDeath));
+                commands.trigger(RemoveUFOEvent::clean_up(enemy_entity));
+            } else if enemy_bullet_q.contains(enemy_entity) {
+                if let Ok(mut entity_commands) = commands.get_entity(enemy_entity) {
+                    entity_commands.despawn();
+                }
+            } else if !boss_q.contains(enemy_entity) {
+                continue;
+            }
+            return destroy_drone(commands.reborrow(), drone, player_entity);
+        }
 
-        if let Ok(player) = spaceship_q.get(player_entity) {
-            return handle_ufo_spaceship_collision(
-                commands.reborrow(),
-                player,
-                player_entity,
-                ufo,
-                collision.enemy,
-            );
+        if let Ok((player, spaceship, shielded, invisible, bullet_invisible)) =
+            spaceship_q.get(player_entity)
+        {
+            // Events already queued before the i-frames started are dropped too
+            let is_enemy_bullet = enemy_bullet_q.contains(enemy_entity);
+            if (is_enemy_bullet && bullet_invisible) || (!is_enemy_bullet && invisible) {
+                continue;
+            }
+            let shielded = shielded.then(|| spaceship.get_position());
+            if let Ok(ufo) = ufo_q.get(enemy_entity) {
+                return handle_ufo_spaceship_collision(
+                    commands.reborrow(),
+                    player,
+                    shielded,
+                    player_entity,
+                    ufo,
+                    enemy_entity,
+                );
+            }
+            if boss_q.contains(enemy_entity) {
+                return damage_spaceship(
+                    commands.reborrow(),
+                    player,
+                    shielded,
+                    player_entity,
+                    Invisible::new(),
+                );
+            }
+            if is_enemy_bullet {
+                if let Ok(mut entity_commands) = commands.get_entity(enemy_entity) {
+                    entity_commands.despawn();
+                }
+                return damage_spaceship(
+                    commands.reborrow(),
+                    player,
+                    shielded,
+                    player_entity,
+                    BulletInvisible::new(),
+                );
+            }
         }
 
-        // bullet-ufo collision
         if let Ok(bullet) = bullet_q.get(player_entity) {
-            return handle_bullet_ufo_collision(
-                commands.reborrow(),
-                bullet,
-                player_entity,
-                ufo,
-                collision.enemy,
-            );
+            // bullet-ufo collision
+            if ufo_q.contains(enemy_entity) {
+                spend_bullet(
+                    commands.reborrow(),
+                    &mut pierced_q,
+                    player_entity,
+                    enemy_entity,
+                );
+                return handle_bullet_ufo_collision(commands.reborrow(), bullet, enemy_entity);
+            }
+            // bullet-boss collision
+            if boss_q.contains(enemy_entity) {
+                spend_bullet(
+                    commands.reborrow(),
+                    &mut pierced_q,
+                    player_entity,
+                    enemy_entity,
+                );
+                return handle_bullet_boss_collision(commands.reborrow(), bullet, enemy_entity);
+            }
+        }
+
+        if let Ok(missile) = missile_q.get(player_entity) {
+            // missile-ufo collision
+            if ufo_q.contains(enemy_entity) {
+                explode_missile(commands.reborrow(), missile, player_entity);
+                commands.trigger(
+                    DamageUFOEvent::by_player(enemy_entity, missile.get_player())
+                        .with_damage(missile.damage()),
+                );
+                return;
+            }
+            // missile-boss collision
+            if boss_q.contains(enemy_entity) {
+                explode_missile(commands.reborrow(), missile, player_entity);
+                commands.trigger(
+                    DamageBossEvent::by_player(enemy_entity, missile.get_player())
+                        .with_damage(missile.damage()),
+                );
+                return;
+            }
         }
     }
 }
 
-fn handle_ufo_spaceship_collision(
+// Hits from the other player cost health like any other hit, shields still take them first
+fn handle_friendly_fire(
+    mut commands: Commands,
+    mut friendly_fire_events: EventReader<FriendlyFireEvent>,
+    spaceship_q: Query<(&Player, &Spaceship, Has<Shield>, Has<Invisible>)>,
+    bullet_q: Query<&Bullet>,
+    mut pierced_q: Query<&mut Pierced>,
+) {
+    for friendly_fire in friendly_fire_events.read() {
+        let Ok((player, spaceship, shielded, invisible)) = spaceship_q.get(friendly_fire.spaceship)
+        else {
+            continue;
+        };
+        if invisible || !bullet_q.contains(friendly_fire.bullet) {
+            continue;
+        }
+        spend_bullet(
+            commands.reborrow(),
+            &mut pierced_q,
+            friendly_fire.bullet,
+            friendly_fire.spaceship,
+        );
+        damage_spaceship(
+            commands.reborrow(),
+            player,
+            shielded.then(|| spaceship.get_position()),
+            friendly_fire.spaceship,
+            Invisible::new(),
+        );
+        // A spread can land several bullets in the same frame, only the first one counts
+        return;
+    }
+}
+
+// Piercing bullets carry on, only remembering what they went through
+pub(super) fn spend_bullet(
+    mut commands: Commands,
+    pierced_q: &mut Query<&mut Pierced>,
+    bullet_entity: Entity,
+    enemy_entity: Entity,
+) {
+    match pierced_q.get_mut(bullet_entity) {
+        Ok(mut pierced) => pierced.add(enemy_entity),
+        Err(_) => commands.release_pooled::<Bullet>(bullet_entity),
+    }
+}
+
+pub(super) fn explode_missile(mut commands: Commands, missile: &Missile, missile_entity: Entity) {
+    commands.spawn_pooled(
+        Explosion::new(missile.get_position(), ExplosionKind::BulletImpact)
+            .with_size(EXPLOSION_SIZE / 2.),
+    );
+    if let Ok(mut entity_commands) = commands.get_entity(missile_entity) {
+        entity_commands.despawn();
+    }
+}
+
+pub(super) fn destroy_drone(mut commands: Commands, drone: &Drone, drone_entity: Entity) {
+    commands.spawn_pooled(
+        Explosion::new(drone.get_position(), ExplosionKind::BulletImpact)
+            .with_size(EXPLOSION_SIZE / 2.),
+    );
+    if let Ok(mut entity_commands) = commands.get_entity(drone_entity) {
+        entity_commands.despawn();
+    }
+}
+
+// A shield takes the hit instead, `shielded` holds where to show it breaking
+pub(super) fn damage_spaceship(
     mut commands: Commands,
     player: &Player,
+    shielded: Option<Vec2>,
     player_entity: Entity,
-    ufo: &UFO,
-    ufo_entity: Entity,
+    invisible: impl Bundle,
 ) {
-    commands.trigger(HealthReduceEvent::new(player.0));
-    if let Ok(mut entity_commands) = commands.get_entity(player_entity) {
-        entity_commands.insert(Invisible::new());
+    let Ok(mut entity_commands) = commands.get_entity(player_entity) else {
+        return;
+    };
+    entity_commands.insert(invisible);
+    match shielded {
+        Some(position) => {
+            entity_commands.remove::<Shield>();
+            commands.spawn(ShieldBreak::new(position));
+            commands
+                .spawn(FloatingText::new(position, "SHIELD BROKEN").with_color(Color::from(AQUA)));
+        }
+        None => commands.trigger(HealthReduceEvent::new(player.0)),
     }
-    commands.spawn(Explosion::new(ufo.get_position()));
-    commands.trigger(RemoveUFOEvent::clean_up(ufo_entity));
 }
 
-fn handle_bullet_ufo_collision(
+fn handle_ufo_spaceship_collision(
     mut commands: Commands,
-    bullet: &Bullet,
-    bullet_entity: Entity,
+    player: &Player,
+    shielded: Option<Vec2>,
+    player_entity: Entity,
     ufo: &UFO,
     ufo_entity: Entity,
 ) {
-    if let Ok(mut entity_commands) = commands.get_entity(bullet_entity) {
-        entity_commands.despawn();
-    }
-    commands.trigger(RemoveUFOEvent::by_player(ufo_entity, bullet.get_player()));
-    commands.spawn(Explosion::new(ufo.get_position()));
+    damage_spaceship(
+        commands.reborrow(),
+        player,
+        shielded,
+        player_entity,
+        Invisible::new(),
+    );
+    commands.spawn_pooled(Explosion::new(ufo.get_position(), ExplosionKind::UfoDeath));
+    commands.trigger(RemoveUFOEvent::clean_up(ufo_entity));
+}
+
+fn handle_bullet_ufo_collision(mut commands: Commands, bullet: &Bullet, ufo_entity: Entity) {
+    commands.trigger(
+        DamageUFOEvent::by_player(ufo_entity, bullet.get_player())
+            .with_damage(bullet.kind().damage()),
+    );
+}
+
+fn handle_bullet_boss_collision(mut commands: Commands, bullet: &Bullet, boss_entity: Entity) {
+    commands.spawn_pooled(Explosion::new(
+        bullet.get_position(),
+        ExplosionKind::BulletImpact,
+    ));
+    commands.trigger(
+        DamageBossEvent::by_player(boss_entity, bullet.get_player())
+            .with_damage(bullet.kind().damage()),
+    );
 }
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+use crate::flow::game::triggers::HealthReduceEvent;
+use crate::res::{Combo, GameSpeed};
+use crate::states::GameState;
+
+pub struct ComboPlugin;
+
+impl Plugin for ComboPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Ready), reset_combo)
+            .add_systems(Update, decay_combo.run_if(in_state(GameState::InPlay)))
+            .add_observer(break_combo_on_damage);
+    }
+}
+
+fn reset_combo(mut commands: Commands) {
+    commands.insert_resource(Combo::default());
+}
+
+fn decay_combo(mut combo: ResMut<Combo>, time: Res<Time>, game_speed: Res<GameSpeed>) {
+    combo.tick(game_speed.delta(&time));
+}
+
+fn break_combo_on_damage(_trigger: Trigger<HealthReduceEvent>, mut combo: ResMut<Combo>) {
+    combo.reset();
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+use crate::components::{Bullet, Drone, PoolCommandsExt, UFO};
+use crate::res::GameSpeed;
+use crate::states::GameState;
+use crate::util::{closest_position, Position};
+
+pub struct DronePlugin;
+
+impl Plugin for DronePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, fire_drones.run_if(in_state(GameState::InPlay)));
+    }
+}
+
+// Aims at the nearest UFO, the timer keeps running while there is nothing to shoot
+fn fire_drones(
+    mut commands: Commands,
+    mut drone_q: Query<&mut Drone>,
+    ufo_q: Query<&UFO>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    for mut drone in drone_q.iter_mut() {
+        if !drone.tick_fire(game_speed.delta(&time)) {
+            continue;
+        }
+        let position = drone.get_position();
+        let Some(target) = closest_position(position, ufo_q.iter()) else {
+            continue;
+        };
+        let Some(direction) = (target - position).try_normalize() else {
+            continue;
+        };
+        commands.spawn_pooled(Bullet::by_drone(drone.get_player(), position, direction));
+    }
+}
@@ -1,27 +1,129 @@
+use std::ops::Range;
+
 use bevy::prelude::*;
-use rand::{rng, Rng};
-use shooting_game_shared::util::{EdgeUtil, UFO_SIZE};
+use rand::Rng;
+use shooting_game_shared::game_related::FIXED_TICKS_PER_SECOND;
+use shooting_game_shared::util::{EdgeUtil, SPACESHIP_SIZE, UFO_SIZE};
 
-use crate::components::{Player, Score, Velocity, UFO};
+use crate::components::{EnemyBullet, Spaceship, UFOKind, Velocity, UFO};
+use crate::constant::ENEMY_BULLET_SIZE;
+use crate::res::{
+    AdaptiveDifficulty, Difficulty, DifficultyCurve, EnemyCatalog, EnemyMovement, GameRng,
+    GameSpeed, RngStream,
+};
 use crate::states::GameState;
-use shooting_game_shared::game_related::Stage;
+use crate::util::{closest_position, Position};
+
+use super::formation::{FormationMember, FormationPattern};
+use super::wave::WaveManager;
+#[cfg(feature = "dev-console")]
+use crate::flow::dev_console::{parse_arg, ConsoleArgs, ConsoleCommandAppExt, ConsoleResult};
+
+const UFO_BULLET_SPEED: f32 = 4.;
+const ZIGZAG_AMPLITUDE: f32 = 5.;
+const ZIGZAG_FREQUENCY: f32 = 3.;
+const KAMIKAZE_ACCELERATION: f32 = 15.;
+const KAMIKAZE_MAX_SPEED: f32 = 12.;
+// Stalkers stop coming down here and track the spaceship from above
+const STALKER_HOVER_Y: f32 = 250.;
+// Share of the sideways gap closed per tick, lower lags further behind
+const STALKER_TRACKING: f32 = 0.04;
+const STALKER_MAX_SPEED_X: f32 = 6.;
+const STALKER_DIVE_SPEED: f32 = 10.;
+const STALKER_CLIMB_SPEED: f32 = 4.;
+const STALKER_DIVE_INTERVAL: Range<f32> = 2.5..4.5;
+// Spawn spots tried before settling on the one farthest from the spaceships
+const SPAWN_CANDIDATES: usize = 6;
+// Points checked along the first second of a candidate's path
+const SPAWN_PATH_STEPS: usize = 10;
+const FORMATION_MIN_WAVE: u32 = 2;
+const FORMATION_CHANCE: f64 = 0.25;
+
 pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
+            Update,
+            check_and_spawn_enemy.run_if(in_state(GameState::InPlay)),
+        )
+        // The tutorial spawns its own UFOs, they still move and fire like in a run
+        .add_systems(
             Update,
             (
-                check_and_spawn_enemy,
                 handle_horizontal_movement,
+                handle_zigzag_movement,
+                handle_kamikaze_movement,
+                handle_stalker_movement,
+                handle_ufo_fire,
                 cleanup_on_out_screen,
             )
-                .run_if(in_state(GameState::InPlay)),
+                .run_if(in_state(GameState::InPlay).or(in_state(GameState::Tutorial))),
         );
+        #[cfg(feature = "dev-console")]
+        app.add_console_command("spawn ufo", "spawn ufo <count>", console_spawn_ufo);
+    }
+}
+
+#[derive(Component)]
+struct UFOWeapon(Timer);
+
+impl UFOWeapon {
+    fn new(interval: Range<f32>, rng: &mut impl Rng) -> Self {
+        let secs = rng.random_range(interval);
+        Self(Timer::from_seconds(secs, TimerMode::Once))
+    }
+}
+
+#[derive(Component)]
+struct ZigzagMovement {
+    elapsed: f32,
+}
+
+#[derive(Component)]
+struct KamikazeMovement;
+
+#[derive(Component)]
+struct Stalker {
+    phase: StalkerPhase,
+    dive_timer: Timer,
+}
+
+impl Stalker {
+    fn new(rng: &mut impl Rng) -> Self {
+        Self {
+            phase: StalkerPhase::Arriving,
+            dive_timer: Self::dive_timer(rng),
+        }
+    }
+
+    fn dive_timer(rng: &mut impl Rng) -> Timer {
+        Timer::from_seconds(rng.random_range(STALKER_DIVE_INTERVAL), TimerMode::Once)
     }
 }
 
-fn handle_horizontal_movement(mut ufo_query: Query<(&mut Velocity, &Transform), With<UFO>>) {
+enum StalkerPhase {
+    // Coming down from the top at the wave speed
+    Arriving,
+    Tracking,
+    // Straight down to where the spaceship was, no more steering
+    Diving { floor: f32 },
+    Climbing,
+}
+
+#[allow(clippy::type_complexity)]
+fn handle_horizontal_movement(
+    mut ufo_query: Query<
+        (&mut Velocity, &Transform),
+        (
+            With<UFO>,
+            Without<ZigzagMovement>,
+            Without<KamikazeMovement>,
+            Without<Stalker>,
+            Without<FormationMember>,
+        ),
+    >,
+) {
     let edge = EdgeUtil::new(UFO_SIZE);
     for (mut velocity, transform) in ufo_query.iter_mut() {
         let x = transform.translation.x;
@@ -31,35 +133,348 @@ fn handle_horizontal_movement(mut ufo_query: Query<(&mut Velocity, &Transform),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn check_and_spawn_enemy(
     commands: Commands,
-    ufo_query: Query<Entity, With<UFO>>,
-    score_query: Query<&Score, With<Player>>,
+    spaceship_query: Query<&Spaceship>,
+    mut wave_manager: ResMut<WaveManager>,
+    curve: Res<DifficultyCurve>,
+    difficulty: Res<Difficulty>,
+    catalog: Res<EnemyCatalog>,
+    mut game_rng: ResMut<GameRng>,
+    adaptive_difficulty: Option<Res<AdaptiveDifficulty>>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
 ) {
-    let ufo_number = ufo_query.iter().len();
-    let Ok(score) = score_query.single() else {
-        warn!("Should have exactly one player");
+    let wave = wave_manager.wave();
+    let (spawn_rate_scale, speed_scale) = match adaptive_difficulty {
+        Some(adaptive_difficulty) => (
+            adaptive_difficulty.spawn_rate_scale(),
+            difficulty.ufo_speed_scale() * adaptive_difficulty.ufo_speed_scale(),
+        ),
+        None => (1., difficulty.ufo_speed_scale()),
+    };
+    let delta = game_speed.delta(&time).mul_f32(spawn_rate_scale);
+    if wave_manager.is_boss_wave() || !wave_manager.tick_spawn(delta) {
+        return;
+    }
+    let spaceships: Vec<Vec2> = spaceship_query.iter().map(Position::get_position).collect();
+    let rng = game_rng.stream(RngStream::UfoSpawn);
+    if wave >= FORMATION_MIN_WAVE && rng.random_bool(FORMATION_CHANCE) {
+        let pattern = FormationPattern::random(rng);
+        let count = wave_manager.take_remaining(pattern.offsets().len() as u32 - 1) + 1;
+        let velocity = curve.ufo_velocity(wave, rng) * speed_scale;
+        spawn_formation(
+            commands,
+            &catalog,
+            pattern,
+            count as usize,
+            velocity.y,
+            &spaceships,
+            rng,
+        );
         return;
+    }
+    let kind = catalog.choose(wave, rng);
+    let velocity = curve.ufo_velocity(wave, rng) * speed_scale;
+    spawn_ufo(commands, &catalog, kind, velocity, &spaceships, rng);
+}
+
+// Picks a spawn x along the top edge whose first second of travel stays clear of every
+// spaceship, `half_width` is how far the spawn reaches either side of that x
+fn safe_spawn_x(
+    range: Range<f32>,
+    half_width: f32,
+    velocity: Vec2,
+    spaceships: &[Vec2],
+    rng: &mut impl Rng,
+) -> f32 {
+    let y = EdgeUtil::ufo().top_out();
+    let path = velocity * FIXED_TICKS_PER_SECOND;
+    let reach = Vec2::new(
+        half_width + (UFO_SIZE.x + SPACESHIP_SIZE.x) / 2.,
+        (UFO_SIZE.y + SPACESHIP_SIZE.y) / 2.,
+    );
+    let hits = |x: f32| {
+        (0..=SPAWN_PATH_STEPS).any(|step| {
+            let position = Vec2::new(x, y) + path * step as f32 / SPAWN_PATH_STEPS as f32;
+            spaceships.iter().any(|spaceship| {
+                let offset = (position - *spaceship).abs();
+                offset.x < reach.x && offset.y < reach.y
+            })
+        })
     };
-    let stage = Stage::new(score.0);
-    if ufo_number == 0 || stage.random_generator(ufo_number) {
-        spawn_ufo(commands, Velocity::from_vec2(stage.get_ufo_velocity()));
+    let clearance = |x: &f32| {
+        spaceships
+            .iter()
+            .map(|spaceship| (x - spaceship.x).abs())
+            .fold(f32::INFINITY, f32::min)
+    };
+    let mut candidates = Vec::with_capacity(SPAWN_CANDIDATES);
+    for _ in 0..SPAWN_CANDIDATES {
+        let x = rng.random_range(range.clone());
+        if !hits(x) {
+            return x;
+        }
+        candidates.push(x);
     }
+    candidates
+        .into_iter()
+        .max_by(|a, b| clearance(a).total_cmp(&clearance(b)))
+        .unwrap_or(range.start)
 }
 
-fn spawn_ufo(mut commands: Commands, velocity: Velocity) {
-    let mut rng = rng();
+fn spawn_ufo(
+    mut commands: Commands,
+    catalog: &EnemyCatalog,
+    kind: UFOKind,
+    velocity: Vec2,
+    spaceships: &[Vec2],
+    rng: &mut impl Rng,
+) {
     let edge = EdgeUtil::ufo();
+    let definition = catalog.get(kind);
+    // Zigzag sways this far either side of where it spawns
+    let margin = match definition.movement {
+        EnemyMovement::Zigzag => ZIGZAG_AMPLITUDE * FIXED_TICKS_PER_SECOND / ZIGZAG_FREQUENCY,
+        _ => 0.,
+    };
+    let velocity = velocity * definition.speed;
+    // Only straight UFOs keep their sideways speed
+    let path_velocity = match definition.movement {
+        EnemyMovement::Straight => velocity,
+        _ => Vec2::new(0., velocity.y),
+    };
     let ufo_position = Vec2::new(
-        rng.random_range(edge.left_in()..edge.right_in()),
+        safe_spawn_x(
+            edge.left_in() + margin..edge.right_in() - margin,
+            margin,
+            path_velocity,
+            spaceships,
+            rng,
+        ),
+        edge.top_out(),
+    );
+    let mut entity_commands = commands.spawn((
+        UFO::with_kind(ufo_position, kind, catalog),
+        UFOWeapon::new(definition.fire_interval.clone(), rng),
+    ));
+    match definition.movement {
+        EnemyMovement::Straight => entity_commands.insert(Velocity::from_vec2(velocity)),
+        EnemyMovement::Zigzag => entity_commands.insert((
+            Velocity::from_vec2(Vec2::new(0., velocity.y)),
+            ZigzagMovement { elapsed: 0. },
+        )),
+        EnemyMovement::Kamikaze => entity_commands.insert((
+            Velocity::from_vec2(Vec2::new(0., velocity.y)),
+            KamikazeMovement,
+        )),
+        EnemyMovement::Stalker => entity_commands.insert((
+            Velocity::from_vec2(Vec2::new(0., velocity.y)),
+            Stalker::new(rng),
+        )),
+    };
+}
+
+// Plain UFOs at the current wave's speed, on top of whatever the wave spawns
+#[cfg(feature = "dev-console")]
+#[allow(clippy::too_many_arguments)]
+fn console_spawn_ufo(
+    In(args): In<ConsoleArgs>,
+    mut commands: Commands,
+    wave_manager: Option<Res<WaveManager>>,
+    curve: Res<DifficultyCurve>,
+    difficulty: Res<Difficulty>,
+    catalog: Option<Res<EnemyCatalog>>,
+    spaceship_query: Query<&Spaceship>,
+    mut game_rng: ResMut<GameRng>,
+) -> ConsoleResult {
+    let count: u32 = parse_arg(&args, "spawn ufo <count>")?;
+    let (Some(wave_manager), Some(catalog)) = (wave_manager, catalog) else {
+        return Err("UFOs can only be spawned during a run".to_string());
+    };
+    let spaceships: Vec<Vec2> = spaceship_query.iter().map(Position::get_position).collect();
+    let rng = game_rng.stream(RngStream::UfoSpawn);
+    for _ in 0..count {
+        let velocity = curve.ufo_velocity(wave_manager.wave(), rng) * difficulty.ufo_speed_scale();
+        spawn_ufo(
+            commands.reborrow(),
+            &catalog,
+            UFOKind::BASIC,
+            velocity,
+            &spaceships,
+            rng,
+        );
+    }
+    Ok(format!("Spawned {count} UFOs"))
+}
+
+// Bounces between the side edges without coming down, only fires when armed
+pub fn spawn_strafing_ufo(
+    commands: &mut Commands,
+    catalog: &EnemyCatalog,
+    position: Vec2,
+    speed: f32,
+    armed: bool,
+    rng: &mut impl Rng,
+) {
+    let mut entity_commands = commands.spawn((
+        UFO::new(position, catalog),
+        Velocity::from_vec2(Vec2::new(speed, 0.)),
+    ));
+    if armed {
+        let interval = catalog.get(UFOKind::BASIC).fire_interval.clone();
+        entity_commands.insert(UFOWeapon::new(interval, rng));
+    }
+}
+
+fn spawn_formation(
+    mut commands: Commands,
+    catalog: &EnemyCatalog,
+    pattern: FormationPattern,
+    count: usize,
+    speed_y: f32,
+    spaceships: &[Vec2],
+    rng: &mut impl Rng,
+) {
+    let edge = EdgeUtil::ufo();
+    let margin = pattern.half_width();
+    let leader = Vec2::new(
+        safe_spawn_x(
+            edge.left_in() + margin..edge.right_in() - margin,
+            margin,
+            Vec2::new(0., speed_y),
+            spaceships,
+            rng,
+        ),
         edge.top_out(),
     );
-    commands.spawn((UFO::new(ufo_position), velocity));
+    let interval = &catalog.get(UFOKind::BASIC).fire_interval;
+    for offset in pattern.offsets().iter().take(count) {
+        commands.spawn((
+            UFO::new(leader + *offset, catalog),
+            UFOWeapon::new(interval.clone(), rng),
+            Velocity::from_vec2(Vec2::new(0., speed_y)),
+            FormationMember::new(*offset),
+        ));
+    }
+}
+
+fn handle_zigzag_movement(
+    mut ufo_query: Query<(&mut Velocity, &mut ZigzagMovement)>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    for (mut velocity, mut zigzag) in ufo_query.iter_mut() {
+        zigzag.elapsed += game_speed.delta_secs(&time);
+        velocity.x = ZIGZAG_AMPLITUDE * (zigzag.elapsed * ZIGZAG_FREQUENCY).cos();
+    }
+}
+
+// Homes in while still above the closest spaceship, then dives past it
+fn handle_kamikaze_movement(
+    mut ufo_query: Query<(&UFO, &mut Velocity), With<KamikazeMovement>>,
+    spaceship_query: Query<&Spaceship>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    for (ufo, mut velocity) in ufo_query.iter_mut() {
+        let position = ufo.get_position();
+        let Some(target) = closest_position(position, spaceship_query.iter()) else {
+            continue;
+        };
+        if position.y < target.y {
+            continue;
+        }
+        let direction = (target - position).normalize_or_zero();
+        let new_velocity = (Vec2::new(velocity.x, velocity.y)
+            + direction * KAMIKAZE_ACCELERATION * game_speed.delta_secs(&time))
+        .clamp_length_max(KAMIKAZE_MAX_SPEED);
+        *velocity = Velocity::from_vec2(new_velocity);
+    }
+}
+
+// Lags behind the closest spaceship's x, dives at it and climbs back up to track again
+fn handle_stalker_movement(
+    mut ufo_query: Query<(&UFO, &mut Velocity, &mut Stalker)>,
+    spaceship_query: Query<&Spaceship>,
+    mut game_rng: ResMut<GameRng>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    let edge = EdgeUtil::ufo();
+    for (ufo, mut velocity, mut stalker) in ufo_query.iter_mut() {
+        let position = ufo.get_position();
+        let target = closest_position(position, spaceship_query.iter());
+        match stalker.phase {
+            StalkerPhase::Arriving => {
+                if position.y <= STALKER_HOVER_Y {
+                    velocity.y = 0.;
+                    stalker.phase = StalkerPhase::Tracking;
+                }
+            }
+            StalkerPhase::Tracking => {
+                let gap = target.map_or(0., |target| target.x - position.x);
+                velocity.x =
+                    (gap * STALKER_TRACKING).clamp(-STALKER_MAX_SPEED_X, STALKER_MAX_SPEED_X);
+                if !stalker.dive_timer.tick(game_speed.delta(&time)).finished() {
+                    continue;
+                }
+                let floor = target.map_or(edge.bottom_in(), |target| target.y);
+                *velocity = Velocity::from_vec2(Vec2::new(0., -STALKER_DIVE_SPEED));
+                stalker.phase = StalkerPhase::Diving { floor };
+            }
+            StalkerPhase::Diving { floor } => {
+                if position.y <= floor {
+                    velocity.y = STALKER_CLIMB_SPEED;
+                    stalker.phase = StalkerPhase::Climbing;
+                }
+            }
+            StalkerPhase::Climbing => {
+                if position.y >= STALKER_HOVER_Y {
+                    velocity.y = 0.;
+                    stalker.dive_timer = Stalker::dive_timer(game_rng.stream(RngStream::UfoSpawn));
+                    stalker.phase = StalkerPhase::Tracking;
+                }
+            }
+        }
+    }
+}
+
+fn handle_ufo_fire(
+    mut commands: Commands,
+    mut ufo_query: Query<(&UFO, &mut UFOWeapon)>,
+    spaceship_query: Query<&Spaceship>,
+    catalog: Res<EnemyCatalog>,
+    mut game_rng: ResMut<GameRng>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    let rng = game_rng.stream(RngStream::UfoWeapon);
+    let edge = EdgeUtil::ufo();
+    for (ufo, mut weapon) in ufo_query.iter_mut() {
+        weapon.0.tick(game_speed.delta(&time));
+        if !weapon.0.finished() {
+            continue;
+        }
+        *weapon = UFOWeapon::new(catalog.get(ufo.kind()).fire_interval.clone(), rng);
+        let position = ufo.get_position();
+        let Some(target) = closest_position(position, spaceship_query.iter()) else {
+            continue;
+        };
+        // Only fire when fully on screen and still above the spaceship
+        if edge.over_top_in(position.y) || position.y < target.y {
+            continue;
+        }
+        let direction = (target - position).normalize_or_zero();
+        commands.spawn(EnemyBullet::new(position, direction * UFO_BULLET_SPEED));
+    }
 }
 
 fn cleanup_on_out_screen(
     mut commands: Commands,
     ufo_query: Query<(Entity, &Transform), With<UFO>>,
+    enemy_bullet_query: Query<(Entity, &Transform), With<EnemyBullet>>,
 ) {
     let edge = EdgeUtil::new(UFO_SIZE);
     for (entity, transform) in ufo_query.iter() {
@@ -69,4 +484,17 @@ fn cleanup_on_out_screen(
             }
         }
     }
+    let edge = EdgeUtil::new(ENEMY_BULLET_SIZE);
+    for (entity, transform) in enemy_bullet_query.iter() {
+        let Vec3 { x, y, .. } = transform.translation;
+        if edge.over_bottom_out(y)
+            || edge.over_top_out(y)
+            || edge.over_left_out(x)
+            || edge.over_right_out(x)
+        {
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.despawn();
+            }
+        }
+    }
 }
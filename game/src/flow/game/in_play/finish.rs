@@ -1,11 +1,17 @@
 use bevy::prelude::*;
 
 use crate::{
-    components::{Explosion, Health, Spaceship},
+    components::{
+        Downed, Explosion, ExplosionKind, Health, Lives, Player, PoolCommandsExt, Spaceship,
+    },
+    flow::juice::{SlowMotion, SlowMotionEvent},
+    res::LocalCoop,
     states::GameState,
     util::Position,
 };
 
+use super::respawn::RespawnCountdown;
+
 pub struct FinishPlugin;
 
 impl Plugin for FinishPlugin {
@@ -16,20 +22,47 @@ impl Plugin for FinishPlugin {
 
 fn check_finish(
     mut commands: Commands,
-    health_q: Query<&Health>,
-    mut next_state: ResMut<NextState<GameState>>,
-    spaceship_q: Query<(Entity, &Spaceship)>,
+    health_q: Query<(&Health, &Player)>,
+    mut lives_q: Query<(&mut Lives, &Player)>,
+    spaceship_q: Query<(Entity, &Spaceship, &Player)>,
+    slow_motion: Option<Res<SlowMotion>>,
+    local_coop: Option<Res<LocalCoop>>,
 ) {
-    let Ok(health) = health_q.single() else {
+    if health_q.is_empty() {
         panic!("Health not found");
-    };
-    if let Ok((entity, spaceship)) = spaceship_q.single() {
-        if health.0 == 0 {
-            commands.spawn(Explosion::new(spaceship.get_position()));
-            if let Ok(mut entity_commands) = commands.get_entity(entity) {
-                entity_commands.despawn();
-            }
-            next_state.set(GameState::Result);
+    }
+    for (entity, spaceship, player) in spaceship_q.iter() {
+        let out_of_health = health_q
+            .iter()
+            .any(|(health, health_player)| health_player.0 == player.0 && health.current == 0);
+        if !out_of_health {
+            continue;
+        }
+        commands.spawn_pooled(Explosion::new(
+            spaceship.get_position(),
+            ExplosionKind::PlayerDeath,
+        ));
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.despawn();
         }
+        let Some((mut lives, _)) = lives_q
+            .iter_mut()
+            .find(|(_, lives_player)| lives_player.0 == player.0)
+        else {
+            warn!("Lives not found in check_finish");
+            continue;
+        };
+        lives.lose();
+        if lives.0 > 0 {
+            commands.spawn(RespawnCountdown::new(player.0));
+        } else if local_coop.is_some() {
+            // Online beacons come from the server, which decides who is downed
+            commands.spawn(Downed::new(player.0, spaceship.get_position()));
+        }
+    }
+    // In local co-op the game goes on until both players are out of lives
+    // The final blow plays out in slow motion before the game is over
+    if lives_q.iter().all(|(lives, _)| lives.0 == 0) && slow_motion.is_none() {
+        commands.trigger(SlowMotionEvent::then(GameState::GameOver));
     }
 }
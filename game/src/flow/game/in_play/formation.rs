@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::components::{Velocity, UFO};
+use crate::res::GameSpeed;
+use crate::states::GameState;
+
+const FORMATION_SWAY_SPEED: f32 = 2.;
+const FORMATION_SWAY_FREQUENCY: f32 = 1.5;
+const FORMATION_HOLD_SECS: f32 = 3.;
+const BREAK_SPEED: f32 = 4.;
+
+// Offsets from the leader at the bottom, positive y trails behind it
+const V_SHAPE_OFFSETS: [Vec2; 5] = [
+    Vec2::new(0., 0.),
+    Vec2::new(-60., 50.),
+    Vec2::new(60., 50.),
+    Vec2::new(-120., 100.),
+    Vec2::new(120., 100.),
+];
+const LINE_OFFSETS: [Vec2; 5] = [
+    Vec2::new(0., 0.),
+    Vec2::new(-70., 0.),
+    Vec2::new(70., 0.),
+    Vec2::new(-140., 0.),
+    Vec2::new(140., 0.),
+];
+const CIRCLE_OFFSETS: [Vec2; 6] = [
+    Vec2::new(0., 0.),
+    Vec2::new(69.3, 40.),
+    Vec2::new(69.3, 120.),
+    Vec2::new(0., 160.),
+    Vec2::new(-69.3, 120.),
+    Vec2::new(-69.3, 40.),
+];
+
+#[derive(Clone, Copy)]
+pub enum FormationPattern {
+    VShape,
+    Line,
+    Circle,
+}
+
+impl FormationPattern {
+    const ALL: [FormationPattern; 3] = [
+        FormationPattern::VShape,
+        FormationPattern::Line,
+        FormationPattern::Circle,
+    ];
+
+    pub fn random(rng: &mut impl Rng) -> Self {
+        Self::ALL[rng.random_range(0..Self::ALL.len())]
+    }
+
+    pub fn offsets(&self) -> &'static [Vec2] {
+        match self {
+            FormationPattern::VShape => &V_SHAPE_OFFSETS,
+            FormationPattern::Line => &LINE_OFFSETS,
+            FormationPattern::Circle => &CIRCLE_OFFSETS,
+        }
+    }
+
+    // How far the members reach either side of the leader
+    pub fn half_width(&self) -> f32 {
+        self.offsets()
+            .iter()
+            .map(|offset| offset.x.abs())
+            .fold(0., f32::max)
+    }
+}
+
+// Members spawn on the same frame, so sharing a timer keeps them moving as one
+#[derive(Component)]
+pub struct FormationMember {
+    offset: Vec2,
+    timer: Timer,
+}
+
+impl FormationMember {
+    pub fn new(offset: Vec2) -> Self {
+        Self {
+            offset,
+            timer: Timer::from_seconds(FORMATION_HOLD_SECS, TimerMode::Once),
+        }
+    }
+}
+
+pub struct FormationPlugin;
+
+impl Plugin for FormationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            handle_formation_movement.run_if(in_state(GameState::InPlay)),
+        );
+    }
+}
+
+fn handle_formation_movement(
+    mut commands: Commands,
+    mut member_query: Query<(Entity, &mut FormationMember, &mut Velocity), With<UFO>>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    for (entity, mut member, mut velocity) in member_query.iter_mut() {
+        member.timer.tick(game_speed.delta(&time));
+        if !member.timer.finished() {
+            let elapsed = member.timer.elapsed_secs();
+            velocity.x = FORMATION_SWAY_SPEED * (elapsed * FORMATION_SWAY_FREQUENCY).cos();
+            continue;
+        }
+        // Break apart by scattering sideways away from the leader
+        velocity.x = member.offset.normalize_or_zero().x * BREAK_SPEED;
+        commands.entity(entity).remove::<FormationMember>();
+    }
+}
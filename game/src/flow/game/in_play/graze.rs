@@ -0,0 +1,47 @@
+use bevy::color::palettes::css::AQUA;
+use bevy::prelude::*;
+
+use crate::components::{BombCharges, FloatingText, Graze, GrazeEvent, Player, Spaceship};
+use crate::flow::game::triggers::AddScoreEvent;
+use crate::states::GameState;
+use crate::util::Position;
+
+const GRAZE_SCORE: u32 = 1;
+
+pub struct GrazePlugin;
+
+impl Plugin for GrazePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, handle_grazes.run_if(in_state(GameState::InPlay)));
+    }
+}
+
+// A full meter gives back a bomb charge
+fn handle_grazes(
+    mut commands: Commands,
+    mut graze_events: EventReader<GrazeEvent>,
+    mut spaceship_q: Query<(&Player, &Spaceship, &mut BombCharges)>,
+    mut graze_q: Query<(&mut Graze, &Player)>,
+) {
+    for graze_event in graze_events.read() {
+        let Ok((player, spaceship, mut charges)) = spaceship_q.get_mut(graze_event.spaceship)
+        else {
+            warn!("Spaceship not found in handle_grazes");
+            continue;
+        };
+        let Some((mut graze, _)) = graze_q
+            .iter_mut()
+            .find(|(_, graze_player)| graze_player.0 == player.0)
+        else {
+            warn!("Graze not found in handle_grazes");
+            continue;
+        };
+        commands.trigger(AddScoreEvent::new(player.0, GRAZE_SCORE));
+        if graze.add() && charges.refill() {
+            commands.spawn(
+                FloatingText::new(spaceship.get_position(), "Bomb +1")
+                    .with_color(Color::from(AQUA)),
+            );
+        }
+    }
+}
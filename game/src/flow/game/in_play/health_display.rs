@@ -1,9 +1,21 @@
+use std::time::Duration;
+
 use bevy::app::{App, Plugin};
+use bevy::color::palettes::css::{AQUA, LIME, ORANGE, RED};
 use bevy::prelude::*;
 
-use crate::components::Health;
+use crate::components::{
+    BombCharges, Dash, Graze, Health, Lives, MissileAmmo, Player, WeaponHeat, WeaponLevel,
+};
+use crate::constant::{HEALTH_PIP_SIZE, HEAT_GAUGE_SIZE};
+use crate::flow::game::triggers::HealthReduceEvent;
 use crate::states::GameState;
-use crate::util::cleanup_components;
+use crate::ui_components::HudAnchor;
+
+const SHAKE_DURATION: Duration = Duration::from_millis(300);
+const SHAKE_DISTANCE: f32 = 4.;
+const GLOW_DURATION: Duration = Duration::from_millis(600);
+const EMPTY_PIP_COLOR: Color = Color::srgba(0.2, 0.2, 0.2, 0.8);
 
 pub struct HealthDisplayPlugin;
 
@@ -12,59 +24,482 @@ impl Plugin for HealthDisplayPlugin {
         app.add_systems(OnEnter(GameState::InPlay), display_health)
             .add_systems(
                 Update,
-                update_health_text.run_if(in_state(GameState::InPlay)),
+                (
+                    sync_health_bars,
+                    animate_health_bars,
+                    update_lives_text,
+                    update_missile_text,
+                    update_bomb_text,
+                    update_graze_display,
+                    update_weapon_text,
+                    update_heat_gauges,
+                    update_dash_gauges,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::InPlay)),
             )
-            .add_systems(
-                OnExit(GameState::InPlay),
-                cleanup_components::<HealthDisplay>,
-            );
+            .add_observer(shake_health_bar);
     }
 }
 
+// Remembers the last health it showed so a change can be animated
+#[derive(Component)]
+struct HealthBar {
+    player: u8,
+    health: u8,
+    // Pips from `health` up to this one were just lost
+    lost: u8,
+    // One pip each, grows with max health upgrades
+    max: u8,
+    shake: Option<Timer>,
+    glow: Option<Timer>,
+}
+
+#[derive(Component)]
+struct HealthPip {
+    player: u8,
+    index: u8,
+}
+
+#[derive(Component)]
+struct PlayerLivesText(u8);
+
 #[derive(Component)]
-struct HealthDisplay;
+struct PlayerMissileText(u8);
 
 #[derive(Component)]
-struct PlayerHealthText;
+struct PlayerBombText(u8);
 
-fn display_health(mut commands: Commands, health_q: Query<&Health>) {
+#[derive(Component)]
+struct PlayerWeaponText(u8);
+
+#[derive(Component)]
+struct PlayerGrazeText(u8);
+
+#[derive(Component)]
+struct GrazeMeterFill(u8);
+
+#[derive(Component)]
+struct HeatGaugeFill(u8);
+
+#[derive(Component)]
+struct DashGaugeFill(u8);
+
+#[allow(clippy::too_many_arguments)]
+fn display_health(
+    mut commands: Commands,
+    health_q: Query<(&Health, &Player)>,
+    lives_q: Query<(&Lives, &Player)>,
+    ammo_q: Query<(&MissileAmmo, &Player)>,
+    bomb_q: Query<(&BombCharges, &Player)>,
+    graze_q: Query<(&Graze, &Player)>,
+    level_q: Query<(&WeaponLevel, &Player)>,
+    heat_q: Query<&Player, With<WeaponHeat>>,
+) {
+    let mut healths: Vec<(&Health, &Player)> = health_q.iter().collect();
+    if healths.is_empty() {
+        warn!("Health not found in display_health");
+        return;
+    }
+    healths.sort_by_key(|(_, player)| player.0);
+    let show_player = healths.len() > 1;
     commands
         .spawn((
-            HealthDisplay,
+            HudAnchor::TopRight,
             Node {
-                position_type: PositionType::Absolute,
-                top: Val::Px(5.),
-                left: Val::Px(5.),
-                display: Display::Flex,
                 flex_direction: FlexDirection::Column,
+                align_items: AlignItems::FlexEnd,
+                row_gap: Val::Px(4.),
                 ..default()
             },
         ))
         .with_children(|health_display| {
-            let Ok(health) = health_q.single() else {
-                warn!("Health not found in display_health");
-                return;
-            };
-            health_display
-                .spawn(Text::new("Health:"))
-                .with_child((PlayerHealthText, TextSpan::new(health.0.to_string())));
+            for (health, player) in healths.iter() {
+                let lives = lives_q
+                    .iter()
+                    .find(|(_, lives_player)| lives_player.0 == player.0)
+                    .map_or(0, |(lives, _)| lives.0);
+                let grazes = graze_q
+                    .iter()
+                    .find(|(_, graze_player)| graze_player.0 == player.0)
+                    .map_or(0, |(graze, _)| graze.count());
+                let weapon_level = level_q
+                    .iter()
+                    .find(|(_, level_player)| level_player.0 == player.0)
+                    .map_or(1, |(weapon_level, _)| weapon_level.level());
+                health_display
+                    .spawn(Node {
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(6.),
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        if show_player {
+                            row.spawn(Text::new(format!("P{}", player.0)));
+                        }
+                        row.spawn((
+                            HealthBar {
+                                player: player.0,
+                                health: health.current,
+                                lost: health.current,
+                                max: health.max,
+                                shake: None,
+                                glow: None,
+                            },
+                            Node {
+                                column_gap: Val::Px(2.),
+                                ..default()
+                            },
+                        ))
+                        .with_children(|bar| {
+                            for index in 0..health.max {
+                                bar.spawn(health_pip(player.0, index));
+                            }
+                        });
+                        row.spawn(Text::new("Lives: ")).with_child((
+                            PlayerLivesText(player.0),
+                            TextSpan::new(lives.to_string()),
+                        ));
+                        row.spawn(Text::new("Lv: ")).with_child((
+                            PlayerWeaponText(player.0),
+                            TextSpan::new(weapon_level.to_string()),
+                        ));
+                        row.spawn(Text::new("Grazes: ")).with_child((
+                            PlayerGrazeText(player.0),
+                            TextSpan::new(grazes.to_string()),
+                        ));
+                        row.spawn((
+                            Node {
+                                width: Val::Px(HEAT_GAUGE_SIZE.x),
+                                height: Val::Px(HEAT_GAUGE_SIZE.y),
+                                border: UiRect::all(Val::Px(1.)),
+                                ..default()
+                            },
+                            BackgroundColor(EMPTY_PIP_COLOR),
+                            BorderColor::from(Color::BLACK),
+                        ))
+                        .with_child((
+                            GrazeMeterFill(player.0),
+                            Node {
+                                width: Val::Percent(0.),
+                                height: Val::Percent(100.),
+                                ..default()
+                            },
+                            BackgroundColor(Color::from(AQUA)),
+                        ));
+                        row.spawn(Text::new("Dash"));
+                        row.spawn((
+                            Node {
+                                width: Val::Px(HEAT_GAUGE_SIZE.x),
+                                height: Val::Px(HEAT_GAUGE_SIZE.y),
+                                border: UiRect::all(Val::Px(1.)),
+                                ..default()
+                            },
+                            BackgroundColor(EMPTY_PIP_COLOR),
+                            BorderColor::from(Color::BLACK),
+                        ))
+                        .with_child((
+                            DashGaugeFill(player.0),
+                            Node {
+                                width: Val::Percent(100.),
+                                height: Val::Percent(100.),
+                                ..default()
+                            },
+                            BackgroundColor(Color::from(LIME)),
+                        ));
+                        if heat_q.iter().any(|heat_player| heat_player.0 == player.0) {
+                            row.spawn((
+                                Node {
+                                    width: Val::Px(HEAT_GAUGE_SIZE.x),
+                                    height: Val::Px(HEAT_GAUGE_SIZE.y),
+                                    border: UiRect::all(Val::Px(1.)),
+                                    ..default()
+                                },
+                                BackgroundColor(EMPTY_PIP_COLOR),
+                                BorderColor::from(Color::BLACK),
+                            ))
+                            .with_child((
+                                HeatGaugeFill(player.0),
+                                Node {
+                                    width: Val::Percent(0.),
+                                    height: Val::Percent(100.),
+                                    ..default()
+                                },
+                                BackgroundColor(Color::from(ORANGE)),
+                            ));
+                        }
+                    });
+            }
+        });
+    // Counters sit in the bottom corners, clear of the touch controls in the middle
+    commands
+        .spawn((
+            HudAnchor::BottomLeft,
+            Node {
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+        ))
+        .with_children(|missile_display| {
+            for (_, player) in healths.iter() {
+                let missiles = ammo_q
+                    .iter()
+                    .find(|(_, ammo_player)| ammo_player.0 == player.0)
+                    .map_or(0, |(ammo, _)| ammo.count());
+                missile_display
+                    .spawn(Text::new(counter_label("Missiles", player.0, show_player)))
+                    .with_child((
+                        PlayerMissileText(player.0),
+                        TextSpan::new(missiles.to_string()),
+                    ));
+            }
+        });
+    commands
+        .spawn((
+            HudAnchor::BottomRight,
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::FlexEnd,
+                ..default()
+            },
+        ))
+        .with_children(|bomb_display| {
+            for (_, player) in healths.iter() {
+                let bombs = bomb_q
+                    .iter()
+                    .find(|(_, bomb_player)| bomb_player.0 == player.0)
+                    .map_or(0, |(charges, _)| charges.count());
+                bomb_display
+                    .spawn(Text::new(counter_label("Bombs", player.0, show_player)))
+                    .with_child((PlayerBombText(player.0), TextSpan::new(bombs.to_string())));
+            }
         });
 }
 
-fn update_health_text(
-    health_q: Query<&Health, Changed<Health>>,
-    mut player_health_text_q: Query<&mut TextSpan, With<PlayerHealthText>>,
-) {
-    if health_q.is_empty() {
-        return;
+fn health_pip(player: u8, index: u8) -> impl Bundle {
+    (
+        HealthPip { player, index },
+        Node {
+            width: Val::Px(HEALTH_PIP_SIZE.x),
+            height: Val::Px(HEALTH_PIP_SIZE.y),
+            border: UiRect::all(Val::Px(1.)),
+            ..default()
+        },
+        BackgroundColor(EMPTY_PIP_COLOR),
+        BorderColor::from(Color::BLACK),
+    )
+}
+
+fn counter_label(name: &str, player: u8, show_player: bool) -> String {
+    if show_player {
+        format!("P{player} {name}: ")
+    } else {
+        format!("{name}: ")
     }
-    let Ok(health) = health_q.single() else {
-        warn!("Health not found in update_health_text");
+}
+
+fn shake_health_bar(ev: Trigger<HealthReduceEvent>, mut bar_q: Query<&mut HealthBar>) {
+    let Some(mut bar) = bar_q.iter_mut().find(|bar| bar.player == ev.player()) else {
         return;
     };
-    let Ok(mut text_span) = player_health_text_q.single_mut() else {
-        warn!("Player health text not found in update_health_text");
-        return;
+    bar.shake = Some(Timer::new(SHAKE_DURATION, TimerMode::Once));
+}
+
+// Gaining health back (e.g. respawning) makes the bar glow,
+// a new max health adds or takes away pips at the end of the bar
+fn sync_health_bars(
+    mut commands: Commands,
+    health_q: Query<(&Health, &Player), Changed<Health>>,
+    mut bar_q: Query<(Entity, &mut HealthBar)>,
+    pip_q: Query<(Entity, &HealthPip)>,
+) {
+    for (health, player) in health_q.iter() {
+        let Some((bar_entity, mut bar)) = bar_q.iter_mut().find(|(_, bar)| bar.player == player.0)
+        else {
+            warn!("Health bar not found in sync_health_bars");
+            continue;
+        };
+        if health.current > bar.health {
+            bar.glow = Some(Timer::new(GLOW_DURATION, TimerMode::Once));
+        }
+        bar.lost = bar.health.max(health.current);
+        bar.health = health.current;
+        if health.max == bar.max {
+            continue;
+        }
+        for index in bar.max..health.max {
+            commands
+                .entity(bar_entity)
+                .with_child(health_pip(player.0, index));
+        }
+        for (pip_entity, pip) in pip_q.iter() {
+            if pip.player == player.0 && pip.index >= health.max {
+                commands.entity(pip_entity).despawn();
+            }
+        }
+        bar.max = health.max;
+    }
+}
+
+fn animate_health_bars(
+    mut bar_q: Query<(&mut HealthBar, &mut Node)>,
+    mut pip_q: Query<(&HealthPip, &mut BackgroundColor)>,
+    time: Res<Time>,
+) {
+    for (mut bar, mut node) in bar_q.iter_mut() {
+        let shake = tick_effect(&mut bar.shake, time.delta());
+        let glow = tick_effect(&mut bar.glow, time.delta());
+        node.left = Val::Px((shake * 40.).sin() * SHAKE_DISTANCE * shake);
+        for (pip, mut background_color) in pip_q.iter_mut() {
+            if pip.player != bar.player {
+                continue;
+            }
+            background_color.0 = if pip.index < bar.health {
+                Color::from(LIME).mix(&Color::WHITE, glow)
+            } else if pip.index < bar.lost && shake > 0. {
+                Color::from(RED)
+            } else {
+                EMPTY_PIP_COLOR
+            };
+        }
+    }
+}
+
+// How much of the effect is left, from 1 when it starts to 0 once it is over
+fn tick_effect(effect: &mut Option<Timer>, delta: Duration) -> f32 {
+    let Some(timer) = effect else {
+        return 0.;
     };
-    text_span.0 = health.0.to_string();
+    timer.tick(delta);
+    if timer.finished() {
+        *effect = None;
+        return 0.;
+    }
+    timer.fraction_remaining()
+}
+
+fn update_lives_text(
+    lives_q: Query<(&Lives, &Player), Changed<Lives>>,
+    mut player_lives_text_q: Query<(&mut TextSpan, &PlayerLivesText)>,
+) {
+    for (lives, player) in lives_q.iter() {
+        let Some((mut text_span, _)) = player_lives_text_q
+            .iter_mut()
+            .find(|(_, lives_text)| lives_text.0 == player.0)
+        else {
+            warn!("Player lives text not found in update_lives_text");
+            continue;
+        };
+        text_span.0 = lives.0.to_string();
+    }
+}
+
+// A respawned spaceship comes with its own ammo, which also counts as a change
+fn update_missile_text(
+    ammo_q: Query<(&MissileAmmo, &Player), Changed<MissileAmmo>>,
+    mut player_missile_text_q: Query<(&mut TextSpan, &PlayerMissileText)>,
+) {
+    for (ammo, player) in ammo_q.iter() {
+        let Some((mut text_span, _)) = player_missile_text_q
+            .iter_mut()
+            .find(|(_, missile_text)| missile_text.0 == player.0)
+        else {
+            warn!("Player missile text not found in update_missile_text");
+            continue;
+        };
+        text_span.0 = ammo.count().to_string();
+    }
+}
+
+// Charges are refilled on respawn by inserting a fresh component
+fn update_bomb_text(
+    bomb_q: Query<(&BombCharges, &Player), Changed<BombCharges>>,
+    mut player_bomb_text_q: Query<(&mut TextSpan, &PlayerBombText)>,
+) {
+    for (charges, player) in bomb_q.iter() {
+        let Some((mut text_span, _)) = player_bomb_text_q
+            .iter_mut()
+            .find(|(_, bomb_text)| bomb_text.0 == player.0)
+        else {
+            warn!("Player bomb text not found in update_bomb_text");
+            continue;
+        };
+        text_span.0 = charges.count().to_string();
+    }
+}
+
+fn update_weapon_text(
+    level_q: Query<(&WeaponLevel, &Player), Changed<WeaponLevel>>,
+    mut player_weapon_text_q: Query<(&mut TextSpan, &PlayerWeaponText)>,
+) {
+    for (weapon_level, player) in level_q.iter() {
+        let Some((mut text_span, _)) = player_weapon_text_q
+            .iter_mut()
+            .find(|(_, weapon_text)| weapon_text.0 == player.0)
+        else {
+            warn!("Player weapon text not found in update_weapon_text");
+            continue;
+        };
+        text_span.0 = weapon_level.level().to_string();
+    }
+}
+
+fn update_graze_display(
+    graze_q: Query<(&Graze, &Player), Changed<Graze>>,
+    mut player_graze_text_q: Query<(&mut TextSpan, &PlayerGrazeText)>,
+    mut fill_q: Query<(&GrazeMeterFill, &mut Node)>,
+) {
+    for (graze, player) in graze_q.iter() {
+        if let Some((mut text_span, _)) = player_graze_text_q
+            .iter_mut()
+            .find(|(_, graze_text)| graze_text.0 == player.0)
+        {
+            text_span.0 = graze.count().to_string();
+        } else {
+            warn!("Player graze text not found in update_graze_display");
+        }
+        if let Some((_, mut node)) = fill_q.iter_mut().find(|(fill, _)| fill.0 == player.0) {
+            node.width = Val::Percent(graze.meter() * 100.);
+        }
+    }
+}
+
+// Turns red for as long as the weapon is locked
+fn update_heat_gauges(
+    heat_q: Query<(&WeaponHeat, &Player)>,
+    mut fill_q: Query<(&HeatGaugeFill, &mut Node, &mut BackgroundColor)>,
+) {
+    for (weapon_heat, player) in heat_q.iter() {
+        let Some((_, mut node, mut background_color)) =
+            fill_q.iter_mut().find(|(fill, _, _)| fill.0 == player.0)
+        else {
+            continue;
+        };
+        node.width = Val::Percent(weapon_heat.heat() * 100.);
+        background_color.0 = if weapon_heat.is_overheated() {
+            Color::from(RED)
+        } else {
+            Color::from(ORANGE)
+        };
+    }
+}
+
+// Fills back up over the cooldown, a new life comes with a full one
+fn update_dash_gauges(
+    dash_q: Query<(&Dash, &Player)>,
+    mut fill_q: Query<(&DashGaugeFill, &mut Node, &mut BackgroundColor)>,
+) {
+    for (dash, player) in dash_q.iter() {
+        let Some((_, mut node, mut background_color)) =
+            fill_q.iter_mut().find(|(fill, _, _)| fill.0 == player.0)
+        else {
+            continue;
+        };
+        node.width = Val::Percent(dash.charge() * 100.);
+        background_color.0 = if dash.is_ready() {
+            Color::from(LIME)
+        } else {
+            Color::WHITE.with_alpha(0.6)
+        };
+    }
 }
@@ -1,10 +1,33 @@
+mod adaptive_difficulty;
+mod asteroid;
+mod bomb;
+mod bonus_stage;
+mod boss;
 mod collision;
+mod combo;
+mod drone;
 mod enemy;
 mod finish;
+mod formation;
+mod graze;
 mod health_display;
+mod power_up;
+mod respawn;
+mod revive;
 mod score_display;
+mod stats;
+mod wave;
+mod weapon_level;
 
 use bevy::prelude::*;
+
+use crate::states::GameState;
+use crate::ui_components::HudRoot;
+use crate::util::cleanup_components;
+
+pub(super) use enemy::spawn_strafing_ufo;
+pub(super) use wave::StageClearedEvent;
+
 pub struct InPlayPlugin;
 
 impl Plugin for InPlayPlugin {
@@ -12,9 +35,32 @@ impl Plugin for InPlayPlugin {
         app.add_plugins((
             health_display::HealthDisplayPlugin,
             score_display::ScoreDisplayPlugin,
+            wave::WavePlugin,
             enemy::EnemyPlugin,
+            formation::FormationPlugin,
+            boss::BossPlugin,
+            asteroid::AsteroidPlugin,
+            power_up::PowerUpPlugin,
             collision::CollisionPlugin,
             finish::FinishPlugin,
-        ));
+            respawn::RespawnPlugin,
+            stats::StatsPlugin,
+            adaptive_difficulty::AdaptiveDifficultyPlugin,
+            (
+                combo::ComboPlugin,
+                drone::DronePlugin,
+                bomb::BombPlugin,
+                graze::GrazePlugin,
+                weapon_level::WeaponLevelPlugin,
+                bonus_stage::BonusStagePlugin,
+                revive::RevivePlugin,
+            ),
+        ))
+        .add_systems(OnEnter(GameState::InPlay), spawn_hud_root)
+        .add_systems(OnExit(GameState::InPlay), cleanup_components::<HudRoot>);
     }
 }
+
+fn spawn_hud_root(mut commands: Commands) {
+    commands.spawn(HudRoot);
+}
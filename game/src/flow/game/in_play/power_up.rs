@@ -0,0 +1,159 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use rand::{seq::IndexedRandom, Rng};
+use shooting_game_shared::util::{EdgeUtil, POWER_UP_SIZE};
+
+use crate::components::{
+    Buff, Drone, FloatingText, Health, MissileAmmo, Player, PowerUp, PowerUpCollidedEvent,
+    PowerUpKind, Shield, Spaceship, Velocity, WeaponLevel, POWER_UP_VELOCITY,
+};
+use crate::res::{AccessibilityOption, GameRng, GameSpeed, RngStream};
+use crate::states::GameState;
+use crate::util::Position;
+
+const POWER_UP_SPAWN_INTERVAL: Duration = Duration::from_secs(12);
+
+pub struct PowerUpPlugin;
+
+impl Plugin for PowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::InPlay), setup_power_up_timer)
+            .add_systems(
+                Update,
+                (
+                    check_and_spawn_power_up,
+                    handle_power_up_collision,
+                    cleanup_on_out_screen,
+                )
+                    .run_if(in_state(GameState::InPlay)),
+            )
+            .add_systems(OnExit(GameState::InPlay), remove_power_up_timer);
+    }
+}
+
+#[derive(Resource)]
+struct PowerUpTimer(Timer);
+
+fn setup_power_up_timer(mut commands: Commands) {
+    commands.insert_resource(PowerUpTimer(Timer::new(
+        POWER_UP_SPAWN_INTERVAL,
+        TimerMode::Repeating,
+    )));
+}
+
+fn remove_power_up_timer(mut commands: Commands) {
+    commands.remove_resource::<PowerUpTimer>();
+}
+
+fn check_and_spawn_power_up(
+    mut commands: Commands,
+    mut power_up_timer: ResMut<PowerUpTimer>,
+    mut game_rng: ResMut<GameRng>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    power_up_timer.0.tick(game_speed.delta(&time));
+    if !power_up_timer.0.just_finished() {
+        return;
+    }
+    let rng = game_rng.stream(RngStream::PowerUp);
+    let kinds: Vec<PowerUpKind> = PowerUpKind::ALL
+        .into_iter()
+        .filter(|kind| !kind.is_drop_only())
+        .collect();
+    let Some(kind) = kinds.choose(rng) else {
+        return;
+    };
+    let edge = EdgeUtil::new(POWER_UP_SIZE);
+    let position = Vec2::new(
+        rng.random_range(edge.left_in()..edge.right_in()),
+        edge.top_out(),
+    );
+    commands.spawn((
+        PowerUp::new(*kind, position),
+        Velocity::from_vec2(POWER_UP_VELOCITY),
+    ));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_power_up_collision(
+    mut commands: Commands,
+    mut collision_events: EventReader<PowerUpCollidedEvent>,
+    power_up_q: Query<(&PowerUp, &Transform)>,
+    mut ammo_q: Query<&mut MissileAmmo>,
+    spaceship_q: Query<(&Spaceship, &Player)>,
+    drone_q: Query<&Drone>,
+    mut health_q: Query<(&mut Health, &Player), Without<Spaceship>>,
+    mut level_q: Query<(&mut WeaponLevel, &Player)>,
+    accessibility: Res<AccessibilityOption>,
+) {
+    for collision in collision_events.read() {
+        let Ok((power_up, transform)) = power_up_q.get(collision.power_up) else {
+            continue;
+        };
+        commands.spawn(
+            FloatingText::new(transform.translation.truncate(), power_up.kind().name())
+                .with_color(power_up.kind().color(accessibility.color_blind)),
+        );
+        if power_up.kind() == PowerUpKind::Missiles {
+            if let Ok(mut ammo) = ammo_q.get_mut(collision.spaceship) {
+                ammo.refill();
+            }
+        } else if power_up.kind() == PowerUpKind::Drone {
+            // One drone per spaceship, picking another up while it is alive does nothing
+            if let Ok((spaceship, player)) = spaceship_q.get(collision.spaceship) {
+                if !drone_q.iter().any(|drone| drone.get_player() == player.0) {
+                    commands.spawn(Drone::by_player(player.0, spaceship.get_position()));
+                }
+            }
+        } else if power_up.kind() == PowerUpKind::Health {
+            // Wasted when the health is already full
+            if let Ok((_, player)) = spaceship_q.get(collision.spaceship) {
+                for (mut health, health_player) in health_q.iter_mut() {
+                    if health_player.0 == player.0 {
+                        health.heal();
+                    }
+                }
+            }
+        } else if power_up.kind() == PowerUpKind::MaxHealth {
+            if let Ok((_, player)) = spaceship_q.get(collision.spaceship) {
+                for (mut health, health_player) in health_q.iter_mut() {
+                    if health_player.0 == player.0 {
+                        health.raise_max();
+                    }
+                }
+            }
+        } else if power_up.kind() == PowerUpKind::WeaponUp {
+            if let Ok((_, player)) = spaceship_q.get(collision.spaceship) {
+                for (mut weapon_level, level_player) in level_q.iter_mut() {
+                    if level_player.0 == player.0 {
+                        weapon_level.upgrade();
+                    }
+                }
+            }
+        } else if let Ok(mut entity_commands) = commands.get_entity(collision.spaceship) {
+            match power_up.kind() {
+                PowerUpKind::Shield => entity_commands.insert(Shield),
+                kind => entity_commands.insert(Buff::new(kind)),
+            };
+        }
+        if let Ok(mut entity_commands) = commands.get_entity(collision.power_up) {
+            entity_commands.despawn();
+        }
+    }
+}
+
+fn cleanup_on_out_screen(
+    mut commands: Commands,
+    power_up_query: Query<(Entity, &Transform), With<PowerUp>>,
+) {
+    let edge = EdgeUtil::new(POWER_UP_SIZE);
+    for (entity, transform) in power_up_query.iter() {
+        if edge.over_bottom_out(transform.translation.y) {
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.despawn();
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use shooting_game_shared::util::EdgeUtil;
+
+use crate::components::{
+    BombCharges, Dash, Health, Invisible, MissileAmmo, Player, Spaceship, Velocity, WeaponHeat,
+};
+use crate::flow::game::ready::spaceship_start_x;
+use crate::res::{GameSpeed, LocalCoop, WeaponMode};
+use crate::states::GameState;
+use crate::util::cleanup_components;
+
+const RESPAWN_COUNTDOWN: Duration = Duration::from_secs(3);
+const RESPAWN_INVINCIBILITY: Duration = Duration::from_secs(3);
+
+pub struct RespawnPlugin;
+
+impl Plugin for RespawnPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            handle_respawn_countdown.run_if(in_state(GameState::InPlay)),
+        )
+        .add_systems(
+            OnExit(GameState::InPlay),
+            cleanup_components::<RespawnCountdown>,
+        )
+        .add_observer(handle_respawn_countdown_on_add);
+    }
+}
+
+#[derive(Component)]
+pub struct RespawnCountdown {
+    player: u8,
+    timer: Timer,
+}
+
+impl RespawnCountdown {
+    pub fn new(player: u8) -> Self {
+        Self {
+            player,
+            timer: Timer::new(RESPAWN_COUNTDOWN, TimerMode::Once),
+        }
+    }
+
+    fn text(&self, local_coop: bool) -> String {
+        let remaining = self.timer.remaining_secs().ceil() as u32;
+        if local_coop {
+            format!("P{} respawn in {remaining}", self.player)
+        } else {
+            format!("Respawn in {remaining}")
+        }
+    }
+}
+
+fn handle_respawn_countdown_on_add(
+    ev: Trigger<OnAdd, RespawnCountdown>,
+    mut commands: Commands,
+    countdown_q: Query<&RespawnCountdown>,
+    local_coop: Option<Res<LocalCoop>>,
+) {
+    let Ok(countdown) = countdown_q.get(ev.target()) else {
+        warn!("RespawnCountdown not found in handle_respawn_countdown_on_add");
+        return;
+    };
+    // Co-op countdowns are stacked so both can show at once
+    let offset = 10. * countdown.player.saturating_sub(1) as f32;
+    commands.entity(ev.target()).insert((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.),
+            top: Val::Percent(40. + offset),
+            ..default()
+        },
+        Text::new(countdown.text(local_coop.is_some())),
+        TextFont::from_font_size(40.),
+        TextLayout::new_with_justify(JustifyText::Center),
+    ));
+}
+
+fn handle_respawn_countdown(
+    mut commands: Commands,
+    mut countdown_q: Query<(Entity, &mut RespawnCountdown, &mut Text)>,
+    mut health_q: Query<(&mut Health, &Player)>,
+    local_coop: Option<Res<LocalCoop>>,
+    weapon_mode: Res<WeaponMode>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    let edge = EdgeUtil::spaceship();
+    for (entity, mut countdown, mut text) in countdown_q.iter_mut() {
+        countdown.timer.tick(game_speed.delta(&time));
+        if !countdown.timer.finished() {
+            text.0 = countdown.text(local_coop.is_some());
+            continue;
+        }
+        commands.entity(entity).despawn();
+        for (mut health, player) in health_q.iter_mut() {
+            if player.0 == countdown.player {
+                // Max health upgrades last the whole run
+                health.refill();
+            }
+        }
+        let x = spaceship_start_x(countdown.player, local_coop.is_some());
+        respawn_spaceship(
+            commands.reborrow(),
+            countdown.player,
+            Vec2::new(x, edge.bottom_in()),
+            &weapon_mode,
+        );
+    }
+}
+
+// Comes back invincible for a moment with a fresh loadout
+pub fn respawn_spaceship(
+    mut commands: Commands,
+    player: u8,
+    position: Vec2,
+    weapon_mode: &WeaponMode,
+) {
+    let mut entity_commands = commands.spawn((
+        Player(player),
+        Spaceship::new(position),
+        Velocity::from_vec2(Vec2::ZERO),
+        Invisible::with_duration(RESPAWN_INVINCIBILITY),
+        MissileAmmo::default(),
+        BombCharges::default(),
+        Dash::default(),
+    ));
+    if weapon_mode.is_heat() {
+        entity_commands.insert(WeaponHeat::default());
+    }
+}
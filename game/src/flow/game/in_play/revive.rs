@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+
+use crate::components::{Downed, FloatingText, Health, Lives, Player, Spaceship};
+use crate::res::{GameSpeed, WeaponMode};
+use crate::states::GameState;
+use crate::util::cleanup_components;
+
+use super::respawn::respawn_spaceship;
+
+pub struct RevivePlugin;
+
+impl Plugin for RevivePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, channel_revive.run_if(in_state(GameState::InPlay)))
+            .add_systems(OnExit(GameState::InPlay), cleanup_components::<Downed>);
+    }
+}
+
+// Revived players come back on their last life with half their max health
+#[allow(clippy::too_many_arguments)]
+fn channel_revive(
+    mut commands: Commands,
+    mut downed_q: Query<(Entity, &mut Downed)>,
+    spaceship_q: Query<(&Spaceship, &Player)>,
+    mut health_q: Query<(&mut Health, &Player)>,
+    mut lives_q: Query<(&mut Lives, &Player)>,
+    weapon_mode: Res<WeaponMode>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    for (entity, mut downed) in downed_q.iter_mut() {
+        if !downed.hovered(spaceship_q.iter()) {
+            downed.interrupt();
+            continue;
+        }
+        if !downed.channel(game_speed.delta(&time)) {
+            continue;
+        }
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.despawn();
+        }
+        let player = downed.player();
+        if let Some((mut health, _)) = health_q.iter_mut().find(|(_, owner)| owner.0 == player) {
+            health.current = health.max.div_ceil(2);
+        } else {
+            warn!("Health not found in channel_revive");
+        }
+        if let Some((mut lives, _)) = lives_q.iter_mut().find(|(_, owner)| owner.0 == player) {
+            lives.0 = 1;
+        }
+        respawn_spaceship(commands.reborrow(), player, downed.position(), &weapon_mode);
+        commands.spawn(FloatingText::new(
+            downed.position(),
+            format!("P{player} revived!"),
+        ));
+    }
+}
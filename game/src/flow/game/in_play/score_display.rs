@@ -1,9 +1,10 @@
 use bevy::app::{App, Plugin};
 use bevy::prelude::*;
 
-use crate::components::Score;
+use crate::components::{Player, Score};
+use crate::res::Combo;
 use crate::states::GameState;
-use crate::util::cleanup_components;
+use crate::ui_components::HudAnchor;
 
 pub struct ScoreDisplayPlugin;
 
@@ -12,59 +13,84 @@ impl Plugin for ScoreDisplayPlugin {
         app.add_systems(OnEnter(GameState::InPlay), display_score)
             .add_systems(
                 Update,
-                update_score_text.run_if(in_state(GameState::InPlay)),
-            )
-            .add_systems(
-                OnExit(GameState::InPlay),
-                cleanup_components::<ScoreDisplay>,
+                (update_score_text, update_combo_text).run_if(in_state(GameState::InPlay)),
             );
     }
 }
 
 #[derive(Component)]
-struct ScoreDisplay;
+struct PlayerScoreText(u8);
 
 #[derive(Component)]
-struct PlayerScoreText;
+struct ComboText;
 
-fn display_score(mut commands: Commands, score_q: Query<&Score>) {
+fn display_score(mut commands: Commands, score_q: Query<(&Score, &Player)>) {
     commands
         .spawn((
-            ScoreDisplay,
+            HudAnchor::TopLeft,
             Node {
-                position_type: PositionType::Absolute,
-                left: Val::Px(5.),
-                top: Val::Px(100.),
-                display: Display::Flex,
                 flex_direction: FlexDirection::Column,
                 ..default()
             },
         ))
         .with_children(|score_display| {
-            let Ok(score) = score_q.single() else {
+            let mut scores: Vec<(&Score, &Player)> = score_q.iter().collect();
+            if scores.is_empty() {
                 warn!("Score not found in display_score");
                 return;
-            };
-            score_display
-                .spawn(Text::new("Score:"))
-                .with_child((PlayerScoreText, TextSpan::new(score.0.to_string())));
+            }
+            scores.sort_by_key(|(_, player)| player.0);
+            let show_player = scores.len() > 1;
+            for (score, player) in scores {
+                let label = if show_player {
+                    format!("P{} Score:", player.0)
+                } else {
+                    "Score:".to_string()
+                };
+                score_display.spawn(Text::new(label)).with_child((
+                    PlayerScoreText(player.0),
+                    TextSpan::new(score.0.to_string()),
+                ));
+            }
+            // Shared by both players in local co-op
+            score_display.spawn((
+                ComboText,
+                Text::default(),
+                TextColor(Color::srgba(1., 0.8, 0., 1.)),
+            ));
         });
 }
 
+fn combo_text(combo: &Combo) -> String {
+    match combo.multiplier() {
+        1 => String::new(),
+        multiplier => format!("Combo x{multiplier}"),
+    }
+}
+
 fn update_score_text(
-    score_q: Query<&Score, Changed<Score>>,
-    mut player_score_text_q: Query<&mut TextSpan, With<PlayerScoreText>>,
+    score_q: Query<(&Score, &Player), Changed<Score>>,
+    mut player_score_text_q: Query<(&mut TextSpan, &PlayerScoreText)>,
 ) {
-    if score_q.is_empty() {
-        return;
+    for (score, player) in score_q.iter() {
+        let Some((mut text_span, _)) = player_score_text_q
+            .iter_mut()
+            .find(|(_, score_text)| score_text.0 == player.0)
+        else {
+            warn!("Player score text not found in update_score_text");
+            continue;
+        };
+        text_span.0 = score.0.to_string();
     }
-    let Ok(score) = score_q.single() else {
-        warn!("Score not found in update_score_text");
-        return;
-    };
-    let Ok(mut text_span) = player_score_text_q.single_mut() else {
-        warn!("Player score text not found in update_score_text");
+}
+
+fn update_combo_text(combo: Res<Combo>, mut combo_text_q: Query<&mut Text, With<ComboText>>) {
+    let Ok(mut text) = combo_text_q.single_mut() else {
+        warn!("Combo text not found in update_combo_text");
         return;
     };
-    text_span.0 = score.0.to_string();
+    let new_text = combo_text(&combo);
+    if text.0 != new_text {
+        text.0 = new_text;
+    }
 }
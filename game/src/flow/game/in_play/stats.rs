@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+
+use crate::components::Bullet;
+use crate::flow::game::triggers::{DamageBossEvent, DamageUFOEvent, RemoveUFOEvent};
+use crate::flow::replay::ReplayPlayback;
+use crate::res::{DemoMode, GameStats, LifetimeStats};
+use crate::states::GameState;
+
+use super::wave::WaveManager;
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Ready), reset_game_stats)
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                record_lifetime_stats
+                    .run_if(not(resource_exists::<ReplayPlayback>))
+                    .run_if(not(resource_exists::<DemoMode>)),
+            )
+            .add_systems(
+                Update,
+                (track_time_survived, track_wave_reached).run_if(in_state(GameState::InPlay)),
+            )
+            .add_observer(track_bullet_fired)
+            .add_observer(track_ufo_hit)
+            .add_observer(track_boss_hit)
+            .add_observer(track_ufo_destroyed);
+    }
+}
+
+fn reset_game_stats(mut commands: Commands) {
+    commands.insert_resource(GameStats::default());
+}
+
+// Fed by the same triggers as the run stats, so the run is folded in once it ends
+fn record_lifetime_stats(game_stats: Res<GameStats>, mut lifetime_stats: ResMut<LifetimeStats>) {
+    lifetime_stats.add_run(&game_stats);
+}
+
+fn track_time_survived(mut game_stats: ResMut<GameStats>, time: Res<Time>) {
+    game_stats.time_survived += time.delta();
+}
+
+fn track_wave_reached(mut game_stats: ResMut<GameStats>, wave_manager: Option<Res<WaveManager>>) {
+    if let Some(wave_manager) = wave_manager {
+        game_stats.wave_reached = game_stats.wave_reached.max(wave_manager.wave());
+    }
+}
+
+// Online bullets are spawned too, only count them while playing offline
+fn track_bullet_fired(
+    _trigger: Trigger<OnAdd, Bullet>,
+    game_state: Option<Res<State<GameState>>>,
+    game_stats: Option<ResMut<GameStats>>,
+) {
+    let (Some(game_state), Some(mut game_stats)) = (game_state, game_stats) else {
+        return;
+    };
+    if *game_state.get() == GameState::InPlay {
+        game_stats.bullets_fired += 1;
+    }
+}
+
+fn track_ufo_hit(_trigger: Trigger<DamageUFOEvent>, mut game_stats: ResMut<GameStats>) {
+    game_stats.bullets_hit += 1;
+}
+
+fn track_boss_hit(_trigger: Trigger<DamageBossEvent>, mut game_stats: ResMut<GameStats>) {
+    game_stats.bullets_hit += 1;
+}
+
+fn track_ufo_destroyed(trigger: Trigger<RemoveUFOEvent>, mut game_stats: ResMut<GameStats>) {
+    if trigger.event().by().is_some() {
+        game_stats.ufos_destroyed += 1;
+    }
+}
@@ -0,0 +1,386 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::components::{Boss, Health, Player, UFO};
+#[cfg(feature = "dev-console")]
+use crate::flow::dev_console::{parse_arg, ConsoleArgs, ConsoleCommandAppExt, ConsoleResult};
+use crate::flow::game::triggers::AddScoreEvent;
+use crate::res::{
+    BackgroundPalette, ContinueRun, DailyChallenge, Difficulty, DifficultyCurve, GameSpeed,
+    StageProgress, StageScripts,
+};
+use crate::states::{AppState, GameState, InPlayState};
+use crate::ui_components::HudAnchor;
+
+const STAGE_CLEAR_DURATION: Duration = Duration::from_secs(4);
+const STAGE_CLEAR_BONUS: u32 = 1000;
+const HEALTH_BONUS: u32 = 200;
+const BONUS_STAGE_EVERY: u32 = 5;
+
+pub struct WavePlugin;
+
+impl Plugin for WavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::InPlay), setup_wave_manager)
+            .add_systems(
+                Update,
+                handle_wave_progress.run_if(in_state(InPlayState::Waves)),
+            )
+            .add_systems(OnExit(GameState::InPlay), remove_wave_manager)
+            .add_systems(OnExit(AppState::Game), reset_background_palette);
+        #[cfg(feature = "dev-console")]
+        app.add_console_command("goto wave", "goto wave <number>", console_goto_wave);
+    }
+}
+
+// Sent once the next stage is set up, what the run needs to pick up from there
+#[derive(Event)]
+pub struct StageClearedEvent {
+    pub stage: usize,
+    pub loops: u32,
+    pub waves: u32,
+}
+
+enum WavePhase {
+    Banner(Timer),
+    Spawning,
+    Clearing,
+    // Shows the bonus after a boss before the next stage starts
+    StageClear(Timer),
+    // Waiting for the bonus stage to hand back control
+    Bonus,
+}
+
+#[derive(Resource)]
+pub struct WaveManager {
+    wave: u32,
+    phase: WavePhase,
+    remaining: u32,
+    spawn_timer: Timer,
+    boss: bool,
+    asteroids: bool,
+}
+
+impl WaveManager {
+    fn new(
+        curve: &DifficultyCurve,
+        difficulty: &Difficulty,
+        scripts: &StageScripts,
+        progress: &StageProgress,
+        waves_played: u32,
+    ) -> Self {
+        let mut wave_manager = Self {
+            wave: waves_played,
+            phase: WavePhase::Clearing,
+            remaining: 0,
+            spawn_timer: Timer::new(Duration::ZERO, TimerMode::Repeating),
+            boss: false,
+            asteroids: false,
+        };
+        wave_manager.next_wave(curve, difficulty, scripts, progress);
+        wave_manager
+    }
+
+    fn next_wave(
+        &mut self,
+        curve: &DifficultyCurve,
+        difficulty: &Difficulty,
+        scripts: &StageScripts,
+        progress: &StageProgress,
+    ) {
+        self.wave += 1;
+        self.phase = WavePhase::Banner(Timer::new(curve.banner_duration, TimerMode::Once));
+        let wave_script = progress.wave_script(scripts);
+        self.boss = wave_script.is_none();
+        self.asteroids = wave_script.is_some_and(|wave_script| wave_script.asteroids);
+        self.remaining = match wave_script {
+            Some(wave_script) => curve.ufo_count(wave_script.ufos, progress.loops()),
+            None => 1,
+        };
+        let interval = curve
+            .spawn_interval(self.wave)
+            .mul_f32(difficulty.spawn_interval_scale());
+        self.spawn_timer = Timer::new(interval, TimerMode::Repeating);
+    }
+
+    // Runs through the waves in between as if they were cleared, stages included
+    #[cfg(feature = "dev-console")]
+    fn skip_to(
+        &mut self,
+        wave: u32,
+        curve: &DifficultyCurve,
+        difficulty: &Difficulty,
+        scripts: &StageScripts,
+        progress: &mut StageProgress,
+    ) {
+        while self.wave + 1 < wave {
+            self.wave += 1;
+            if progress.wave_script(scripts).is_none() {
+                progress.next_stage(scripts);
+            } else {
+                progress.next_wave();
+            }
+        }
+        self.next_wave(curve, difficulty, scripts, progress);
+    }
+
+    pub fn wave(&self) -> u32 {
+        self.wave
+    }
+
+    pub fn is_boss_wave(&self) -> bool {
+        self.boss
+    }
+
+    pub fn has_asteroids(&self) -> bool {
+        self.asteroids && !matches!(self.phase, WavePhase::Bonus)
+    }
+
+    // Returns true when the caller should spawn the next enemy of this wave
+    pub fn tick_spawn(&mut self, delta: Duration) -> bool {
+        if !matches!(self.phase, WavePhase::Spawning) || self.remaining == 0 {
+            return false;
+        }
+        self.spawn_timer.tick(delta);
+        if !self.spawn_timer.just_finished() {
+            return false;
+        }
+        self.remaining -= 1;
+        true
+    }
+
+    // Claims up to `count` more enemies of this wave for a group spawn
+    pub fn take_remaining(&mut self, count: u32) -> u32 {
+        let taken = count.min(self.remaining);
+        self.remaining -= taken;
+        taken
+    }
+}
+
+#[derive(Component)]
+struct WaveBanner;
+
+#[derive(Component)]
+struct StageClearBanner;
+
+fn setup_wave_manager(
+    mut commands: Commands,
+    curve: Res<DifficultyCurve>,
+    difficulty: Res<Difficulty>,
+    scripts: Res<StageScripts>,
+    mut palette: ResMut<BackgroundPalette>,
+    continue_run: Option<Res<ContinueRun>>,
+) {
+    let (progress, waves_played) = match continue_run {
+        Some(continue_run) => (
+            StageProgress::resume(continue_run.0.stage, continue_run.0.loops),
+            continue_run.0.waves,
+        ),
+        None => (StageProgress::default(), 0),
+    };
+    let wave_manager = WaveManager::new(&curve, &difficulty, &scripts, &progress, waves_played);
+    *palette = progress.stage(&scripts).palette();
+    spawn_wave_banner(commands.reborrow(), &wave_manager, &scripts, &progress);
+    commands.insert_resource(wave_manager);
+    commands.insert_resource(progress);
+}
+
+fn remove_wave_manager(mut commands: Commands) {
+    commands.remove_resource::<WaveManager>();
+    commands.remove_resource::<StageProgress>();
+}
+
+fn reset_background_palette(mut palette: ResMut<BackgroundPalette>) {
+    *palette = BackgroundPalette::default();
+}
+
+fn spawn_wave_banner(
+    mut commands: Commands,
+    wave_manager: &WaveManager,
+    scripts: &StageScripts,
+    progress: &StageProgress,
+) {
+    let mut text = format!("Wave {}", wave_manager.wave);
+    if progress.is_first_wave() {
+        text = format!(
+            "Stage {}\n{}\n{text}",
+            progress.stage_number(scripts),
+            progress.stage(scripts).name
+        );
+    }
+    if wave_manager.boss {
+        text.push_str("\nBoss");
+    }
+    commands.spawn((
+        WaveBanner,
+        HudAnchor::Center,
+        Text::new(text),
+        TextFont::from_font_size(60.),
+        TextLayout::new_with_justify(JustifyText::Center),
+    ));
+}
+
+// Every player still standing gets the stage bonus plus a bonus for the health left
+fn award_stage_clear(
+    mut commands: Commands,
+    health_q: &Query<(&Health, &Player)>,
+    scripts: &StageScripts,
+    progress: &StageProgress,
+) {
+    let stage_number = progress.stage_number(scripts);
+    let stage_bonus = STAGE_CLEAR_BONUS * stage_number;
+    let mut lines = vec![
+        format!("Stage {stage_number} Clear!"),
+        format!("Stage Bonus: {stage_bonus}"),
+    ];
+    let mut players: Vec<(&Health, &Player)> = health_q.iter().collect();
+    players.sort_by_key(|(_, player)| player.0);
+    for (health, player) in players {
+        let health_bonus = HEALTH_BONUS * health.current as u32;
+        let label = if health_q.iter().len() > 1 {
+            format!("P{} Health Bonus", player.0)
+        } else {
+            "Health Bonus".to_string()
+        };
+        lines.push(format!("{label}: {health_bonus}"));
+        commands.trigger(AddScoreEvent::new(player.0, stage_bonus + health_bonus));
+    }
+    commands.spawn((
+        StageClearBanner,
+        HudAnchor::Center,
+        Text::new(lines.join("\n")),
+        TextFont::from_font_size(36.),
+        TextLayout::new_with_justify(JustifyText::Center),
+        TextColor(Color::srgb(1., 0.8, 0.)),
+    ));
+}
+
+// Enemies already out are cleared so the new wave starts clean
+#[cfg(feature = "dev-console")]
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn console_goto_wave(
+    In(args): In<ConsoleArgs>,
+    mut commands: Commands,
+    wave_manager: Option<ResMut<WaveManager>>,
+    mut progress: ResMut<StageProgress>,
+    mut palette: ResMut<BackgroundPalette>,
+    curve: Res<DifficultyCurve>,
+    difficulty: Res<Difficulty>,
+    scripts: Res<StageScripts>,
+    in_play_state: Option<Res<State<InPlayState>>>,
+    enemy_query: Query<Entity, Or<(With<UFO>, With<Boss>)>>,
+    banner_query: Query<Entity, Or<(With<WaveBanner>, With<StageClearBanner>)>>,
+) -> ConsoleResult {
+    let wave: u32 = parse_arg(&args, "goto wave <number>")?;
+    let Some(mut wave_manager) = wave_manager else {
+        return Err("Waves only run during a run".to_string());
+    };
+    if in_play_state.is_none_or(|state| *state.get() != InPlayState::Waves) {
+        return Err("Finish the bonus stage first".to_string());
+    }
+    if wave <= wave_manager.wave {
+        return Err(format!("Already past wave {wave}"));
+    }
+    for entity in enemy_query.iter().chain(banner_query.iter()) {
+        commands.entity(entity).despawn();
+    }
+    wave_manager.skip_to(wave, &curve, &difficulty, &scripts, &mut progress);
+    *palette = progress.stage(&scripts).palette();
+    spawn_wave_banner(commands.reborrow(), &wave_manager, &scripts, &progress);
+    Ok(format!("Skipped to wave {wave}"))
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn handle_wave_progress(
+    mut commands: Commands,
+    mut wave_manager: ResMut<WaveManager>,
+    mut progress: ResMut<StageProgress>,
+    mut palette: ResMut<BackgroundPalette>,
+    curve: Res<DifficultyCurve>,
+    difficulty: Res<Difficulty>,
+    scripts: Res<StageScripts>,
+    enemy_query: Query<(), Or<(With<UFO>, With<Boss>)>>,
+    banner_query: Query<Entity, Or<(With<WaveBanner>, With<StageClearBanner>)>>,
+    health_q: Query<(&Health, &Player)>,
+    mut next_in_play_state: ResMut<NextState<InPlayState>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    daily_challenge: Option<Res<DailyChallenge>>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    match &mut wave_manager.phase {
+        WavePhase::Banner(timer) => {
+            timer.tick(game_speed.delta(&time));
+            if !timer.finished() {
+                return;
+            }
+            for entity in banner_query.iter() {
+                if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                    entity_commands.despawn();
+                }
+            }
+            wave_manager.phase = WavePhase::Spawning;
+        }
+        WavePhase::Spawning => {
+            if wave_manager.remaining == 0 {
+                wave_manager.phase = WavePhase::Clearing;
+            }
+        }
+        WavePhase::Clearing => {
+            if !enemy_query.is_empty() {
+                return;
+            }
+            if wave_manager.boss {
+                award_stage_clear(commands.reborrow(), &health_q, &scripts, &progress);
+                wave_manager.phase =
+                    WavePhase::StageClear(Timer::new(STAGE_CLEAR_DURATION, TimerMode::Once));
+                return;
+            }
+            progress.next_wave();
+            if wave_manager.wave.is_multiple_of(BONUS_STAGE_EVERY) {
+                wave_manager.phase = WavePhase::Bonus;
+                next_in_play_state.set(InPlayState::BonusStage);
+                return;
+            }
+            wave_manager.next_wave(&curve, &difficulty, &scripts, &progress);
+            spawn_wave_banner(commands.reborrow(), &wave_manager, &scripts, &progress);
+        }
+        // Progress already moved on before the bonus stage started
+        WavePhase::Bonus => {
+            wave_manager.next_wave(&curve, &difficulty, &scripts, &progress);
+            spawn_wave_banner(commands.reborrow(), &wave_manager, &scripts, &progress);
+        }
+        WavePhase::StageClear(timer) => {
+            timer.tick(game_speed.delta(&time));
+            if !timer.finished() {
+                return;
+            }
+            for entity in banner_query.iter() {
+                if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                    entity_commands.despawn();
+                }
+            }
+            // The daily challenge is a single stage, its boss ends the run
+            if daily_challenge.is_some() {
+                next_game_state.set(GameState::GameOver);
+                return;
+            }
+            progress.next_stage(&scripts);
+            *palette = progress.stage(&scripts).palette();
+            commands.trigger(StageClearedEvent {
+                stage: progress.stage_index(),
+                loops: progress.loops(),
+                waves: wave_manager.wave,
+            });
+            // Boss waves count too, the bonus stage follows the stage clear
+            if wave_manager.wave.is_multiple_of(BONUS_STAGE_EVERY) {
+                wave_manager.phase = WavePhase::Bonus;
+                next_in_play_state.set(InPlayState::BonusStage);
+                return;
+            }
+            wave_manager.next_wave(&curve, &difficulty, &scripts, &progress);
+            spawn_wave_banner(commands.reborrow(), &wave_manager, &scripts, &progress);
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+use crate::components::{Player, WeaponLevel};
+use crate::flow::game::triggers::HealthReduceEvent;
+
+pub struct WeaponLevelPlugin;
+
+impl Plugin for WeaponLevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(downgrade_on_damage);
+    }
+}
+
+fn downgrade_on_damage(
+    ev: Trigger<HealthReduceEvent>,
+    mut level_q: Query<(&mut WeaponLevel, &Player)>,
+) {
+    for (mut weapon_level, player) in level_q.iter_mut() {
+        if player.0 == ev.player() {
+            weapon_level.downgrade();
+        }
+    }
+}
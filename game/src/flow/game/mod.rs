@@ -1,17 +1,29 @@
+mod daily_challenge;
+mod game_over;
+mod game_speed;
 mod in_play;
+mod pause;
 mod ready;
-mod result;
-mod triggers;
+mod run_settings;
+mod save_slot;
+pub mod triggers;
+mod tutorial;
 
 use bevy::prelude::{App, Plugin};
 pub struct AppGamePlugin;
 impl Plugin for AppGamePlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
+            daily_challenge::DailyChallengePlugin,
             ready::ReadyPlugin,
             in_play::InPlayPlugin,
             triggers::TriggersPlugin,
-            result::ResultPlugin,
+            game_over::GameOverPlugin,
+            tutorial::TutorialPlugin,
+            pause::PausePlugin,
+            save_slot::SaveSlotPlugin,
+            game_speed::GameSpeedPlugin,
+            run_settings::RunSettingsPlugin,
         ));
     }
 }
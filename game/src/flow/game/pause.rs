@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use bevy::window::WindowOccluded;
+
+use crate::states::PauseState;
+use crate::ui_components::{Blink, MainContainer};
+use crate::util::cleanup_components;
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                pause_on_occluded.run_if(in_state(PauseState::Running)),
+                resume_on_input.run_if(in_state(PauseState::Paused)),
+            ),
+        )
+        .add_systems(
+            OnEnter(PauseState::Paused),
+            (pause_time, spawn_pause_notice),
+        )
+        .add_systems(
+            OnExit(PauseState::Paused),
+            (unpause_time, cleanup_components::<PauseNotice>),
+        );
+    }
+}
+
+#[derive(Component)]
+struct PauseNotice;
+
+// Sent when the browser tab is hidden or the window is minimised
+fn pause_on_occluded(
+    mut occluded_events: EventReader<WindowOccluded>,
+    mut next_state: ResMut<NextState<PauseState>>,
+) {
+    if occluded_events.read().any(|ev| ev.occluded) {
+        next_state.set(PauseState::Paused);
+    }
+}
+
+// Waits for the player rather than resuming as soon as the tab is back
+fn resume_on_input(
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    mut next_state: ResMut<NextState<PauseState>>,
+) {
+    if keys.get_just_pressed().next().is_some()
+        || mouse_buttons.get_just_pressed().next().is_some()
+        || touches.any_just_pressed()
+    {
+        next_state.set(PauseState::Running);
+    }
+}
+
+fn pause_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn unpause_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+fn spawn_pause_notice(mut commands: Commands) {
+    commands
+        .spawn((MainContainer, PauseNotice))
+        .with_children(|notice| {
+            notice.spawn((
+                TextLayout::new_with_justify(JustifyText::Center),
+                Text::new("Paused"),
+            ));
+            notice.spawn((
+                TextLayout::new_with_justify(JustifyText::Center),
+                Text::new("Press any key or tap to resume"),
+                Blink::new_with_speed(0.01),
+            ));
+        });
+}
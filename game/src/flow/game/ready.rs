@@ -1,8 +1,13 @@
 use bevy::prelude::*;
 use shooting_game_shared::util::EdgeUtil;
 
-use crate::components::{Health, Player, Score, Spaceship, Velocity};
-use crate::res::PlayerTag;
+use crate::components::{
+    BombCharges, Dash, Graze, Health, Lives, MissileAmmo, Player, Score, Spaceship, Velocity,
+    WeaponHeat, WeaponLevel,
+};
+use crate::res::{
+    ContinueRun, Difficulty, LivesOption, LocalCoop, PlayerTag, TutorialMode, WeaponMode,
+};
 use crate::states::GameState;
 
 pub struct ReadyPlugin;
@@ -20,30 +25,110 @@ impl Plugin for ReadyPlugin {
     }
 }
 
-fn setup_score_and_health(mut commands: Commands, player_tag: Res<PlayerTag>) {
+fn setup_score_and_health(
+    mut commands: Commands,
+    player_tag: Res<PlayerTag>,
+    local_coop: Option<Res<LocalCoop>>,
+    lives_option: Res<LivesOption>,
+    difficulty: Res<Difficulty>,
+    continue_run: Option<Res<ContinueRun>>,
+) {
+    if local_coop.is_some() {
+        for player in LocalCoop::PLAYERS {
+            commands.spawn((Score::new(), Player(player)));
+            commands.spawn((Health::from_difficulty(&difficulty), Player(player)));
+            commands.spawn((Lives::new(lives_option.lives), Player(player)));
+            commands.spawn((Graze::default(), Player(player)));
+            commands.spawn((WeaponLevel::default(), Player(player)));
+        }
+        return;
+    }
     commands.spawn((Score::new(), Player::new_from_res(&player_tag)));
-    commands.spawn((Health::new(), Player::new_from_res(&player_tag)));
+    commands.spawn((
+        Health::from_difficulty(&difficulty),
+        Player::new_from_res(&player_tag),
+    ));
+    // A continued run keeps what it had when the stage was cleared
+    let (lives, weapon_level) = match continue_run {
+        Some(continue_run) => (
+            Lives::new(continue_run.0.lives),
+            WeaponLevel::from_level(continue_run.0.weapon_level),
+        ),
+        None => (Lives::new(lives_option.lives), WeaponLevel::default()),
+    };
+    commands.spawn((lives, Player::new_from_res(&player_tag)));
+    commands.spawn((Graze::default(), Player::new_from_res(&player_tag)));
+    commands.spawn((weapon_level, Player::new_from_res(&player_tag)));
+}
+
+pub fn spaceship_start_x(player: u8, local_coop: bool) -> f32 {
+    match (local_coop, player) {
+        (false, _) => 0.,
+        (true, 1) => -100.,
+        (true, _) => 100.,
+    }
 }
 
-fn spawn_spaceship(mut commands: Commands, player_tag: Res<PlayerTag>) {
+fn spawn_spaceship(
+    mut commands: Commands,
+    player_tag: Res<PlayerTag>,
+    local_coop: Option<Res<LocalCoop>>,
+    weapon_mode: Res<WeaponMode>,
+) {
     let edge = EdgeUtil::spaceship();
-    commands.spawn((
+    if local_coop.is_some() {
+        for player in LocalCoop::PLAYERS {
+            let mut entity_commands = commands.spawn((
+                Player(player),
+                Spaceship::new(Vec2::new(
+                    spaceship_start_x(player, true),
+                    edge.bottom_out(),
+                )),
+                Velocity { x: 0., y: 5. },
+                MissileAmmo::default(),
+                BombCharges::default(),
+                Dash::default(),
+            ));
+            if weapon_mode.is_heat() {
+                entity_commands.insert(WeaponHeat::default());
+            }
+        }
+        return;
+    }
+    let mut entity_commands = commands.spawn((
         Player::new_from_res(&player_tag),
         Spaceship::new(Vec2::new(0., edge.bottom_out())),
         Velocity { x: 0., y: 5. },
+        MissileAmmo::default(),
+        BombCharges::default(),
+        Dash::default(),
     ));
+    if weapon_mode.is_heat() {
+        entity_commands.insert(WeaponHeat::default());
+    }
 }
 
 fn check_spaceship_position(
     mut next_state: ResMut<NextState<GameState>>,
     mut spaceship_query: Query<(&Transform, &mut Velocity), With<Spaceship>>,
+    tutorial_mode: Option<Res<TutorialMode>>,
 ) {
     let edge = EdgeUtil::spaceship();
-    let Ok((transform, mut velocity)) = spaceship_query.single_mut() else {
+    if spaceship_query.is_empty() {
         panic!("Spaceship not found in check_spaceship_position");
-    };
-    if !edge.over_bottom_in(transform.translation.y) {
-        velocity.y = 0.;
-        next_state.set(GameState::InPlay);
+    }
+    let mut all_arrived = true;
+    for (transform, mut velocity) in spaceship_query.iter_mut() {
+        if edge.over_bottom_in(transform.translation.y) {
+            all_arrived = false;
+        } else {
+            velocity.y = 0.;
+        }
+    }
+    if all_arrived {
+        next_state.set(match tutorial_mode {
+            Some(_) => GameState::Tutorial,
+            None => GameState::InPlay,
+        });
     }
 }
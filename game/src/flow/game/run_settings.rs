@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+use crate::res::{PlayerRunSettings, RunSettingsParam};
+use crate::states::AppState;
+
+pub struct RunSettingsPlugin;
+
+impl Plugin for RunSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnExit(AppState::Game),
+            restore_player_settings.run_if(resource_exists::<PlayerRunSettings>),
+        );
+    }
+}
+
+fn restore_player_settings(
+    mut commands: Commands,
+    player_settings: Res<PlayerRunSettings>,
+    mut run_settings: RunSettingsParam,
+) {
+    run_settings.replace(player_settings.0.clone());
+    commands.remove_resource::<PlayerRunSettings>();
+}
@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+
+use crate::components::{Lives, Player, WeaponLevel};
+use crate::flow::replay::ReplayPlayback;
+use crate::persistence::{load_json, save_json};
+use crate::res::{
+    ContinueRun, DailyChallenge, DemoMode, LocalCoop, PlayerRunSettings, PlayerTag,
+    RunSettingsParam, SaveSlot, StageSave, TutorialMode,
+};
+use crate::states::AppState;
+
+use super::in_play::StageClearedEvent;
+
+const SAVE_SLOT_FILE: &str = "save_slot.json";
+
+pub struct SaveSlotPlugin;
+
+impl Plugin for SaveSlotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_save_slot)
+            .add_systems(
+                // Before the game starts, so everything set up on entering it sees the saved ones
+                OnExit(AppState::MainMenu),
+                apply_saved_settings.run_if(resource_exists::<ContinueRun>),
+            )
+            .add_systems(
+                Update,
+                save_save_slot
+                    .run_if(resource_changed::<SaveSlot>.and(not(resource_added::<SaveSlot>))),
+            )
+            .add_observer(record_stage_cleared);
+    }
+}
+
+fn load_save_slot(mut commands: Commands) {
+    commands.insert_resource(load_json::<SaveSlot>(SAVE_SLOT_FILE).unwrap_or_default());
+}
+
+fn save_save_slot(save_slot: Res<SaveSlot>) {
+    save_json(SAVE_SLOT_FILE, save_slot.as_ref());
+}
+
+// A continued run goes on with the settings it was started with
+fn apply_saved_settings(
+    mut commands: Commands,
+    continue_run: Res<ContinueRun>,
+    mut run_settings: RunSettingsParam,
+) {
+    let player_settings = run_settings.replace(continue_run.0.settings.clone());
+    commands.insert_resource(PlayerRunSettings(player_settings));
+}
+
+// Only regular solo runs are saved, the other modes either have no stages to resume or
+// wouldn't be the same run once resumed
+#[allow(clippy::too_many_arguments)]
+fn record_stage_cleared(
+    ev: Trigger<StageClearedEvent>,
+    mut save_slot: ResMut<SaveSlot>,
+    player_tag: Res<PlayerTag>,
+    lives_q: Query<(&Lives, &Player)>,
+    weapon_level_q: Query<(&WeaponLevel, &Player)>,
+    local_coop: Option<Res<LocalCoop>>,
+    tutorial_mode: Option<Res<TutorialMode>>,
+    demo_mode: Option<Res<DemoMode>>,
+    playback: Option<Res<ReplayPlayback>>,
+    daily_challenge: Option<Res<DailyChallenge>>,
+    run_settings: RunSettingsParam,
+) {
+    if local_coop.is_some()
+        || tutorial_mode.is_some()
+        || demo_mode.is_some()
+        || playback.is_some()
+        || daily_challenge.is_some()
+    {
+        return;
+    }
+    let Some((lives, _)) = lives_q.iter().find(|(_, player)| player.0 == player_tag.0) else {
+        warn!("Lives not found in record_stage_cleared");
+        return;
+    };
+    let weapon_level = weapon_level_q
+        .iter()
+        .find(|(_, player)| player.0 == player_tag.0)
+        .map_or(1, |(weapon_level, _)| weapon_level.level());
+    let event = ev.event();
+    save_slot.record(StageSave {
+        stage: event.stage,
+        loops: event.loops,
+        waves: event.waves,
+        weapon_level,
+        lives: lives.0,
+        settings: run_settings.get(),
+    });
+}
@@ -1,16 +1,29 @@
 use bevy::prelude::*;
 
-use crate::components::{Player, Score};
+use crate::components::{FloatingText, Player, Score, Shield, Spaceship};
+
+const SHIELD_SCORE_INTERVAL: u32 = 10_000;
 
 #[derive(Event)]
 pub struct AddScoreEvent {
     player: u8,
-    amount: u8,
+    amount: u32,
+    // Where to show the gain, if anywhere
+    position: Option<Vec2>,
 }
 
 impl AddScoreEvent {
-    pub fn new(player: u8, amount: u8) -> Self {
-        Self { amount, player }
+    pub fn new(player: u8, amount: u32) -> Self {
+        Self {
+            amount,
+            player,
+            position: None,
+        }
+    }
+
+    pub fn at(mut self, position: Vec2) -> Self {
+        self.position = Some(position);
+        self
     }
 }
 
@@ -22,10 +35,35 @@ impl Plugin for AddScorePlugin {
     }
 }
 
-fn add_score(ev: Trigger<AddScoreEvent>, mut score_query: Query<(&mut Score, &Player)>) {
+fn add_score(
+    ev: Trigger<AddScoreEvent>,
+    mut commands: Commands,
+    mut score_query: Query<(&mut Score, &Player)>,
+    spaceship_query: Query<(Entity, &Player), With<Spaceship>>,
+) {
+    if let Some(position) = ev.position {
+        commands.spawn(FloatingText::new(position, format!("+{}", ev.amount)));
+    }
     for (mut score, player) in score_query.iter_mut() {
-        if player.0 == ev.player {
-            score.add(ev.amount);
+        if player.0 != ev.player {
+            continue;
+        }
+        let milestones_before = score.0 / SHIELD_SCORE_INTERVAL;
+        score.add(ev.amount);
+        if score.0 / SHIELD_SCORE_INTERVAL > milestones_before {
+            award_shield(commands.reborrow(), ev.player, &spaceship_query);
+        }
+    }
+}
+
+fn award_shield(
+    mut commands: Commands,
+    player_tag: u8,
+    spaceship_query: &Query<(Entity, &Player), With<Spaceship>>,
+) {
+    for (entity, player) in spaceship_query.iter() {
+        if player.0 == player_tag {
+            commands.entity(entity).insert(Shield);
         }
     }
 }
@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+
+use crate::components::{Boss, Explosion, ExplosionKind, PoolCommandsExt};
+use crate::constant::BOSS_SIZE;
+use crate::flow::juice::{RumbleEvent, SlowMotionEvent};
+use crate::util::Position;
+
+use super::AddScoreEvent;
+
+const BOSS_SCORE: u32 = 20;
+
+#[derive(Event)]
+pub struct DamageBossEvent {
+    boss: Entity,
+    player: u8,
+    damage: u8,
+}
+
+impl DamageBossEvent {
+    pub fn by_player(boss: Entity, player: u8) -> Self {
+        Self {
+            boss,
+            player,
+            damage: 1,
+        }
+    }
+
+    pub fn with_damage(mut self, damage: u8) -> Self {
+        self.damage = damage;
+        self
+    }
+
+    pub fn boss(&self) -> Entity {
+        self.boss
+    }
+}
+
+pub struct DamageBossPlugin;
+
+impl Plugin for DamageBossPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(handle_damage_boss);
+    }
+}
+
+fn handle_damage_boss(
+    ev: Trigger<DamageBossEvent>,
+    mut commands: Commands,
+    mut boss_query: Query<&mut Boss>,
+) {
+    let Ok(mut boss) = boss_query.get_mut(ev.boss) else {
+        warn!("Boss not found in handle_damage_boss");
+        return;
+    };
+    boss.damage(ev.damage);
+    if !boss.is_dead() {
+        return;
+    }
+    let position = boss.get_position();
+    commands.trigger(AddScoreEvent::new(ev.player, BOSS_SCORE).at(position));
+    commands.trigger(SlowMotionEvent::default());
+    commands.trigger(RumbleEvent::BossExplosion);
+    commands
+        .spawn_pooled(Explosion::new(position, ExplosionKind::UfoDeath).with_size(BOSS_SIZE * 1.5));
+    for offset in [
+        Vec2::new(-BOSS_SIZE.x / 3., 0.),
+        Vec2::new(BOSS_SIZE.x / 3., 0.),
+    ] {
+        commands.spawn_pooled(Explosion::new(position + offset, ExplosionKind::UfoDeath));
+    }
+    if let Ok(mut entity_commands) = commands.get_entity(ev.boss) {
+        entity_commands.despawn();
+    }
+}
@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+
+use crate::components::{Explosion, ExplosionKind, PoolCommandsExt, UFO};
+use crate::util::Position;
+
+use super::RemoveUFOEvent;
+
+#[derive(Event)]
+pub struct DamageUFOEvent {
+    ufo: Entity,
+    player: u8,
+    damage: u8,
+}
+
+impl DamageUFOEvent {
+    pub fn by_player(ufo: Entity, player: u8) -> Self {
+        Self {
+            ufo,
+            player,
+            damage: 1,
+        }
+    }
+
+    pub fn with_damage(mut self, damage: u8) -> Self {
+        self.damage = damage;
+        self
+    }
+}
+
+pub struct DamageUFOPlugin;
+
+impl Plugin for DamageUFOPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(handle_damage_ufo);
+    }
+}
+
+fn handle_damage_ufo(
+    ev: Trigger<DamageUFOEvent>,
+    mut commands: Commands,
+    mut ufo_query: Query<&mut UFO>,
+) {
+    let Ok(mut ufo) = ufo_query.get_mut(ev.ufo) else {
+        warn!("UFO not found in handle_damage_ufo");
+        return;
+    };
+    ufo.damage(ev.damage);
+    if !ufo.is_dead() {
+        commands.spawn_pooled(Explosion::new(
+            ufo.get_position(),
+            ExplosionKind::BulletImpact,
+        ));
+        return;
+    }
+    commands.spawn_pooled(Explosion::new(ufo.get_position(), ExplosionKind::UfoDeath));
+    commands.trigger(RemoveUFOEvent::by_player(ev.ufo, ev.player));
+}
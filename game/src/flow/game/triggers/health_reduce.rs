@@ -11,6 +11,10 @@ impl HealthReduceEvent {
     pub fn new(player: u8) -> Self {
         Self { player }
     }
+
+    pub fn player(&self) -> u8 {
+        self.player
+    }
 }
 
 pub struct HealthReducePlugin;
@@ -23,10 +27,8 @@ impl Plugin for HealthReducePlugin {
 
 fn reduce_health(ev: Trigger<HealthReduceEvent>, mut health_query: Query<(&mut Health, &Player)>) {
     for (mut health, player) in health_query.iter_mut() {
-        if player.0 == ev.player {
-            if health.0 > 0 {
-                health.reduce();
-            }
+        if player.0 == ev.player && health.current > 0 {
+            health.reduce();
         }
     }
 }
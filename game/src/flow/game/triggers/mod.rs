@@ -1,8 +1,12 @@
 mod add_score;
+mod damage_boss;
+mod damage_ufo;
 mod health_reduce;
 mod remove_ufo;
 
 pub use add_score::AddScoreEvent;
+pub use damage_boss::DamageBossEvent;
+pub use damage_ufo::DamageUFOEvent;
 pub use health_reduce::HealthReduceEvent;
 pub use remove_ufo::RemoveUFOEvent;
 
@@ -16,6 +20,8 @@ impl Plugin for TriggersPlugin {
             remove_ufo::RemoveUFOPlugin,
             add_score::AddScorePlugin,
             health_reduce::HealthReducePlugin,
+            damage_boss::DamageBossPlugin,
+            damage_ufo::DamageUFOPlugin,
         ));
     }
 }
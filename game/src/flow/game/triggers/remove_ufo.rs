@@ -1,13 +1,27 @@
+use std::f32::consts::TAU;
+
 use bevy::prelude::*;
 
-use crate::components::UFO;
+use bevy::color::palettes::css::GOLD;
+use rand::Rng;
+
+use crate::components::{Drift, FloatingText, PowerUp, Velocity, POWER_UP_VELOCITY, UFO};
+use crate::res::{Combo, DropTable, EnemyCatalog, GameRng, RngStream};
+use crate::util::Position;
 
 use super::AddScoreEvent;
 
+// Above the score text so both stay readable
+const COMBO_TEXT_OFFSET: Vec2 = Vec2::new(0., 30.);
+// Bomb kills are worth a share of the usual score so bombs stay a panic button
+const BOMB_SCORE_DIVISOR: u32 = 4;
+const DROP_BURST_SPEED: f32 = 3.;
+
 #[derive(Event)]
 pub struct RemoveUFOEvent {
     ufo: Entity,
     by: Option<u8>,
+    bombed: bool,
 }
 
 impl RemoveUFOEvent {
@@ -15,11 +29,28 @@ impl RemoveUFOEvent {
         Self {
             ufo,
             by: Some(player),
+            bombed: false,
+        }
+    }
+
+    pub fn by_bomb(ufo: Entity, player: u8) -> Self {
+        Self {
+            ufo,
+            by: Some(player),
+            bombed: true,
         }
     }
 
     pub fn clean_up(ufo: Entity) -> Self {
-        Self { ufo, by: None }
+        Self {
+            ufo,
+            by: None,
+            bombed: false,
+        }
+    }
+
+    pub fn by(&self) -> Option<u8> {
+        self.by
     }
 }
 
@@ -34,13 +65,46 @@ impl Plugin for RemoveUFOPlugin {
 fn handle_remove_ufo(
     ev: Trigger<RemoveUFOEvent>,
     mut commands: Commands,
-    ufo_query: Query<Entity, With<UFO>>,
+    ufo_query: Query<(Entity, &UFO)>,
+    mut combo: ResMut<Combo>,
+    catalog: Res<EnemyCatalog>,
+    drop_table: Res<DropTable>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    let ufo = ufo_query.get(ev.ufo).unwrap();
+    // A shockwave and a collision can both remove the same UFO in one frame
+    let Ok((entity, ufo)) = ufo_query.get(ev.ufo) else {
+        return;
+    };
     if let Some(player_tag) = ev.by {
-        commands.trigger(AddScoreEvent::new(player_tag, 1));
+        let multiplier_before = combo.multiplier();
+        combo.register_kill();
+        let multiplier = combo.multiplier();
+        let position = ufo.get_position();
+        let mut amount = catalog.get(ufo.kind()).score * multiplier;
+        if ev.bombed {
+            amount = (amount / BOMB_SCORE_DIVISOR).max(1);
+        }
+        commands.trigger(AddScoreEvent::new(player_tag, amount).at(position));
+        if multiplier > multiplier_before {
+            commands.spawn(
+                FloatingText::new(position + COMBO_TEXT_OFFSET, format!("COMBO x{multiplier}"))
+                    .with_color(Color::from(GOLD)),
+            );
+        }
+        // Bombs clear the whole screen at once, letting them roll too would shower pickups
+        if !ev.bombed {
+            let rng = game_rng.stream(RngStream::Drop);
+            if let Some(kind) = drop_table.roll(ufo.kind(), rng) {
+                let burst = Vec2::from_angle(rng.random_range(0.0..TAU)) * DROP_BURST_SPEED;
+                commands.spawn((
+                    PowerUp::new(kind, position),
+                    Velocity::from_vec2(POWER_UP_VELOCITY + burst),
+                    Drift,
+                ));
+            }
+        }
     }
-    if let Ok(mut entity_commands) = commands.get_entity(ufo) {
+    if let Ok(mut entity_commands) = commands.get_entity(entity) {
         entity_commands.despawn();
     }
 }
@@ -0,0 +1,248 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use shooting_game_shared::util::EdgeUtil;
+
+use crate::components::{Health, Spaceship, UFO};
+use crate::constant::ZIndex;
+use crate::flow::game::triggers::HealthReduceEvent;
+use crate::res::{
+    ControlMode, ControlOption, EnemyCatalog, GameRng, GameSpeed, KeyAction, KeyBindings, RngStream,
+};
+use crate::states::{AppState, GameState};
+use crate::util::{cleanup_components, Position};
+
+use super::in_play::spawn_strafing_ufo;
+
+const MOVE_DISTANCE: f32 = 150.;
+const DODGE_DURATION: Duration = Duration::from_secs(6);
+const COMPLETE_DURATION: Duration = Duration::from_secs(3);
+const TUTORIAL_UFO_SPEED: f32 = 2.;
+const TUTORIAL_UFO_OFFSET: f32 = 150.;
+
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Tutorial), setup_tutorial)
+            .add_systems(
+                Update,
+                (advance_tutorial, update_prompt_text, refill_health)
+                    .chain()
+                    .run_if(in_state(GameState::Tutorial)),
+            )
+            .add_systems(
+                OnExit(GameState::Tutorial),
+                (cleanup_components::<TutorialPrompt>, remove_tutorial),
+            )
+            .add_observer(reset_dodge_on_hit);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum TutorialStep {
+    Move,
+    Shoot,
+    Dodge,
+    Complete,
+}
+
+// Where the player is in the tutorial, `timer` only matters for timed steps
+#[derive(Resource)]
+struct Tutorial {
+    step: TutorialStep,
+    origin: Option<Vec2>,
+    timer: Timer,
+}
+
+impl Tutorial {
+    fn go_to(&mut self, step: TutorialStep) {
+        self.step = step;
+        self.timer = match step {
+            TutorialStep::Dodge => Timer::new(DODGE_DURATION, TimerMode::Once),
+            _ => Timer::new(COMPLETE_DURATION, TimerMode::Once),
+        };
+    }
+}
+
+#[derive(Component)]
+struct TutorialPrompt;
+
+#[derive(Component)]
+struct TutorialPromptText;
+
+fn setup_tutorial(mut commands: Commands) {
+    commands.insert_resource(Tutorial {
+        step: TutorialStep::Move,
+        origin: None,
+        timer: Timer::new(COMPLETE_DURATION, TimerMode::Once),
+    });
+    commands.spawn((
+        TutorialPrompt,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(30.),
+            width: Val::Percent(100.),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        ZIndex::TEXT.component(),
+        children![(
+            TutorialPromptText,
+            Text::new(""),
+            TextLayout::new_with_justify(JustifyText::Center),
+            BackgroundColor(Color::srgba(0., 0., 0., 0.6)),
+            Node {
+                padding: UiRect::all(Val::Px(10.)),
+                ..default()
+            },
+        )],
+    ));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn advance_tutorial(
+    mut commands: Commands,
+    mut tutorial: ResMut<Tutorial>,
+    spaceship_q: Query<&Spaceship>,
+    ufo_q: Query<(), With<UFO>>,
+    catalog: Res<EnemyCatalog>,
+    mut game_rng: ResMut<GameRng>,
+    mut next_state: ResMut<NextState<AppState>>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    let ufo_position = Vec2::new(0., EdgeUtil::ufo().top_in() - TUTORIAL_UFO_OFFSET);
+    match tutorial.step {
+        TutorialStep::Move => {
+            let Ok(spaceship) = spaceship_q.single() else {
+                return;
+            };
+            let origin = *tutorial.origin.get_or_insert(spaceship.get_position());
+            if spaceship.get_position().distance(origin) >= MOVE_DISTANCE {
+                spawn_strafing_ufo(
+                    &mut commands,
+                    &catalog,
+                    ufo_position,
+                    TUTORIAL_UFO_SPEED,
+                    false,
+                    game_rng.stream(RngStream::UfoWeapon),
+                );
+                tutorial.go_to(TutorialStep::Shoot);
+            }
+        }
+        TutorialStep::Shoot => {
+            if ufo_q.is_empty() {
+                tutorial.go_to(TutorialStep::Dodge);
+            }
+        }
+        TutorialStep::Dodge => {
+            // Shooting the UFO down early does not skip the step
+            if ufo_q.is_empty() {
+                spawn_strafing_ufo(
+                    &mut commands,
+                    &catalog,
+                    ufo_position,
+                    TUTORIAL_UFO_SPEED,
+                    true,
+                    game_rng.stream(RngStream::UfoWeapon),
+                );
+            }
+            tutorial.timer.tick(game_speed.delta(&time));
+            if tutorial.timer.finished() {
+                tutorial.go_to(TutorialStep::Complete);
+            }
+        }
+        TutorialStep::Complete => {
+            tutorial.timer.tick(time.delta());
+            if tutorial.timer.finished() {
+                next_state.set(AppState::MainMenu);
+            }
+        }
+    }
+}
+
+fn update_prompt_text(
+    tutorial: Res<Tutorial>,
+    mut text_q: Query<&mut Text, With<TutorialPromptText>>,
+    control_option: Res<ControlOption>,
+    key_bindings: Res<KeyBindings>,
+) {
+    if !tutorial.is_changed() && !control_option.is_changed() {
+        return;
+    }
+    let Ok(mut text) = text_q.single_mut() else {
+        warn!("Tutorial prompt text not found in update_prompt_text");
+        return;
+    };
+    text.0 = prompt_text(&tutorial, &control_option, &key_bindings);
+}
+
+fn prompt_text(
+    tutorial: &Tutorial,
+    control_option: &ControlOption,
+    key_bindings: &KeyBindings,
+) -> String {
+    match (tutorial.step, &control_option.mode) {
+        (TutorialStep::Move, ControlMode::Keyboard) => format!(
+            "Press {:?}/{:?}/{:?}/{:?} to move around",
+            key_bindings.key(KeyAction::Up),
+            key_bindings.key(KeyAction::Down),
+            key_bindings.key(KeyAction::Left),
+            key_bindings.key(KeyAction::Right),
+        ),
+        (TutorialStep::Move, ControlMode::Button) => "Hover on the arrows to move around".into(),
+        (TutorialStep::Move, ControlMode::Gamepad) => {
+            "Use the Left Stick or D-Pad to move around".into()
+        }
+        (TutorialStep::Move, ControlMode::Touch) => "Drag on the screen to move around".into(),
+        (TutorialStep::Move, ControlMode::Hover) => "Move the mouse to lead the ship around".into(),
+        (TutorialStep::Move, ControlMode::MouseAim) => format!(
+            "Press {:?}/{:?}/{:?}/{:?} to move around",
+            key_bindings.key(KeyAction::Up),
+            key_bindings.key(KeyAction::Down),
+            key_bindings.key(KeyAction::Left),
+            key_bindings.key(KeyAction::Right),
+        ),
+        (TutorialStep::Shoot, ControlMode::Keyboard) => format!(
+            "Press {:?} to shoot down the UFO",
+            key_bindings.key(KeyAction::Shoot)
+        ),
+        (TutorialStep::Shoot, ControlMode::Gamepad) => format!(
+            "Press {:?} to shoot down the UFO",
+            control_option.fire_button
+        ),
+        (TutorialStep::Shoot, ControlMode::MouseAim) => {
+            "Point at the UFO and click to shoot it down".into()
+        }
+        (TutorialStep::Shoot, _) => "Get under the UFO to shoot it down".into(),
+        (TutorialStep::Dodge, _) => {
+            let seconds = tutorial.timer.remaining_secs().ceil();
+            format!("Dodge the enemy bullets\nStay clear for {seconds} more seconds")
+        }
+        (TutorialStep::Complete, _) => "Tutorial complete!\nReturning to main menu".into(),
+    }
+}
+
+// Getting hit during the dodge step starts the countdown over
+fn reset_dodge_on_hit(_trigger: Trigger<HealthReduceEvent>, tutorial: Option<ResMut<Tutorial>>) {
+    let Some(mut tutorial) = tutorial else {
+        return;
+    };
+    if tutorial.step == TutorialStep::Dodge {
+        tutorial.timer.reset();
+    }
+}
+
+// Nobody can lose the tutorial, health is topped up right after a hit
+fn refill_health(mut health_q: Query<&mut Health, Changed<Health>>) {
+    for mut health in health_q.iter_mut() {
+        if health.current < health.max {
+            health.refill();
+        }
+    }
+}
+
+fn remove_tutorial(mut commands: Commands) {
+    commands.remove_resource::<Tutorial>();
+}
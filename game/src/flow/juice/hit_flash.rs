@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+
+use crate::components::{Player, Spaceship};
+use crate::flow::game::triggers::{DamageBossEvent, HealthReduceEvent};
+use crate::res::{AccessibilityOption, EffectOption};
+
+const HIT_FLASH_FRAMES: u8 = 4;
+// Above 1 so the sprite image is pushed towards white rather than just left untinted
+const HIT_FLASH_INTENSITY: f32 = 4.;
+
+pub struct HitFlashPlugin;
+
+impl Plugin for HitFlashPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, handle_hit_flash)
+            .add_observer(flash_on_health_reduce)
+            .add_observer(flash_on_damage_boss);
+    }
+}
+
+// Keeps the tint the sprite had before flashing so it can be restored
+#[derive(Component)]
+struct HitFlash {
+    frames_left: u8,
+    original_color: Color,
+}
+
+fn flash_on_health_reduce(
+    ev: Trigger<HealthReduceEvent>,
+    commands: Commands,
+    spaceship_q: Query<(Entity, &Player), With<Spaceship>>,
+    sprite_q: Query<(&mut Sprite, Option<&mut HitFlash>)>,
+    effect_option: Res<EffectOption>,
+    accessibility: Res<AccessibilityOption>,
+) {
+    let Some((entity, _)) = spaceship_q
+        .iter()
+        .find(|(_, player)| player.0 == ev.player())
+    else {
+        return;
+    };
+    start_hit_flash(commands, entity, sprite_q, &effect_option, &accessibility);
+}
+
+fn flash_on_damage_boss(
+    ev: Trigger<DamageBossEvent>,
+    commands: Commands,
+    sprite_q: Query<(&mut Sprite, Option<&mut HitFlash>)>,
+    effect_option: Res<EffectOption>,
+    accessibility: Res<AccessibilityOption>,
+) {
+    start_hit_flash(
+        commands,
+        ev.boss(),
+        sprite_q,
+        &effect_option,
+        &accessibility,
+    );
+}
+
+fn start_hit_flash(
+    mut commands: Commands,
+    entity: Entity,
+    mut sprite_q: Query<(&mut Sprite, Option<&mut HitFlash>)>,
+    effect_option: &EffectOption,
+    accessibility: &AccessibilityOption,
+) {
+    if !effect_option.hit_flash || accessibility.disable_flashing {
+        return;
+    }
+    let Ok((mut sprite, hit_flash_op)) = sprite_q.get_mut(entity) else {
+        return;
+    };
+    if let Some(mut hit_flash) = hit_flash_op {
+        hit_flash.frames_left = HIT_FLASH_FRAMES;
+        return;
+    }
+    let original_color = sprite.color;
+    sprite.color = Color::linear_rgba(
+        HIT_FLASH_INTENSITY,
+        HIT_FLASH_INTENSITY,
+        HIT_FLASH_INTENSITY,
+        original_color.alpha(),
+    );
+    if let Ok(mut entity_commands) = commands.get_entity(entity) {
+        entity_commands.try_insert(HitFlash {
+            frames_left: HIT_FLASH_FRAMES,
+            original_color,
+        });
+    }
+}
+
+fn handle_hit_flash(
+    mut commands: Commands,
+    mut hit_flash_q: Query<(Entity, &mut HitFlash, &mut Sprite)>,
+) {
+    for (entity, mut hit_flash, mut sprite) in hit_flash_q.iter_mut() {
+        hit_flash.frames_left = hit_flash.frames_left.saturating_sub(1);
+        if hit_flash.frames_left > 0 {
+            continue;
+        }
+        // Blinking may have changed the alpha while flashing
+        sprite.color = hit_flash.original_color.with_alpha(sprite.color.alpha());
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.remove::<HitFlash>();
+        }
+    }
+}
@@ -0,0 +1,24 @@
+mod hit_flash;
+mod post_process;
+mod rumble;
+mod screen_shake;
+mod slow_motion;
+
+pub use rumble::RumbleEvent;
+pub use slow_motion::{SlowMotion, SlowMotionEvent};
+
+use bevy::prelude::{App, Plugin};
+
+pub struct JuicePlugin;
+
+impl Plugin for JuicePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            screen_shake::ScreenShakePlugin,
+            hit_flash::HitFlashPlugin,
+            slow_motion::SlowMotionPlugin,
+            rumble::RumblePlugin,
+            post_process::PostProcessPlugin,
+        ));
+    }
+}
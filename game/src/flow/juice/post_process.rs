@@ -0,0 +1,107 @@
+use bevy::core_pipeline::bloom::{Bloom, BloomCompositeMode, BloomPrefilter};
+use bevy::prelude::*;
+use bevy::render::render_resource::{AsBindGroup, ShaderRef};
+use bevy::sprite::{AlphaMode2d, Material2d, Material2dPlugin};
+use shooting_game_shared::util::MOBILE_WINDOW_SIZE;
+
+use crate::constant::ZIndex;
+use crate::res::EffectOption;
+
+const CRT_SHADER: &str = "shaders/crt.wgsl";
+// One dark line every two pixels of the game view
+const CRT_SCANLINES: f32 = MOBILE_WINDOW_SIZE.y / 2.;
+const CRT_SCANLINE_DARKNESS: f32 = 0.25;
+const CRT_VIGNETTE_DARKNESS: f32 = 0.6;
+
+// Bloom runs on the camera and the CRT filter is a quad over the game view following it,
+// the UI is drawn after both so menu text stays sharp
+pub struct PostProcessPlugin;
+
+impl Plugin for PostProcessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(Material2dPlugin::<CrtMaterial>::default())
+            .add_systems(
+                Update,
+                (apply_bloom, apply_crt).run_if(
+                    resource_changed::<EffectOption>.or(any_match_filter::<Added<Camera2d>>),
+                ),
+            );
+    }
+}
+
+#[derive(Asset, TypePath, AsBindGroup, Clone)]
+struct CrtMaterial {
+    #[uniform(0)]
+    settings: Vec4,
+}
+
+impl Material2d for CrtMaterial {
+    fn fragment_shader() -> ShaderRef {
+        CRT_SHADER.into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        AlphaMode2d::Blend
+    }
+}
+
+#[derive(Component)]
+struct CrtOverlay;
+
+// Only what is pushed past white blooms, see EffectOption::glow
+fn apply_bloom(
+    mut commands: Commands,
+    mut camera_q: Query<(Entity, &mut Camera), With<Camera2d>>,
+    effect_option: Res<EffectOption>,
+) {
+    let Ok((entity, mut camera)) = camera_q.single_mut() else {
+        return;
+    };
+    camera.hdr = effect_option.bloom;
+    if effect_option.bloom {
+        commands.entity(entity).insert(Bloom {
+            prefilter: BloomPrefilter {
+                threshold: 1.,
+                threshold_softness: 0.2,
+            },
+            composite_mode: BloomCompositeMode::Additive,
+            ..Bloom::NATURAL
+        });
+    } else {
+        commands.entity(entity).remove::<Bloom>();
+    }
+}
+
+// A child of the camera so screen shake doesn't slide it off the view
+fn apply_crt(
+    mut commands: Commands,
+    camera_q: Query<Entity, With<Camera2d>>,
+    overlay_q: Query<Entity, With<CrtOverlay>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<CrtMaterial>>,
+    effect_option: Res<EffectOption>,
+) {
+    let Ok(camera) = camera_q.single() else {
+        return;
+    };
+    let overlay = overlay_q.single().ok();
+    match (effect_option.crt, overlay) {
+        (true, None) => {
+            commands.entity(camera).with_child((
+                CrtOverlay,
+                Mesh2d(meshes.add(Rectangle::from_size(MOBILE_WINDOW_SIZE))),
+                MeshMaterial2d(materials.add(CrtMaterial {
+                    settings: Vec4::new(
+                        CRT_SCANLINES,
+                        CRT_SCANLINE_DARKNESS,
+                        CRT_VIGNETTE_DARKNESS,
+                        0.,
+                    ),
+                })),
+                Transform::from_xyz(0., 0., ZIndex::CRT.z_value()),
+            ));
+        }
+        (false, Some(overlay)) => commands.entity(overlay).despawn(),
+        _ => {}
+    }
+}
@@ -0,0 +1,164 @@
+use std::time::Duration;
+
+use bevy::input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy::prelude::*;
+
+use crate::flow::game::triggers::HealthReduceEvent;
+use crate::flow::shared::controlling_gamepad;
+use crate::res::{ControlMode, ControlOption, DemoMode, LocalCoop, PlayerTag, RumbleOption};
+
+const DAMAGE_DURATION: Duration = Duration::from_millis(300);
+const CHARGED_SHOT_DURATION: Duration = Duration::from_millis(80);
+const BOSS_FADE_DURATION: Duration = Duration::from_millis(1500);
+// The fade is played as short steps, each one a little weaker than the last
+const FADE_STEP: Duration = Duration::from_millis(100);
+
+// Damage and charged shots carry the player they happened to
+#[derive(Event, Clone, Copy)]
+pub enum RumbleEvent {
+    Damage(u8),
+    ChargedShot(u8),
+    BossExplosion,
+}
+
+impl RumbleEvent {
+    fn player(&self) -> Option<u8> {
+        match self {
+            RumbleEvent::Damage(player) | RumbleEvent::ChargedShot(player) => Some(*player),
+            RumbleEvent::BossExplosion => None,
+        }
+    }
+}
+
+#[derive(Resource)]
+struct RumbleFade {
+    timer: Timer,
+    step_timer: Timer,
+}
+
+pub struct RumblePlugin;
+
+impl Plugin for RumblePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            play_rumble_fade.run_if(resource_exists::<RumbleFade>),
+        )
+        .add_observer(rumble_on_health_reduce)
+        .add_observer(handle_rumble);
+    }
+}
+
+fn rumble_on_health_reduce(trigger: Trigger<HealthReduceEvent>, mut commands: Commands) {
+    commands.trigger(RumbleEvent::Damage(trigger.event().player()));
+}
+
+fn rumble_enabled(
+    rumble_option: &RumbleOption,
+    control_option: &ControlOption,
+    demo_mode: Option<&DemoMode>,
+) -> bool {
+    !rumble_option.is_off() && control_option.mode == ControlMode::Gamepad && demo_mode.is_none()
+}
+
+// Only the gamepad flying this player's spaceship, local co-op is played on the keyboard
+fn player_gamepad(
+    player: Option<u8>,
+    player_tag: &PlayerTag,
+    local_coop: bool,
+    gamepad_q: &Query<Entity, With<Gamepad>>,
+) -> Option<Entity> {
+    if local_coop || player.is_some_and(|player| player != player_tag.0) {
+        return None;
+    }
+    controlling_gamepad(gamepad_q.iter())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_rumble(
+    trigger: Trigger<RumbleEvent>,
+    mut commands: Commands,
+    mut rumble_requests: EventWriter<GamepadRumbleRequest>,
+    gamepad_q: Query<Entity, With<Gamepad>>,
+    rumble_option: Res<RumbleOption>,
+    control_option: Res<ControlOption>,
+    demo_mode: Option<Res<DemoMode>>,
+    player_tag: Res<PlayerTag>,
+    local_coop: Option<Res<LocalCoop>>,
+) {
+    if !rumble_enabled(&rumble_option, &control_option, demo_mode.as_deref()) {
+        return;
+    }
+    let player = trigger.event().player();
+    let Some(gamepad) = player_gamepad(player, &player_tag, local_coop.is_some(), &gamepad_q)
+    else {
+        return;
+    };
+    let scale = rumble_option.intensity;
+    let (duration, intensity) = match trigger.event() {
+        RumbleEvent::Damage(_) => (DAMAGE_DURATION, both_motors(scale)),
+        RumbleEvent::ChargedShot(_) => (
+            CHARGED_SHOT_DURATION,
+            GamepadRumbleIntensity::weak_motor(scale * 0.6),
+        ),
+        RumbleEvent::BossExplosion => {
+            commands.insert_resource(RumbleFade {
+                timer: Timer::new(BOSS_FADE_DURATION, TimerMode::Once),
+                step_timer: Timer::new(FADE_STEP, TimerMode::Repeating),
+            });
+            (FADE_STEP, both_motors(scale))
+        }
+    };
+    rumble_requests.write(GamepadRumbleRequest::Add {
+        duration,
+        intensity,
+        gamepad,
+    });
+}
+
+// Each step replaces the previous one, otherwise the overlapping rumbles would add up
+#[allow(clippy::too_many_arguments)]
+fn play_rumble_fade(
+    mut commands: Commands,
+    mut rumble_fade: ResMut<RumbleFade>,
+    mut rumble_requests: EventWriter<GamepadRumbleRequest>,
+    gamepad_q: Query<Entity, With<Gamepad>>,
+    rumble_option: Res<RumbleOption>,
+    control_option: Res<ControlOption>,
+    demo_mode: Option<Res<DemoMode>>,
+    player_tag: Res<PlayerTag>,
+    local_coop: Option<Res<LocalCoop>>,
+    time: Res<Time>,
+) {
+    rumble_fade.timer.tick(time.delta());
+    rumble_fade.step_timer.tick(time.delta());
+    let gamepad = player_gamepad(None, &player_tag, local_coop.is_some(), &gamepad_q);
+    let enabled = rumble_enabled(&rumble_option, &control_option, demo_mode.as_deref());
+    if rumble_fade.timer.finished() || !enabled {
+        commands.remove_resource::<RumbleFade>();
+        if let Some(gamepad) = gamepad {
+            rumble_requests.write(GamepadRumbleRequest::Stop { gamepad });
+        }
+        return;
+    }
+    let Some(gamepad) = gamepad else {
+        return;
+    };
+    if !rumble_fade.step_timer.just_finished() {
+        return;
+    }
+    let strength = rumble_option.intensity * rumble_fade.timer.fraction_remaining();
+    rumble_requests.write(GamepadRumbleRequest::Stop { gamepad });
+    rumble_requests.write(GamepadRumbleRequest::Add {
+        duration: FADE_STEP * 2,
+        intensity: both_motors(strength),
+        gamepad,
+    });
+}
+
+fn both_motors(strength: f32) -> GamepadRumbleIntensity {
+    GamepadRumbleIntensity {
+        strong_motor: strength,
+        weak_motor: strength,
+    }
+}
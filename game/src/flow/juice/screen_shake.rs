@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::components::Explosion;
+use crate::constant::EXPLOSION_SIZE;
+use crate::flow::game::triggers::HealthReduceEvent;
+use crate::res::{EffectOption, GameRng, RngStream};
+
+const MAX_OFFSET: f32 = 12.;
+const TRAUMA_DECAY_PER_SECOND: f32 = 1.5;
+const DAMAGE_TRAUMA: f32 = 0.5;
+const BIG_EXPLOSION_TRAUMA: f32 = 0.7;
+
+pub struct ScreenShakePlugin;
+
+impl Plugin for ScreenShakePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScreenShake>()
+            .add_systems(Update, apply_screen_shake)
+            .add_observer(shake_on_health_reduce)
+            .add_observer(shake_on_big_explosion);
+    }
+}
+
+// Trauma goes from 0 to 1, the camera offset grows with its square
+#[derive(Resource, Default)]
+pub struct ScreenShake {
+    trauma: f32,
+}
+
+impl ScreenShake {
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.);
+    }
+}
+
+fn shake_on_health_reduce(
+    _trigger: Trigger<HealthReduceEvent>,
+    mut screen_shake: ResMut<ScreenShake>,
+) {
+    screen_shake.add_trauma(DAMAGE_TRAUMA);
+}
+
+fn shake_on_big_explosion(
+    ev: Trigger<OnAdd, Explosion>,
+    explosion_q: Query<&Explosion>,
+    mut screen_shake: ResMut<ScreenShake>,
+) {
+    let Ok(explosion) = explosion_q.get(ev.target()) else {
+        return;
+    };
+    if explosion.size().x > EXPLOSION_SIZE.x {
+        screen_shake.add_trauma(BIG_EXPLOSION_TRAUMA);
+    }
+}
+
+fn apply_screen_shake(
+    mut screen_shake: ResMut<ScreenShake>,
+    mut camera_q: Query<&mut Transform, With<Camera2d>>,
+    effect_option: Res<EffectOption>,
+    mut game_rng: ResMut<GameRng>,
+    time: Res<Time>,
+) {
+    let Ok(mut transform) = camera_q.single_mut() else {
+        return;
+    };
+    if !effect_option.screen_shake {
+        screen_shake.trauma = 0.;
+    }
+    if screen_shake.trauma <= 0. {
+        if transform.translation.truncate() != Vec2::ZERO {
+            transform.translation.x = 0.;
+            transform.translation.y = 0.;
+        }
+        return;
+    }
+    let rng = game_rng.stream(RngStream::Cosmetic);
+    let strength = MAX_OFFSET * screen_shake.trauma.powi(2);
+    transform.translation.x = rng.random_range(-1.0..1.0) * strength;
+    transform.translation.y = rng.random_range(-1.0..1.0) * strength;
+    screen_shake.trauma =
+        (screen_shake.trauma - TRAUMA_DECAY_PER_SECOND * time.delta_secs()).max(0.);
+}
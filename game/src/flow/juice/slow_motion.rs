@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::res::{EffectOption, GameSpeed};
+use crate::states::GameState;
+
+const SLOW_MOTION_SPEED: f32 = 0.2;
+const SLOW_MOTION_DURATION: Duration = Duration::from_millis(500);
+
+// Slows the game down for a moment, then optionally moves on to another state
+#[derive(Event, Default)]
+pub struct SlowMotionEvent {
+    then: Option<GameState>,
+}
+
+impl SlowMotionEvent {
+    pub fn then(next_state: GameState) -> Self {
+        Self {
+            then: Some(next_state),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct SlowMotion {
+    timer: Timer,
+    then: Option<GameState>,
+}
+
+pub struct SlowMotionPlugin;
+
+impl Plugin for SlowMotionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            handle_slow_motion.run_if(resource_exists::<SlowMotion>),
+        )
+        .add_systems(OnExit(GameState::InPlay), reset_game_speed)
+        .add_observer(start_slow_motion);
+    }
+}
+
+fn start_slow_motion(
+    ev: Trigger<SlowMotionEvent>,
+    mut commands: Commands,
+    mut game_speed: ResMut<GameSpeed>,
+    effect_option: Res<EffectOption>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let then = ev.event().then.clone();
+    if !effect_option.slow_motion {
+        if let Some(state) = then {
+            next_state.set(state);
+        }
+        return;
+    }
+    game_speed.slow_motion = SLOW_MOTION_SPEED;
+    commands.insert_resource(SlowMotion {
+        timer: Timer::new(SLOW_MOTION_DURATION, TimerMode::Once),
+        then,
+    });
+}
+
+// Ticked with the unscaled delta, otherwise it would slow itself down too
+fn handle_slow_motion(
+    mut commands: Commands,
+    mut slow_motion: ResMut<SlowMotion>,
+    mut game_speed: ResMut<GameSpeed>,
+    mut next_state: ResMut<NextState<GameState>>,
+    time: Res<Time>,
+) {
+    slow_motion.timer.tick(time.delta());
+    if !slow_motion.timer.finished() {
+        return;
+    }
+    game_speed.slow_motion = 1.;
+    if let Some(state) = slow_motion.then.take() {
+        next_state.set(state);
+    }
+    commands.remove_resource::<SlowMotion>();
+}
+
+fn reset_game_speed(mut commands: Commands, mut game_speed: ResMut<GameSpeed>) {
+    game_speed.slow_motion = 1.;
+    commands.remove_resource::<SlowMotion>();
+}
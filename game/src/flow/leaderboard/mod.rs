@@ -0,0 +1,273 @@
+mod online;
+
+use bevy::app::App;
+use bevy::prelude::*;
+
+use crate::cleanup::DespawnOnExit;
+use crate::res::{
+    ControlMode, ControlOption, Difficulty, HighScores, LeaderboardOption, LeaderboardPartition,
+};
+use crate::states::AppState;
+use crate::ui_components::{FocusBack, InteractionUI, MainContainer, SelectableText};
+
+pub use online::SubmitScoreEvent;
+use online::{LeaderboardTab, OnlineLeaderboardPlugin, OnlineScore, OnlineScoresFetchedEvent};
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(OnlineLeaderboardPlugin)
+            .add_systems(
+                OnEnter(AppState::Leaderboard),
+                (select_current_tab, show_leaderboard).chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_back_button_interaction,
+                    handle_tab_selection,
+                    handle_tab_text.run_if(resource_changed::<LeaderboardTab>),
+                )
+                    .run_if(in_state(AppState::Leaderboard)),
+            )
+            .add_observer(show_online_scores);
+    }
+}
+
+#[derive(Component)]
+struct Leaderboard;
+
+#[derive(Component)]
+struct BackButton;
+
+#[derive(Component)]
+struct OnlineScoresText;
+
+#[derive(Component)]
+struct LocalScoresText;
+
+#[derive(Component)]
+struct DifficultyTab(Difficulty);
+
+#[derive(Component)]
+struct ControlTab(ControlMode);
+
+fn select_current_tab(
+    mut commands: Commands,
+    difficulty: Res<Difficulty>,
+    control_option: Res<ControlOption>,
+) {
+    commands.insert_resource(LeaderboardTab(LeaderboardPartition::current(
+        &difficulty,
+        &control_option,
+    )));
+}
+
+fn show_leaderboard(
+    mut commands: Commands,
+    high_scores: Res<HighScores>,
+    leaderboard_option: Res<LeaderboardOption>,
+    tab: Res<LeaderboardTab>,
+) {
+    commands
+        .spawn((
+            Leaderboard,
+            MainContainer,
+            DespawnOnExit(AppState::Leaderboard),
+        ))
+        .with_children(|leaderboard_background| {
+            leaderboard_background.spawn((
+                Text::new("Leaderboard"),
+                TextFont::from_font_size(40.),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+            leaderboard_background
+                .spawn(tab_row_node())
+                .with_children(|tab_row| {
+                    for difficulty in Difficulty::ALL {
+                        tab_row.spawn((
+                            SelectableText::new(
+                                &format!("{difficulty:?}"),
+                                difficulty == tab.0.difficulty,
+                            ),
+                            DifficultyTab(difficulty),
+                            Interaction::default(),
+                            TextFont::from_font_size(16.),
+                        ));
+                    }
+                });
+            leaderboard_background
+                .spawn(tab_row_node())
+                .with_children(|tab_row| {
+                    for control in ControlMode::ALL {
+                        tab_row.spawn((
+                            SelectableText::new(&format!("{control:?}"), control == tab.0.control),
+                            ControlTab(control),
+                            Interaction::default(),
+                            TextFont::from_font_size(16.),
+                        ));
+                    }
+                });
+            leaderboard_background.spawn((
+                LocalScoresText,
+                Node {
+                    margin: UiRect::top(Val::Px(20.)),
+                    ..default()
+                },
+                Text::new(local_scores_text(&high_scores, &tab.0)),
+            ));
+            if leaderboard_option.is_online() {
+                leaderboard_background.spawn((
+                    Node {
+                        margin: UiRect::top(Val::Px(20.)),
+                        ..default()
+                    },
+                    Text::new("Online Top 20"),
+                    TextLayout::new_with_justify(JustifyText::Center),
+                ));
+                leaderboard_background.spawn((
+                    OnlineScoresText,
+                    Text::new("Loading..."),
+                    TextFont::from_font_size(14.),
+                ));
+            }
+            leaderboard_background
+                .spawn(Node {
+                    height: Val::Percent(100.),
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::FlexEnd,
+                    ..default()
+                })
+                .with_children(|back_container| {
+                    back_container
+                        .spawn((
+                            BackButton,
+                            FocusBack,
+                            InteractionUI,
+                            Node {
+                                align_self: AlignSelf::FlexEnd,
+                                width: Val::Px(120.),
+                                height: Val::Px(50.),
+                                border: UiRect::all(Val::Px(2.)),
+                                display: Display::Flex,
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                            BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
+                            BorderColor::from(Color::BLACK),
+                        ))
+                        .with_child(Text::new("Back"));
+                });
+        });
+}
+
+fn tab_row_node() -> Node {
+    Node {
+        margin: UiRect::top(Val::Px(10.)),
+        flex_wrap: FlexWrap::Wrap,
+        justify_content: JustifyContent::Center,
+        column_gap: Val::Px(16.),
+        ..default()
+    }
+}
+
+fn local_scores_text(high_scores: &HighScores, partition: &LeaderboardPartition) -> String {
+    let entries = high_scores.entries(partition);
+    if entries.is_empty() {
+        return "No high score yet".to_string();
+    }
+    entries
+        .iter()
+        .enumerate()
+        .map(|(rank, entry)| {
+            format!(
+                "{:>2}. {:<3}  {:>6}  {}",
+                rank + 1,
+                entry.name,
+                entry.score,
+                entry.date.format("%Y-%m-%d")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn handle_tab_selection(
+    difficulty_tab_q: Query<(&Interaction, &DifficultyTab), Changed<Interaction>>,
+    control_tab_q: Query<(&Interaction, &ControlTab), Changed<Interaction>>,
+    mut tab: ResMut<LeaderboardTab>,
+) {
+    for (interaction, difficulty_tab) in difficulty_tab_q.iter() {
+        if *interaction == Interaction::Pressed && tab.0.difficulty != difficulty_tab.0 {
+            tab.0.difficulty = difficulty_tab.0;
+        }
+    }
+    for (interaction, control_tab) in control_tab_q.iter() {
+        if *interaction == Interaction::Pressed && tab.0.control != control_tab.0 {
+            tab.0.control = control_tab.0.clone();
+        }
+    }
+}
+
+// The online list is fetched again for the new tab, see fetch_top_scores
+fn handle_tab_text(
+    mut difficulty_tab_q: Query<(&DifficultyTab, &mut SelectableText), Without<ControlTab>>,
+    mut control_tab_q: Query<(&ControlTab, &mut SelectableText), Without<DifficultyTab>>,
+    mut local_text_q: Query<&mut Text, (With<LocalScoresText>, Without<OnlineScoresText>)>,
+    mut online_text_q: Query<&mut Text, (With<OnlineScoresText>, Without<LocalScoresText>)>,
+    high_scores: Res<HighScores>,
+    tab: Res<LeaderboardTab>,
+) {
+    for (difficulty_tab, mut selectable_text) in difficulty_tab_q.iter_mut() {
+        selectable_text.set_selected(difficulty_tab.0 == tab.0.difficulty);
+    }
+    for (control_tab, mut selectable_text) in control_tab_q.iter_mut() {
+        selectable_text.set_selected(control_tab.0 == tab.0.control);
+    }
+    if let Ok(mut text) = local_text_q.single_mut() {
+        text.0 = local_scores_text(&high_scores, &tab.0);
+    }
+    if let Ok(mut text) = online_text_q.single_mut() {
+        text.0 = "Loading...".to_string();
+    }
+}
+
+fn show_online_scores(
+    trigger: Trigger<OnlineScoresFetchedEvent>,
+    mut text_q: Query<&mut Text, With<OnlineScoresText>>,
+) {
+    let Ok(mut text) = text_q.single_mut() else {
+        warn!("Online scores text not found in show_online_scores");
+        return;
+    };
+    text.0 = match &trigger.event().0 {
+        None => "Online leaderboard unavailable".to_string(),
+        Some(scores) if scores.is_empty() => "No online score yet".to_string(),
+        Some(scores) => online_scores_text(scores),
+    };
+}
+
+fn online_scores_text(scores: &[OnlineScore]) -> String {
+    scores
+        .iter()
+        .enumerate()
+        .map(|(rank, entry)| format!("{:>2}. {:<3}  {:>6}", rank + 1, entry.name, entry.score))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn handle_back_button_interaction(
+    back_button_query: Query<&Interaction, With<BackButton>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let Ok(interaction) = back_button_query.single() else {
+        warn!("Back button not found in handle_back_button_interaction");
+        return;
+    };
+    if *interaction == Interaction::Pressed {
+        next_state.set(AppState::MainMenu);
+    }
+}
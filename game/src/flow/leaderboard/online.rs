@@ -0,0 +1,194 @@
+use bevy::prelude::*;
+use bevy::tasks::{block_on, futures_lite::future::poll_once, IoTaskPool, Task};
+use serde::{Deserialize, Serialize};
+
+use crate::cleanup::DespawnOnExit;
+use crate::res::{LeaderboardOption, LeaderboardPartition};
+use crate::states::AppState;
+
+const ONLINE_TOP_COUNT: usize = 20;
+#[cfg(not(target_arch = "wasm32"))]
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+#[derive(Event)]
+pub struct SubmitScoreEvent {
+    name: String,
+    score: u32,
+    partition: LeaderboardPartition,
+}
+
+impl SubmitScoreEvent {
+    pub fn new(name: String, score: u32, partition: LeaderboardPartition) -> Self {
+        Self {
+            name,
+            score,
+            partition,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OnlineScore {
+    pub name: String,
+    pub score: u32,
+    #[serde(flatten)]
+    pub partition: LeaderboardPartition,
+}
+
+// The partition the leaderboard screen shows, starts on the one of the current settings
+#[derive(Resource)]
+pub struct LeaderboardTab(pub LeaderboardPartition);
+
+// None once the request failed, the screen only says the board is unavailable
+#[derive(Event)]
+pub struct OnlineScoresFetchedEvent(pub Option<Vec<OnlineScore>>);
+
+pub struct OnlineLeaderboardPlugin;
+
+impl Plugin for OnlineLeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            fetch_top_scores
+                .run_if(in_state(AppState::Leaderboard))
+                .run_if(resource_changed::<LeaderboardTab>),
+        )
+        .add_systems(Update, (handle_submit_task, handle_fetch_task))
+        .add_observer(submit_score);
+    }
+}
+
+#[derive(Component)]
+struct SubmitScoreTask(Task<Result<(), String>>);
+
+#[derive(Component)]
+struct FetchTopScoresTask(Task<Result<Vec<OnlineScore>, String>>);
+
+fn submit_score(
+    trigger: Trigger<SubmitScoreEvent>,
+    mut commands: Commands,
+    leaderboard_option: Res<LeaderboardOption>,
+) {
+    let Some(endpoint) = leaderboard_option.endpoint.clone() else {
+        return;
+    };
+    let event = trigger.event();
+    let body = match serde_json::to_string(&OnlineScore {
+        name: event.name.clone(),
+        score: event.score,
+        partition: event.partition.clone(),
+    }) {
+        Ok(body) => body,
+        Err(e) => {
+            warn!("Failed to serialize score: {e}");
+            return;
+        }
+    };
+    let task = IoTaskPool::get().spawn(async move {
+        http::post_json(&endpoint, &body).map_err(|e| format!("Failed to submit score: {e}"))
+    });
+    // Not scoped to a state, the score still goes out after leaving the game over screen
+    commands.spawn(SubmitScoreTask(task));
+}
+
+// A tab switch drops the request still going for the last one
+fn fetch_top_scores(
+    mut commands: Commands,
+    task_q: Query<Entity, With<FetchTopScoresTask>>,
+    leaderboard_option: Res<LeaderboardOption>,
+    tab: Res<LeaderboardTab>,
+) {
+    let Some(endpoint) = leaderboard_option.endpoint.clone() else {
+        return;
+    };
+    for entity in task_q.iter() {
+        commands.entity(entity).despawn();
+    }
+    let LeaderboardPartition {
+        difficulty,
+        control,
+    } = &tab.0;
+    let url = format!(
+        "{endpoint}?limit={ONLINE_TOP_COUNT}&difficulty={difficulty:?}&control={control:?}"
+    );
+    let task = IoTaskPool::get().spawn(async move {
+        let body = http::get(&url).map_err(|e| format!("Failed to fetch scores: {e}"))?;
+        let mut scores: Vec<OnlineScore> =
+            serde_json::from_str(&body).map_err(|e| format!("Failed to parse scores: {e}"))?;
+        scores.truncate(ONLINE_TOP_COUNT);
+        Ok(scores)
+    });
+    commands.spawn((
+        FetchTopScoresTask(task),
+        DespawnOnExit(AppState::Leaderboard),
+    ));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod http {
+    use super::REQUEST_TIMEOUT;
+
+    // Blocking, so requests only go out from the IoTaskPool where waiting on them is expected
+    fn agent() -> ureq::Agent {
+        ureq::Agent::config_builder()
+            .timeout_global(Some(REQUEST_TIMEOUT))
+            .build()
+            .into()
+    }
+
+    pub fn post_json(url: &str, body: &str) -> Result<(), String> {
+        agent()
+            .post(url)
+            .content_type("application/json")
+            .send(body)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn get(url: &str) -> Result<String, String> {
+        agent()
+            .get(url)
+            .call()
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|e| e.to_string())
+    }
+}
+
+// A blocking client can't run in the browser, the web build shows the board as unavailable
+#[cfg(target_arch = "wasm32")]
+mod http {
+    const UNAVAILABLE: &str = "not available in the web build";
+
+    pub fn post_json(_url: &str, _body: &str) -> Result<(), String> {
+        Err(UNAVAILABLE.to_string())
+    }
+
+    pub fn get(_url: &str) -> Result<String, String> {
+        Err(UNAVAILABLE.to_string())
+    }
+}
+
+fn handle_submit_task(mut commands: Commands, mut task_q: Query<(Entity, &mut SubmitScoreTask)>) {
+    for (entity, mut task) in task_q.iter_mut() {
+        let Some(result) = block_on(poll_once(&mut task.0)) else {
+            continue;
+        };
+        if let Err(e) = result {
+            warn!("Score submission failed with: {e}");
+        }
+        commands.entity(entity).despawn();
+    }
+}
+
+fn handle_fetch_task(mut commands: Commands, mut task_q: Query<(Entity, &mut FetchTopScoresTask)>) {
+    for (entity, mut task) in task_q.iter_mut() {
+        let Some(result) = block_on(poll_once(&mut task.0)) else {
+            continue;
+        };
+        let scores = result
+            .inspect_err(|e| warn!("Fetching online scores failed with: {e}"))
+            .ok();
+        commands.trigger(OnlineScoresFetchedEvent(scores));
+        commands.entity(entity).despawn();
+    }
+}
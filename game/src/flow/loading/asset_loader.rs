@@ -1,20 +1,79 @@
-use bevy::asset::LoadState;
+use std::error::Error;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext, LoadState};
 use bevy::prelude::*;
 
-use crate::res::ImageHandles;
+use crate::components::{EXPLOSION_FRAMES, EXPLOSION_FRAME_SIZE};
+use crate::res::{DropTable, EnemyCatalog, EnemyDefinitionScript, ImageHandles};
 use crate::states::AppState;
 
 pub struct AssetLoaderPlugin;
 
 impl Plugin for AssetLoaderPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(PreStartup, load_assets)
-            .add_systems(Update, check_assets.run_if(in_state(AppState::Loading)));
+        app.init_asset::<EnemyCatalog>()
+            .register_asset_loader(EnemyCatalogLoader)
+            .add_systems(PreStartup, load_assets)
+            .add_systems(
+                Update,
+                (
+                    check_assets.run_if(in_state(AppState::Loading)),
+                    reload_enemy_catalog.run_if(resource_exists::<EnemyCatalog>),
+                ),
+            );
+    }
+}
+
+#[derive(Resource)]
+struct EnemyCatalogHandle(Handle<EnemyCatalog>);
+
+// Sprites are loaded next to the catalog file and count as its dependencies
+struct EnemyCatalogLoader;
+
+impl AssetLoader for EnemyCatalogLoader {
+    type Asset = EnemyCatalog;
+    type Settings = ();
+    type Error = Box<dyn Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<EnemyCatalog, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let scripts: Vec<EnemyDefinitionScript> = ron::de::from_bytes(&bytes)?;
+        if scripts.is_empty() {
+            return Err("enemies.ron has no enemy".into());
+        }
+        let mut definitions = Vec::with_capacity(scripts.len());
+        for script in scripts {
+            script.check()?;
+            let sprite_path = load_context.asset_path().resolve_embed(&script.sprite)?;
+            let sprite = load_context.load(sprite_path);
+            definitions.push(script.into_definition(sprite));
+        }
+        Ok(EnemyCatalog::new(definitions))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
     }
 }
-fn load_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+
+fn load_assets(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    commands.insert_resource(EnemyCatalogHandle(asset_server.load("enemies.ron")));
+    let explosion_layout =
+        TextureAtlasLayout::from_grid(EXPLOSION_FRAME_SIZE, EXPLOSION_FRAMES as u32, 1, None, None);
     commands.insert_resource(ImageHandles {
         explosion: asset_server.load("embedded://explosion.png"),
+        explosion_layout: atlas_layouts.add(explosion_layout),
         spaceship: asset_server.load("embedded://spaceship.png"),
         ufo: asset_server.load("embedded://ufo.png"),
         stars: asset_server.load("embedded://stars.png"),
@@ -22,8 +81,11 @@ fn load_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
 }
 
 fn check_assets(
+    mut commands: Commands,
     mut next_state: ResMut<NextState<AppState>>,
     image_handles: Res<ImageHandles>,
+    catalog_handle: Res<EnemyCatalogHandle>,
+    catalogs: Res<Assets<EnemyCatalog>>,
     asset_server: Res<AssetServer>,
 ) {
     let asset_ids = [
@@ -37,9 +99,41 @@ fn check_assets(
             return;
         }
     }
+    if let LoadState::Failed(error) = asset_server.get_load_state(&catalog_handle.0).unwrap() {
+        panic!("Invalid enemies.ron: {error}");
+    }
+    if !asset_server.is_loaded_with_dependencies(&catalog_handle.0) {
+        return;
+    }
+    let Some(catalog) = catalogs.get(&catalog_handle.0) else {
+        return;
+    };
+    // Copied out so gameplay systems can read it without going through Assets
+    commands.insert_resource(DropTable::from_catalog(catalog));
+    commands.insert_resource(catalog.clone());
     next_state.set(AppState::MainMenu);
 }
 
+// An edited enemies.ron replaces the catalog, runs already going pick it up from their next spawn
+fn reload_enemy_catalog(
+    mut commands: Commands,
+    mut asset_events: EventReader<AssetEvent<EnemyCatalog>>,
+    catalog_handle: Res<EnemyCatalogHandle>,
+    catalogs: Res<Assets<EnemyCatalog>>,
+) {
+    for event in asset_events.read() {
+        if !event.is_modified(&catalog_handle.0) {
+            continue;
+        }
+        let Some(catalog) = catalogs.get(&catalog_handle.0) else {
+            warn!("EnemyCatalog not found in reload_enemy_catalog");
+            continue;
+        };
+        commands.insert_resource(DropTable::from_catalog(catalog));
+        commands.insert_resource(catalog.clone());
+    }
+}
+
 fn asset_is_loaded(id: AssetId<Image>, asset_server: &Res<AssetServer>) -> bool {
     match asset_server.get_load_state(id).unwrap() {
         LoadState::Loaded => true,
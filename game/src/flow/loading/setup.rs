@@ -4,33 +4,58 @@ use bevy::window::WindowResolution;
 use shooting_game_shared::util::MOBILE_WINDOW_SIZE;
 
 use crate::constant::ZIndex;
+use crate::flow::shared::Backdrop;
+use crate::res::BackgroundPalette;
 use crate::states::AppState;
 
 pub struct SetupPlugin;
 
 impl Plugin for SetupPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                canvas: Some("#shooting-game".into()),
-                fit_canvas_to_parent: true,
-                resolution: WindowResolution::from(MOBILE_WINDOW_SIZE),
-                ..default()
-            }),
-            ..default()
-        }))
+        app.add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: Some(Window {
+                        canvas: Some("#shooting-game".into()),
+                        fit_canvas_to_parent: true,
+                        resolution: WindowResolution::from(MOBILE_WINDOW_SIZE),
+                        ..default()
+                    }),
+                    ..default()
+                })
+                // Edited data files are picked up while the game runs in dev builds
+                .set(AssetPlugin {
+                    file_path: asset_folder(),
+                    watch_for_changes_override: Some(cfg!(all(
+                        debug_assertions,
+                        not(target_arch = "wasm32")
+                    ))),
+                    ..default()
+                }),
+        )
         .add_systems(OnExit(AppState::Loading), (setup_camera, setup_background));
     }
 }
 
+// Under cargo the asset root is the game crate, the assets folder is at the workspace root
+fn asset_folder() -> String {
+    let folder = if std::env::var_os("CARGO_MANIFEST_DIR").is_some() {
+        "../assets"
+    } else {
+        "assets"
+    };
+    folder.to_string()
+}
+
 fn setup_camera(mut commands: Commands) {
-    commands.spawn(Camera2d::default());
+    commands.spawn(Camera2d);
 }
 
-fn setup_background(mut commands: Commands) {
+fn setup_background(mut commands: Commands, palette: Res<BackgroundPalette>) {
     commands.spawn((
+        Backdrop,
         Sprite {
-            color: Color::srgb(0.05, 0., 0.05),
+            color: palette.backdrop,
             custom_size: Some(MOBILE_WINDOW_SIZE),
             ..default()
         },
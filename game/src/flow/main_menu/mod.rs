@@ -0,0 +1,683 @@
+mod title;
+
+use bevy::app::App;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+
+use crate::cleanup::DespawnOnExit;
+use crate::flow::replay::{Replay, ReplayPlayback};
+use crate::res::{
+    ContinueRun, ControlMode, ControlOption, DailyChallenge, DailyRecord, Difficulty, KeyAction,
+    KeyBindings, LocalCoop, Nickname, SaveSlot, StageProgress, StageSave, StageScripts,
+    TutorialMode,
+};
+use crate::states::AppState;
+use crate::ui_components::{Blink, FocusLock, InteractionUI, MainContainer, SelectableText};
+
+pub struct MainMenuPlugin;
+
+impl Plugin for MainMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(title::TitlePlugin)
+            .add_systems(OnEnter(title::MenuState::Menu), show_main_menu)
+            .add_systems(
+                Update,
+                (
+                    (
+                        (
+                            handle_control_mode_selection,
+                            handle_fire_button_selection,
+                            handle_difficulty_selection,
+                            (
+                                handle_nickname_selection,
+                                handle_nickname_typing,
+                                handle_nickname_focus_lock,
+                            )
+                                .chain(),
+                        ),
+                        (
+                            handle_control_mode_selection_text,
+                            handle_fire_button_selection_text,
+                            handle_difficulty_selection_text,
+                            handle_nickname_text,
+                        ),
+                    )
+                        .chain(),
+                    handle_start_button_interaction,
+                )
+                    .run_if(in_state(AppState::MainMenu)),
+            )
+            .add_systems(OnExit(AppState::MainMenu), remove_focus_lock);
+    }
+}
+
+#[derive(Component)]
+struct MainMenu;
+
+#[derive(Component)]
+struct FireButtonSelection;
+
+#[derive(Component)]
+struct DifficultySelection;
+
+// Clicked to start typing, Enter or Escape stops
+#[derive(Component, Default)]
+struct NicknameInput {
+    editing: bool,
+}
+
+#[derive(Component)]
+enum StartButton {
+    Game,
+    Continue,
+    LocalCoop,
+    Tutorial,
+    DailyChallenge,
+    OnlineGame,
+    Leaderboard,
+    Statistics,
+    Replay,
+    Settings,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn show_main_menu(
+    mut commands: Commands,
+    control_option: Res<ControlOption>,
+    key_bindings: Res<KeyBindings>,
+    difficulty: Res<Difficulty>,
+    daily_record: Res<DailyRecord>,
+    save_slot: Res<SaveSlot>,
+    scripts: Res<StageScripts>,
+    nickname: Res<Nickname>,
+) {
+    commands
+        .spawn((MainMenu, MainContainer, DespawnOnExit(AppState::MainMenu)))
+        .with_children(|menu_background| {
+            menu_background.spawn(Text::new(
+                "Whenever the ufo crash you, you will lose health.\nEvery wave brings more and faster ufo,\nand every stage ends with a boss",
+            ));
+            menu_background.spawn((
+                Node {
+                    margin: UiRect::top(Val::Px(50.)),
+                    ..default()
+                },
+                Text::new("In KeyBoard Mode:"),
+                TextColor(Color::srgba(0., 0., 1., 1.)),
+            ));
+            menu_background.spawn(Text::new(keyboard_help_text(&key_bindings)));
+
+            menu_background.spawn((
+                Node {
+                    margin: UiRect::top(Val::Px(50.)),
+                    ..default()
+                },
+                Text::new("In Button Mode:"),
+                TextColor(Color::srgba(0., 1., 0., 1.)),
+            ));
+            menu_background.spawn(Text::new("Hover on Arrow to move\nBullet will shoot automatically"));
+
+            menu_background.spawn((
+                Node {
+                    margin: UiRect::top(Val::Px(50.)),
+                    ..default()
+                },
+                Text::new("In Gamepad Mode:"),
+                TextColor(Color::srgba(1., 0.5, 0., 1.)),
+            ));
+            menu_background.spawn(Text::new(
+                "Use Left Stick or D-Pad to move\nPress Fire Button to shoot bullet\nConnecting a gamepad switches to this mode",
+            ));
+
+            menu_background.spawn((
+                Node {
+                    margin: UiRect::top(Val::Px(50.)),
+                    ..default()
+                },
+                Text::new("In Touch Mode:"),
+                TextColor(Color::srgba(0., 1., 1., 1.)),
+            ));
+            menu_background.spawn(Text::new("Drag anywhere on the screen to move\nBullet will shoot automatically"));
+
+            menu_background.spawn((
+                Node {
+                    margin: UiRect::top(Val::Px(50.)),
+                    ..default()
+                },
+                Text::new("In Hover Mode:"),
+                TextColor(Color::srgba(1., 1., 0., 1.)),
+            ));
+            menu_background.spawn(Text::new("The ship follows the mouse\nBullet will shoot automatically"));
+
+            menu_background.spawn((
+                Node {
+                    margin: UiRect::top(Val::Px(50.)),
+                    ..default()
+                },
+                Text::new("In Mouse Aim Mode:"),
+                TextColor(Color::srgba(1., 0., 0.5, 1.)),
+            ));
+            menu_background.spawn(Text::new(
+                "Move with the keyboard and aim with the mouse\nLeft click to shoot, right click fires a missile\nOnline games always shoot straight up",
+            ));
+
+            menu_background.spawn((
+                Node {
+                    margin: UiRect::top(Val::Px(50.)),
+                    ..default()
+                },
+                Text::new("In Local Co-op:"),
+                TextColor(Color::srgba(1., 0., 1., 1.)),
+            ));
+            menu_background.spawn(Text::new(
+                "P1 uses WASD to move and F to shoot\nP2 uses Arrows to move and Space to shoot",
+            ));
+
+            menu_background
+                .spawn(Node {
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::FlexEnd,
+                    row_gap: Val::Px(10.),
+                    flex_grow: 1.,
+                    ..default()
+                })
+                .with_children(|option_node| {
+                    option_node
+                        .spawn((
+                            ControlMode::Keyboard,
+                            SelectableText::new("Use Keyboard Mode to play",control_option.mode == ControlMode::Keyboard),
+                            Interaction::default(),
+                            TextLayout::new_with_justify(JustifyText::Right),
+                            TextColor(Color::srgba(0., 0., 1., 1.)),
+                        ));
+                    option_node
+                        .spawn((
+                            ControlMode::Button,
+                            SelectableText::new("Use Button Mode to play",control_option.mode == ControlMode::Button),
+                            Interaction::default(),
+                            TextLayout::new_with_justify(JustifyText::Right),
+                            TextColor(Color::srgba(0., 1., 0., 1.)),
+                        ));
+                    option_node
+                        .spawn((
+                            ControlMode::Gamepad,
+                            SelectableText::new("Use Gamepad Mode to play",control_option.mode == ControlMode::Gamepad),
+                            Interaction::default(),
+                            TextLayout::new_with_justify(JustifyText::Right),
+                            TextColor(Color::srgba(1., 0.5, 0., 1.)),
+                        ));
+                    option_node
+                        .spawn((
+                            ControlMode::Touch,
+                            SelectableText::new("Use Touch Mode to play",control_option.mode == ControlMode::Touch),
+                            Interaction::default(),
+                            TextLayout::new_with_justify(JustifyText::Right),
+                            TextColor(Color::srgba(0., 1., 1., 1.)),
+                        ));
+                    option_node
+                        .spawn((
+                            ControlMode::Hover,
+                            SelectableText::new("Use Hover Mode to play",control_option.mode == ControlMode::Hover),
+                            Interaction::default(),
+                            TextLayout::new_with_justify(JustifyText::Right),
+                            TextColor(Color::srgba(1., 1., 0., 1.)),
+                        ));
+                    option_node
+                        .spawn((
+                            ControlMode::MouseAim,
+                            SelectableText::new("Use Mouse Aim Mode to play",control_option.mode == ControlMode::MouseAim),
+                            Interaction::default(),
+                            TextLayout::new_with_justify(JustifyText::Right),
+                            TextColor(Color::srgba(1., 0., 0.5, 1.)),
+                        ));
+                    option_node.spawn((
+                        FireButtonSelection,
+                        InteractionUI,
+                        Text::new(fire_button_text(&control_option)),
+                        TextLayout::new_with_justify(JustifyText::Right),
+                        TextColor(Color::srgba(1., 0.5, 0., 1.)),
+                    ));
+                    option_node.spawn((
+                        DifficultySelection,
+                        InteractionUI,
+                        Text::new(difficulty_text(&difficulty)),
+                        TextLayout::new_with_justify(JustifyText::Right),
+                    ));
+                    option_node.spawn((
+                        Blink::new_with_speed(0.02),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                        Text::new("Click Start to start the game"),
+                    ));
+                    option_node
+                        .spawn((
+                            StartButton::Game,
+                            InteractionUI,
+                            Node {
+                                align_self: AlignSelf::FlexEnd,
+                                width: Val::Px(100.),
+                                height: Val::Px(50.),
+                                border: UiRect::all(Val::Px(2.)),
+                                display: Display::Flex,
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                            BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
+                            BorderColor::from(Color::BLACK),
+                            BorderRadius::all(Val::Px(5.))
+                        ))
+                        .with_child(Text::new("Start"));
+                    if let Some(save) = &save_slot.save {
+                        option_node
+                        .spawn((
+                            StartButton::Continue,
+                            InteractionUI,
+                            Node {
+                                align_self: AlignSelf::FlexEnd,
+                                width: Val::Px(200.),
+                                height: Val::Px(50.),
+                                border: UiRect::all(Val::Px(2.)),
+                                display: Display::Flex,
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                            BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
+                            BorderColor::from(Color::BLACK),
+                            BorderRadius::all(Val::Px(5.))
+                        ))
+                        .with_child(Text::new(continue_text(save, &scripts)));
+                    }
+                    option_node
+                    .spawn((
+                        StartButton::LocalCoop,
+                        InteractionUI,
+                        Node {
+                            align_self: AlignSelf::FlexEnd,
+                            width: Val::Px(200.),
+                            height: Val::Px(50.),
+                            border: UiRect::all(Val::Px(2.)),
+                            display: Display::Flex,
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
+                        BorderColor::from(Color::BLACK),
+                        BorderRadius::all(Val::Px(5.))
+                    ))
+                    .with_child(Text::new("Local Co-op"));
+                    option_node
+                    .spawn((
+                        StartButton::Tutorial,
+                        InteractionUI,
+                        Node {
+                            align_self: AlignSelf::FlexEnd,
+                            width: Val::Px(200.),
+                            height: Val::Px(50.),
+                            border: UiRect::all(Val::Px(2.)),
+                            display: Display::Flex,
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
+                        BorderColor::from(Color::BLACK),
+                        BorderRadius::all(Val::Px(5.))
+                    ))
+                    .with_child(Text::new("Tutorial"));
+                    option_node
+                    .spawn((
+                        StartButton::DailyChallenge,
+                        InteractionUI,
+                        Node {
+                            align_self: AlignSelf::FlexEnd,
+                            width: Val::Px(200.),
+                            height: Val::Px(50.),
+                            border: UiRect::all(Val::Px(2.)),
+                            display: Display::Flex,
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
+                        BorderColor::from(Color::BLACK),
+                        BorderRadius::all(Val::Px(5.))
+                    ))
+                    .with_child(Text::new(daily_challenge_text(&daily_record)));
+                    let nickname_input = NicknameInput::default();
+                    option_node.spawn((
+                        InteractionUI,
+                        Text::new(nickname_text(&nickname, &nickname_input)),
+                        TextLayout::new_with_justify(JustifyText::Right),
+                        nickname_input,
+                    ));
+                    option_node
+                    .spawn((
+                        StartButton::OnlineGame,
+                        InteractionUI,
+                        Node {
+                            align_self: AlignSelf::FlexEnd,
+                            width: Val::Px(200.),
+                            height: Val::Px(50.),
+                            border: UiRect::all(Val::Px(2.)),
+                            display: Display::Flex,
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
+                        BorderColor::from(Color::BLACK),
+                        BorderRadius::all(Val::Px(5.))
+                    ))
+                    .with_child(Text::new("Online Game"));
+                    option_node
+                    .spawn((
+                        StartButton::Leaderboard,
+                        InteractionUI,
+                        Node {
+                            align_self: AlignSelf::FlexEnd,
+                            width: Val::Px(200.),
+                            height: Val::Px(50.),
+                            border: UiRect::all(Val::Px(2.)),
+                            display: Display::Flex,
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
+                        BorderColor::from(Color::BLACK),
+                        BorderRadius::all(Val::Px(5.))
+                    ))
+                    .with_child(Text::new("Leaderboard"));
+                    option_node
+                    .spawn((
+                        StartButton::Statistics,
+                        InteractionUI,
+                        Node {
+                            align_self: AlignSelf::FlexEnd,
+                            width: Val::Px(200.),
+                            height: Val::Px(50.),
+                            border: UiRect::all(Val::Px(2.)),
+                            display: Display::Flex,
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
+                        BorderColor::from(Color::BLACK),
+                        BorderRadius::all(Val::Px(5.))
+                    ))
+                    .with_child(Text::new("Statistics"));
+                    option_node
+                    .spawn((
+                        StartButton::Replay,
+                        InteractionUI,
+                        Node {
+                            align_self: AlignSelf::FlexEnd,
+                            width: Val::Px(200.),
+                            height: Val::Px(50.),
+                            border: UiRect::all(Val::Px(2.)),
+                            display: Display::Flex,
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
+                        BorderColor::from(Color::BLACK),
+                        BorderRadius::all(Val::Px(5.))
+                    ))
+                    .with_child(Text::new("Watch Replay"));
+                    option_node
+                    .spawn((
+                        StartButton::Settings,
+                        InteractionUI,
+                        Node {
+                            align_self: AlignSelf::FlexEnd,
+                            width: Val::Px(200.),
+                            height: Val::Px(50.),
+                            border: UiRect::all(Val::Px(2.)),
+                            display: Display::Flex,
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
+                        BorderColor::from(Color::BLACK),
+                        BorderRadius::all(Val::Px(5.))
+                    ))
+                    .with_child(Text::new("Settings"));
+                });
+        });
+}
+
+fn handle_control_mode_selection(
+    control_mode_query: Query<(&ControlMode, &Interaction)>,
+    mut control_option: ResMut<ControlOption>,
+) {
+    for (control_mode, interaction) in control_mode_query.iter() {
+        if *interaction == Interaction::Pressed {
+            control_option.set_mode(control_mode);
+        }
+    }
+}
+
+fn handle_fire_button_selection(
+    fire_button_query: Query<&Interaction, (Changed<Interaction>, With<FireButtonSelection>)>,
+    mut control_option: ResMut<ControlOption>,
+) {
+    for interaction in fire_button_query.iter() {
+        if *interaction == Interaction::Pressed {
+            control_option.next_fire_button();
+        }
+    }
+}
+
+fn handle_difficulty_selection(
+    difficulty_query: Query<&Interaction, (Changed<Interaction>, With<DifficultySelection>)>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    for interaction in difficulty_query.iter() {
+        if *interaction == Interaction::Pressed {
+            difficulty.next();
+        }
+    }
+}
+
+fn handle_nickname_selection(
+    mut nickname_query: Query<(&Interaction, &mut NicknameInput), Changed<Interaction>>,
+) {
+    for (interaction, mut nickname_input) in nickname_query.iter_mut() {
+        if *interaction == Interaction::Pressed {
+            nickname_input.editing = !nickname_input.editing;
+        }
+    }
+}
+
+fn handle_nickname_typing(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut nickname_query: Query<&mut NicknameInput>,
+    mut nickname: ResMut<Nickname>,
+) {
+    let Ok(mut nickname_input) = nickname_query.single_mut() else {
+        return;
+    };
+    if !nickname_input.editing {
+        keyboard_events.clear();
+        return;
+    }
+    for keyboard_event in keyboard_events.read() {
+        if keyboard_event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &keyboard_event.logical_key {
+            Key::Enter | Key::Escape => nickname_input.editing = false,
+            Key::Backspace => nickname.pop(),
+            Key::Character(character) => nickname.push_str(character),
+            _ => {}
+        }
+    }
+}
+
+// Arrow keys and Enter belong to the nickname while it is typed
+fn handle_nickname_focus_lock(mut commands: Commands, nickname_query: Query<Ref<NicknameInput>>) {
+    let Ok(nickname_input) = nickname_query.single() else {
+        return;
+    };
+    if !nickname_input.is_changed() {
+        return;
+    }
+    if nickname_input.editing {
+        commands.insert_resource(FocusLock);
+    } else {
+        commands.remove_resource::<FocusLock>();
+    }
+}
+
+fn remove_focus_lock(mut commands: Commands) {
+    commands.remove_resource::<FocusLock>();
+}
+
+fn handle_nickname_text(
+    mut nickname_query: Query<(Ref<NicknameInput>, &mut Text)>,
+    nickname: Res<Nickname>,
+) {
+    for (nickname_input, mut text) in nickname_query.iter_mut() {
+        if nickname.is_changed() || nickname_input.is_changed() {
+            text.0 = nickname_text(&nickname, &nickname_input);
+        }
+    }
+}
+
+fn handle_control_mode_selection_text(
+    mut control_mode_query: Query<(&ControlMode, &mut SelectableText)>,
+    control_option: Res<ControlOption>,
+) {
+    if control_option.is_changed() {
+        for (control_mode, mut selectable_text) in control_mode_query.iter_mut() {
+            selectable_text.set_selected(*control_mode == control_option.mode);
+        }
+    }
+}
+
+fn handle_fire_button_selection_text(
+    mut fire_button_query: Query<&mut Text, With<FireButtonSelection>>,
+    control_option: Res<ControlOption>,
+) {
+    if control_option.is_changed() {
+        for mut text in fire_button_query.iter_mut() {
+            text.0 = fire_button_text(&control_option);
+        }
+    }
+}
+
+fn handle_difficulty_selection_text(
+    mut difficulty_query: Query<&mut Text, With<DifficultySelection>>,
+    difficulty: Res<Difficulty>,
+) {
+    if difficulty.is_changed() {
+        for mut text in difficulty_query.iter_mut() {
+            text.0 = difficulty_text(&difficulty);
+        }
+    }
+}
+
+fn keyboard_help_text(key_bindings: &KeyBindings) -> String {
+    format!(
+        "Press {:?}/{:?}/{:?}/{:?} to move\nPress {:?} to shoot bullet\nPress {:?} to fire missile\nPress {:?} to drop a bomb\nPress {:?} or double-tap a direction to dash",
+        key_bindings.key(KeyAction::Up),
+        key_bindings.key(KeyAction::Down),
+        key_bindings.key(KeyAction::Left),
+        key_bindings.key(KeyAction::Right),
+        key_bindings.key(KeyAction::Shoot),
+        key_bindings.key(KeyAction::Missile),
+        key_bindings.key(KeyAction::Bomb),
+        key_bindings.key(KeyAction::Dash),
+    )
+}
+
+fn fire_button_text(control_option: &ControlOption) -> String {
+    format!("Gamepad Fire Button: {:?}", control_option.fire_button)
+}
+
+fn nickname_text(nickname: &Nickname, nickname_input: &NicknameInput) -> String {
+    let cursor = if nickname_input.editing { "_" } else { "" };
+    match nickname.get() {
+        "" if !nickname_input.editing => "Nickname: click to set".to_string(),
+        name => format!("Nickname: {name}{cursor}"),
+    }
+}
+
+fn difficulty_text(difficulty: &Difficulty) -> String {
+    format!("Difficulty: {difficulty:?}")
+}
+
+fn daily_challenge_text(daily_record: &DailyRecord) -> String {
+    if !daily_record.played(DailyChallenge::today().date) {
+        return "Daily Challenge".to_string();
+    }
+    match daily_record.score {
+        Some(score) => format!("Daily Done: {score}"),
+        None => "Daily Done".to_string(),
+    }
+}
+
+fn continue_text(save: &StageSave, scripts: &StageScripts) -> String {
+    let stage_number = StageProgress::resume(save.stage, save.loops).stage_number(scripts);
+    format!("Continue: Stage {stage_number}")
+}
+
+fn handle_start_button_interaction(
+    mut commands: Commands,
+    start_button_query: Query<(&Interaction, &StartButton)>,
+    daily_record: Res<DailyRecord>,
+    save_slot: Res<SaveSlot>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, start_button) in start_button_query.iter() {
+        if *interaction == Interaction::Pressed {
+            if let StartButton::Replay = start_button {
+                let Some(replay) = Replay::load() else {
+                    warn!("No replay to watch in handle_start_button_interaction");
+                    continue;
+                };
+                commands.insert_resource(ReplayPlayback::new(replay));
+            }
+            if let StartButton::Continue = start_button {
+                let Some(save) = save_slot.save.clone() else {
+                    continue;
+                };
+                commands.insert_resource(ContinueRun(save));
+            }
+            if let StartButton::LocalCoop = start_button {
+                commands.insert_resource(LocalCoop);
+            }
+            if let StartButton::Tutorial = start_button {
+                commands.insert_resource(TutorialMode);
+            }
+            if let StartButton::DailyChallenge = start_button {
+                let daily_challenge = DailyChallenge::today();
+                // Only one attempt a day
+                if daily_record.played(daily_challenge.date) {
+                    continue;
+                }
+                commands.insert_resource(daily_challenge);
+            }
+            let target_state = match start_button {
+                StartButton::Game
+                | StartButton::Continue
+                | StartButton::LocalCoop
+                | StartButton::Tutorial
+                | StartButton::DailyChallenge
+                | StartButton::Replay => AppState::Game,
+                StartButton::OnlineGame => AppState::OnlineGame,
+                StartButton::Leaderboard => AppState::Leaderboard,
+                StartButton::Statistics => AppState::Statistics,
+                StartButton::Settings => AppState::Settings,
+            };
+            next_state.set(target_state);
+        };
+    }
+}
@@ -0,0 +1,212 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+use rand::Rng;
+use shooting_game_shared::util::{MOBILE_WINDOW_SIZE, SPACESHIP_SIZE};
+
+use crate::cleanup::DespawnOnExit;
+use crate::constant::{ZIndex, BULLET_SIZE};
+use crate::res::{GameRng, ImageHandles, RngStream};
+use crate::states::AppState;
+use crate::ui_components::Blink;
+use crate::util::{any_input_just_pressed, cleanup_components};
+
+const TITLE_FONT_SIZE: f32 = 56.;
+const TITLE_PULSE: f32 = 4.;
+const TITLE_PULSE_SPEED: f32 = 3.;
+const FLYBY_INTERVAL: f32 = 5.;
+const FLYBY_SPEED: f32 = 220.;
+const FLYBY_SHOOT_INTERVAL: f32 = 0.25;
+const FLYBY_BULLET_SPEED: f32 = 520.;
+const FLYBY_SCALE: f32 = 0.6;
+
+// The main menu opens on the title until any input, only the first time per run
+#[derive(Default, SubStates, Debug, Hash, Eq, PartialEq, Clone)]
+#[source(AppState = AppState::MainMenu)]
+pub enum MenuState {
+    #[default]
+    Attract,
+    Menu,
+}
+
+pub struct TitlePlugin;
+
+impl Plugin for TitlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_sub_state::<MenuState>()
+            .add_systems(OnEnter(MenuState::Attract), show_title)
+            .add_systems(
+                OnExit(MenuState::Attract),
+                cleanup_components::<TitleScreen>,
+            )
+            .add_systems(OnEnter(AppState::MainMenu), reset_flyby_timer)
+            .add_systems(
+                Update,
+                (
+                    (pulse_title, dismiss_title).run_if(in_state(MenuState::Attract)),
+                    (spawn_flyby, shoot_flyby, move_flyby).run_if(in_state(AppState::MainMenu)),
+                ),
+            );
+    }
+}
+
+#[derive(Resource)]
+struct TitleSeen;
+
+#[derive(Component)]
+struct TitleScreen;
+
+#[derive(Component)]
+struct TitleText;
+
+#[derive(Resource)]
+struct FlybyTimer(Timer);
+
+// Decorative only, neither the ship nor its bullets collide with anything
+#[derive(Component)]
+struct FlybyShip {
+    shoot_timer: Timer,
+}
+
+#[derive(Component)]
+struct FlybyBullet;
+
+#[derive(Component)]
+struct FlybyVelocity(Vec2);
+
+fn show_title(
+    mut commands: Commands,
+    title_seen: Option<Res<TitleSeen>>,
+    mut next_state: ResMut<NextState<MenuState>>,
+) {
+    if title_seen.is_some() {
+        next_state.set(MenuState::Menu);
+        return;
+    }
+    commands.spawn((
+        TitleScreen,
+        Node {
+            width: Val::Percent(100.),
+            height: Val::Percent(100.),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(80.),
+            ..default()
+        },
+        children![
+            (
+                TitleText,
+                Text::new("SHOOTING GAME"),
+                TextFont::from_font_size(TITLE_FONT_SIZE),
+                TextColor(Color::srgb(1., 0.8, 0.2)),
+            ),
+            (Text::new("Press any key"), Blink::new_with_speed(0.02)),
+        ],
+    ));
+}
+
+fn pulse_title(mut title_q: Query<&mut TextFont, With<TitleText>>, time: Res<Time>) {
+    let Ok(mut font) = title_q.single_mut() else {
+        warn!("Title text not found in pulse_title");
+        return;
+    };
+    font.font_size =
+        TITLE_FONT_SIZE + TITLE_PULSE * (time.elapsed_secs() * TITLE_PULSE_SPEED).sin();
+}
+
+fn dismiss_title(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    gamepad_q: Query<&Gamepad>,
+    mut next_state: ResMut<NextState<MenuState>>,
+) {
+    if any_input_just_pressed(&keys, &mouse, &touches, &gamepad_q) {
+        commands.insert_resource(TitleSeen);
+        next_state.set(MenuState::Menu);
+    }
+}
+
+fn reset_flyby_timer(mut commands: Commands) {
+    commands.insert_resource(FlybyTimer(Timer::from_seconds(
+        FLYBY_INTERVAL,
+        TimerMode::Repeating,
+    )));
+}
+
+// Crosses from one side to the other at a random height, facing where it flies
+fn spawn_flyby(
+    mut commands: Commands,
+    mut flyby_timer: ResMut<FlybyTimer>,
+    image_handles: Res<ImageHandles>,
+    mut game_rng: ResMut<GameRng>,
+    time: Res<Time>,
+) {
+    if !flyby_timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    let rng = game_rng.stream(RngStream::Cosmetic);
+    let direction = if rng.random_bool(0.5) { 1. } else { -1. };
+    let half_height = MOBILE_WINDOW_SIZE.y / 2.;
+    let start = Vec2::new(
+        -direction * (MOBILE_WINDOW_SIZE.x / 2. + SPACESHIP_SIZE.x),
+        rng.random_range(-half_height * 0.6..half_height * 0.6),
+    );
+    commands.spawn((
+        FlybyShip {
+            shoot_timer: Timer::from_seconds(FLYBY_SHOOT_INTERVAL, TimerMode::Repeating),
+        },
+        FlybyVelocity(Vec2::new(direction * FLYBY_SPEED, 0.)),
+        Sprite {
+            image: image_handles.spaceship.clone(),
+            custom_size: Some(SPACESHIP_SIZE * FLYBY_SCALE),
+            ..default()
+        },
+        Transform::from_translation(start.extend(ZIndex::SPACESHIP.z_value()))
+            .with_rotation(Quat::from_rotation_z(-direction * FRAC_PI_2)),
+        DespawnOnExit(AppState::MainMenu),
+    ));
+}
+
+fn shoot_flyby(
+    mut commands: Commands,
+    mut ship_q: Query<(&mut FlybyShip, &FlybyVelocity, &Transform)>,
+    time: Res<Time>,
+) {
+    for (mut ship, velocity, transform) in ship_q.iter_mut() {
+        if !ship.shoot_timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+        let heading = velocity.0.normalize_or_zero();
+        let muzzle =
+            transform.translation.truncate() + heading * SPACESHIP_SIZE.y * FLYBY_SCALE / 2.;
+        commands.spawn((
+            FlybyBullet,
+            FlybyVelocity(heading * FLYBY_BULLET_SPEED),
+            Sprite {
+                color: Color::WHITE,
+                custom_size: Some(BULLET_SIZE),
+                ..default()
+            },
+            Transform::from_translation(muzzle.extend(ZIndex::BULLET.z_value()))
+                .with_rotation(transform.rotation),
+            DespawnOnExit(AppState::MainMenu),
+        ));
+    }
+}
+
+fn move_flyby(
+    mut commands: Commands,
+    mut flyby_q: Query<(Entity, &FlybyVelocity, &mut Transform)>,
+    time: Res<Time>,
+) {
+    let limit = MOBILE_WINDOW_SIZE.x / 2. + SPACESHIP_SIZE.x * 2.;
+    for (entity, velocity, mut transform) in flyby_q.iter_mut() {
+        transform.translation += (velocity.0 * time.delta_secs()).extend(0.);
+        if transform.translation.x.abs() > limit {
+            commands.entity(entity).despawn();
+        }
+    }
+}
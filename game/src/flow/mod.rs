@@ -1,8 +1,18 @@
+mod capture;
+mod debug_overlay;
+mod demo;
+#[cfg(feature = "dev-console")]
+pub mod dev_console;
 mod game;
+mod juice;
+mod leaderboard;
 mod loading;
 mod main_menu;
 mod online_game;
+mod replay;
+mod settings;
 mod shared;
+mod statistics;
 
 use bevy::prelude::{App, Plugin};
 pub struct FlowPlugin;
@@ -13,8 +23,18 @@ impl Plugin for FlowPlugin {
             game::AppGamePlugin,
             loading::AppLoadingPlugin,
             main_menu::MainMenuPlugin,
+            leaderboard::LeaderboardPlugin,
+            settings::SettingsPlugin,
+            statistics::StatisticsPlugin,
             shared::SharedSystemPlugin,
             online_game::OnlineGamePlugin,
+            juice::JuicePlugin,
+            replay::ReplayPlugin,
+            debug_overlay::DebugOverlayPlugin,
+            demo::DemoPlugin,
+            capture::CapturePlugin,
         ));
+        #[cfg(feature = "dev-console")]
+        app.add_plugins(dev_console::DevConsolePlugin);
     }
 }
@@ -0,0 +1,188 @@
+use std::collections::VecDeque;
+
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use shooting_game_shared::{ClientMessage, ServerMessage, CHAT_MAX_LENGTH};
+
+use crate::constant::ZIndex;
+use crate::res::{PlayerNames, PlayerTag, Spectator};
+use crate::states::AppState;
+use crate::ui_components::FocusLock;
+use crate::util::cleanup_components;
+
+use super::connection::{ReceiveMessageEvent, SendMessageEvent};
+
+const CHAT_LOG_SIZE: usize = 6;
+
+pub struct ChatPlugin;
+
+impl Plugin for ChatPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::OnlineGame), setup_chat)
+            .add_systems(
+                Update,
+                (handle_chat_typing, update_chat_input_text)
+                    .chain()
+                    .run_if(in_state(AppState::OnlineGame)),
+            )
+            .add_observer(receive_chat)
+            .add_systems(
+                OnExit(AppState::OnlineGame),
+                (cleanup_components::<ChatOverlay>, remove_chat),
+            );
+    }
+}
+
+// Only exists while the player is typing, keyboard controls and focus are locked meanwhile
+#[derive(Resource, Default)]
+pub struct ChatDraft(String);
+
+#[derive(Resource, Default)]
+struct ChatLog(VecDeque<String>);
+
+#[derive(Component)]
+struct ChatOverlay;
+
+#[derive(Component)]
+struct ChatLogText;
+
+#[derive(Component)]
+struct ChatInputText;
+
+fn setup_chat(mut commands: Commands) {
+    commands.init_resource::<ChatLog>();
+    commands
+        .spawn((
+            ChatOverlay,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(5.),
+                bottom: Val::Px(5.),
+                max_width: Val::Percent(60.),
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            ZIndex::TEXT.component(),
+        ))
+        .with_children(|overlay| {
+            overlay.spawn((ChatLogText, Text::default(), TextFont::from_font_size(16.)));
+            overlay.spawn((
+                ChatInputText,
+                Text::default(),
+                TextFont::from_font_size(16.),
+                TextColor(Color::srgba(1., 1., 0., 1.)),
+            ));
+        });
+}
+
+fn remove_chat(mut commands: Commands) {
+    close_draft(&mut commands);
+    commands.remove_resource::<ChatLog>();
+}
+
+// Enter opens the draft and sends it, Escape throws it away
+fn handle_chat_typing(
+    mut commands: Commands,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut chat_draft: Option<ResMut<ChatDraft>>,
+    spectator: Option<Res<Spectator>>,
+) {
+    for keyboard_event in keyboard_events.read() {
+        if keyboard_event.state != ButtonState::Pressed || spectator.is_some() {
+            continue;
+        }
+        let Some(draft) = chat_draft.as_mut() else {
+            if keyboard_event.logical_key == Key::Enter {
+                commands.init_resource::<ChatDraft>();
+                commands.insert_resource(FocusLock);
+            }
+            // The rest of this frame's keys still belong to the game
+            return;
+        };
+        match &keyboard_event.logical_key {
+            Key::Enter => {
+                if !draft.0.is_empty() {
+                    commands.trigger(SendMessageEvent(ClientMessage::Chat {
+                        text: draft.0.clone(),
+                    }));
+                }
+                close_draft(&mut commands);
+                return;
+            }
+            Key::Escape => {
+                close_draft(&mut commands);
+                return;
+            }
+            Key::Backspace => {
+                draft.0.pop();
+            }
+            _ => {
+                let Some(text) = &keyboard_event.text else {
+                    continue;
+                };
+                let room = CHAT_MAX_LENGTH.saturating_sub(draft.0.chars().count());
+                draft
+                    .0
+                    .extend(text.chars().filter(|c| !c.is_control()).take(room));
+            }
+        }
+    }
+}
+
+// Focus sits out the frame, so the Enter or Escape that closed the draft presses nothing
+fn close_draft(commands: &mut Commands) {
+    commands.remove_resource::<ChatDraft>();
+    commands.remove_resource::<FocusLock>();
+}
+
+fn update_chat_input_text(
+    chat_draft: Option<Res<ChatDraft>>,
+    mut chat_input_text_q: Query<&mut Text, With<ChatInputText>>,
+) {
+    let Ok(mut text) = chat_input_text_q.single_mut() else {
+        return;
+    };
+    let input_text = match chat_draft {
+        Some(draft) => format!("> {}_", draft.0),
+        None => String::new(),
+    };
+    if text.0 != input_text {
+        text.0 = input_text;
+    }
+}
+
+fn receive_chat(
+    ev: Trigger<ReceiveMessageEvent>,
+    chat_log: Option<ResMut<ChatLog>>,
+    player_tag: Res<PlayerTag>,
+    player_names: Res<PlayerNames>,
+    mut chat_log_text_q: Query<&mut Text, With<ChatLogText>>,
+) {
+    let ServerMessage::Chat {
+        player_tag: sender_tag,
+        ref text,
+    } = ev.0
+    else {
+        return;
+    };
+    let Some(mut chat_log) = chat_log else {
+        warn!("Chat log not found in receive_chat");
+        return;
+    };
+    let sender = if sender_tag == player_tag.0 {
+        "You".to_string()
+    } else {
+        player_names.name(sender_tag)
+    };
+    chat_log.0.push_back(format!("{sender}: {text}"));
+    if chat_log.0.len() > CHAT_LOG_SIZE {
+        chat_log.0.pop_front();
+    }
+    let Ok(mut log_text) = chat_log_text_q.single_mut() else {
+        warn!("Chat log text not found in receive_chat");
+        return;
+    };
+    log_text.0 = Vec::from(chat_log.0.clone()).join("\n");
+}
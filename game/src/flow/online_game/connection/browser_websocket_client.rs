@@ -0,0 +1,203 @@
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    future::poll_fn,
+    rc::Rc,
+    task::{Poll, Waker},
+};
+
+use bevy::prelude::Component;
+use js_sys::{ArrayBuffer, Uint8Array};
+use shooting_game_shared::{
+    server_version_from_reason, ClientMessage, Encoding, Payload, ServerMessage,
+    INCOMPATIBLE_VERSION_CLOSE_CODE, PROTOCOL_VERSION,
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{BinaryType, CloseEvent, Event, MessageEvent, WebSocket};
+
+enum Incoming {
+    Text(String),
+    Binary(Vec<u8>),
+    Close { code: u16, reason: String },
+}
+
+// Filled by the socket callbacks, drained by read
+#[derive(Default)]
+struct Inbox {
+    messages: VecDeque<Incoming>,
+    opened: bool,
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+impl Inbox {
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+// Kept alive for as long as the socket may call them
+struct Callbacks {
+    _on_open: Closure<dyn FnMut(Event)>,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+    _on_close: Closure<dyn FnMut(CloseEvent)>,
+}
+
+#[derive(Component)]
+pub struct WebSocketClient {
+    websocket: WebSocket,
+    inbox: Rc<RefCell<Inbox>>,
+    _callbacks: Callbacks,
+    closed: bool,
+    // Follows whatever the server answers the handshake with
+    encoding: Encoding,
+}
+
+// The browser build runs on a single thread, the client never leaves it
+unsafe impl Send for WebSocketClient {}
+unsafe impl Sync for WebSocketClient {}
+
+impl WebSocketClient {
+    // Resolves once the browser has opened the socket or given up on it
+    pub async fn connect(url: String) -> Result<Self, String> {
+        let websocket =
+            WebSocket::new(&url).map_err(|_| "Failed to connect to server".to_string())?;
+        websocket.set_binary_type(BinaryType::Arraybuffer);
+        let inbox = Rc::new(RefCell::new(Inbox::default()));
+        let callbacks = Callbacks {
+            _on_open: on_open(&websocket, &inbox),
+            _on_message: on_message(&websocket, &inbox),
+            _on_close: on_close(&websocket, &inbox),
+        };
+        let opened = poll_fn(|cx| {
+            let mut inbox = inbox.borrow_mut();
+            if inbox.opened {
+                Poll::Ready(true)
+            } else if inbox.closed {
+                Poll::Ready(false)
+            } else {
+                inbox.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        })
+        .await;
+        if !opened {
+            return Err("Failed to connect to server".to_string());
+        }
+        Ok(Self {
+            websocket,
+            inbox,
+            _callbacks: callbacks,
+            closed: false,
+            encoding: Encoding::Json,
+        })
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    pub fn read(&mut self) -> Result<Option<ServerMessage>, String> {
+        let next = self.inbox.borrow_mut().messages.pop_front();
+        match next {
+            Some(Incoming::Text(text)) => serde_json::from_str(&text)
+                .map(Some)
+                .map_err(|e| format!("Invalid text message: {e}")),
+            Some(Incoming::Binary(bytes)) => {
+                self.encoding = Encoding::Binary;
+                ServerMessage::from_binary(&bytes)
+                    .map(Some)
+                    .ok_or("Invalid binary message".to_string())
+            }
+            // The server turns other protocol versions away with a close frame
+            Some(Incoming::Close { code, reason }) if code == INCOMPATIBLE_VERSION_CLOSE_CODE => {
+                Ok(Some(ServerMessage::IncompatibleVersion {
+                    server: server_version_from_reason(&reason).unwrap_or_default(),
+                    client: PROTOCOL_VERSION,
+                }))
+            }
+            Some(Incoming::Close { .. }) => {
+                self.cleanup();
+                Err("Connection closed".to_string())
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn send(&mut self, message: ClientMessage) -> Result<(), String> {
+        // Like a would-block on native, the message is dropped while the socket isn't open
+        if self.websocket.ready_state() != WebSocket::OPEN {
+            return Ok(());
+        }
+        let result = match message.encode(self.encoding) {
+            Payload::Text(text) => self.websocket.send_with_str(&text),
+            Payload::Binary(bytes) => self.websocket.send_with_u8_array(&bytes),
+        };
+        result.map_err(|e| format!("{e:?}"))
+    }
+
+    pub fn cleanup(&mut self) {
+        // Closing an already closed socket is a no-op in the browser
+        let _ = self.websocket.close();
+        self.closed = true;
+    }
+}
+
+impl Drop for WebSocketClient {
+    fn drop(&mut self) {
+        // The callbacks are freed with the client, the socket must not call them after
+        self.websocket.set_onopen(None);
+        self.websocket.set_onmessage(None);
+        self.websocket.set_onclose(None);
+        let _ = self.websocket.close();
+    }
+}
+
+fn on_open(websocket: &WebSocket, inbox: &Rc<RefCell<Inbox>>) -> Closure<dyn FnMut(Event)> {
+    let inbox = inbox.clone();
+    let callback = Closure::<dyn FnMut(Event)>::new(move |_: Event| {
+        let mut inbox = inbox.borrow_mut();
+        inbox.opened = true;
+        inbox.wake();
+    });
+    websocket.set_onopen(Some(callback.as_ref().unchecked_ref()));
+    callback
+}
+
+fn on_message(
+    websocket: &WebSocket,
+    inbox: &Rc<RefCell<Inbox>>,
+) -> Closure<dyn FnMut(MessageEvent)> {
+    let inbox = inbox.clone();
+    let callback = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+        let data = event.data();
+        let message = if let Some(text) = data.as_string() {
+            Incoming::Text(text)
+        } else if let Ok(buffer) = data.dyn_into::<ArrayBuffer>() {
+            Incoming::Binary(Uint8Array::new(&buffer).to_vec())
+        } else {
+            return;
+        };
+        inbox.borrow_mut().messages.push_back(message);
+    });
+    websocket.set_onmessage(Some(callback.as_ref().unchecked_ref()));
+    callback
+}
+
+// An error is always followed by a close, so that is the only one listened to
+fn on_close(websocket: &WebSocket, inbox: &Rc<RefCell<Inbox>>) -> Closure<dyn FnMut(CloseEvent)> {
+    let inbox = inbox.clone();
+    let callback = Closure::<dyn FnMut(CloseEvent)>::new(move |event: CloseEvent| {
+        let mut inbox = inbox.borrow_mut();
+        inbox.closed = true;
+        inbox.messages.push_back(Incoming::Close {
+            code: event.code(),
+            reason: event.reason(),
+        });
+        inbox.wake();
+    });
+    websocket.set_onclose(Some(callback.as_ref().unchecked_ref()));
+    callback
+}
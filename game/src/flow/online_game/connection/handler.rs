@@ -1,14 +1,44 @@
+use std::time::Duration;
+
 use bevy::{
     ecs::world::CommandQueue,
     prelude::*,
     tasks::{block_on, futures_lite::future::poll_once, AsyncComputeTaskPool, Task},
 };
 
-use tungstenite::{connect, stream::MaybeTlsStream};
+use shooting_game_shared::{RoomClosedReason, ServerMessage, PROTOCOL_VERSION};
 
+use crate::res::{Nickname, SessionToken};
 use crate::states::{AppState, OnlineGameState};
+use crate::ui_components::Blink;
+use crate::util::cleanup_components;
 
 use super::websocket_client::WebSocketClient;
+use super::ReceiveMessageEvent;
+
+const SERVER_URL: &str = "ws://127.0.0.1:8000/ws/game";
+const RECONNECT_ATTEMPTS: u8 = 10;
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Event)]
+pub struct ConnectionLostEvent;
+
+// Present from a mid-game drop until the server sends back ResumeState
+#[derive(Resource)]
+pub struct Reconnecting {
+    attempts_left: u8,
+}
+
+// The server closed the room on purpose, so losing the connection is not worth a reconnect
+#[derive(Resource)]
+pub struct RoomClosed(pub RoomClosedReason);
+
+// The server speaks a protocol this build can't, reconnecting would only be refused again
+#[derive(Resource)]
+pub struct IncompatibleServer {
+    pub server: u8,
+    pub client: u8,
+}
 
 pub struct HandlerPlugin;
 
@@ -17,34 +47,69 @@ impl Plugin for HandlerPlugin {
         app.add_systems(OnEnter(AppState::OnlineGame), setup_connection)
             .add_systems(
                 Update,
-                handle_setup_task.run_if(in_state(OnlineGameState::Matching)),
+                (start_pending_connection, handle_setup_task).run_if(
+                    in_state(OnlineGameState::Matching).or(in_state(OnlineGameState::InPlay)),
+                ),
             )
+            .add_systems(Update, cleanup_reconnecting_notice)
             .add_systems(OnEnter(OnlineGameState::Error), teardown_connection)
-            .add_systems(OnEnter(OnlineGameState::Result), teardown_connection);
+            // The result screen keeps the connection open for a rematch
+            .add_systems(
+                OnExit(AppState::OnlineGame),
+                (
+                    teardown_connection,
+                    remove_room_closed,
+                    remove_incompatible_server,
+                ),
+            )
+            .add_observer(handle_connection_lost)
+            .add_observer(listen_room_closed)
+            .add_observer(listen_incompatible_version);
     }
 }
 
 #[derive(Component)]
 struct WebSocketConnectionSetupTask(Task<Result<CommandQueue, String>>);
 
-fn setup_connection(mut commands: Commands) {
-    let url = "ws://127.0.0.1:8000/ws/game";
+// Waits out the reconnect delay here, so the task only has to connect
+#[derive(Component)]
+struct PendingConnection {
+    url: String,
+    delay: Timer,
+}
+
+impl PendingConnection {
+    fn new(url: String) -> Self {
+        Self {
+            url,
+            delay: Timer::new(RECONNECT_DELAY, TimerMode::Once),
+        }
+    }
+}
+
+#[derive(Component)]
+struct ReconnectingNotice;
+
+fn setup_connection(commands: Commands, nickname: Res<Nickname>) {
+    spawn_connection_task(commands, join_url(&nickname));
+}
+
+// The protocol version lets the server pick binary encoding for this client
+fn server_url() -> String {
+    format!("{SERVER_URL}?protocol={PROTOCOL_VERSION}")
+}
+
+fn spawn_connection_task(mut commands: Commands, url: String) {
     let entity = commands.spawn_empty().id();
     let pool = AsyncComputeTaskPool::get();
 
     let task = pool.spawn(async move {
-        let Ok(mut client) = connect(url) else {
-            return Err("Failed to connect to server".to_string());
-        };
-        match client.0.get_mut() {
-            MaybeTlsStream::Plain(p) => p.set_nonblocking(true).unwrap(),
-            _ => return Err("Unsupported stream type".to_string()),
-        };
+        let client = WebSocketClient::connect(url).await?;
         let mut command_queue = CommandQueue::default();
         command_queue.push(move |world: &mut World| {
             world
                 .entity_mut(entity)
-                .insert(WebSocketClient::new(client.0))
+                .insert(client)
                 .remove::<WebSocketConnectionSetupTask>();
         });
 
@@ -56,12 +121,41 @@ fn setup_connection(mut commands: Commands) {
         .insert(WebSocketConnectionSetupTask(task));
 }
 
+// The server keeps the nickname for the session, so only a fresh join sends it
+fn join_url(nickname: &Nickname) -> String {
+    match nickname.get() {
+        "" => server_url(),
+        name => format!("{}&name={name}", server_url()),
+    }
+}
+
+fn resume_url(session_token: &SessionToken) -> String {
+    format!("{}&session={}", server_url(), session_token.0)
+}
+
+fn start_pending_connection(
+    mut commands: Commands,
+    mut pending_q: Query<(Entity, &mut PendingConnection)>,
+    time: Res<Time>,
+) {
+    for (entity, mut pending) in pending_q.iter_mut() {
+        if !pending.delay.tick(time.delta()).finished() {
+            continue;
+        }
+        commands.entity(entity).despawn();
+        spawn_connection_task(commands.reborrow(), std::mem::take(&mut pending.url));
+    }
+}
+
 fn handle_setup_task(
     mut commands: Commands,
-    mut setup_task_q: Query<&mut WebSocketConnectionSetupTask>,
-    mut next_state: ResMut<NextState<AppState>>,
+    mut setup_task_q: Query<(Entity, &mut WebSocketConnectionSetupTask)>,
+    session_token: Option<Res<SessionToken>>,
+    mut reconnecting: Option<ResMut<Reconnecting>>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut next_online_state: ResMut<NextState<OnlineGameState>>,
 ) {
-    for mut task in setup_task_q.iter_mut() {
+    for (entity, mut task) in setup_task_q.iter_mut() {
         if let Some(result) = block_on(poll_once(&mut task.0)) {
             match result {
                 Ok(mut commands_queue) => {
@@ -70,18 +164,102 @@ fn handle_setup_task(
                 Err(e) => {
                     // TODO: Should add warning to the client
                     warn!("Connection failed with: {e:?}");
-                    next_state.set(AppState::MainMenu);
+                    commands.entity(entity).despawn();
+                    match (reconnecting.as_mut(), session_token.as_ref()) {
+                        (Some(reconnecting), Some(session_token))
+                            if reconnecting.attempts_left > 0 =>
+                        {
+                            reconnecting.attempts_left -= 1;
+                            commands.spawn(PendingConnection::new(resume_url(session_token)));
+                        }
+                        (Some(_), _) => next_online_state.set(OnlineGameState::Error),
+                        (None, _) => next_app_state.set(AppState::MainMenu),
+                    }
                 }
             }
         }
     }
 }
 
+fn handle_connection_lost(
+    _trigger: Trigger<ConnectionLostEvent>,
+    mut commands: Commands,
+    current_state: Res<State<OnlineGameState>>,
+    session_token: Option<Res<SessionToken>>,
+    room_closed: Option<Res<RoomClosed>>,
+    incompatible_server: Option<Res<IncompatibleServer>>,
+    mut next_state: ResMut<NextState<OnlineGameState>>,
+) {
+    let session_token =
+        session_token.filter(|_| room_closed.is_none() && incompatible_server.is_none());
+    match (current_state.get(), session_token) {
+        (OnlineGameState::InPlay, Some(session_token)) => {
+            commands.insert_resource(Reconnecting {
+                attempts_left: RECONNECT_ATTEMPTS,
+            });
+            commands.spawn((
+                ReconnectingNotice,
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    top: Val::Percent(40.),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(
+                    Text::new("Reconnecting"),
+                    TextLayout::new_with_justify(JustifyText::Center),
+                    Blink::new_with_speed(0.01),
+                )],
+            ));
+            commands.spawn(PendingConnection::new(resume_url(&session_token)));
+        }
+        (OnlineGameState::Error | OnlineGameState::Result, _) => {}
+        _ => next_state.set(OnlineGameState::Error),
+    }
+}
+
+fn cleanup_reconnecting_notice(
+    commands: Commands,
+    reconnecting: Option<Res<Reconnecting>>,
+    notice_q: Query<Entity, With<ReconnectingNotice>>,
+) {
+    if reconnecting.is_none() && !notice_q.is_empty() {
+        cleanup_components::<ReconnectingNotice>(commands, notice_q);
+    }
+}
+
 fn teardown_connection(
     mut commands: Commands,
     web_socket_clients: Query<Entity, With<WebSocketClient>>,
+    pending_q: Query<Entity, With<PendingConnection>>,
 ) {
     for entity in &web_socket_clients {
         commands.entity(entity).remove::<WebSocketClient>();
     }
+    for entity in &pending_q {
+        commands.entity(entity).despawn();
+    }
+    commands.remove_resource::<Reconnecting>();
+}
+
+fn listen_room_closed(trigger: Trigger<ReceiveMessageEvent>, mut commands: Commands) {
+    if let ServerMessage::RoomClosed { reason } = trigger.event().0 {
+        commands.insert_resource(RoomClosed(reason));
+    }
+}
+
+fn remove_room_closed(mut commands: Commands) {
+    commands.remove_resource::<RoomClosed>();
+}
+
+fn listen_incompatible_version(trigger: Trigger<ReceiveMessageEvent>, mut commands: Commands) {
+    if let ServerMessage::IncompatibleVersion { server, client } = trigger.event().0 {
+        warn!("Server speaks protocol {server}, this client speaks {client}");
+        commands.insert_resource(IncompatibleServer { server, client });
+    }
+}
+
+fn remove_incompatible_server(mut commands: Commands) {
+    commands.remove_resource::<IncompatibleServer>();
 }
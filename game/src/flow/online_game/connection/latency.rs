@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use shooting_game_shared::{ClientMessage, ServerMessage};
+
+use crate::res::Spectator;
+use crate::states::AppState;
+
+use super::{ReceiveMessageEvent, SendMessageEvent};
+
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+// Share of each new sample blended into the jitter, as in RTP
+const JITTER_SMOOTHING: f32 = 1. / 16.;
+const STALE_AFTER: Duration = Duration::from_secs(1);
+
+// Measured from Ping and Pong round trips, lives as long as the online game
+#[derive(Resource, Default)]
+pub struct ConnectionQuality {
+    rtt: Option<Duration>,
+    jitter: f32,
+    last_received: Duration,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum QualityLevel {
+    Good,
+    Fair,
+    Poor,
+}
+
+impl ConnectionQuality {
+    pub fn rtt_ms(&self) -> Option<u32> {
+        self.rtt.map(|rtt| rtt.as_millis() as u32)
+    }
+
+    pub fn jitter_ms(&self) -> u32 {
+        self.jitter as u32
+    }
+
+    // Jitter counts against the round trip since it shows up as stutter all the same
+    pub fn level(&self) -> QualityLevel {
+        let Some(rtt_ms) = self.rtt_ms() else {
+            return QualityLevel::Fair;
+        };
+        match rtt_ms + self.jitter_ms() {
+            0..100 => QualityLevel::Good,
+            100..250 => QualityLevel::Fair,
+            _ => QualityLevel::Poor,
+        }
+    }
+
+    // Nothing at all has arrived for a while, not even a Pong
+    pub fn is_stale(&self, now: Duration) -> bool {
+        now.saturating_sub(self.last_received) > STALE_AFTER
+    }
+
+    fn record_rtt(&mut self, rtt: Duration) {
+        if let Some(previous) = self.rtt {
+            let delta = (rtt.as_secs_f32() - previous.as_secs_f32()).abs() * 1000.;
+            self.jitter += (delta - self.jitter) * JITTER_SMOOTHING;
+        }
+        self.rtt = Some(rtt);
+    }
+}
+
+#[derive(Resource)]
+struct PingTimer(Timer);
+
+pub struct LatencyPlugin;
+
+impl Plugin for LatencyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::OnlineGame), setup_connection_quality)
+            .add_systems(
+                Update,
+                send_ping
+                    .run_if(in_state(AppState::OnlineGame))
+                    .run_if(not(resource_exists::<Spectator>)),
+            )
+            .add_systems(OnExit(AppState::OnlineGame), remove_connection_quality)
+            .add_observer(track_received);
+    }
+}
+
+fn setup_connection_quality(mut commands: Commands, time: Res<Time<Real>>) {
+    commands.insert_resource(ConnectionQuality {
+        last_received: time.elapsed(),
+        ..default()
+    });
+    commands.insert_resource(PingTimer(Timer::new(PING_INTERVAL, TimerMode::Repeating)));
+}
+
+fn remove_connection_quality(mut commands: Commands) {
+    commands.remove_resource::<ConnectionQuality>();
+    commands.remove_resource::<PingTimer>();
+}
+
+// Real time so pausing or slowing the game can't skew the measurement
+fn send_ping(mut commands: Commands, mut ping_timer: ResMut<PingTimer>, time: Res<Time<Real>>) {
+    ping_timer.0.tick(time.delta());
+    if ping_timer.0.just_finished() {
+        commands.trigger(SendMessageEvent(ClientMessage::Ping {
+            sent_at: time.elapsed().as_millis() as u64,
+        }));
+    }
+}
+
+fn track_received(
+    trigger: Trigger<ReceiveMessageEvent>,
+    connection_quality: Option<ResMut<ConnectionQuality>>,
+    time: Res<Time<Real>>,
+) {
+    let Some(mut connection_quality) = connection_quality else {
+        return;
+    };
+    let now = time.elapsed();
+    connection_quality.last_received = now;
+    if let ServerMessage::Pong { sent_at } = trigger.event().0 {
+        connection_quality.record_rtt(now.saturating_sub(Duration::from_millis(sent_at)));
+    }
+}
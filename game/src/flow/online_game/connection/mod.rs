@@ -1,8 +1,15 @@
 mod handler;
+mod latency;
 mod receive_message;
 mod send_message;
+#[cfg(not(target_arch = "wasm32"))]
+mod websocket_client;
+#[cfg(target_arch = "wasm32")]
+#[path = "browser_websocket_client.rs"]
 mod websocket_client;
 
+pub use handler::{IncompatibleServer, Reconnecting, RoomClosed};
+pub use latency::{ConnectionQuality, QualityLevel};
 pub use receive_message::ReceiveMessageEvent;
 pub use send_message::SendMessageEvent;
 
@@ -14,6 +21,7 @@ impl Plugin for ConnectionPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
             handler::HandlerPlugin,
+            latency::LatencyPlugin,
             receive_message::ReceiveMessagePlugin,
             send_message::SendMessagePlugin,
         ));
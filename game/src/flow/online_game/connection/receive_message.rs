@@ -3,7 +3,7 @@ use shooting_game_shared::ServerMessage;
 
 use crate::states::AppState;
 
-use super::websocket_client::WebSocketClient;
+use super::{handler::ConnectionLostEvent, websocket_client::WebSocketClient};
 
 #[derive(Event)]
 pub struct ReceiveMessageEvent(pub ServerMessage);
@@ -19,12 +19,21 @@ impl Plugin for ReceiveMessagePlugin {
     }
 }
 
-fn receive_message(mut commands: Commands, mut web_socket_clients: Query<&mut WebSocketClient>) {
-    for mut client in web_socket_clients.iter_mut() {
+fn receive_message(
+    mut commands: Commands,
+    mut web_socket_clients: Query<(Entity, &mut WebSocketClient)>,
+) {
+    for (entity, mut client) in web_socket_clients.iter_mut() {
         match client.read() {
             Ok(Some(message)) => commands.trigger(ReceiveMessageEvent(message)),
             Ok(None) => {}
-            Err(e) => warn!("error receiving: {e}"),
+            Err(e) => {
+                warn!("error receiving: {e}");
+                if client.is_closed() {
+                    commands.entity(entity).despawn();
+                    commands.trigger(ConnectionLostEvent);
+                }
+            }
         }
     }
 }
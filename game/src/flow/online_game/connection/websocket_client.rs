@@ -1,44 +1,86 @@
 use std::{io, net::TcpStream};
 
 use bevy::prelude::Component;
-use shooting_game_shared::{ClientMessage, ServerMessage};
-use tungstenite::{stream::MaybeTlsStream, Error, Message, WebSocket};
+use shooting_game_shared::{
+    server_version_from_reason, ClientMessage, Encoding, Payload, ServerMessage,
+    INCOMPATIBLE_VERSION_CLOSE_CODE, PROTOCOL_VERSION,
+};
+use tungstenite::{connect, stream::MaybeTlsStream, Error, Message, WebSocket};
 
 #[derive(Component)]
-pub struct WebSocketClient(WebSocket<MaybeTlsStream<TcpStream>>);
+pub struct WebSocketClient {
+    websocket: WebSocket<MaybeTlsStream<TcpStream>>,
+    closed: bool,
+    // Follows whatever the server answers the handshake with
+    encoding: Encoding,
+}
 
 impl WebSocketClient {
-    pub fn new(websocket: WebSocket<MaybeTlsStream<TcpStream>>) -> Self {
-        Self(websocket)
+    // Blocks until the handshake is done, so it only runs inside a task
+    pub async fn connect(url: String) -> Result<Self, String> {
+        let Ok((mut websocket, _)) = connect(url) else {
+            return Err("Failed to connect to server".to_string());
+        };
+        match websocket.get_mut() {
+            MaybeTlsStream::Plain(p) => p.set_nonblocking(true).unwrap(),
+            _ => return Err("Unsupported stream type".to_string()),
+        };
+        Ok(Self {
+            websocket,
+            closed: false,
+            encoding: Encoding::Json,
+        })
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
     }
 
     pub fn read(&mut self) -> Result<Option<ServerMessage>, String> {
-        match self.0.read() {
+        match self.websocket.read() {
             Ok(message) => match message {
-                Message::Text(text) => {
-                    let message: ServerMessage = serde_json::from_str(&text).unwrap();
-                    Ok(Some(message))
+                Message::Text(text) => serde_json::from_str(&text)
+                    .map(Some)
+                    .map_err(|e| format!("Invalid text message: {e}")),
+                Message::Binary(bytes) => {
+                    self.encoding = Encoding::Binary;
+                    ServerMessage::from_binary(&bytes)
+                        .map(Some)
+                        .ok_or("Invalid binary message".to_string())
+                }
+                // The server turns other protocol versions away with a close frame
+                Message::Close(Some(frame))
+                    if u16::from(frame.code) == INCOMPATIBLE_VERSION_CLOSE_CODE =>
+                {
+                    Ok(Some(ServerMessage::IncompatibleVersion {
+                        server: server_version_from_reason(&frame.reason).unwrap_or_default(),
+                        client: PROTOCOL_VERSION,
+                    }))
                 }
                 _ => Err("Invalid message type".to_string()),
             },
             Err(Error::Io(e)) => {
                 if e.kind() == io::ErrorKind::WouldBlock {
-                    return Ok(None);
+                    Ok(None)
                 } else {
                     self.cleanup();
-                    return Err("Fatal error: Connection closed".to_string());
+                    Err("Fatal error: Connection closed".to_string())
                 }
             }
             Err(Error::ConnectionClosed) => {
                 self.cleanup();
-                return Err("Connection closed".to_string());
+                Err("Connection closed".to_string())
             }
             Err(e) => Err(e.to_string()),
         }
     }
 
     pub fn send(&mut self, message: ClientMessage) -> Result<(), String> {
-        match self.0.send(message.text()) {
+        let message = match message.encode(self.encoding) {
+            Payload::Text(text) => Message::text(text),
+            Payload::Binary(bytes) => Message::binary(bytes),
+        };
+        match self.websocket.send(message) {
             Ok(_) => Ok(()),
             Err(Error::Io(_)) => Ok(()),
             Err(e) => Err(e.to_string()),
@@ -46,6 +88,8 @@ impl WebSocketClient {
     }
 
     pub fn cleanup(&mut self) {
-        self.0.close(None).unwrap();
+        // Closing an already dropped connection errors, nothing else to do then
+        let _ = self.websocket.close(None);
+        self.closed = true;
     }
 }
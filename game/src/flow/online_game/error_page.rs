@@ -1,13 +1,13 @@
 use bevy::prelude::*;
-use shooting_game_shared::ServerMessage;
+use shooting_game_shared::{RoomClosedReason, ServerMessage};
 
 use crate::{
     states::{AppState, OnlineGameState},
-    ui_components::{Blink, InteractionUI, MainContainer},
+    ui_components::{Blink, FocusBack, InteractionUI, MainContainer},
     util::cleanup_components,
 };
 
-use super::connection::ReceiveMessageEvent;
+use super::connection::{IncompatibleServer, ReceiveMessageEvent, RoomClosed};
 pub struct ErrorPagePlugin;
 
 impl Plugin for ErrorPagePlugin {
@@ -31,11 +31,22 @@ struct ErrorPage;
 #[derive(Component)]
 struct ReturnButton;
 
-fn show_error_page(mut commands: Commands) {
+fn show_error_page(
+    mut commands: Commands,
+    room_closed: Option<Res<RoomClosed>>,
+    incompatible_server: Option<Res<IncompatibleServer>>,
+) {
+    let error_text = match (room_closed, incompatible_server) {
+        (_, Some(incompatible)) => {
+            incompatible_version_text(incompatible.server, incompatible.client)
+        }
+        (Some(room_closed), _) => room_closed_text(room_closed.0).to_string(),
+        _ => "Error Occured".to_string(),
+    };
     commands
         .spawn((ErrorPage, MainContainer))
         .with_children(|error_page_background| {
-            error_page_background.spawn(Text::new("Error Occured"));
+            error_page_background.spawn(Text::new(error_text));
             error_page_background
                 .spawn(Node {
                     height: Val::Percent(100.),
@@ -54,6 +65,7 @@ fn show_error_page(mut commands: Commands) {
                     return_container
                         .spawn((
                             ReturnButton,
+                            FocusBack,
                             InteractionUI,
                             Node {
                                 align_self: AlignSelf::FlexEnd,
@@ -86,6 +98,7 @@ fn handle_return_button_interaction(
     };
 }
 
+// The result screen shows these itself
 fn listen_to_game_interrupted(
     trigger: Trigger<ReceiveMessageEvent>,
     current_state: Res<State<OnlineGameState>>,
@@ -97,7 +110,28 @@ fn listen_to_game_interrupted(
     ) {
         return;
     }
-    if matches!(trigger.event().0, ServerMessage::GameInterrupted) {
+    if matches!(
+        trigger.event().0,
+        ServerMessage::GameInterrupted
+            | ServerMessage::RoomClosed { .. }
+            | ServerMessage::IncompatibleVersion { .. }
+    ) {
         next_state.set(OnlineGameState::Error);
     }
 }
+
+pub(super) fn room_closed_text(reason: RoomClosedReason) -> &'static str {
+    match reason {
+        RoomClosedReason::Idle => "Room closed for being idle too long",
+        RoomClosedReason::ServerShutdown => "Server is shutting down",
+    }
+}
+
+fn incompatible_version_text(server: u8, client: u8) -> String {
+    let advice = if server > client {
+        "Please update the game"
+    } else {
+        "The server has not been updated yet"
+    };
+    format!("Incompatible version\nServer: {server}, Game: {client}\n{advice}")
+}
@@ -1,10 +1,17 @@
 use crate::{
-    components::{BulletTag, CollidedEvent, EnemyTag, SelfPlayer, Spaceship, UFO},
+    components::{
+        BulletTag, CollidedEvent, EnemyTag, Pierced, PowerUpCollidedEvent, PowerUpTag, SelfPlayer,
+        Spaceship, UFO,
+    },
     flow::online_game::connection::SendMessageEvent,
+    res::Spectator,
     states::OnlineGameState,
 };
 use bevy::prelude::*;
 use shooting_game_shared::ClientMessage;
+use std::time::Duration;
+
+use super::from_server::SnapshotTick;
 
 pub struct CollisionPlugin;
 
@@ -12,7 +19,13 @@ impl Plugin for CollisionPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            handle_collisions.run_if(in_state(OnlineGameState::InPlay)),
+            (
+                handle_collisions,
+                handle_power_up_collisions,
+                expire_power_up_claims,
+            )
+                .run_if(in_state(OnlineGameState::InPlay))
+                .run_if(not(resource_exists::<Spectator>)),
         );
     }
 }
@@ -23,6 +36,8 @@ pub fn handle_collisions(
     enemy_tag_q: Query<&EnemyTag, With<UFO>>,
     spaceship_q: Query<Entity, (With<Spaceship>, With<SelfPlayer>)>,
     bullet_q: Query<&BulletTag>,
+    mut pierced_q: Query<&mut Pierced>,
+    snapshot_tick: Res<SnapshotTick>,
 ) {
     for collision in collision_events.read() {
         let Ok(enemy_tag) = enemy_tag_q.get(collision.enemy) else {
@@ -30,9 +45,10 @@ pub fn handle_collisions(
         };
         let player_entity = collision.player;
 
-        if let Ok(_) = spaceship_q.get(player_entity) {
+        if spaceship_q.get(player_entity).is_ok() {
             commands.trigger(SendMessageEvent(ClientMessage::DamagedIntent {
                 enemy_tag: enemy_tag.0,
+                tick: snapshot_tick.0,
             }));
         }
 
@@ -40,7 +56,53 @@ pub fn handle_collisions(
             commands.trigger(SendMessageEvent(ClientMessage::DestroyEnemyIntent {
                 bullet_tag: bullet_tag.0,
                 enemy_tag: enemy_tag.0,
+                tick: snapshot_tick.0,
             }));
+            // Asked once per enemy, the bullet keeps going whatever the server says
+            if let Ok(mut pierced) = pierced_q.get_mut(player_entity) {
+                pierced.add(collision.enemy);
+            }
+        }
+    }
+}
+
+// An unanswered claim was lost or turned down, the power-up can be asked for again after this
+const CLAIM_TIMEOUT: Duration = Duration::from_secs(1);
+
+// Marks a power-up this client already asked for, the overlap is reported every frame
+#[derive(Component)]
+struct PowerUpClaimed(Timer);
+
+fn handle_power_up_collisions(
+    mut commands: Commands,
+    mut collision_events: EventReader<PowerUpCollidedEvent>,
+    power_up_q: Query<&PowerUpTag, Without<PowerUpClaimed>>,
+    spaceship_q: Query<(), (With<Spaceship>, With<SelfPlayer>)>,
+) {
+    for collision in collision_events.read() {
+        if !spaceship_q.contains(collision.spaceship) {
+            continue;
+        }
+        let Ok(power_up_tag) = power_up_q.get(collision.power_up) else {
+            continue;
+        };
+        commands.trigger(SendMessageEvent(ClientMessage::ClaimPowerUp {
+            power_up_tag: power_up_tag.0,
+        }));
+        commands
+            .entity(collision.power_up)
+            .insert(PowerUpClaimed(Timer::new(CLAIM_TIMEOUT, TimerMode::Once)));
+    }
+}
+
+fn expire_power_up_claims(
+    mut commands: Commands,
+    mut claimed_q: Query<(Entity, &mut PowerUpClaimed)>,
+    time: Res<Time>,
+) {
+    for (entity, mut claimed) in claimed_q.iter_mut() {
+        if claimed.0.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<PowerUpClaimed>();
         }
     }
 }
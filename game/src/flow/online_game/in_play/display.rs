@@ -1,8 +1,13 @@
+use bevy::color::palettes::css::{LIME, RED, YELLOW};
 use bevy::prelude::*;
 
 use crate::{
     components::{Health, Player, Score, SelfPlayer},
+    constant::{COLOR_BLIND_BLUISH_GREEN, COLOR_BLIND_VERMILLION, COLOR_BLIND_YELLOW},
+    flow::online_game::connection::{ConnectionQuality, QualityLevel, Reconnecting},
+    res::{AccessibilityOption, Spectator},
     states::OnlineGameState,
+    ui_components::{Blink, HudAnchor, HudRoot},
     util::cleanup_components,
 };
 
@@ -10,27 +15,46 @@ pub struct DisplayPlugin;
 
 impl Plugin for DisplayPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(OnlineGameState::InPlay), setup_display)
-            .add_systems(
-                Update,
-                (update_health_text, update_score_text).run_if(in_state(OnlineGameState::InPlay)),
+        app.add_systems(
+            OnEnter(OnlineGameState::InPlay),
+            (
+                spawn_hud_root,
+                (setup_display, setup_connection_display).run_if(not(resource_exists::<Spectator>)),
+                setup_spectator_display.run_if(resource_exists::<Spectator>),
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                update_health_text,
+                update_score_text,
+                update_connection_display,
+                toggle_stale_warning,
             )
-            .add_systems(
-                OnExit(OnlineGameState::InPlay),
-                cleanup_components::<InfoDisplay>,
-            );
+                .run_if(in_state(OnlineGameState::InPlay)),
+        )
+        .add_systems(
+            OnExit(OnlineGameState::InPlay),
+            cleanup_components::<HudRoot>,
+        );
     }
 }
 
-#[derive(Component)]
-struct InfoDisplay;
-
 #[derive(Component)]
 struct HealthText;
 
 #[derive(Component)]
 struct ScoreText;
 
+#[derive(Component)]
+struct QualityIcon;
+
+#[derive(Component)]
+struct LatencyText;
+
+#[derive(Component)]
+struct StaleWarning;
+
 fn setup_display(
     mut commands: Commands,
     health_without_self_q: Query<(&Health, &Player), Without<SelfPlayer>>,
@@ -40,12 +64,8 @@ fn setup_display(
 ) {
     commands
         .spawn((
-            InfoDisplay,
+            HudAnchor::TopLeft,
             Node {
-                position_type: PositionType::Absolute,
-                top: Val::Px(5.),
-                left: Val::Px(5.),
-                display: Display::Flex,
                 flex_direction: FlexDirection::Column,
                 ..default()
             },
@@ -56,7 +76,7 @@ fn setup_display(
                 your_info.spawn(Text::new("Health: ")).with_child((
                     player.clone(),
                     HealthText,
-                    TextSpan::new(health.0.to_string()),
+                    TextSpan::new(health.current.to_string()),
                 ));
             }
             if let Ok((score, player)) = self_score_q.single() {
@@ -69,13 +89,10 @@ fn setup_display(
         });
     commands
         .spawn((
-            InfoDisplay,
+            HudAnchor::TopRight,
             Node {
-                position_type: PositionType::Absolute,
-                top: Val::Px(100.),
-                left: Val::Px(5.),
-                display: Display::Flex,
                 flex_direction: FlexDirection::Column,
+                align_items: AlignItems::FlexEnd,
                 ..default()
             },
         ))
@@ -85,7 +102,7 @@ fn setup_display(
                 your_info.spawn(Text::new("Health: ")).with_child((
                     player.clone(),
                     HealthText,
-                    TextSpan::new(health.0.to_string()),
+                    TextSpan::new(health.current.to_string()),
                 ));
             }
             if let Ok((score, player)) = score_without_self_q.single() {
@@ -98,6 +115,132 @@ fn setup_display(
         });
 }
 
+fn setup_connection_display(mut commands: Commands) {
+    commands.spawn((
+        HudAnchor::TopRight,
+        Node {
+            column_gap: Val::Px(5.),
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        children![
+            (
+                QualityIcon,
+                Node {
+                    width: Val::Px(10.),
+                    height: Val::Px(10.),
+                    ..default()
+                },
+                BorderRadius::MAX,
+                BackgroundColor(Color::WHITE),
+            ),
+            (
+                LatencyText,
+                Text::new("-- ms"),
+                TextFont::from_font_size(14.)
+            ),
+        ],
+    ));
+    commands.spawn((
+        HudAnchor::Center,
+        StaleWarning,
+        Visibility::Hidden,
+        Text::new("Connection unstable"),
+        TextFont::from_font_size(18.),
+        Blink::new_with_speed(0.02),
+    ));
+}
+
+fn spawn_hud_root(mut commands: Commands) {
+    commands.spawn(HudRoot);
+}
+
+fn quality_color(level: QualityLevel, color_blind: bool) -> Color {
+    match (level, color_blind) {
+        (QualityLevel::Good, false) => LIME.into(),
+        (QualityLevel::Fair, false) => YELLOW.into(),
+        (QualityLevel::Poor, false) => RED.into(),
+        (QualityLevel::Good, true) => COLOR_BLIND_BLUISH_GREEN,
+        (QualityLevel::Fair, true) => COLOR_BLIND_YELLOW,
+        (QualityLevel::Poor, true) => COLOR_BLIND_VERMILLION,
+    }
+}
+
+fn update_connection_display(
+    connection_quality: Option<Res<ConnectionQuality>>,
+    accessibility: Res<AccessibilityOption>,
+    mut icon_q: Query<&mut BackgroundColor, With<QualityIcon>>,
+    mut text_q: Query<&mut Text, With<LatencyText>>,
+) {
+    let Some(connection_quality) = connection_quality.filter(|quality| quality.is_changed()) else {
+        return;
+    };
+    let (Ok(mut icon_color), Ok(mut text)) = (icon_q.single_mut(), text_q.single_mut()) else {
+        return;
+    };
+    icon_color.0 = quality_color(connection_quality.level(), accessibility.color_blind);
+    text.0 = match connection_quality.rtt_ms() {
+        Some(rtt_ms) => format!("{rtt_ms} ms"),
+        None => "-- ms".to_string(),
+    };
+}
+
+// The reconnecting notice already covers a dropped connection
+fn toggle_stale_warning(
+    connection_quality: Option<Res<ConnectionQuality>>,
+    reconnecting: Option<Res<Reconnecting>>,
+    mut warning_q: Query<&mut Visibility, With<StaleWarning>>,
+    time: Res<Time<Real>>,
+) {
+    let Ok(mut visibility) = warning_q.single_mut() else {
+        return;
+    };
+    let stale = connection_quality.is_some_and(|quality| quality.is_stale(time.elapsed()));
+    visibility.set_if_neq(if stale && reconnecting.is_none() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    });
+}
+
+fn setup_spectator_display(
+    mut commands: Commands,
+    health_q: Query<(&Health, &Player)>,
+    score_q: Query<(&Score, &Player)>,
+) {
+    for (anchor, player_tag) in [(HudAnchor::TopLeft, 1), (HudAnchor::TopRight, 2)] {
+        commands
+            .spawn((
+                anchor,
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+            ))
+            .with_children(|player_info| {
+                player_info.spawn(Text::new(format!("Player {player_tag}")));
+                if let Some((health, player)) =
+                    health_q.iter().find(|(_, player)| player.0 == player_tag)
+                {
+                    player_info.spawn(Text::new("Health: ")).with_child((
+                        player.clone(),
+                        HealthText,
+                        TextSpan::new(health.current.to_string()),
+                    ));
+                }
+                if let Some((score, player)) =
+                    score_q.iter().find(|(_, player)| player.0 == player_tag)
+                {
+                    player_info.spawn(Text::new("Score: ")).with_child((
+                        player.clone(),
+                        ScoreText,
+                        TextSpan::new(score.0.to_string()),
+                    ));
+                }
+            });
+    }
+}
+
 fn update_health_text(
     health_q: Query<(&Health, &Player), Changed<Health>>,
     mut health_text_q: Query<(&mut TextSpan, &Player), With<HealthText>>,
@@ -108,7 +251,7 @@ fn update_health_text(
     for (health, target_player) in health_q.iter() {
         for (mut text_span, player) in health_text_q.iter_mut() {
             if target_player.0 == player.0 {
-                text_span.0 = health.0.to_string();
+                text_span.0 = health.current.to_string();
             }
         }
     }
@@ -1,15 +1,20 @@
+use std::collections::HashMap;
+
 use bevy::prelude::*;
-use shooting_game_shared::ServerMessage;
+use shooting_game_shared::{DownedSnapshot, EnemySnapshot, OnlinePowerUp, ServerMessage};
 
 use crate::{
+    components::EnemyTag,
     flow::online_game::{
-        connection::ReceiveMessageEvent,
+        connection::{ReceiveMessageEvent, Reconnecting},
+        result::MatchResult,
         trigger::{
-            AddScoreEvent, DestroyEnemyEvent, PlayerDamagedEvent, RemoveBulletEvent,
-            SpawnEnemyEvent,
+            AddScoreEvent, CollectPowerUpEvent, DestroyEnemyEvent, EffectEvent, PlayerDamagedEvent,
+            PlayerDownedEvent, PlayerRevivedEvent, RemoveBulletEvent, ResumeStateEvent,
+            SpawnEnemyEvent, SpawnPowerUpEvent,
         },
     },
-    res::PlayerTag,
+    res::{PlayerTag, SessionToken},
     states::OnlineGameState,
 };
 
@@ -17,26 +22,37 @@ pub struct FromServerPlugin;
 
 impl Plugin for FromServerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(listen_from_server);
+        app.init_resource::<SnapshotTick>()
+            .add_observer(listen_from_server);
     }
 }
 
+// The latest snapshot seen, hit reports carry it so the server can rewind enemies
+#[derive(Resource, Default)]
+pub(super) struct SnapshotTick(pub u32);
+
+#[allow(clippy::too_many_arguments)]
 fn listen_from_server(
     ev: Trigger<ReceiveMessageEvent>,
-    commands: Commands,
+    mut commands: Commands,
     current_state: Res<State<OnlineGameState>>,
     self_player_tag: Res<PlayerTag>,
+    reconnecting: Option<Res<Reconnecting>>,
+    session_token: Option<Res<SessionToken>>,
     next_state: ResMut<NextState<OnlineGameState>>,
+    enemy_q: Query<&EnemyTag>,
+    mut snapshot_tick: ResMut<SnapshotTick>,
 ) {
     match ev.0 {
-        ServerMessage::SpawnEnemy {
-            tag,
-            position,
-            velocity,
-        } => {
-            if *current_state.get() == OnlineGameState::InPlay {
-                handle_spawn_enemy(commands, tag, position, velocity);
-            }
+        ServerMessage::Snapshot {
+            tick,
+            ref enemies,
+            ref scores,
+            ..
+        } if *current_state.get() == OnlineGameState::InPlay => {
+            snapshot_tick.0 = tick;
+            handle_snapshot_scores(commands.reborrow(), scores);
+            handle_snapshot_enemies(commands, enemies, enemy_q);
         }
         ServerMessage::ConfirmDamaged {
             player_tag,
@@ -56,24 +72,94 @@ fn listen_from_server(
             enemy_tag,
             new_score,
         ),
-        ServerMessage::GameOver => handle_game_over(next_state),
+        ServerMessage::SpawnPowerUp {
+            tag,
+            kind,
+            position,
+            velocity,
+        } => commands.trigger(SpawnPowerUpEvent {
+            tag,
+            kind: kind.into(),
+            position: Vec2::new(position.0, position.1),
+            velocity: Vec2::new(velocity.0, velocity.1),
+        }),
+        ServerMessage::ConfirmPowerUp {
+            player_tag,
+            power_up_tag,
+            kind,
+        } => handle_confirm_power_up(commands, player_tag, power_up_tag, kind),
+        ServerMessage::ResumeState {
+            score,
+            health,
+            ref enemies,
+            ref downed,
+        } => handle_resume_state(commands, score, health, enemies, downed),
+        ServerMessage::PlayerDowned {
+            player_tag,
+            position,
+        } => commands.trigger(PlayerDownedEvent {
+            tag: player_tag,
+            position: Vec2::new(position.0, position.1),
+        }),
+        ServerMessage::PlayerRevived {
+            player_tag,
+            health,
+            position,
+        } => commands.trigger(PlayerRevivedEvent {
+            tag: player_tag,
+            health,
+            position: Vec2::new(position.0, position.1),
+        }),
+        // A resume is answered with our own session, any other join means it expired
+        // while reconnecting and the server treated us as a fresh connection
+        ServerMessage::Joined {
+            session_token: joined_session,
+            ..
+        } if reconnecting.is_some()
+            && session_token.is_none_or(|session_token| session_token.0 != joined_session) =>
+        {
+            handle_session_expired(next_state)
+        }
+        ServerMessage::SpectatorJoined { .. } if reconnecting.is_some() => {
+            handle_session_expired(next_state)
+        }
+        ServerMessage::Effect { kind, position } => commands.trigger(EffectEvent {
+            kind,
+            position: Vec2::new(position.0, position.1),
+        }),
+        ServerMessage::GameOver { ref scores, winner } => {
+            handle_game_over(commands, next_state, scores, winner)
+        }
         _ => {}
     }
 }
 
-fn handle_spawn_enemy(
+// Known enemies keep moving locally, only the new ones are spawned
+fn handle_snapshot_enemies(
     mut commands: Commands,
-    tag: u16,
-    tuple_position: (f32, f32),
-    tuple_velocity: (f32, f32),
+    enemies: &[EnemySnapshot],
+    enemy_q: Query<&EnemyTag>,
 ) {
-    let position = Vec2::new(tuple_position.0, tuple_position.1);
-    let velocity = Vec2::new(tuple_velocity.0, tuple_velocity.1);
-    commands.trigger(SpawnEnemyEvent {
-        tag,
-        position,
-        velocity,
-    });
+    for (tag, position, velocity) in enemies.iter() {
+        if enemy_q.iter().any(|enemy_tag| enemy_tag.0 == *tag) {
+            continue;
+        }
+        commands.trigger(SpawnEnemyEvent {
+            tag: *tag,
+            position: Vec2::new(position.0, position.1),
+            velocity: Vec2::new(velocity.0, velocity.1),
+        });
+    }
+}
+
+// The server's scoreboard wins over whatever the client has counted
+fn handle_snapshot_scores(mut commands: Commands, scores: &HashMap<u8, u32>) {
+    for (player_tag, score) in scores.iter() {
+        commands.trigger(AddScoreEvent {
+            player_tag: *player_tag,
+            score: *score,
+        });
+    }
 }
 
 fn handle_confirm_damaged(mut commands: Commands, player_tag: u8, enemy_tag: u16, health: u8) {
@@ -92,13 +178,68 @@ fn handle_confirm_destroy_enemy(
     commands.trigger(DestroyEnemyEvent(enemy_tag));
     commands.trigger(AddScoreEvent {
         player_tag,
-        score: new_score,
+        score: new_score.into(),
     });
     if self_player_tag.0 == player_tag {
         commands.trigger(RemoveBulletEvent(bullet_tag));
     }
 }
 
-fn handle_game_over(mut next_state: ResMut<NextState<OnlineGameState>>) {
+fn handle_confirm_power_up(
+    mut commands: Commands,
+    player_tag: u8,
+    power_up_tag: u16,
+    kind: OnlinePowerUp,
+) {
+    commands.trigger(CollectPowerUpEvent {
+        player_tag,
+        power_up_tag,
+        kind: kind.into(),
+    });
+}
+
+fn handle_resume_state(
+    mut commands: Commands,
+    score: u8,
+    health: u8,
+    enemies: &[EnemySnapshot],
+    downed: &[DownedSnapshot],
+) {
+    let enemies = enemies
+        .iter()
+        .map(|(tag, position, velocity)| {
+            (
+                *tag,
+                Vec2::new(position.0, position.1),
+                Vec2::new(velocity.0, velocity.1),
+            )
+        })
+        .collect();
+    let downed = downed
+        .iter()
+        .map(|(tag, position)| (*tag, Vec2::new(position.0, position.1)))
+        .collect();
+    commands.trigger(ResumeStateEvent {
+        score,
+        health,
+        enemies,
+        downed,
+    });
+}
+
+fn handle_session_expired(mut next_state: ResMut<NextState<OnlineGameState>>) {
+    next_state.set(OnlineGameState::Error);
+}
+
+fn handle_game_over(
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<OnlineGameState>>,
+    scores: &HashMap<u8, u32>,
+    winner: Option<u8>,
+) {
+    commands.insert_resource(MatchResult {
+        scores: scores.clone(),
+        winner,
+    });
     next_state.set(OnlineGameState::Result);
 }
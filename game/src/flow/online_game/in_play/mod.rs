@@ -1,8 +1,9 @@
 mod collision;
 mod display;
+mod enemy;
 mod from_server;
 mod out_screen_cleanup;
-mod enemy;
+mod revive;
 use bevy::prelude::*;
 
 pub struct InPlayPlugin;
@@ -15,6 +16,7 @@ impl Plugin for InPlayPlugin {
             from_server::FromServerPlugin,
             out_screen_cleanup::OutScreenCleanupPlugin,
             enemy::EnemyPlugin,
+            revive::RevivePlugin,
         ));
     }
 }
@@ -1,8 +1,8 @@
 use bevy::prelude::*;
-use shooting_game_shared::util::EdgeUtil;
+use shooting_game_shared::util::{EdgeUtil, POWER_UP_SIZE};
 
 use crate::{
-    components::{BulletTag, UFO},
+    components::{BulletTag, PowerUp, UFO},
     constant::BULLET_SIZE,
 };
 
@@ -18,6 +18,7 @@ fn cleanup_on_out_screen(
     mut commands: Commands,
     ufo_query: Query<(Entity, &Transform), With<UFO>>,
     bullet_query: Query<(Entity, &Transform), With<BulletTag>>,
+    power_up_query: Query<(Entity, &Transform), With<PowerUp>>,
 ) {
     let ufo_edge = EdgeUtil::ufo();
     for (entity, transform) in ufo_query.iter() {
@@ -30,7 +31,22 @@ fn cleanup_on_out_screen(
 
     let bullet_edge = EdgeUtil::new(BULLET_SIZE);
     for (entity, transform) in bullet_query.iter() {
-        if bullet_edge.over_top_out(transform.translation.y) {
+        let Vec2 { x, y } = transform.translation.truncate();
+        // Mouse aim fires in every direction
+        if bullet_edge.over_top_out(y)
+            || bullet_edge.over_bottom_out(y)
+            || bullet_edge.over_left_out(x)
+            || bullet_edge.over_right_out(x)
+        {
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.despawn();
+            }
+        }
+    }
+
+    let power_up_edge = EdgeUtil::new(POWER_UP_SIZE);
+    for (entity, transform) in power_up_query.iter() {
+        if power_up_edge.over_bottom_out(transform.translation.y) {
             if let Ok(mut entity_commands) = commands.get_entity(entity) {
                 entity_commands.despawn();
             }
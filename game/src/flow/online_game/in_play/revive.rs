@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::{Downed, Player, Spaceship},
+    states::OnlineGameState,
+};
+
+pub struct RevivePlugin;
+
+impl Plugin for RevivePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            show_revive_progress.run_if(in_state(OnlineGameState::InPlay)),
+        );
+    }
+}
+
+// The server times the revive itself, the ring only follows along
+fn show_revive_progress(
+    mut downed_q: Query<&mut Downed>,
+    spaceship_q: Query<(&Spaceship, &Player)>,
+    time: Res<Time>,
+) {
+    for mut downed in downed_q.iter_mut() {
+        if downed.hovered(spaceship_q.iter()) {
+            downed.channel(time.delta());
+        } else {
+            downed.interrupt();
+        }
+    }
+}
@@ -1,21 +1,30 @@
 use bevy::prelude::*;
-use shooting_game_shared::ServerMessage;
+use shooting_game_shared::{ClientMessage, LobbyPlayer, ServerMessage};
 
 use crate::{
-    res::PlayerTag,
+    res::{PlayerNames, PlayerTag, SessionToken, Spectator},
     states::OnlineGameState,
-    ui_components::{Blink, MainContainer},
+    ui_components::{Blink, InteractionUI, MainContainer},
     util::cleanup_components,
 };
 
-use super::connection::ReceiveMessageEvent;
+use super::connection::{ReceiveMessageEvent, SendMessageEvent};
 
 pub struct MatchingPlugin;
 
 impl Plugin for MatchingPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(OnlineGameState::Matching), setup_matching_notice)
+            .add_systems(
+                Update,
+                (
+                    handle_ready_button_interaction,
+                    handle_add_bot_button_interaction,
+                )
+                    .run_if(in_state(OnlineGameState::Matching)),
+            )
             .add_observer(handle_matching_message)
+            .add_observer(handle_lobby_state)
             .add_systems(
                 OnExit(OnlineGameState::Matching),
                 cleanup_components::<MatchingNotice>,
@@ -25,17 +34,178 @@ impl Plugin for MatchingPlugin {
 
 #[derive(Component)]
 struct MatchingNotice;
+
+#[derive(Component)]
+struct RoomIdText;
+
+#[derive(Component)]
+struct LobbyPlayersText;
+
+// Mirrors what the server last said about this player
+#[derive(Component)]
+struct ReadyButton {
+    ready: bool,
+}
+
+#[derive(Component)]
+struct ReadyButtonText;
+
+// Only shown while this player is alone in the lobby
+#[derive(Component)]
+struct AddBotButton;
+
 fn setup_matching_notice(mut commands: Commands) {
-    commands.spawn((MainContainer, MatchingNotice)).with_child((
-        TextLayout::new_with_justify(JustifyText::Center),
-        Text::new("Matching"),
-        Blink::new_with_speed(0.01),
-    ));
+    commands
+        .spawn((MainContainer, MatchingNotice))
+        .with_children(|notice| {
+            notice.spawn((
+                TextLayout::new_with_justify(JustifyText::Center),
+                Text::new("Matching"),
+                Blink::new_with_speed(0.01),
+            ));
+            notice.spawn((
+                RoomIdText,
+                TextLayout::new_with_justify(JustifyText::Center),
+                Text::default(),
+            ));
+            notice.spawn((
+                LobbyPlayersText,
+                Node {
+                    margin: UiRect::vertical(Val::Px(30.)),
+                    ..default()
+                },
+                TextLayout::new_with_justify(JustifyText::Center),
+                Text::default(),
+            ));
+            notice
+                .spawn((
+                    ReadyButton { ready: false },
+                    InteractionUI,
+                    lobby_button_node(Display::Flex),
+                    BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
+                    BorderColor::from(Color::BLACK),
+                ))
+                .with_child((ReadyButtonText, Text::new(ready_button_text(false))));
+            notice
+                .spawn((
+                    AddBotButton,
+                    InteractionUI,
+                    Node {
+                        margin: UiRect::top(Val::Px(20.)),
+                        ..lobby_button_node(Display::None)
+                    },
+                    BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
+                    BorderColor::from(Color::BLACK),
+                ))
+                .with_child(Text::new("Play vs Bot"));
+        });
+}
+
+fn lobby_button_node(display: Display) -> Node {
+    Node {
+        width: Val::Px(200.),
+        height: Val::Px(50.),
+        border: UiRect::all(Val::Px(2.)),
+        display,
+        align_items: AlignItems::Center,
+        justify_content: JustifyContent::Center,
+        ..default()
+    }
+}
+
+fn ready_button_text(ready: bool) -> &'static str {
+    if ready {
+        "Cancel Ready"
+    } else {
+        "Ready"
+    }
+}
+
+fn lobby_players_text(
+    players: &[LobbyPlayer],
+    self_player_tag: u8,
+    player_names: &PlayerNames,
+) -> String {
+    players
+        .iter()
+        .map(|(player_tag, ready)| {
+            let you = if *player_tag == self_player_tag {
+                " (You)"
+            } else {
+                ""
+            };
+            let status = if *ready { "Ready" } else { "Not Ready" };
+            let name = player_names.name(*player_tag);
+            format!("{name}{you}: {status}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn handle_ready_button_interaction(
+    mut commands: Commands,
+    ready_button_q: Query<(&Interaction, &ReadyButton), Changed<Interaction>>,
+) {
+    for (interaction, ready_button) in ready_button_q.iter() {
+        if *interaction == Interaction::Pressed {
+            commands.trigger(SendMessageEvent(ClientMessage::SetReady {
+                ready: !ready_button.ready,
+            }));
+        }
+    }
+}
+
+fn handle_add_bot_button_interaction(
+    mut commands: Commands,
+    add_bot_button_q: Query<&Interaction, (Changed<Interaction>, With<AddBotButton>)>,
+) {
+    for interaction in add_bot_button_q.iter() {
+        if *interaction == Interaction::Pressed {
+            commands.trigger(SendMessageEvent(ClientMessage::AddBot));
+        }
+    }
+}
+
+fn handle_lobby_state(
+    ev: Trigger<ReceiveMessageEvent>,
+    self_player_tag: Res<PlayerTag>,
+    player_names: Res<PlayerNames>,
+    mut players_text_q: Query<&mut Text, (With<LobbyPlayersText>, Without<ReadyButtonText>)>,
+    mut ready_button_q: Query<&mut ReadyButton>,
+    mut ready_button_text_q: Query<&mut Text, With<ReadyButtonText>>,
+    mut add_bot_button_q: Query<&mut Node, With<AddBotButton>>,
+) {
+    let ServerMessage::LobbyState { ref players } = ev.0 else {
+        return;
+    };
+    let Ok(mut players_text) = players_text_q.single_mut() else {
+        warn!("Lobby players text not found in handle_lobby_state");
+        return;
+    };
+    players_text.0 = lobby_players_text(players, self_player_tag.0, &player_names);
+    let ready = players
+        .iter()
+        .any(|(player_tag, ready)| *player_tag == self_player_tag.0 && *ready);
+    if let Ok(mut ready_button) = ready_button_q.single_mut() {
+        ready_button.ready = ready;
+    }
+    if let Ok(mut button_text) = ready_button_text_q.single_mut() {
+        button_text.0 = ready_button_text(ready).to_string();
+    }
+    if let Ok(mut add_bot_node) = add_bot_button_q.single_mut() {
+        add_bot_node.display = if players.len() == 1 {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
 }
 
 fn handle_matching_message(
     ev: Trigger<ReceiveMessageEvent>,
+    mut commands: Commands,
     mut current_player_tag: ResMut<PlayerTag>,
+    mut room_id_text_q: Query<&mut Text, With<RoomIdText>>,
     current_state: ResMut<State<OnlineGameState>>,
     mut next_state: ResMut<NextState<OnlineGameState>>,
 ) {
@@ -43,7 +213,28 @@ fn handle_matching_message(
         return;
     }
     match ev.0 {
-        ServerMessage::Joined { player_tag } => current_player_tag.0 = player_tag,
+        ServerMessage::Joined {
+            player_tag,
+            session_token,
+        } => {
+            current_player_tag.0 = player_tag;
+            commands.insert_resource(SessionToken(session_token));
+        }
+        ServerMessage::SpectatorJoined { game_started } => {
+            // No player owns tag 0, so nothing becomes SelfPlayer
+            current_player_tag.0 = 0;
+            commands.insert_resource(Spectator {
+                joined_mid_game: game_started,
+            });
+            next_state.set(OnlineGameState::Ready);
+        }
+        ServerMessage::RoomCreated { room_id } => {
+            let Ok(mut room_id_text) = room_id_text_q.single_mut() else {
+                warn!("Room id text not found in handle_matching_message");
+                return;
+            };
+            room_id_text.0 = format!("Room {room_id}");
+        }
         ServerMessage::GameReady => next_state.set(OnlineGameState::Ready),
         _ => {}
     }
@@ -1,7 +1,9 @@
+mod chat;
 mod connection;
 mod error_page;
 mod in_play;
 mod matching;
+mod player_names;
 mod ready;
 mod result;
 mod shared;
@@ -9,13 +11,17 @@ mod trigger;
 
 use bevy::prelude::*;
 
+pub use chat::ChatDraft;
+
 pub struct OnlineGamePlugin;
 
 impl Plugin for OnlineGamePlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
             connection::ConnectionPlugin,
+            chat::ChatPlugin,
             matching::MatchingPlugin,
+            player_names::PlayerNamesPlugin,
             ready::ReadyPlugin,
             shared::SharedPlugin,
             trigger::TriggerPlugin,
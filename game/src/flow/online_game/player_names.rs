@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use shooting_game_shared::{util::SPACESHIP_SIZE, ServerMessage};
+
+use crate::components::{Player, SelfPlayer, Spaceship};
+use crate::res::PlayerNames;
+use crate::states::AppState;
+
+use super::connection::ReceiveMessageEvent;
+
+pub struct PlayerNamesPlugin;
+
+impl Plugin for PlayerNamesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            sync_name_labels.run_if(
+                in_state(AppState::OnlineGame)
+                    .and(resource_changed::<PlayerNames>.or(any_match_filter::<Added<Spaceship>>)),
+            ),
+        )
+        .add_observer(receive_player_names);
+    }
+}
+
+// Floats above the spaceships of everyone but this player
+#[derive(Component)]
+struct NameLabel;
+
+// Written straight away rather than through commands, the LobbyState right after reads it
+fn receive_player_names(ev: Trigger<ReceiveMessageEvent>, mut player_names: ResMut<PlayerNames>) {
+    if let ServerMessage::PlayerNames { ref names } = ev.0 {
+        player_names.0 = names.clone();
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn sync_name_labels(
+    mut commands: Commands,
+    player_names: Res<PlayerNames>,
+    spaceship_q: Query<
+        (Entity, &Player, Option<&Children>),
+        (With<Spaceship>, Without<SelfPlayer>),
+    >,
+    mut label_q: Query<&mut Text2d, With<NameLabel>>,
+) {
+    for (entity, player, children) in spaceship_q.iter() {
+        let name = player_names.name(player.0);
+        let label = children
+            .into_iter()
+            .flatten()
+            .find(|child| label_q.contains(**child));
+        match label {
+            Some(label) => {
+                if let Ok(mut text) = label_q.get_mut(*label) {
+                    text.0 = name;
+                }
+            }
+            None => {
+                commands.entity(entity).with_child((
+                    NameLabel,
+                    Text2d::new(name),
+                    TextFont::from_font_size(16.),
+                    Transform::from_xyz(0., SPACESHIP_SIZE.y / 2. + 12., 0.1),
+                ));
+            }
+        }
+    }
+}
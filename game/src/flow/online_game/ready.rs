@@ -1,8 +1,8 @@
 use crate::components::{Health, Player, Score, SelfPlayer, Spaceship, Velocity};
-use crate::res::PlayerTag;
+use crate::res::{PlayerTag, Spectator};
 use crate::states::OnlineGameState;
 use bevy::prelude::*;
-use shooting_game_shared::util::EdgeUtil;
+use shooting_game_shared::game_related::spaceship_start_position;
 use shooting_game_shared::ServerMessage;
 
 use super::connection::ReceiveMessageEvent;
@@ -13,9 +13,16 @@ impl Plugin for ReadyPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             OnEnter(OnlineGameState::Ready),
-            (spawn_spaceship, setup_score_and_health),
+            (
+                spawn_spaceship,
+                setup_score_and_health,
+                skip_ready_for_late_spectator.run_if(resource_exists::<Spectator>),
+            ),
+        )
+        .add_systems(
+            OnExit(OnlineGameState::Ready),
+            stop_spaceship.run_if(not(resource_exists::<Spectator>)),
         )
-        .add_systems(OnExit(OnlineGameState::Ready), stop_spaceship)
         .add_observer(listen_message);
     }
 }
@@ -28,20 +35,26 @@ fn setup_score_and_health(mut commands: Commands) {
 }
 
 fn spawn_spaceship(mut commands: Commands, player_tag: Res<PlayerTag>) {
-    let edge = EdgeUtil::spaceship();
     for i in 1..=2 {
         let y = if i == player_tag.0 { 5. } else { 0. };
+        let (x, start_y) = spaceship_start_position(i);
         commands.spawn((
             Player(i),
-            Spaceship::new(Vec2::new(
-                if i == 1 { -100. } else { 100. },
-                edge.bottom_out(),
-            )),
+            Spaceship::new(Vec2::new(x, start_y)),
             Velocity { x: 0., y },
         ));
     }
 }
 
+fn skip_ready_for_late_spectator(
+    spectator: Res<Spectator>,
+    mut next_state: ResMut<NextState<OnlineGameState>>,
+) {
+    if spectator.joined_mid_game {
+        next_state.set(OnlineGameState::InPlay);
+    }
+}
+
 fn stop_spaceship(mut spaceship_q: Query<&mut Velocity, (With<Spaceship>, With<SelfPlayer>)>) {
     let Ok(mut velocity) = spaceship_q.single_mut() else {
         warn!("Should only have one spaceship with SelfPlayer in stop_spaceship");
@@ -59,10 +72,7 @@ fn listen_message(
     if *current_state.get() != OnlineGameState::Ready {
         return;
     }
-    match trigger.event().0.clone() {
-        ServerMessage::GameStart => {
-            next_state.set(OnlineGameState::InPlay);
-        }
-        _ => {}
+    if matches!(trigger.event().0, ServerMessage::GameStart) {
+        next_state.set(OnlineGameState::InPlay);
     }
 }
@@ -1,25 +1,52 @@
+use std::collections::HashMap;
+
 use bevy::prelude::*;
+use shooting_game_shared::{ClientMessage, ServerMessage};
 
 use crate::{
-    components::{Score, SelfPlayer},
+    res::{PlayerNames, PlayerTag, Spectator},
     states::{AppState, OnlineGameState},
-    ui_components::{Blink, InteractionUI, MainContainer},
+    ui_components::{Blink, FocusBack, InteractionUI, MainContainer},
     util::cleanup_components,
 };
 
+use super::connection::{ReceiveMessageEvent, SendMessageEvent};
+use super::error_page::room_closed_text;
+
 pub struct ResultPlugin;
 
 impl Plugin for ResultPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(OnlineGameState::Result), show_result)
-            .add_systems(
-                Update,
-                handle_return_button_interaction.run_if(in_state(OnlineGameState::Result)),
+        app.add_systems(
+            OnEnter(OnlineGameState::Result),
+            show_result.run_if(resource_exists::<MatchResult>),
+        )
+        .add_systems(
+            Update,
+            (
+                handle_return_button_interaction,
+                handle_rematch_button_interaction,
             )
-            .add_systems(
-                OnExit(OnlineGameState::Result),
-                cleanup_components::<Result>,
-            );
+                .run_if(in_state(OnlineGameState::Result)),
+        )
+        .add_systems(
+            OnExit(OnlineGameState::Result),
+            (cleanup_components::<Result>, remove_match_result),
+        )
+        .add_observer(listen_rematch_message);
+    }
+}
+
+// Final scores and winner as the server decided them
+#[derive(Resource)]
+pub struct MatchResult {
+    pub scores: HashMap<u8, u32>,
+    pub winner: Option<u8>,
+}
+
+impl MatchResult {
+    fn score(&self, player_tag: u8) -> u32 {
+        self.scores.get(&player_tag).copied().unwrap_or_default()
     }
 }
 
@@ -29,29 +56,55 @@ struct Result;
 #[derive(Component)]
 struct ReturnButton;
 
-fn show_result(mut commands: Commands, score_q: Query<(&Score, Option<&SelfPlayer>)>) {
-    let mut your_score = 0;
-    let mut opponent_score = 0;
-    for (score, self_player_op) in score_q.iter() {
-        if self_player_op.is_none() {
-            opponent_score = score.0 as i8;
-        } else {
-            your_score = score.0 as i8;
-        }
-    }
-    let result_text = match your_score - opponent_score {
-        0 => "Draw",
-        x if x > 0 => "You Win",
-        x if x < 0 => "You Lose",
-        _ => "Error",
+#[derive(Component)]
+struct RematchButton;
+
+#[derive(Component)]
+struct RematchHint;
+
+fn show_result(
+    mut commands: Commands,
+    match_result: Res<MatchResult>,
+    player_tag: Res<PlayerTag>,
+    player_names: Res<PlayerNames>,
+    spectator: Option<Res<Spectator>>,
+) {
+    // Spectators read player 1 as "your" side
+    let first_tag = match spectator {
+        Some(_) => 1,
+        None => player_tag.0,
+    };
+    let second_tag = if first_tag == 1 { 2 } else { 1 };
+    let result_text = match (spectator.is_some(), match_result.winner) {
+        (_, None) => "Draw".to_string(),
+        (true, Some(winner)) => format!("{} Wins", player_names.name(winner)),
+        (false, Some(winner)) if winner == first_tag => "You Win".to_string(),
+        (false, Some(_)) => "You Lose".to_string(),
     };
+    let (first_label, hint) = match spectator {
+        Some(_) => (
+            format!("{}'s", player_names.name(first_tag)),
+            "Click Return to return to main menu",
+        ),
+        None => (
+            "Your".to_string(),
+            "Click Rematch to play again in this room",
+        ),
+    };
+    let second_label = format!("{}'s", player_names.name(second_tag));
 
     commands
         .spawn((Result, MainContainer))
         .with_children(|result_background| {
             result_background.spawn(Text::new(result_text));
-            result_background.spawn(Text::new(format!("Your Score: {}", your_score)));
-            result_background.spawn(Text::new(format!("Opponent Score: {}", opponent_score)));
+            result_background.spawn(Text::new(format!(
+                "{first_label} Score: {}",
+                match_result.score(first_tag)
+            )));
+            result_background.spawn(Text::new(format!(
+                "{second_label} Score: {}",
+                match_result.score(second_tag)
+            )));
             result_background
                 .spawn(Node {
                     height: Val::Percent(100.),
@@ -63,32 +116,47 @@ fn show_result(mut commands: Commands, score_q: Query<(&Score, Option<&SelfPlaye
                 })
                 .with_children(|return_container| {
                     return_container.spawn((
+                        RematchHint,
                         Blink::new_with_speed(0.02),
-                        Text::new("Click Return to return to main menu"),
+                        Text::new(hint),
                         TextLayout::new_with_justify(JustifyText::Center),
                     ));
                     return_container
-                        .spawn((
-                            ReturnButton,
-                            InteractionUI,
-                            Node {
-                                align_self: AlignSelf::FlexEnd,
-                                width: Val::Px(120.),
-                                height: Val::Px(50.),
-                                border: UiRect::all(Val::Px(2.)),
-                                display: Display::Flex,
-                                align_items: AlignItems::Center,
-                                justify_content: JustifyContent::Center,
-                                ..default()
-                            },
-                            BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
-                            BorderColor::from(Color::BLACK),
-                        ))
-                        .with_child(Text::new("Return"));
+                        .spawn(Node {
+                            align_self: AlignSelf::FlexEnd,
+                            column_gap: Val::Px(5.),
+                            ..default()
+                        })
+                        .with_children(|button_row| {
+                            if spectator.is_none() {
+                                button_row
+                                    .spawn((RematchButton, InteractionUI, result_button()))
+                                    .with_child(Text::new("Rematch"));
+                            }
+                            button_row
+                                .spawn((ReturnButton, FocusBack, InteractionUI, result_button()))
+                                .with_child(Text::new("Return"));
+                        });
                 });
         });
 }
 
+fn result_button() -> impl Bundle {
+    (
+        Node {
+            width: Val::Px(120.),
+            height: Val::Px(50.),
+            border: UiRect::all(Val::Px(2.)),
+            display: Display::Flex,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
+        BorderColor::from(Color::BLACK),
+    )
+}
+
 fn handle_return_button_interaction(
     mut return_button_query: Query<&Interaction, With<ReturnButton>>,
     mut next_state: ResMut<NextState<AppState>>,
@@ -101,3 +169,51 @@ fn handle_return_button_interaction(
         next_state.set(AppState::MainMenu);
     };
 }
+
+fn handle_rematch_button_interaction(
+    mut commands: Commands,
+    rematch_button_query: Query<(Entity, &Interaction), With<RematchButton>>,
+    mut hint_query: Query<&mut Text, With<RematchHint>>,
+) {
+    let Ok((entity, interaction)) = rematch_button_query.single() else {
+        return;
+    };
+    if *interaction != Interaction::Pressed {
+        return;
+    }
+    commands.trigger(SendMessageEvent(ClientMessage::Rematch));
+    commands.entity(entity).despawn();
+    if let Ok(mut hint) = hint_query.single_mut() {
+        hint.0 = "Waiting for opponent".to_string();
+    }
+}
+
+// GameReady restarts the room, GameInterrupted and RoomClosed end it for good
+fn listen_rematch_message(
+    trigger: Trigger<ReceiveMessageEvent>,
+    mut commands: Commands,
+    current_state: Res<State<OnlineGameState>>,
+    mut next_state: ResMut<NextState<OnlineGameState>>,
+    rematch_button_query: Query<Entity, With<RematchButton>>,
+    mut hint_query: Query<&mut Text, With<RematchHint>>,
+) {
+    if *current_state.get() != OnlineGameState::Result {
+        return;
+    }
+    let hint_text = match trigger.event().0 {
+        ServerMessage::GameReady => return next_state.set(OnlineGameState::Ready),
+        ServerMessage::GameInterrupted => "Opponent left the room",
+        ServerMessage::RoomClosed { reason } => room_closed_text(reason),
+        _ => return,
+    };
+    for entity in rematch_button_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    if let Ok(mut hint) = hint_query.single_mut() {
+        hint.0 = hint_text.to_string();
+    }
+}
+
+fn remove_match_result(mut commands: Commands) {
+    commands.remove_resource::<MatchResult>();
+}
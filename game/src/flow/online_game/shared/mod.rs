@@ -1,3 +1,4 @@
+mod prediction;
 mod send_player_info;
 mod update_player_info;
 
@@ -7,6 +8,7 @@ pub struct SharedPlugin;
 impl Plugin for SharedPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
+            prediction::PredictionPlugin,
             send_player_info::SendPlayerInfoPlugin,
             update_player_info::UpdatePlayerInfoPlugin,
         ));
@@ -0,0 +1,67 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use shooting_game_shared::{game_related::apply_player_input, PlayerInput};
+
+use crate::states::OnlineGameState;
+
+// Inputs the server has never acknowledged stop being worth replaying
+const MAX_PENDING_INPUTS: usize = 256;
+
+// Own spaceship moves right away, every server snapshot then corrects it by replaying what it hasn't seen yet
+#[derive(Resource, Default)]
+pub struct InputHistory {
+    next_sequence: u32,
+    pending: VecDeque<(u32, Vec2)>,
+    // Where the spaceship was when the last input was taken
+    baseline: Option<Vec2>,
+}
+
+impl InputHistory {
+    // Turns the movement since the last call into the next input
+    pub fn record(&mut self, position: Vec2) -> PlayerInput {
+        let displacement = position - self.baseline.unwrap_or(position);
+        self.baseline = Some(position);
+        self.next_sequence += 1;
+        self.pending.push_back((self.next_sequence, displacement));
+        if self.pending.len() > MAX_PENDING_INPUTS {
+            self.pending.pop_front();
+        }
+        PlayerInput {
+            sequence: self.next_sequence,
+            displacement: (displacement.x, displacement.y),
+        }
+    }
+
+    // Returns where the spaceship should be now given the authoritative position
+    pub fn reconcile(&mut self, acknowledged: u32, server_position: Vec2, current: Vec2) -> Vec2 {
+        self.pending
+            .retain(|(sequence, _)| *sequence > acknowledged);
+        let predicted = self.pending.iter().fold(
+            (server_position.x, server_position.y),
+            |position, (_, displacement)| {
+                apply_player_input(position, (displacement.x, displacement.y))
+            },
+        );
+        let predicted = Vec2::new(predicted.0, predicted.1);
+        // Movement since the last input hasn't been sent yet, so it stays on top
+        let unsent = self
+            .baseline
+            .map_or(Vec2::ZERO, |baseline| current - baseline);
+        self.baseline = Some(predicted);
+        predicted + unsent
+    }
+}
+
+pub struct PredictionPlugin;
+
+impl Plugin for PredictionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputHistory>()
+            .add_systems(OnEnter(OnlineGameState::Ready), reset_input_history);
+    }
+}
+
+fn reset_input_history(mut commands: Commands) {
+    commands.insert_resource(InputHistory::default());
+}
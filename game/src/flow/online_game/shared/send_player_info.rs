@@ -1,9 +1,11 @@
 use bevy::prelude::*;
 use shooting_game_shared::ClientMessage;
 
+use super::prediction::InputHistory;
 use crate::{
     components::{Bullet, SelfPlayer, Spaceship},
     flow::online_game::connection::SendMessageEvent,
+    res::Spectator,
     states::OnlineGameState,
 };
 
@@ -11,23 +13,26 @@ pub struct SendPlayerInfoPlugin;
 
 impl Plugin for SendPlayerInfoPlugin {
     fn build(&self, app: &mut App) {
+        // Movement only changes on a fixed tick, so sending every frame would flood high refresh rates
         app.add_systems(
-            Update,
+            FixedUpdate,
             send_player_info
-                .run_if(in_state(OnlineGameState::Ready).or(in_state(OnlineGameState::InPlay))),
+                .run_if(in_state(OnlineGameState::Ready).or(in_state(OnlineGameState::InPlay)))
+                .run_if(not(resource_exists::<Spectator>)),
         );
     }
 }
 
 fn send_player_info(
     mut commands: Commands,
-    spaceship_q: Query<&Spaceship, With<SelfPlayer>>,
+    spaceship_q: Query<&Transform, (With<Spaceship>, With<SelfPlayer>)>,
     bullet_q: Query<&Bullet, With<SelfPlayer>>,
+    mut input_history: ResMut<InputHistory>,
 ) {
-    let position = spaceship_q
+    let input = spaceship_q
         .single()
-        .map(|spaceship| Some(spaceship.get_position_tuple()))
-        .unwrap_or(None);
+        .ok()
+        .map(|transform| input_history.record(transform.translation.truncate()));
 
     let bullets = bullet_q
         .iter()
@@ -35,7 +40,7 @@ fn send_player_info(
         .collect();
 
     commands.trigger(SendMessageEvent(ClientMessage::UpdatePlayerInfo {
-        position,
+        input,
         bullets,
     }));
 }
@@ -1,7 +1,9 @@
 use bevy::prelude::*;
 use shooting_game_shared::ServerMessage;
 
+use super::prediction::InputHistory;
 use crate::{
+    components::{SelfPlayer, Spaceship},
     flow::online_game::{connection::ReceiveMessageEvent, trigger::UpdatePositionEvent},
     res::PlayerTag,
     states::OnlineGameState,
@@ -20,26 +22,43 @@ fn update_player_info(
     mut commands: Commands,
     current_state: ResMut<State<OnlineGameState>>,
     self_player_tag: Res<PlayerTag>,
+    mut spaceship_q: Query<&mut Transform, (With<Spaceship>, With<SelfPlayer>)>,
+    mut input_history: ResMut<InputHistory>,
 ) {
     match current_state.get() {
         OnlineGameState::Ready | OnlineGameState::InPlay => {}
         _ => return,
     }
 
-    match trigger.event().0.clone() {
-        ServerMessage::UpdatePosition {
-            player_tag,
-            position,
-            bullets,
-        } => {
-            if player_tag != self_player_tag.0 {
-                commands.trigger(UpdatePositionEvent {
-                    player_tag,
-                    position: Vec2::new(position.0, position.1),
-                    bullets,
-                });
+    let ServerMessage::Snapshot {
+        ref players,
+        ref bullets,
+        ..
+    } = trigger.event().0
+    else {
+        return;
+    };
+    for (player_tag, position, acknowledged) in players.iter() {
+        if *player_tag == self_player_tag.0 {
+            if let Ok(mut transform) = spaceship_q.single_mut() {
+                let corrected = input_history.reconcile(
+                    *acknowledged,
+                    Vec2::new(position.0, position.1),
+                    transform.translation.truncate(),
+                );
+                transform.translation.x = corrected.x;
+                transform.translation.y = corrected.y;
             }
+            continue;
         }
-        _ => {}
+        commands.trigger(UpdatePositionEvent {
+            player_tag: *player_tag,
+            position: Vec2::new(position.0, position.1),
+            bullets: bullets
+                .iter()
+                .filter(|(owner_tag, _)| owner_tag == player_tag)
+                .map(|(_, bullet)| *bullet)
+                .collect(),
+        });
     }
 }
@@ -5,7 +5,7 @@ use crate::components::{Player, Score};
 #[derive(Event)]
 pub struct AddScoreEvent {
     pub player_tag: u8,
-    pub score: u8,
+    pub score: u32,
 }
 
 pub struct AddScorePlugin;
@@ -19,8 +19,11 @@ impl Plugin for AddScorePlugin {
 fn add_score(ev: Trigger<AddScoreEvent>, mut score_q: Query<(&mut Score, &Player)>) {
     let event = ev.event();
     for (mut score, player) in score_q.iter_mut() {
+        // Every snapshot repeats the scoreboard, only actual changes are written
         if player.0 == event.player_tag {
-            score.0 = event.score;
+            if score.0 != event.score {
+                score.0 = event.score;
+            }
             break;
         }
     }
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::{Buff, FloatingText, Player, PowerUpKind, PowerUpTag, Spaceship},
+    res::AccessibilityOption,
+};
+
+// The server confirmed `player_tag` got the power-up, it is taken away from everyone else
+#[derive(Event)]
+pub struct CollectPowerUpEvent {
+    pub player_tag: u8,
+    pub power_up_tag: u16,
+    pub kind: PowerUpKind,
+}
+
+pub struct CollectPowerUpPlugin;
+
+impl Plugin for CollectPowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(collect_power_up);
+    }
+}
+
+fn collect_power_up(
+    ev: Trigger<CollectPowerUpEvent>,
+    mut commands: Commands,
+    power_up_q: Query<(Entity, &PowerUpTag, &Transform)>,
+    spaceship_q: Query<(Entity, &Player), With<Spaceship>>,
+    accessibility: Res<AccessibilityOption>,
+) {
+    let collected = ev.event();
+    if let Some((entity, _, transform)) = power_up_q
+        .iter()
+        .find(|(_, tag, _)| tag.0 == collected.power_up_tag)
+    {
+        commands.spawn(
+            FloatingText::new(transform.translation.truncate(), collected.kind.name())
+                .with_color(collected.kind.color(accessibility.color_blind)),
+        );
+        commands.entity(entity).despawn();
+    }
+    // Other players' spaceships get it too so everyone sees the buff color
+    let Some((spaceship, _)) = spaceship_q
+        .iter()
+        .find(|(_, player)| player.0 == collected.player_tag)
+    else {
+        return;
+    };
+    commands.entity(spaceship).insert(Buff::new(collected.kind));
+}
@@ -1,9 +1,6 @@
 use bevy::prelude::*;
 
-use crate::{
-    components::{EnemyTag, Explosion, UFO},
-    util::Position,
-};
+use crate::components::EnemyTag;
 
 #[derive(Event)]
 pub struct DestroyEnemyEvent(pub u16);
@@ -19,12 +16,12 @@ impl Plugin for DestroyEnemyPlugin {
 fn remove_enemy(
     ev: Trigger<DestroyEnemyEvent>,
     mut commands: Commands,
-    enemy_q: Query<(Entity, &UFO, &EnemyTag)>,
+    enemy_q: Query<(Entity, &EnemyTag)>,
 ) {
     let remove_enemy_tag = ev.event().0;
-    for (enemy, ufo, enemy_tag) in enemy_q.iter() {
+    // The explosion comes separately as an Effect from the server
+    for (enemy, enemy_tag) in enemy_q.iter() {
         if enemy_tag.0 == remove_enemy_tag {
-            commands.spawn(Explosion::new(ufo.get_position()));
             commands.entity(enemy).despawn();
             return;
         }
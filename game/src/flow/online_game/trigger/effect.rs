@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+use shooting_game_shared::EffectKind;
+
+use crate::components::{Explosion, ExplosionKind, PoolCommandsExt};
+
+// Spawned where the server says, so both players see it in the same spot
+#[derive(Event)]
+pub struct EffectEvent {
+    pub kind: EffectKind,
+    pub position: Vec2,
+}
+
+pub struct EffectPlugin;
+
+impl Plugin for EffectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(spawn_effect);
+    }
+}
+
+fn spawn_effect(ev: Trigger<EffectEvent>, mut commands: Commands) {
+    let kind = match ev.event().kind {
+        EffectKind::EnemyDestroyed => ExplosionKind::UfoDeath,
+        EffectKind::PlayerDamaged => ExplosionKind::BulletImpact,
+        EffectKind::PlayerDestroyed => ExplosionKind::PlayerDeath,
+    };
+    commands.spawn_pooled(Explosion::new(ev.event().position, kind));
+}
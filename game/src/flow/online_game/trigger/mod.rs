@@ -1,17 +1,27 @@
 mod add_score;
+mod collect_power_up;
 mod destroy_enemy;
+mod effect;
 mod player_damaged;
 mod remove_bullet;
+mod resume_state;
+mod revive;
 mod spawn_enemy;
+mod spawn_power_up;
 mod update_position;
 
 use bevy::prelude::*;
 
 pub use add_score::AddScoreEvent;
+pub use collect_power_up::CollectPowerUpEvent;
 pub use destroy_enemy::DestroyEnemyEvent;
+pub use effect::EffectEvent;
 pub use player_damaged::PlayerDamagedEvent;
 pub use remove_bullet::RemoveBulletEvent;
+pub use resume_state::ResumeStateEvent;
+pub use revive::{PlayerDownedEvent, PlayerRevivedEvent};
 pub use spawn_enemy::SpawnEnemyEvent;
+pub use spawn_power_up::SpawnPowerUpEvent;
 pub use update_position::UpdatePositionEvent;
 pub struct TriggerPlugin;
 
@@ -24,6 +34,11 @@ impl Plugin for TriggerPlugin {
             player_damaged::PlayerDamagedPlugin,
             add_score::AddScorePlugin,
             remove_bullet::RemoveBulletPlugin,
+            resume_state::ResumeStatePlugin,
+            spawn_power_up::SpawnPowerUpPlugin,
+            collect_power_up::CollectPowerUpPlugin,
+            effect::EffectPlugin,
+            revive::RevivePlugin,
         ));
     }
 }
@@ -1,9 +1,6 @@
 use bevy::prelude::*;
 
-use crate::{
-    components::{Explosion, Health, Invisible, Player, Spaceship},
-    util::Position,
-};
+use crate::components::{Health, Invisible, Player, Spaceship};
 
 #[derive(Event)]
 pub struct PlayerDamagedEvent {
@@ -28,20 +25,20 @@ impl Plugin for PlayerDamagedPlugin {
 fn player_damaged(
     ev: Trigger<PlayerDamagedEvent>,
     mut commands: Commands,
-    spaceship_q: Query<(Entity, &Player, &Spaceship)>,
+    spaceship_q: Query<(Entity, &Player), With<Spaceship>>,
     mut health_q: Query<(&mut Health, &Player)>,
 ) {
     let event = ev.event();
     for (mut health, player) in health_q.iter_mut() {
         if player.0 == event.tag {
-            health.0 = event.new_health;
+            health.current = event.new_health;
             break;
         }
     }
-    for (entity, player, spaceship) in spaceship_q.iter() {
+    // The explosion comes separately as an Effect from the server
+    for (entity, player) in spaceship_q.iter() {
         if player.0 == event.tag {
             if event.new_health == 0 {
-                commands.spawn(Explosion::new(spaceship.get_position()));
                 commands.entity(entity).despawn();
             } else {
                 commands.entity(entity).insert(Invisible::new());
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::components::BulletTag;
+use crate::components::{Bullet, BulletTag, Pierced, PoolCommandsExt};
 
 #[derive(Event)]
 pub struct RemoveBulletEvent(pub u16);
@@ -16,12 +16,12 @@ impl Plugin for RemoveBulletPlugin {
 fn remove_bullet(
     ev: Trigger<RemoveBulletEvent>,
     mut commands: Commands,
-    bullet_q: Query<(Entity, &BulletTag)>,
+    bullet_q: Query<(Entity, &BulletTag), Without<Pierced>>,
 ) {
     let event = ev.event();
     for (entity, bullet_tag) in bullet_q.iter() {
         if bullet_tag.0 == event.0 {
-            commands.entity(entity).despawn();
+            commands.release_pooled::<Bullet>(entity);
         }
     }
 }
@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::{Downed, Health, Player, Score, UFO},
+    flow::online_game::connection::Reconnecting,
+    res::PlayerTag,
+};
+
+use super::SpawnEnemyEvent;
+
+#[derive(Event)]
+pub struct ResumeStateEvent {
+    pub score: u8,
+    pub health: u8,
+    pub enemies: Vec<(u16, Vec2, Vec2)>,
+    pub downed: Vec<(u8, Vec2)>,
+}
+
+pub struct ResumeStatePlugin;
+
+impl Plugin for ResumeStatePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(resume_state);
+    }
+}
+
+fn resume_state(
+    ev: Trigger<ResumeStateEvent>,
+    mut commands: Commands,
+    player_tag: Res<PlayerTag>,
+    mut score_q: Query<(&mut Score, &Player)>,
+    mut health_q: Query<(&mut Health, &Player)>,
+    enemy_q: Query<Entity, With<UFO>>,
+    downed_q: Query<Entity, With<Downed>>,
+) {
+    let event = ev.event();
+    for (mut score, player) in score_q.iter_mut() {
+        if player.0 == player_tag.0 {
+            score.0 = event.score.into();
+        }
+    }
+    for (mut health, player) in health_q.iter_mut() {
+        if player.0 == player_tag.0 {
+            health.current = event.health;
+        }
+    }
+    // Enemies seen before the drop may already be gone on the server
+    for entity in enemy_q.iter() {
+        commands.entity(entity).despawn();
+    }
+    for (tag, position, velocity) in event.enemies.iter() {
+        commands.trigger(SpawnEnemyEvent {
+            tag: *tag,
+            position: *position,
+            velocity: *velocity,
+        });
+    }
+    // Beacons may have been revived or left behind while away
+    for entity in downed_q.iter() {
+        commands.entity(entity).despawn();
+    }
+    for (tag, position) in event.downed.iter() {
+        commands.spawn(Downed::new(*tag, *position));
+    }
+    commands.remove_resource::<Reconnecting>();
+}
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::{Downed, FloatingText, Health, Invisible, Player, Spaceship, Velocity},
+    res::PlayerNames,
+};
+
+#[derive(Event)]
+pub struct PlayerDownedEvent {
+    pub tag: u8,
+    pub position: Vec2,
+}
+
+#[derive(Event)]
+pub struct PlayerRevivedEvent {
+    pub tag: u8,
+    pub health: u8,
+    pub position: Vec2,
+}
+
+pub struct RevivePlugin;
+
+impl Plugin for RevivePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(player_downed).add_observer(player_revived);
+    }
+}
+
+fn player_downed(ev: Trigger<PlayerDownedEvent>, mut commands: Commands) {
+    let event = ev.event();
+    commands.spawn(Downed::new(event.tag, event.position));
+}
+
+fn player_revived(
+    ev: Trigger<PlayerRevivedEvent>,
+    mut commands: Commands,
+    downed_q: Query<(Entity, &Downed)>,
+    mut health_q: Query<(&mut Health, &Player)>,
+    player_names: Res<PlayerNames>,
+) {
+    let event = ev.event();
+    for (entity, downed) in downed_q.iter() {
+        if downed.player() == event.tag {
+            commands.entity(entity).despawn();
+        }
+    }
+    if let Some((mut health, _)) = health_q.iter_mut().find(|(_, owner)| owner.0 == event.tag) {
+        health.current = event.health;
+    } else {
+        warn!("Health not found in player_revived");
+    }
+    commands.spawn((
+        Player(event.tag),
+        Spaceship::new(event.position),
+        Velocity::from_vec2(Vec2::ZERO),
+        Invisible::new(),
+    ));
+    commands.spawn(FloatingText::new(
+        event.position,
+        format!("{} revived!", player_names.name(event.tag)),
+    ));
+}
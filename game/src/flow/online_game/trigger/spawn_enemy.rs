@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
 use crate::components::{EnemyTag, Velocity, UFO};
+use crate::res::EnemyCatalog;
 
 #[derive(Event)]
 pub struct SpawnEnemyEvent {
@@ -17,10 +18,10 @@ impl Plugin for SpawnEnemyPlugin {
     }
 }
 
-fn spawn_enemy(ev: Trigger<SpawnEnemyEvent>, mut commands: Commands) {
+fn spawn_enemy(ev: Trigger<SpawnEnemyEvent>, mut commands: Commands, catalog: Res<EnemyCatalog>) {
     let enemy = ev.event();
     commands.spawn((
-        UFO::new(enemy.position),
+        UFO::new(enemy.position, &catalog),
         EnemyTag(enemy.tag),
         Velocity::from_vec2(enemy.velocity),
     ));
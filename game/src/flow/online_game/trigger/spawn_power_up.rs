@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+
+use crate::components::{PowerUp, PowerUpKind, PowerUpTag, Velocity};
+
+#[derive(Event)]
+pub struct SpawnPowerUpEvent {
+    pub tag: u16,
+    pub kind: PowerUpKind,
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+pub struct SpawnPowerUpPlugin;
+
+impl Plugin for SpawnPowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(spawn_power_up);
+    }
+}
+
+fn spawn_power_up(ev: Trigger<SpawnPowerUpEvent>, mut commands: Commands) {
+    let power_up = ev.event();
+    commands.spawn((
+        PowerUp::new(power_up.kind, power_up.position),
+        PowerUpTag(power_up.tag),
+        Velocity::from_vec2(power_up.velocity),
+    ));
+}
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::components::{Bullet, Player, Spaceship};
+use crate::components::{Bullet, InterpolationBuffer, Player, PoolCommandsExt, Spaceship};
 
 #[derive(Event)]
 pub struct UpdatePositionEvent {
@@ -20,24 +20,43 @@ impl Plugin for UpdatePositionPlugin {
 fn update_position(
     trigger: Trigger<UpdatePositionEvent>,
     mut commands: Commands,
-    mut spaceships: Query<(&mut Transform, &Player), With<Spaceship>>,
+    mut spaceships: Query<
+        (
+            Entity,
+            &mut Transform,
+            &Player,
+            Option<&mut InterpolationBuffer>,
+        ),
+        With<Spaceship>,
+    >,
     bullets: Query<(Entity, &Player), With<Bullet>>,
+    time: Res<Time>,
 ) {
     let ev = trigger.event();
-    for (mut transform, player) in spaceships.iter_mut() {
-        if player.0 == ev.player_tag {
-            transform.translation.x = ev.position.x;
-            transform.translation.y = ev.position.y;
-            break;
+    for (entity, mut transform, player, buffer) in spaceships.iter_mut() {
+        if player.0 != ev.player_tag {
+            continue;
+        }
+        match buffer {
+            Some(mut buffer) => buffer.push(time.elapsed(), ev.position),
+            // First snapshot, nothing to interpolate from yet
+            None => {
+                transform.translation.x = ev.position.x;
+                transform.translation.y = ev.position.y;
+                let mut buffer = InterpolationBuffer::default();
+                buffer.push(time.elapsed(), ev.position);
+                commands.entity(entity).insert(buffer);
+            }
         }
+        break;
     }
     for (entity, player) in bullets.iter() {
         if player.0 == ev.player_tag {
-            commands.entity(entity).despawn();
+            commands.release_pooled::<Bullet>(entity);
         }
     }
     for bullet in ev.bullets.iter() {
-        commands.spawn(Bullet::by_player(
+        commands.spawn_pooled(Bullet::by_player(
             ev.player_tag,
             Vec2::new(bullet.0, bullet.1),
         ));
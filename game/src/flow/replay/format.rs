@@ -0,0 +1,215 @@
+use std::fs;
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::flow::shared::game_trigger::SpaceShipMovement;
+use crate::persistence::save_path;
+use crate::res::RunSettings;
+
+const REPLAY_FILE: &str = "replay.bin";
+const MAGIC: &[u8; 4] = b"SGRP";
+const VERSION: u8 = 4;
+// delta nanos (u32) + seed (u64) + input (u8) + charge (u8) + aim (u16)
+const FRAME_BYTES: usize = 16;
+// Never a real aim step
+const NO_AIM: u16 = u16::MAX;
+const SHOOT_FLAG: u8 = 0x80;
+// Older replays never set it, so they still load
+const MISSILE_FLAG: u8 = 0x40;
+const BOMB_FLAG: u8 = 0x20;
+const DASH_FLAG: u8 = 0x10;
+// In the charge byte, as the input byte has no bits left
+const CHARGE_HOLD_FLAG: u8 = 0x01;
+const CHARGE_RELEASE_FLAG: u8 = 0x02;
+
+// Everything needed to reproduce a single frame of a run
+#[derive(Clone, Copy)]
+pub struct ReplayFrame {
+    pub delta: Duration,
+    pub seed: u64,
+    pub movement: Option<SpaceShipMovement>,
+    pub shoot: bool,
+    pub missile: bool,
+    pub bomb: bool,
+    pub dash: bool,
+    pub charge_hold: bool,
+    pub charge_release: bool,
+    // Only recorded while aiming with the mouse, other modes never turn the ship
+    pub aim: Option<u16>,
+}
+
+pub struct Replay {
+    // Played back with these instead of the viewer's own
+    settings: RunSettings,
+    frames: Vec<ReplayFrame>,
+}
+
+impl Replay {
+    pub fn new(settings: RunSettings) -> Self {
+        Self {
+            settings,
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn settings(&self) -> &RunSettings {
+        &self.settings
+    }
+
+    pub fn push(&mut self, frame: ReplayFrame) {
+        self.frames.push(frame);
+    }
+
+    pub fn last_mut(&mut self) -> Option<&mut ReplayFrame> {
+        self.frames.last_mut()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&ReplayFrame> {
+        self.frames.get(index)
+    }
+
+    pub fn load() -> Option<Self> {
+        let bytes = fs::read(save_path(REPLAY_FILE)?).ok()?;
+        let replay = Self::from_bytes(&bytes);
+        if replay.is_none() {
+            warn!("Failed to parse replay");
+        }
+        replay
+    }
+
+    pub fn save(&self) {
+        let Some(path) = save_path(REPLAY_FILE) else {
+            warn!("No data directory to save replay");
+            return;
+        };
+        if let Some(dir) = path.parent() {
+            if let Err(e) = fs::create_dir_all(dir) {
+                warn!("Failed to create save directory: {e}");
+                return;
+            }
+        }
+        if let Err(e) = fs::write(path, self.to_bytes()) {
+            warn!("Failed to save replay: {e}");
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        // RON like settings.ron, a setting added later falls back to its default
+        let settings = ron::to_string(&self.settings).unwrap_or_else(|e| {
+            warn!("Failed to serialize replay settings: {e}");
+            String::new()
+        });
+        let mut bytes =
+            Vec::with_capacity(MAGIC.len() + 9 + settings.len() + self.frames.len() * FRAME_BYTES);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&(settings.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(settings.as_bytes());
+        bytes.extend_from_slice(&(self.frames.len() as u32).to_le_bytes());
+        for frame in self.frames.iter() {
+            // Frame delta is clamped by Time<Virtual>, so it always fits
+            bytes.extend_from_slice(&(frame.delta.as_nanos() as u32).to_le_bytes());
+            bytes.extend_from_slice(&frame.seed.to_le_bytes());
+            let mut input = encode_movement(frame.movement);
+            if frame.shoot {
+                input |= SHOOT_FLAG;
+            }
+            if frame.missile {
+                input |= MISSILE_FLAG;
+            }
+            if frame.bomb {
+                input |= BOMB_FLAG;
+            }
+            if frame.dash {
+                input |= DASH_FLAG;
+            }
+            bytes.push(input);
+            let mut charge = 0;
+            if frame.charge_hold {
+                charge |= CHARGE_HOLD_FLAG;
+            }
+            if frame.charge_release {
+                charge |= CHARGE_RELEASE_FLAG;
+            }
+            bytes.push(charge);
+            bytes.extend_from_slice(&frame.aim.unwrap_or(NO_AIM).to_le_bytes());
+        }
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let (magic, rest) = bytes.split_at_checked(MAGIC.len())?;
+        let (&version, rest) = rest.split_first()?;
+        if magic != MAGIC || version != VERSION {
+            return None;
+        }
+        let (settings_len, rest) = rest.split_at_checked(4)?;
+        let settings_len = u32::from_le_bytes(settings_len.try_into().ok()?) as usize;
+        let (settings, rest) = rest.split_at_checked(settings_len)?;
+        let settings = ron::from_str(std::str::from_utf8(settings).ok()?).ok()?;
+        let (count, rest) = rest.split_at_checked(4)?;
+        let count = u32::from_le_bytes(count.try_into().ok()?) as usize;
+        if rest.len() != count * FRAME_BYTES {
+            return None;
+        }
+        let frames = rest
+            .chunks_exact(FRAME_BYTES)
+            .map(|chunk| {
+                let delta = u32::from_le_bytes(chunk[0..4].try_into().ok()?);
+                let seed = u64::from_le_bytes(chunk[4..12].try_into().ok()?);
+                let input = chunk[12];
+                let charge = chunk[13];
+                let aim = u16::from_le_bytes(chunk[14..16].try_into().ok()?);
+                Some(ReplayFrame {
+                    delta: Duration::from_nanos(delta.into()),
+                    seed,
+                    movement: decode_movement(
+                        input & !(SHOOT_FLAG | MISSILE_FLAG | BOMB_FLAG | DASH_FLAG),
+                    )?,
+                    shoot: input & SHOOT_FLAG != 0,
+                    missile: input & MISSILE_FLAG != 0,
+                    bomb: input & BOMB_FLAG != 0,
+                    dash: input & DASH_FLAG != 0,
+                    charge_hold: charge & CHARGE_HOLD_FLAG != 0,
+                    charge_release: charge & CHARGE_RELEASE_FLAG != 0,
+                    aim: (aim != NO_AIM).then_some(aim),
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self { settings, frames })
+    }
+}
+
+fn encode_movement(movement: Option<SpaceShipMovement>) -> u8 {
+    match movement {
+        None => 0,
+        Some(SpaceShipMovement::Up) => 1,
+        Some(SpaceShipMovement::UpRight) => 2,
+        Some(SpaceShipMovement::Right) => 3,
+        Some(SpaceShipMovement::DownRight) => 4,
+        Some(SpaceShipMovement::Down) => 5,
+        Some(SpaceShipMovement::DownLeft) => 6,
+        Some(SpaceShipMovement::Left) => 7,
+        Some(SpaceShipMovement::UpLeft) => 8,
+        Some(SpaceShipMovement::Rest) => 9,
+    }
+}
+
+// Outer None means the byte is corrupted
+fn decode_movement(byte: u8) -> Option<Option<SpaceShipMovement>> {
+    let movement = match byte {
+        0 => None,
+        1 => Some(SpaceShipMovement::Up),
+        2 => Some(SpaceShipMovement::UpRight),
+        3 => Some(SpaceShipMovement::Right),
+        4 => Some(SpaceShipMovement::DownRight),
+        5 => Some(SpaceShipMovement::Down),
+        6 => Some(SpaceShipMovement::DownLeft),
+        7 => Some(SpaceShipMovement::Left),
+        8 => Some(SpaceShipMovement::UpLeft),
+        9 => Some(SpaceShipMovement::Rest),
+        _ => return None,
+    };
+    Some(movement)
+}
@@ -0,0 +1,25 @@
+mod format;
+mod playback;
+mod recording;
+
+use bevy::prelude::*;
+
+use crate::states::GameState;
+
+pub use format::Replay;
+pub use playback::ReplayPlayback;
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((recording::RecordingPlugin, playback::PlaybackPlugin))
+            .add_systems(OnEnter(GameState::Ready), reset_fixed_overstep);
+    }
+}
+
+// Leftover time from the menu or last run would shift fixed ticks between recording and playback
+fn reset_fixed_overstep(mut fixed_time: ResMut<Time<Fixed>>) {
+    let overstep = fixed_time.overstep();
+    fixed_time.discard_overstep(overstep);
+}
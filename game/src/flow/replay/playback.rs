@@ -0,0 +1,158 @@
+use bevy::app::RunFixedMainLoopSystem;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+
+use crate::components::{SelfPlayer, Spaceship};
+use crate::flow::shared::game_trigger::{
+    ChargeShotEvent, DashEvent, FireBombEvent, FireMissileEvent, ShootBulletEvent,
+    SpaceShipMovement, SpaceShipMovementEvent,
+};
+use crate::res::{GameRng, PlayerRunSettings, RunSettingsParam};
+use crate::states::{AppState, GameState, PauseState};
+use crate::ui_components::Blink;
+use crate::util::cleanup_components;
+
+use super::format::{Replay, ReplayFrame};
+
+pub struct PlaybackPlugin;
+
+impl Plugin for PlaybackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            // Before the game starts, so everything set up on entering it sees the replay's
+            OnExit(AppState::MainMenu),
+            apply_replay_settings.run_if(resource_exists::<ReplayPlayback>),
+        )
+        .add_systems(
+            OnEnter(AppState::Game),
+            spawn_replay_indicator.run_if(resource_exists::<ReplayPlayback>),
+        )
+        .add_systems(
+            RunFixedMainLoop,
+            advance_playback
+                .in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop)
+                .run_if(in_state(AppState::Game).and(resource_exists::<ReplayPlayback>))
+                .run_if(not(in_state(PauseState::Paused))),
+        )
+        .add_systems(
+            Update,
+            apply_playback_input
+                .run_if(in_state(GameState::InPlay).and(resource_exists::<ReplayPlayback>))
+                .run_if(not(in_state(PauseState::Paused))),
+        )
+        .add_systems(
+            Last,
+            schedule_next_frame_delta.run_if(resource_exists::<ReplayPlayback>),
+        )
+        .add_systems(
+            OnExit(AppState::Game),
+            (stop_playback, cleanup_components::<ReplayIndicator>),
+        );
+    }
+}
+
+// Present while a saved run is being played back instead of read from the controls
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    replay: Replay,
+    cursor: usize,
+    current: Option<ReplayFrame>,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            cursor: 0,
+            current: None,
+        }
+    }
+}
+
+#[derive(Component)]
+struct ReplayIndicator;
+
+fn apply_replay_settings(
+    mut commands: Commands,
+    playback: Res<ReplayPlayback>,
+    mut run_settings: RunSettingsParam,
+) {
+    let player_settings = run_settings.replace(playback.replay.settings().clone());
+    commands.insert_resource(PlayerRunSettings(player_settings));
+}
+
+fn spawn_replay_indicator(mut commands: Commands) {
+    commands.spawn((
+        ReplayIndicator,
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(10.),
+            bottom: Val::Px(10.),
+            ..default()
+        },
+        children![(Text::new("REPLAY"), Blink::new_with_speed(0.02))],
+    ));
+}
+
+fn advance_playback(mut playback: ResMut<ReplayPlayback>, mut game_rng: ResMut<GameRng>) {
+    let cursor = playback.cursor;
+    playback.current = playback.replay.get(cursor).copied();
+    let Some(frame) = playback.current else {
+        return;
+    };
+    game_rng.reseed(frame.seed);
+    playback.cursor += 1;
+}
+
+fn apply_playback_input(
+    mut commands: Commands,
+    playback: Res<ReplayPlayback>,
+    mut spaceship_q: Query<&mut Spaceship, With<SelfPlayer>>,
+) {
+    let Some(frame) = playback.current else {
+        return;
+    };
+    // Turned first, the shots of the frame go where it aims like they did when recorded
+    if let Some(aim) = frame.aim {
+        if let Ok(mut spaceship) = spaceship_q.single_mut() {
+            spaceship.set_aim_step(aim);
+        }
+    }
+    if let Some(movement) = frame.movement {
+        commands.trigger(SpaceShipMovementEvent(movement));
+    }
+    if frame.shoot {
+        commands.trigger(ShootBulletEvent);
+    }
+    // Hold before release, in the order the controls send them
+    if frame.charge_hold {
+        commands.trigger(ChargeShotEvent::Hold);
+    }
+    if frame.charge_release {
+        commands.trigger(ChargeShotEvent::Release);
+    }
+    if frame.missile {
+        commands.trigger(FireMissileEvent);
+    }
+    if frame.bomb {
+        commands.trigger(FireBombEvent);
+    }
+    if frame.dash {
+        let movement = frame.movement.unwrap_or(SpaceShipMovement::Rest);
+        commands.trigger(DashEvent(movement));
+    }
+}
+
+// The next frame has to advance time by exactly what was recorded
+fn schedule_next_frame_delta(mut commands: Commands, playback: Res<ReplayPlayback>) {
+    let strategy = match playback.replay.get(playback.cursor) {
+        Some(frame) => TimeUpdateStrategy::ManualDuration(frame.delta),
+        None => TimeUpdateStrategy::Automatic,
+    };
+    commands.insert_resource(strategy);
+}
+
+fn stop_playback(mut commands: Commands) {
+    commands.remove_resource::<ReplayPlayback>();
+    commands.insert_resource(TimeUpdateStrategy::Automatic);
+}
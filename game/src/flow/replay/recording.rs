@@ -0,0 +1,160 @@
+use bevy::app::RunFixedMainLoopSystem;
+use bevy::prelude::*;
+
+use crate::components::{SelfPlayer, Spaceship};
+use crate::flow::shared::game_trigger::{
+    ChargeShotEvent, DashEvent, FireBombEvent, FireMissileEvent, ShootBulletEvent,
+    SpaceShipMovementEvent,
+};
+use crate::res::{
+    ContinueRun, ControlMode, ControlOption, DailyChallenge, DemoMode, GameRng, LocalCoop,
+    RunSettingsParam, TutorialMode,
+};
+use crate::states::{AppState, GameState, PauseState};
+
+use super::format::{Replay, ReplayFrame};
+use super::playback::ReplayPlayback;
+
+pub struct RecordingPlugin;
+
+impl Plugin for RecordingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(GameState::Ready),
+            // Recorded input has no player, so local co-op runs are not recorded
+            start_recording
+                .run_if(not(resource_exists::<ReplayPlayback>))
+                .run_if(not(resource_exists::<LocalCoop>))
+                .run_if(not(resource_exists::<TutorialMode>))
+                .run_if(not(resource_exists::<DemoMode>))
+                // Playback would run the daily run against the regular stages
+                .run_if(not(resource_exists::<DailyChallenge>))
+                // and a continued run from the first stage
+                .run_if(not(resource_exists::<ContinueRun>)),
+        )
+        .add_systems(
+            RunFixedMainLoop,
+            record_frame
+                .in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop)
+                .run_if(resource_exists::<ReplayRecorder>)
+                .run_if(not(in_state(PauseState::Paused))),
+        )
+        // After the controls have turned the ship for the frame
+        .add_systems(
+            PostUpdate,
+            record_aim
+                .run_if(resource_exists::<ReplayRecorder>)
+                .run_if(in_state(GameState::InPlay))
+                .run_if(mouse_aiming),
+        )
+        .add_systems(
+            OnEnter(GameState::GameOver),
+            save_recording.run_if(resource_exists::<ReplayRecorder>),
+        )
+        .add_systems(OnExit(AppState::Game), remove_recorder)
+        .add_observer(record_movement)
+        .add_observer(record_shoot)
+        .add_observer(record_missile)
+        .add_observer(record_bomb)
+        .add_observer(record_dash)
+        .add_observer(record_charge);
+    }
+}
+
+#[derive(Resource)]
+struct ReplayRecorder(Replay);
+
+fn mouse_aiming(control_option: Res<ControlOption>) -> bool {
+    control_option.mode == ControlMode::MouseAim
+}
+
+// Inserted rather than initialised so a retry starts from an empty recording
+fn start_recording(mut commands: Commands, run_settings: RunSettingsParam) {
+    commands.insert_resource(ReplayRecorder(Replay::new(run_settings.get())));
+}
+
+fn record_frame(
+    mut recorder: ResMut<ReplayRecorder>,
+    mut game_rng: ResMut<GameRng>,
+    time: Res<Time>,
+) {
+    let seed = game_rng.next_seed();
+    game_rng.reseed(seed);
+    recorder.0.push(ReplayFrame {
+        delta: time.delta(),
+        seed,
+        movement: None,
+        shoot: false,
+        missile: false,
+        bomb: false,
+        dash: false,
+        charge_hold: false,
+        charge_release: false,
+        aim: None,
+    });
+}
+
+fn record_aim(
+    mut recorder: ResMut<ReplayRecorder>,
+    spaceship_q: Query<&Spaceship, With<SelfPlayer>>,
+) {
+    let Ok(spaceship) = spaceship_q.single() else {
+        return;
+    };
+    if let Some(frame) = recorder.0.last_mut() {
+        frame.aim = Some(spaceship.aim_step());
+    }
+}
+
+// Only the last movement of a frame matters as velocity is applied in FixedUpdate
+fn record_movement(
+    trigger: Trigger<SpaceShipMovementEvent>,
+    recorder: Option<ResMut<ReplayRecorder>>,
+) {
+    if let Some(frame) = recorder.and_then(|recorder| recorder.into_inner().0.last_mut()) {
+        frame.movement = Some(trigger.event().0);
+    }
+}
+
+fn record_shoot(_trigger: Trigger<ShootBulletEvent>, recorder: Option<ResMut<ReplayRecorder>>) {
+    if let Some(frame) = recorder.and_then(|recorder| recorder.into_inner().0.last_mut()) {
+        frame.shoot = true;
+    }
+}
+
+fn record_missile(_trigger: Trigger<FireMissileEvent>, recorder: Option<ResMut<ReplayRecorder>>) {
+    if let Some(frame) = recorder.and_then(|recorder| recorder.into_inner().0.last_mut()) {
+        frame.missile = true;
+    }
+}
+
+fn record_bomb(_trigger: Trigger<FireBombEvent>, recorder: Option<ResMut<ReplayRecorder>>) {
+    if let Some(frame) = recorder.and_then(|recorder| recorder.into_inner().0.last_mut()) {
+        frame.bomb = true;
+    }
+}
+
+// Replays dash along the frame's movement, which is what the keyboard dashes along too
+fn record_dash(_trigger: Trigger<DashEvent>, recorder: Option<ResMut<ReplayRecorder>>) {
+    if let Some(frame) = recorder.and_then(|recorder| recorder.into_inner().0.last_mut()) {
+        frame.dash = true;
+    }
+}
+
+fn record_charge(trigger: Trigger<ChargeShotEvent>, recorder: Option<ResMut<ReplayRecorder>>) {
+    if let Some(frame) = recorder.and_then(|recorder| recorder.into_inner().0.last_mut()) {
+        match trigger.event() {
+            ChargeShotEvent::Hold => frame.charge_hold = true,
+            ChargeShotEvent::Release => frame.charge_release = true,
+        }
+    }
+}
+
+fn save_recording(mut commands: Commands, recorder: Res<ReplayRecorder>) {
+    recorder.0.save();
+    commands.remove_resource::<ReplayRecorder>();
+}
+
+fn remove_recorder(mut commands: Commands) {
+    commands.remove_resource::<ReplayRecorder>();
+}
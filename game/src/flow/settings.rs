@@ -0,0 +1,722 @@
+use bevy::app::App;
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+
+use crate::cleanup::DespawnOnExit;
+use crate::res::{
+    AccessibilityOption, AdaptiveDifficultyOption, EdgeMode, EffectOption, FriendlyFireOption,
+    GameSpeedOption, KeyAction, KeyBindings, LivesOption, RumbleOption, WeaponMode,
+};
+use crate::states::AppState;
+use crate::ui_components::{FocusBack, FocusLock, InteractionUI, MainContainer};
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Settings), show_settings)
+            .add_systems(
+                Update,
+                (
+                    (
+                        handle_binding_selection,
+                        handle_rebind_key,
+                        handle_reset_button_interaction,
+                        handle_effect_toggle,
+                        handle_accessibility_toggle,
+                        handle_lives_toggle,
+                        handle_weapon_toggle,
+                        handle_edge_toggle,
+                        handle_speed_toggle,
+                        handle_adaptive_toggle,
+                        handle_friendly_fire_toggle,
+                        handle_rumble_slider,
+                    ),
+                    (
+                        handle_binding_text,
+                        handle_effect_toggle_text,
+                        handle_accessibility_toggle_text,
+                        handle_lives_toggle_text,
+                        handle_weapon_toggle_text,
+                        handle_edge_toggle_text,
+                        handle_speed_toggle_text,
+                        handle_adaptive_toggle_text,
+                        handle_friendly_fire_toggle_text,
+                        handle_rumble_slider_display,
+                    ),
+                    handle_back_button_interaction,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Settings)),
+            )
+            .add_systems(OnExit(AppState::Settings), remove_rebinding);
+    }
+}
+
+#[derive(Component)]
+struct Settings;
+
+#[derive(Component)]
+struct BackButton;
+
+#[derive(Component)]
+struct ResetButton;
+
+#[derive(Component)]
+struct LivesToggle;
+
+#[derive(Component)]
+struct WeaponToggle;
+
+#[derive(Component)]
+struct EdgeToggle;
+
+#[derive(Component)]
+struct SpeedToggle;
+
+#[derive(Component)]
+struct AdaptiveToggle;
+
+#[derive(Component)]
+struct FriendlyFireToggle;
+
+#[derive(Component)]
+struct RumbleText;
+
+#[derive(Component)]
+struct RumbleSlider;
+
+#[derive(Component)]
+struct RumbleSliderFill;
+
+#[derive(Component, Clone, Copy)]
+enum EffectToggle {
+    ScreenShake,
+    HitFlash,
+    SlowMotion,
+    Bloom,
+    Crt,
+}
+
+impl EffectToggle {
+    const ALL: [EffectToggle; 5] = [
+        EffectToggle::ScreenShake,
+        EffectToggle::HitFlash,
+        EffectToggle::SlowMotion,
+        EffectToggle::Bloom,
+        EffectToggle::Crt,
+    ];
+
+    fn value(&self, effect_option: &EffectOption) -> bool {
+        match self {
+            EffectToggle::ScreenShake => effect_option.screen_shake,
+            EffectToggle::HitFlash => effect_option.hit_flash,
+            EffectToggle::SlowMotion => effect_option.slow_motion,
+            EffectToggle::Bloom => effect_option.bloom,
+            EffectToggle::Crt => effect_option.crt,
+        }
+    }
+
+    fn text(&self, effect_option: &EffectOption) -> String {
+        let label = match self {
+            EffectToggle::ScreenShake => "Screen Shake",
+            EffectToggle::HitFlash => "Hit Flash",
+            EffectToggle::SlowMotion => "Slow Motion",
+            EffectToggle::Bloom => "Bloom",
+            EffectToggle::Crt => "CRT Filter",
+        };
+        let state = if self.value(effect_option) {
+            "On"
+        } else {
+            "Off"
+        };
+        format!("{label}: {state}")
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+enum AccessibilityToggle {
+    ColorBlind,
+    DisableFlashing,
+    ReduceParticles,
+}
+
+impl AccessibilityToggle {
+    const ALL: [AccessibilityToggle; 3] = [
+        AccessibilityToggle::ColorBlind,
+        AccessibilityToggle::DisableFlashing,
+        AccessibilityToggle::ReduceParticles,
+    ];
+
+    fn value(&self, accessibility: &AccessibilityOption) -> bool {
+        match self {
+            AccessibilityToggle::ColorBlind => accessibility.color_blind,
+            AccessibilityToggle::DisableFlashing => accessibility.disable_flashing,
+            AccessibilityToggle::ReduceParticles => accessibility.reduce_particles,
+        }
+    }
+
+    fn text(&self, accessibility: &AccessibilityOption) -> String {
+        let label = match self {
+            AccessibilityToggle::ColorBlind => "Color Blind Mode",
+            AccessibilityToggle::DisableFlashing => "Disable Flashing",
+            AccessibilityToggle::ReduceParticles => "Reduce Particles",
+        };
+        let state = if self.value(accessibility) {
+            "On"
+        } else {
+            "Off"
+        };
+        format!("{label}: {state}")
+    }
+}
+
+// The action waiting for its next key press
+#[derive(Resource)]
+struct Rebinding(KeyAction);
+
+#[allow(clippy::too_many_arguments)]
+fn show_settings(
+    mut commands: Commands,
+    key_bindings: Res<KeyBindings>,
+    effect_option: Res<EffectOption>,
+    accessibility: Res<AccessibilityOption>,
+    lives_option: Res<LivesOption>,
+    weapon_mode: Res<WeaponMode>,
+    edge_mode: Res<EdgeMode>,
+    game_speed_option: Res<GameSpeedOption>,
+    adaptive_option: Res<AdaptiveDifficultyOption>,
+    friendly_fire_option: Res<FriendlyFireOption>,
+    rumble_option: Res<RumbleOption>,
+) {
+    commands
+        .spawn((Settings, MainContainer, DespawnOnExit(AppState::Settings)))
+        .with_children(|settings_background| {
+            settings_background.spawn((
+                Text::new("Settings"),
+                TextFont::from_font_size(40.),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+            settings_background.spawn((
+                Node {
+                    margin: UiRect::vertical(Val::Px(30.)),
+                    ..default()
+                },
+                Text::new("Click an action then press a key to rebind it\nPress Escape to cancel"),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+            for action in KeyAction::ALL {
+                settings_background.spawn((
+                    action,
+                    InteractionUI,
+                    Text::new(binding_text(action, &key_bindings, None)),
+                ));
+            }
+            settings_background.spawn((
+                Node {
+                    margin: UiRect::top(Val::Px(30.)),
+                    ..default()
+                },
+                Text::new("Effects"),
+                TextColor(Color::srgba(1., 0.5, 0., 1.)),
+            ));
+            for effect_toggle in EffectToggle::ALL {
+                settings_background.spawn((
+                    effect_toggle,
+                    InteractionUI,
+                    Text::new(effect_toggle.text(&effect_option)),
+                ));
+            }
+            settings_background
+                .spawn(Node {
+                    column_gap: Val::Px(10.),
+                    align_items: AlignItems::Center,
+                    ..default()
+                })
+                .with_children(|rumble_row| {
+                    rumble_row.spawn((RumbleText, Text::new(rumble_text(&rumble_option))));
+                    rumble_row
+                        .spawn((
+                            RumbleSlider,
+                            InteractionUI,
+                            RelativeCursorPosition::default(),
+                            Node {
+                                width: Val::Px(160.),
+                                height: Val::Px(12.),
+                                ..default()
+                            },
+                            BackgroundColor::from(Color::srgba(0.3, 0.3, 0.3, 1.)),
+                        ))
+                        .with_child((
+                            RumbleSliderFill,
+                            Node {
+                                width: Val::Percent(rumble_option.intensity * 100.),
+                                height: Val::Percent(100.),
+                                ..default()
+                            },
+                            BackgroundColor::from(Color::srgba(1., 0.5, 0., 1.)),
+                        ));
+                });
+            settings_background.spawn((
+                Node {
+                    margin: UiRect::top(Val::Px(30.)),
+                    ..default()
+                },
+                Text::new("Accessibility"),
+                TextColor(Color::srgba(1., 0.5, 0., 1.)),
+            ));
+            for accessibility_toggle in AccessibilityToggle::ALL {
+                settings_background.spawn((
+                    accessibility_toggle,
+                    InteractionUI,
+                    Text::new(accessibility_toggle.text(&accessibility)),
+                ));
+            }
+            settings_background.spawn((
+                Node {
+                    margin: UiRect::top(Val::Px(30.)),
+                    ..default()
+                },
+                Text::new("Game"),
+                TextColor(Color::srgba(1., 0.5, 0., 1.)),
+            ));
+            settings_background.spawn((
+                LivesToggle,
+                InteractionUI,
+                Text::new(lives_text(&lives_option)),
+            ));
+            settings_background.spawn((
+                WeaponToggle,
+                InteractionUI,
+                Text::new(weapon_text(&weapon_mode)),
+            ));
+            settings_background.spawn((
+                EdgeToggle,
+                InteractionUI,
+                Text::new(edge_text(&edge_mode)),
+            ));
+            settings_background.spawn((
+                SpeedToggle,
+                InteractionUI,
+                Text::new(speed_text(&game_speed_option)),
+            ));
+            settings_background.spawn((
+                AdaptiveToggle,
+                InteractionUI,
+                Text::new(adaptive_text(&adaptive_option)),
+            ));
+            settings_background.spawn((
+                FriendlyFireToggle,
+                InteractionUI,
+                Text::new(friendly_fire_text(&friendly_fire_option)),
+            ));
+            settings_background
+                .spawn(Node {
+                    height: Val::Percent(100.),
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::FlexEnd,
+                    row_gap: Val::Px(10.),
+                    ..default()
+                })
+                .with_children(|button_container| {
+                    button_container
+                        .spawn((
+                            ResetButton,
+                            InteractionUI,
+                            Node {
+                                align_self: AlignSelf::FlexEnd,
+                                width: Val::Px(200.),
+                                height: Val::Px(50.),
+                                border: UiRect::all(Val::Px(2.)),
+                                display: Display::Flex,
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                            BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
+                            BorderColor::from(Color::BLACK),
+                        ))
+                        .with_child(Text::new("Reset Keys"));
+                    button_container
+                        .spawn((
+                            BackButton,
+                            FocusBack,
+                            InteractionUI,
+                            Node {
+                                align_self: AlignSelf::FlexEnd,
+                                width: Val::Px(120.),
+                                height: Val::Px(50.),
+                                border: UiRect::all(Val::Px(2.)),
+                                display: Display::Flex,
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                            BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
+                            BorderColor::from(Color::BLACK),
+                        ))
+                        .with_child(Text::new("Back"));
+                });
+        });
+}
+
+fn binding_text(
+    action: KeyAction,
+    key_bindings: &KeyBindings,
+    rebinding: Option<&Rebinding>,
+) -> String {
+    match rebinding {
+        Some(rebinding) if rebinding.0 == action => format!("{}: Press a key", action.label()),
+        _ => format!("{}: {:?}", action.label(), key_bindings.key(action)),
+    }
+}
+
+fn handle_binding_selection(
+    mut commands: Commands,
+    action_query: Query<(&KeyAction, &Interaction), Changed<Interaction>>,
+) {
+    for (action, interaction) in action_query.iter() {
+        if *interaction == Interaction::Pressed {
+            commands.insert_resource(Rebinding(*action));
+            commands.insert_resource(FocusLock);
+        }
+    }
+}
+
+fn handle_rebind_key(
+    mut commands: Commands,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    rebinding: Option<Res<Rebinding>>,
+    mut key_bindings: ResMut<KeyBindings>,
+) {
+    let Some(rebinding) = rebinding else {
+        keyboard_events.clear();
+        return;
+    };
+    let Some(keyboard_event) = keyboard_events
+        .read()
+        .find(|keyboard_event| keyboard_event.state == ButtonState::Pressed)
+    else {
+        return;
+    };
+    if keyboard_event.key_code != KeyCode::Escape {
+        key_bindings.bind(rebinding.0, keyboard_event.key_code);
+    }
+    commands.remove_resource::<Rebinding>();
+    commands.remove_resource::<FocusLock>();
+}
+
+fn handle_reset_button_interaction(
+    reset_button_query: Query<&Interaction, (Changed<Interaction>, With<ResetButton>)>,
+    mut key_bindings: ResMut<KeyBindings>,
+) {
+    for interaction in reset_button_query.iter() {
+        if *interaction == Interaction::Pressed {
+            *key_bindings = KeyBindings::default();
+        }
+    }
+}
+
+fn handle_effect_toggle(
+    effect_toggle_query: Query<(&EffectToggle, &Interaction), Changed<Interaction>>,
+    mut effect_option: ResMut<EffectOption>,
+) {
+    for (effect_toggle, interaction) in effect_toggle_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match effect_toggle {
+            EffectToggle::ScreenShake => effect_option.screen_shake = !effect_option.screen_shake,
+            EffectToggle::HitFlash => effect_option.hit_flash = !effect_option.hit_flash,
+            EffectToggle::SlowMotion => effect_option.slow_motion = !effect_option.slow_motion,
+            EffectToggle::Bloom => effect_option.bloom = !effect_option.bloom,
+            EffectToggle::Crt => effect_option.crt = !effect_option.crt,
+        }
+    }
+}
+
+fn handle_effect_toggle_text(
+    mut effect_toggle_query: Query<(&EffectToggle, &mut Text)>,
+    effect_option: Res<EffectOption>,
+) {
+    if effect_option.is_changed() {
+        for (effect_toggle, mut text) in effect_toggle_query.iter_mut() {
+            text.0 = effect_toggle.text(&effect_option);
+        }
+    }
+}
+
+fn handle_accessibility_toggle(
+    accessibility_toggle_query: Query<(&AccessibilityToggle, &Interaction), Changed<Interaction>>,
+    mut accessibility: ResMut<AccessibilityOption>,
+) {
+    for (accessibility_toggle, interaction) in accessibility_toggle_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match accessibility_toggle {
+            AccessibilityToggle::ColorBlind => {
+                accessibility.color_blind = !accessibility.color_blind
+            }
+            AccessibilityToggle::DisableFlashing => {
+                accessibility.disable_flashing = !accessibility.disable_flashing
+            }
+            AccessibilityToggle::ReduceParticles => {
+                accessibility.reduce_particles = !accessibility.reduce_particles
+            }
+        }
+    }
+}
+
+fn handle_accessibility_toggle_text(
+    mut accessibility_toggle_query: Query<(&AccessibilityToggle, &mut Text)>,
+    accessibility: Res<AccessibilityOption>,
+) {
+    if accessibility.is_changed() {
+        for (accessibility_toggle, mut text) in accessibility_toggle_query.iter_mut() {
+            text.0 = accessibility_toggle.text(&accessibility);
+        }
+    }
+}
+
+fn lives_text(lives_option: &LivesOption) -> String {
+    format!("Lives: {}", lives_option.lives)
+}
+
+fn handle_lives_toggle(
+    lives_toggle_query: Query<&Interaction, (Changed<Interaction>, With<LivesToggle>)>,
+    mut lives_option: ResMut<LivesOption>,
+) {
+    for interaction in lives_toggle_query.iter() {
+        if *interaction == Interaction::Pressed {
+            lives_option.next();
+        }
+    }
+}
+
+fn handle_lives_toggle_text(
+    mut lives_toggle_query: Query<&mut Text, With<LivesToggle>>,
+    lives_option: Res<LivesOption>,
+) {
+    if lives_option.is_changed() {
+        for mut text in lives_toggle_query.iter_mut() {
+            text.0 = lives_text(&lives_option);
+        }
+    }
+}
+
+fn weapon_text(weapon_mode: &WeaponMode) -> String {
+    format!("Weapon: {weapon_mode:?}")
+}
+
+fn handle_weapon_toggle(
+    weapon_toggle_query: Query<&Interaction, (Changed<Interaction>, With<WeaponToggle>)>,
+    mut weapon_mode: ResMut<WeaponMode>,
+) {
+    for interaction in weapon_toggle_query.iter() {
+        if *interaction == Interaction::Pressed {
+            weapon_mode.next();
+        }
+    }
+}
+
+fn handle_weapon_toggle_text(
+    mut weapon_toggle_query: Query<&mut Text, With<WeaponToggle>>,
+    weapon_mode: Res<WeaponMode>,
+) {
+    if weapon_mode.is_changed() {
+        for mut text in weapon_toggle_query.iter_mut() {
+            text.0 = weapon_text(&weapon_mode);
+        }
+    }
+}
+
+fn edge_text(edge_mode: &EdgeMode) -> String {
+    format!("Edges: {edge_mode:?}")
+}
+
+fn handle_edge_toggle(
+    edge_toggle_query: Query<&Interaction, (Changed<Interaction>, With<EdgeToggle>)>,
+    mut edge_mode: ResMut<EdgeMode>,
+) {
+    for interaction in edge_toggle_query.iter() {
+        if *interaction == Interaction::Pressed {
+            edge_mode.next();
+        }
+    }
+}
+
+fn handle_edge_toggle_text(
+    mut edge_toggle_query: Query<&mut Text, With<EdgeToggle>>,
+    edge_mode: Res<EdgeMode>,
+) {
+    if edge_mode.is_changed() {
+        for mut text in edge_toggle_query.iter_mut() {
+            text.0 = edge_text(&edge_mode);
+        }
+    }
+}
+
+fn speed_text(game_speed_option: &GameSpeedOption) -> String {
+    format!("Game Speed: {game_speed_option:?}")
+}
+
+fn handle_speed_toggle(
+    speed_toggle_query: Query<&Interaction, (Changed<Interaction>, With<SpeedToggle>)>,
+    mut game_speed_option: ResMut<GameSpeedOption>,
+) {
+    for interaction in speed_toggle_query.iter() {
+        if *interaction == Interaction::Pressed {
+            game_speed_option.next();
+        }
+    }
+}
+
+fn handle_speed_toggle_text(
+    mut speed_toggle_query: Query<&mut Text, With<SpeedToggle>>,
+    game_speed_option: Res<GameSpeedOption>,
+) {
+    if game_speed_option.is_changed() {
+        for mut text in speed_toggle_query.iter_mut() {
+            text.0 = speed_text(&game_speed_option);
+        }
+    }
+}
+
+fn adaptive_text(adaptive_option: &AdaptiveDifficultyOption) -> String {
+    let state = if adaptive_option.enabled { "On" } else { "Off" };
+    format!("Adaptive Difficulty: {state}")
+}
+
+fn handle_adaptive_toggle(
+    adaptive_toggle_query: Query<&Interaction, (Changed<Interaction>, With<AdaptiveToggle>)>,
+    mut adaptive_option: ResMut<AdaptiveDifficultyOption>,
+) {
+    for interaction in adaptive_toggle_query.iter() {
+        if *interaction == Interaction::Pressed {
+            adaptive_option.toggle();
+        }
+    }
+}
+
+fn handle_adaptive_toggle_text(
+    mut adaptive_toggle_query: Query<&mut Text, With<AdaptiveToggle>>,
+    adaptive_option: Res<AdaptiveDifficultyOption>,
+) {
+    if adaptive_option.is_changed() {
+        for mut text in adaptive_toggle_query.iter_mut() {
+            text.0 = adaptive_text(&adaptive_option);
+        }
+    }
+}
+
+fn friendly_fire_text(friendly_fire_option: &FriendlyFireOption) -> String {
+    let state = if friendly_fire_option.enabled {
+        "On"
+    } else {
+        "Off"
+    };
+    format!("Co-op Friendly Fire: {state}")
+}
+
+fn handle_friendly_fire_toggle(
+    friendly_fire_toggle_query: Query<
+        &Interaction,
+        (Changed<Interaction>, With<FriendlyFireToggle>),
+    >,
+    mut friendly_fire_option: ResMut<FriendlyFireOption>,
+) {
+    for interaction in friendly_fire_toggle_query.iter() {
+        if *interaction == Interaction::Pressed {
+            friendly_fire_option.toggle();
+        }
+    }
+}
+
+fn handle_friendly_fire_toggle_text(
+    mut friendly_fire_toggle_query: Query<&mut Text, With<FriendlyFireToggle>>,
+    friendly_fire_option: Res<FriendlyFireOption>,
+) {
+    if friendly_fire_option.is_changed() {
+        for mut text in friendly_fire_toggle_query.iter_mut() {
+            text.0 = friendly_fire_text(&friendly_fire_option);
+        }
+    }
+}
+
+fn rumble_text(rumble_option: &RumbleOption) -> String {
+    if rumble_option.is_off() {
+        return "Rumble: Off".to_string();
+    }
+    format!("Rumble: {:.0}%", rumble_option.intensity * 100.)
+}
+
+// Follows the cursor for as long as the slider is held
+fn handle_rumble_slider(
+    slider_query: Query<(&Interaction, &RelativeCursorPosition), With<RumbleSlider>>,
+    mut rumble_option: ResMut<RumbleOption>,
+) {
+    for (interaction, cursor_position) in slider_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(position) = cursor_position.normalized else {
+            continue;
+        };
+        let mut updated = *rumble_option;
+        updated.set(position.x);
+        if updated.intensity != rumble_option.intensity {
+            *rumble_option = updated;
+        }
+    }
+}
+
+fn handle_rumble_slider_display(
+    mut text_query: Query<&mut Text, With<RumbleText>>,
+    mut fill_query: Query<&mut Node, With<RumbleSliderFill>>,
+    rumble_option: Res<RumbleOption>,
+) {
+    if !rumble_option.is_changed() {
+        return;
+    }
+    for mut text in text_query.iter_mut() {
+        text.0 = rumble_text(&rumble_option);
+    }
+    for mut node in fill_query.iter_mut() {
+        node.width = Val::Percent(rumble_option.intensity * 100.);
+    }
+}
+
+fn handle_binding_text(
+    mut action_query: Query<(&KeyAction, &mut Text)>,
+    key_bindings: Res<KeyBindings>,
+    rebinding: Option<Res<Rebinding>>,
+) {
+    for (action, mut text) in action_query.iter_mut() {
+        let binding_text = binding_text(*action, &key_bindings, rebinding.as_deref());
+        if text.0 != binding_text {
+            text.0 = binding_text;
+        }
+    }
+}
+
+fn handle_back_button_interaction(
+    back_button_query: Query<&Interaction, With<BackButton>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let Ok(interaction) = back_button_query.single() else {
+        warn!("Back button not found in handle_back_button_interaction");
+        return;
+    };
+    if *interaction == Interaction::Pressed {
+        next_state.set(AppState::MainMenu);
+    }
+}
+
+fn remove_rebinding(mut commands: Commands) {
+    commands.remove_resource::<Rebinding>();
+    commands.remove_resource::<FocusLock>();
+}
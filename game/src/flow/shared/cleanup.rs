@@ -1,8 +1,10 @@
 use bevy::prelude::*;
 
-use crate::components::{Bullet, Player, UFO};
-use crate::res::PlayerTag;
-use crate::{states::AppState, util::cleanup_components};
+use crate::res::{
+    ContinueRun, DailyChallenge, DemoMode, LocalCoop, PlayerNames, PlayerTag, SessionToken,
+    Spectator, TutorialMode,
+};
+use crate::states::AppState;
 
 pub struct CleanupPlugin;
 
@@ -11,10 +13,15 @@ impl Plugin for CleanupPlugin {
         app.add_systems(
             OnEnter(AppState::MainMenu),
             (
-                cleanup_components::<Player>,
-                cleanup_components::<Bullet>,
-                cleanup_components::<UFO>,
                 reset_player_tag,
+                reset_player_names,
+                remove_spectator,
+                remove_session_token,
+                remove_local_coop,
+                remove_tutorial_mode,
+                remove_demo_mode,
+                remove_daily_challenge,
+                remove_continue_run,
             ),
         );
     }
@@ -23,3 +30,35 @@ impl Plugin for CleanupPlugin {
 fn reset_player_tag(mut player_tag: ResMut<PlayerTag>) {
     player_tag.0 = 1;
 }
+
+fn reset_player_names(mut player_names: ResMut<PlayerNames>) {
+    player_names.0.clear();
+}
+
+fn remove_spectator(mut commands: Commands) {
+    commands.remove_resource::<Spectator>();
+}
+
+fn remove_session_token(mut commands: Commands) {
+    commands.remove_resource::<SessionToken>();
+}
+
+fn remove_local_coop(mut commands: Commands) {
+    commands.remove_resource::<LocalCoop>();
+}
+
+fn remove_tutorial_mode(mut commands: Commands) {
+    commands.remove_resource::<TutorialMode>();
+}
+
+fn remove_demo_mode(mut commands: Commands) {
+    commands.remove_resource::<DemoMode>();
+}
+
+fn remove_daily_challenge(mut commands: Commands) {
+    commands.remove_resource::<DailyChallenge>();
+}
+
+fn remove_continue_run(mut commands: Commands) {
+    commands.remove_resource::<ContinueRun>();
+}
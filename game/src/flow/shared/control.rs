@@ -1,52 +1,165 @@
+use std::collections::HashMap;
+
+use bevy::input::gamepad::GamepadConnectionEvent;
 use bevy::prelude::*;
 
-use crate::flow::shared::game_trigger::{SpaceShipMovement, SpaceShipMovementEvent};
-use crate::states::OnlineGameState;
-use crate::ui_components::{ControlButton, ControlButtonPanel};
-use crate::util::cleanup_components;
+use crate::components::{Player, SelfPlayer, Spaceship, Velocity};
+use crate::flow::online_game::ChatDraft;
+use crate::flow::replay::ReplayPlayback;
+use crate::flow::shared::game_trigger::{
+    ChargeShotEvent, DashEvent, FireBombEvent, FireMissileEvent, ShootBulletEvent,
+    SpaceShipMovement, SpaceShipMovementEvent,
+};
+use crate::res::{DemoMode, LocalCoop, Spectator};
+use crate::states::{OnlineGameState, PauseState};
+use crate::ui_components::{ControlButton, ControlButtonPanel, VirtualJoystick};
+use crate::util::{cleanup_components, Position};
 use crate::{
-    res::{ControlMode, ControlOption},
+    res::{ControlMode, ControlOption, KeyAction, KeyBindings, MovementTuning},
     states::GameState,
 };
+
+// Never one of the buttons the fire button can be set to
+const MISSILE_BUTTON: GamepadButton = GamepadButton::LeftTrigger2;
+const BOMB_BUTTON: GamepadButton = GamepadButton::LeftTrigger;
+const DASH_BUTTON: GamepadButton = GamepadButton::RightTrigger;
+// Two presses of the same direction this close together dash
+const DOUBLE_TAP_WINDOW: f32 = 0.25;
+
 pub struct ControlPlugin;
 
 impl Plugin for ControlPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::InPlay), spawn_control_button_panel)
-            .add_systems(OnEnter(OnlineGameState::InPlay), spawn_control_button_panel)
-            .add_systems(
-                Update,
-                (
-                    handle_clicking_interaction,
-                    handle_spaceship_keyboard_interaction,
-                )
-                    .run_if(in_state(GameState::InPlay).or(in_state(OnlineGameState::InPlay))),
+        app.add_systems(
+            OnEnter(GameState::InPlay),
+            (spawn_control_button_panel, spawn_virtual_joystick)
+                .run_if(not(resource_exists::<ReplayPlayback>))
+                .run_if(not(resource_exists::<DemoMode>))
+                .run_if(not(resource_exists::<LocalCoop>)),
+        )
+        .add_systems(
+            OnEnter(GameState::Tutorial),
+            (spawn_control_button_panel, spawn_virtual_joystick),
+        )
+        .add_systems(
+            OnEnter(OnlineGameState::InPlay),
+            (spawn_control_button_panel, spawn_virtual_joystick)
+                .run_if(not(resource_exists::<Spectator>)),
+        )
+        .add_systems(Update, handle_gamepad_connection)
+        .add_systems(
+            Update,
+            (
+                sync_control_button_panel,
+                sync_virtual_joystick,
+                handle_clicking_interaction,
+                handle_touch_interaction,
+                handle_hover_interaction,
+                handle_mouse_aim_interaction,
+                handle_spaceship_keyboard_interaction,
+                handle_gamepad_interaction,
             )
-            .add_systems(
-                OnExit(GameState::InPlay),
+                .run_if(
+                    in_state(GameState::InPlay)
+                        .or(in_state(GameState::Tutorial))
+                        .or(in_state(OnlineGameState::InPlay)),
+                )
+                .run_if(not(resource_exists::<Spectator>))
+                .run_if(not(resource_exists::<ReplayPlayback>))
+                .run_if(not(resource_exists::<DemoMode>))
+                .run_if(not(resource_exists::<LocalCoop>))
+                .run_if(not(in_state(PauseState::Paused))),
+        )
+        .add_systems(
+            Update,
+            handle_local_coop_keyboard_interaction
+                .run_if(in_state(GameState::InPlay).and(resource_exists::<LocalCoop>))
+                .run_if(not(in_state(PauseState::Paused))),
+        )
+        .add_systems(
+            OnExit(GameState::InPlay),
+            (
                 cleanup_components::<ControlButtonPanel>,
-            )
-            .add_systems(
-                OnExit(OnlineGameState::InPlay),
+                cleanup_components::<VirtualJoystick>,
+            ),
+        )
+        .add_systems(
+            OnExit(GameState::Tutorial),
+            (
                 cleanup_components::<ControlButtonPanel>,
-            );
+                cleanup_components::<VirtualJoystick>,
+            ),
+        )
+        .add_systems(
+            OnExit(OnlineGameState::InPlay),
+            (
+                cleanup_components::<ControlButtonPanel>,
+                cleanup_components::<VirtualJoystick>,
+            ),
+        );
     }
 }
 
 fn spawn_control_button_panel(mut commands: Commands, control_option: Res<ControlOption>) {
-    if control_option.mode == ControlMode::Keyboard {
+    if control_option.mode != ControlMode::Button {
         return;
     }
     commands.spawn(ControlButtonPanel);
 }
 
+// Keep the panel in line with the mode when it changes mid-game (e.g. gamepad hot-plug)
+fn sync_control_button_panel(
+    mut commands: Commands,
+    control_option: Res<ControlOption>,
+    panel_q: Query<Entity, With<ControlButtonPanel>>,
+) {
+    if !control_option.is_changed() {
+        return;
+    }
+    if control_option.mode == ControlMode::Button {
+        if panel_q.is_empty() {
+            commands.spawn(ControlButtonPanel);
+        }
+        return;
+    }
+    for entity in panel_q.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn spawn_virtual_joystick(mut commands: Commands, control_option: Res<ControlOption>) {
+    if control_option.mode != ControlMode::Touch {
+        return;
+    }
+    commands.spawn(VirtualJoystick::default());
+}
+
+fn sync_virtual_joystick(
+    mut commands: Commands,
+    control_option: Res<ControlOption>,
+    joystick_q: Query<Entity, With<VirtualJoystick>>,
+) {
+    if !control_option.is_changed() {
+        return;
+    }
+    if control_option.mode == ControlMode::Touch {
+        if joystick_q.is_empty() {
+            commands.spawn(VirtualJoystick::default());
+        }
+        return;
+    }
+    for entity in joystick_q.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
 // Button Mode
 fn handle_clicking_interaction(
     mut commands: Commands,
     mut control_button_query: Query<(&Interaction, &mut BackgroundColor, &ControlButton)>,
     control_option: Res<ControlOption>,
 ) {
-    if control_option.mode == ControlMode::Keyboard {
+    if control_option.mode != ControlMode::Button {
         return;
     }
     let mut all_not_pressed = true;
@@ -71,6 +184,168 @@ fn handle_clicking_interaction(
     if all_not_pressed {
         commands.trigger(SpaceShipMovementEvent(SpaceShipMovement::Rest));
     }
+    // Bullet will shoot automatically in Button Mode
+    commands.trigger(ShootBulletEvent);
+}
+
+// Touch Mode
+fn handle_touch_interaction(
+    mut commands: Commands,
+    touches: Res<Touches>,
+    mut joystick_q: Query<&mut VirtualJoystick>,
+    camera_q: Query<&Camera, With<Camera2d>>,
+    ui_scale: Res<UiScale>,
+    control_option: Res<ControlOption>,
+) {
+    if control_option.mode != ControlMode::Touch {
+        return;
+    }
+    let Ok(mut joystick) = joystick_q.single_mut() else {
+        return;
+    };
+    // Touches are in window pixels, the joystick is drawn in the letterboxed UI
+    let viewport_min = camera_q
+        .single()
+        .ok()
+        .and_then(Camera::logical_viewport_rect)
+        .map_or(Vec2::ZERO, |rect| rect.min);
+    let to_ui = |position: Vec2| (position - viewport_min) / ui_scale.0;
+    // The first finger down owns the joystick until it is lifted
+    if joystick.touch_id().is_none() {
+        if let Some(touch) = touches.iter_just_pressed().next() {
+            joystick.press(touch.id(), to_ui(touch.position()));
+        }
+    }
+    if let Some(touch_id) = joystick.touch_id() {
+        match touches.get_pressed(touch_id) {
+            Some(touch) => joystick.drag(to_ui(touch.position())),
+            None => joystick.release(),
+        }
+    }
+    commands.trigger(SpaceShipMovementEvent(SpaceShipMovement::from_direction(
+        joystick.direction(),
+    )));
+    // Bullet will shoot automatically in Touch Mode
+    commands.trigger(ShootBulletEvent);
+}
+
+// Hover Mode
+#[allow(clippy::too_many_arguments)]
+fn handle_hover_interaction(
+    mut commands: Commands,
+    mut followed: Local<Option<Vec2>>,
+    window_q: Query<&Window>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    spaceship_q: Query<(&Spaceship, &Velocity), With<SelfPlayer>>,
+    control_option: Res<ControlOption>,
+    tuning: Res<MovementTuning>,
+    time: Res<Time>,
+    fixed_time: Res<Time<Fixed>>,
+) {
+    if control_option.mode != ControlMode::Hover {
+        *followed = None;
+        return;
+    }
+    let Ok((spaceship, velocity)) = spaceship_q.single() else {
+        *followed = None;
+        return;
+    };
+    let position = spaceship.get_position();
+    // The last known point is kept while the cursor is outside the window
+    let cursor = window_q
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera_q.single().ok())
+        .and_then(|(cursor, (camera, camera_transform))| {
+            camera.viewport_to_world_2d(camera_transform, cursor).ok()
+        });
+    let target = followed.get_or_insert(position);
+    if let Some(cursor) = cursor {
+        let smoothing = control_option.hover_smoothing;
+        let blend = if smoothing <= 0. {
+            1.
+        } else {
+            1. - (-time.delta_secs() / smoothing).exp()
+        };
+        *target = target.lerp(cursor, blend);
+    }
+
+    // Lets go early enough for drag to stop it on the point instead of overshooting
+    let speed = Vec2::new(velocity.x, velocity.y).length();
+    let ticks_per_second = 1. / fixed_time.timestep().as_secs_f32();
+    let stopping_distance = speed * speed * ticks_per_second / (2. * tuning.drag.max(f32::EPSILON));
+    let offset = *target - position;
+    let movement = if offset.length() <= control_option.hover_dead_zone + stopping_distance {
+        SpaceShipMovement::Rest
+    } else {
+        SpaceShipMovement::from_direction(offset)
+    };
+    commands.trigger(SpaceShipMovementEvent(movement));
+    // Bullet will shoot automatically in Hover Mode
+    commands.trigger(ShootBulletEvent);
+}
+
+// Mouse Aim Mode
+#[allow(clippy::too_many_arguments)]
+fn handle_mouse_aim_interaction(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    window_q: Query<&Window>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    mut spaceship_q: Query<&mut Spaceship, With<SelfPlayer>>,
+    control_option: Res<ControlOption>,
+    key_bindings: Res<KeyBindings>,
+    chat_draft: Option<Res<ChatDraft>>,
+    mut double_tap: Local<DoubleTap>,
+    time: Res<Time>,
+    online_game_state: Option<Res<State<OnlineGameState>>>,
+) {
+    if control_option.mode != ControlMode::MouseAim {
+        return;
+    }
+    if chat_draft.is_some() {
+        commands.trigger(SpaceShipMovementEvent(SpaceShipMovement::Rest));
+        return;
+    }
+    let movement = keyboard_movement(&keys, &key_bindings);
+    commands.trigger(SpaceShipMovementEvent(movement));
+    if wants_dash(&keys, &key_bindings, &mut double_tap, &time) {
+        commands.trigger(DashEvent(movement));
+    }
+    let Ok(mut spaceship) = spaceship_q.single_mut() else {
+        return;
+    };
+    // The ship keeps its last aim while the cursor is outside the window
+    let cursor = window_q
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera_q.single().ok())
+        .and_then(|(cursor, (camera, camera_transform))| {
+            camera.viewport_to_world_2d(camera_transform, cursor).ok()
+        });
+    // Other clients only hear where bullets are, so online the ship keeps shooting straight up
+    if let Some(cursor) = cursor.filter(|_| online_game_state.is_none()) {
+        let direction = cursor - spaceship.get_position();
+        spaceship.set_aim(direction);
+    }
+    if mouse.pressed(MouseButton::Left) {
+        commands.trigger(ShootBulletEvent);
+        commands.trigger(ChargeShotEvent::Hold);
+    }
+    if mouse.just_released(MouseButton::Left) {
+        commands.trigger(ChargeShotEvent::Release);
+    }
+    if mouse.just_pressed(MouseButton::Right)
+        || keys.just_pressed(key_bindings.key(KeyAction::Missile))
+    {
+        commands.trigger(FireMissileEvent);
+    }
+    if keys.just_pressed(key_bindings.key(KeyAction::Bomb)) {
+        commands.trigger(FireBombEvent);
+    }
 }
 
 // Keyboard Mode
@@ -78,15 +353,114 @@ fn handle_spaceship_keyboard_interaction(
     mut commands: Commands,
     keys: Res<ButtonInput<KeyCode>>,
     control_option: Res<ControlOption>,
+    key_bindings: Res<KeyBindings>,
+    chat_draft: Option<Res<ChatDraft>>,
+    mut double_tap: Local<DoubleTap>,
+    time: Res<Time>,
 ) {
     if control_option.mode != ControlMode::Keyboard {
         return;
     }
-    let movement = match (
-        keys.pressed(KeyCode::ArrowUp),
-        keys.pressed(KeyCode::ArrowDown),
-        keys.pressed(KeyCode::ArrowLeft),
-        keys.pressed(KeyCode::ArrowRight),
+    if chat_draft.is_some() {
+        commands.trigger(SpaceShipMovementEvent(SpaceShipMovement::Rest));
+        return;
+    }
+    let movement = keyboard_movement(&keys, &key_bindings);
+    commands.trigger(SpaceShipMovementEvent(movement));
+    if wants_dash(&keys, &key_bindings, &mut double_tap, &time) {
+        commands.trigger(DashEvent(movement));
+    }
+    let shoot = key_bindings.key(KeyAction::Shoot);
+    if keys.pressed(shoot) {
+        commands.trigger(ShootBulletEvent);
+        commands.trigger(ChargeShotEvent::Hold);
+    }
+    if keys.just_released(shoot) {
+        commands.trigger(ChargeShotEvent::Release);
+    }
+    if keys.just_pressed(key_bindings.key(KeyAction::Missile)) {
+        commands.trigger(FireMissileEvent);
+    }
+    if keys.just_pressed(key_bindings.key(KeyAction::Bomb)) {
+        commands.trigger(FireBombEvent);
+    }
+}
+
+// Local Co-op, both players share the keyboard with their own fixed bindings
+#[allow(clippy::type_complexity)]
+fn handle_local_coop_keyboard_interaction(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    spaceship_q: Query<(Entity, &Player), (With<Spaceship>, With<SelfPlayer>)>,
+    mut double_taps: Local<HashMap<u8, DoubleTap>>,
+    time: Res<Time>,
+) {
+    for (entity, player) in spaceship_q.iter() {
+        let key_bindings = LocalCoop::key_bindings(player.0);
+        let movement = keyboard_movement(&keys, &key_bindings);
+        commands.trigger_targets(SpaceShipMovementEvent(movement), entity);
+        let double_tap = double_taps.entry(player.0).or_default();
+        if wants_dash(&keys, &key_bindings, double_tap, &time) {
+            commands.trigger_targets(DashEvent(movement), entity);
+        }
+        let shoot = key_bindings.key(KeyAction::Shoot);
+        if keys.pressed(shoot) {
+            commands.trigger_targets(ShootBulletEvent, entity);
+            commands.trigger_targets(ChargeShotEvent::Hold, entity);
+        }
+        if keys.just_released(shoot) {
+            commands.trigger_targets(ChargeShotEvent::Release, entity);
+        }
+        if keys.just_pressed(key_bindings.key(KeyAction::Missile)) {
+            commands.trigger_targets(FireMissileEvent, entity);
+        }
+        if keys.just_pressed(key_bindings.key(KeyAction::Bomb)) {
+            commands.trigger_targets(FireBombEvent, entity);
+        }
+    }
+}
+
+#[derive(Default)]
+struct DoubleTap {
+    // The direction pressed last and when
+    last: Option<(KeyAction, f32)>,
+}
+
+// The dash key, or the same direction pressed twice in a row
+fn wants_dash(
+    keys: &ButtonInput<KeyCode>,
+    key_bindings: &KeyBindings,
+    double_tap: &mut DoubleTap,
+    time: &Time,
+) -> bool {
+    let now = time.elapsed_secs();
+    let tapped = [
+        KeyAction::Up,
+        KeyAction::Down,
+        KeyAction::Left,
+        KeyAction::Right,
+    ]
+    .into_iter()
+    .find(|action| keys.just_pressed(key_bindings.key(*action)));
+    let double_tapped = tapped.is_some_and(|action| {
+        double_tap
+            .last
+            .is_some_and(|(last, at)| last == action && now - at <= DOUBLE_TAP_WINDOW)
+    });
+    if let Some(action) = tapped {
+        // A third tap starts over instead of dashing again
+        double_tap.last = (!double_tapped).then_some((action, now));
+    }
+    double_tapped || keys.just_pressed(key_bindings.key(KeyAction::Dash))
+}
+
+fn keyboard_movement(keys: &ButtonInput<KeyCode>, key_bindings: &KeyBindings) -> SpaceShipMovement {
+    let pressed = |action: KeyAction| keys.pressed(key_bindings.key(action));
+    match (
+        pressed(KeyAction::Up),
+        pressed(KeyAction::Down),
+        pressed(KeyAction::Left),
+        pressed(KeyAction::Right),
     ) {
         (true, false, true, false) => SpaceShipMovement::UpLeft,
         (true, false, false, true) => SpaceShipMovement::UpRight,
@@ -97,6 +471,69 @@ fn handle_spaceship_keyboard_interaction(
         (_, _, true, false) => SpaceShipMovement::Left,
         (_, _, false, true) => SpaceShipMovement::Right,
         _ => SpaceShipMovement::Rest,
+    }
+}
+
+// The first connected gamepad flies the spaceship, any others are left alone
+pub fn controlling_gamepad(gamepads: impl Iterator<Item = Entity>) -> Option<Entity> {
+    gamepads.min()
+}
+
+// Gamepad Mode
+fn handle_gamepad_interaction(
+    mut commands: Commands,
+    gamepads: Query<(Entity, &Gamepad)>,
+    control_option: Res<ControlOption>,
+) {
+    if control_option.mode != ControlMode::Gamepad {
+        return;
+    }
+    let gamepad = controlling_gamepad(gamepads.iter().map(|(entity, _)| entity))
+        .and_then(|entity| gamepads.get(entity).ok());
+    let Some((_, gamepad)) = gamepad else {
+        commands.trigger(SpaceShipMovementEvent(SpaceShipMovement::Rest));
+        return;
     };
-    commands.trigger(SpaceShipMovementEvent(movement));
+    let mut direction = gamepad.left_stick();
+    if direction.length() <= control_option.dead_zone {
+        direction = gamepad.dpad();
+    }
+    if direction.length() <= control_option.dead_zone {
+        direction = Vec2::ZERO;
+    }
+    commands.trigger(SpaceShipMovementEvent(SpaceShipMovement::from_direction(
+        direction,
+    )));
+    if gamepad.pressed(control_option.fire_button) {
+        commands.trigger(ShootBulletEvent);
+        commands.trigger(ChargeShotEvent::Hold);
+    }
+    if gamepad.just_released(control_option.fire_button) {
+        commands.trigger(ChargeShotEvent::Release);
+    }
+    if gamepad.just_pressed(MISSILE_BUTTON) {
+        commands.trigger(FireMissileEvent);
+    }
+    if gamepad.just_pressed(BOMB_BUTTON) {
+        commands.trigger(FireBombEvent);
+    }
+    if gamepad.just_pressed(DASH_BUTTON) {
+        commands.trigger(DashEvent(SpaceShipMovement::from_direction(direction)));
+    }
+}
+
+fn handle_gamepad_connection(
+    mut connection_events: EventReader<GamepadConnectionEvent>,
+    gamepads: Query<(), With<Gamepad>>,
+    mut control_option: ResMut<ControlOption>,
+) {
+    for connection_event in connection_events.read() {
+        if connection_event.connected() {
+            if control_option.mode != ControlMode::Gamepad {
+                control_option.set_mode(&ControlMode::Gamepad);
+            }
+        } else if control_option.mode == ControlMode::Gamepad && gamepads.is_empty() {
+            control_option.set_mode(&ControlMode::Keyboard);
+        }
+    }
 }
@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+
+use crate::components::{
+    BulletInvisible, Dash, Invisible, SelfPlayer, Spaceship, DASH_INVINCIBILITY,
+};
+
+use super::SpaceShipMovement;
+
+// Dashes toward the movement, resting dashes go where the ship points
+#[derive(Event)]
+pub struct DashEvent(pub SpaceShipMovement);
+
+pub struct DashPlugin;
+
+impl Plugin for DashPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(handle_dash);
+    }
+}
+
+// Only offline spaceships come with a Dash
+#[allow(clippy::type_complexity)]
+fn handle_dash(
+    trigger: Trigger<DashEvent>,
+    mut commands: Commands,
+    mut spaceship_query: Query<
+        (
+            Entity,
+            &Spaceship,
+            &mut Dash,
+            Has<Invisible>,
+            Has<BulletInvisible>,
+        ),
+        With<SelfPlayer>,
+    >,
+) {
+    // Local co-op targets a spaceship, otherwise there is only one SelfPlayer
+    let spaceship = if trigger.target() == Entity::PLACEHOLDER {
+        spaceship_query.single_mut().ok()
+    } else {
+        spaceship_query.get_mut(trigger.target()).ok()
+    };
+    let Some((entity, spaceship, mut dash, invisible, bullet_invisible)) = spaceship else {
+        return;
+    };
+    let direction = match trigger.event().0 {
+        SpaceShipMovement::Rest => spaceship.aim(),
+        movement => movement.direction(),
+    };
+    if !dash.start(direction) {
+        return;
+    }
+    // Never cuts a longer invincibility (e.g. after respawning) short
+    let mut entity_commands = commands.entity(entity);
+    if !invisible {
+        entity_commands.insert(Invisible::with_duration(DASH_INVINCIBILITY));
+    }
+    if !bullet_invisible {
+        entity_commands.insert(BulletInvisible::with_duration(DASH_INVINCIBILITY));
+    }
+}
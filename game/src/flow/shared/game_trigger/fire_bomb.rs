@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::{BombCharges, Player, SelfPlayer, Shockwave, Spaceship},
+    util::Position,
+};
+
+#[derive(Event)]
+pub struct FireBombEvent;
+
+pub struct FireBombPlugin;
+
+impl Plugin for FireBombPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(handle_fire_bomb);
+    }
+}
+
+fn handle_fire_bomb(
+    trigger: Trigger<FireBombEvent>,
+    mut commands: Commands,
+    mut spaceship_query: Query<(&Spaceship, &Player, &mut BombCharges), With<SelfPlayer>>,
+) {
+    // Local co-op targets a spaceship, otherwise there is only one SelfPlayer
+    let spaceship = if trigger.target() == Entity::PLACEHOLDER {
+        spaceship_query.single_mut().ok()
+    } else {
+        spaceship_query.get_mut(trigger.target()).ok()
+    };
+    let Some((spaceship, player, mut charges)) = spaceship else {
+        return;
+    };
+    if charges.take() {
+        commands.spawn(Shockwave::by_player(player.0, spaceship.get_position()));
+    }
+}
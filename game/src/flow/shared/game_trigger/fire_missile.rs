@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::{Missile, MissileAmmo, Player, SelfPlayer, Spaceship},
+    util::Position,
+};
+
+#[derive(Event)]
+pub struct FireMissileEvent;
+
+pub struct FireMissilePlugin;
+
+impl Plugin for FireMissilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(handle_fire_missile);
+    }
+}
+
+fn handle_fire_missile(
+    trigger: Trigger<FireMissileEvent>,
+    mut commands: Commands,
+    mut spaceship_query: Query<(&Spaceship, &Player, &mut MissileAmmo), With<SelfPlayer>>,
+) {
+    // Local co-op targets a spaceship, otherwise there is only one SelfPlayer
+    let spaceship = if trigger.target() == Entity::PLACEHOLDER {
+        spaceship_query.single_mut().ok()
+    } else {
+        spaceship_query.get_mut(trigger.target()).ok()
+    };
+    let Some((spaceship, player, mut ammo)) = spaceship else {
+        return;
+    };
+    if ammo.take() {
+        commands.spawn(Missile::by_player(player.0, spaceship.get_position()));
+    }
+}
@@ -1,12 +1,26 @@
+mod dash;
+mod fire_bomb;
+mod fire_missile;
+mod shoot_bullet;
 mod spaceship_movement;
 use bevy::prelude::*;
 
+pub use dash::DashEvent;
+pub use fire_bomb::FireBombEvent;
+pub use fire_missile::FireMissileEvent;
+pub use shoot_bullet::{ChargeShotEvent, ShootBulletEvent};
 pub use spaceship_movement::{SpaceShipMovement, SpaceShipMovementEvent};
 
 pub struct GameTriggerPlugin;
 
 impl Plugin for GameTriggerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(spaceship_movement::SpaceshipMovementPlugin);
+        app.add_plugins((
+            spaceship_movement::SpaceshipMovementPlugin,
+            shoot_bullet::ShootBulletPlugin,
+            fire_missile::FireMissilePlugin,
+            fire_bomb::FireBombPlugin,
+            dash::DashPlugin,
+        ));
     }
 }
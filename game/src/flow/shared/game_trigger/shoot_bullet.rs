@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    components::{
+        Buff, Bullet, Player, PoolCommandsExt, PowerUpKind, SelfPlayer, Spaceship, WeaponHeat,
+        WeaponLevel, HEAT_FIRE_INTERVAL,
+    },
+    flow::juice::RumbleEvent,
+    res::Difficulty,
+    states::GameState,
+    util::Position,
+};
+
+// Added on both sides of the middle stream
+const SPREAD_SHOT_ANGLES: [f32; 2] = [-15., 15.];
+const CHARGE_DURATION: Duration = Duration::from_secs(1);
+
+#[derive(Event)]
+pub struct ShootBulletEvent;
+
+// Sent every frame shoot is held and once on release, a long enough hold fires a charged shot
+#[derive(Event)]
+pub enum ChargeShotEvent {
+    Hold,
+    Release,
+}
+
+pub struct ShootBulletPlugin;
+
+impl Plugin for ShootBulletPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(handle_shoot_bullet)
+            .add_observer(handle_charge_shot);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn handle_shoot_bullet(
+    trigger: Trigger<ShootBulletEvent>,
+    mut commands: Commands,
+    mut spaceship_query: Query<
+        (
+            &mut Spaceship,
+            &Player,
+            Option<&Buff>,
+            Option<&mut WeaponHeat>,
+        ),
+        With<SelfPlayer>,
+    >,
+    level_q: Query<(&WeaponLevel, &Player)>,
+    difficulty: Res<Difficulty>,
+    game_state: Option<Res<State<GameState>>>,
+) {
+    // Local co-op targets a spaceship, otherwise there is only one SelfPlayer
+    let spaceship = if trigger.target() == Entity::PLACEHOLDER {
+        spaceship_query.single_mut().ok()
+    } else {
+        spaceship_query.get_mut(trigger.target()).ok()
+    };
+    let Some((mut spaceship, player, buff_op, mut weapon_heat)) = spaceship else {
+        return;
+    };
+    if !spaceship.can_shoot()
+        || weapon_heat
+            .as_ref()
+            .is_some_and(|heat| heat.is_overheated())
+    {
+        return;
+    }
+    let position = spaceship.get_position();
+    let aim = spaceship.aim_rotation();
+    // Online games have no WeaponLevel and always fire a single stream
+    let default_level = WeaponLevel::default();
+    let weapon_level = level_q
+        .iter()
+        .find(|(_, level_player)| level_player.0 == player.0)
+        .map_or(&default_level, |(weapon_level, _)| weapon_level);
+    for (offset, angle) in weapon_level.pattern() {
+        commands.spawn_pooled(
+            Bullet::by_player_with_angle(player.0, position + aim * Vec2::new(offset, 0.), angle)
+                .rotated(aim),
+        );
+    }
+    if let Some(PowerUpKind::SpreadShot) = buff_op.map(Buff::kind) {
+        for angle in SPREAD_SHOT_ANGLES {
+            commands
+                .spawn_pooled(Bullet::by_player_with_angle(player.0, position, angle).rotated(aim));
+        }
+    }
+    // Online games always play on Normal so every player fires at the same rate
+    let difficulty = if game_state.is_some() {
+        *difficulty
+    } else {
+        Difficulty::Normal
+    };
+    let base_cooldown = match weapon_heat.as_mut() {
+        Some(weapon_heat) => {
+            weapon_heat.add_shot();
+            HEAT_FIRE_INTERVAL
+        }
+        None => difficulty.bullet_cooldown(),
+    };
+    let cooldown = match buff_op.map(Buff::kind) {
+        Some(PowerUpKind::RapidFire) => base_cooldown / 2,
+        _ => base_cooldown,
+    };
+    spaceship.start_cd(cooldown);
+}
+
+fn handle_charge_shot(
+    trigger: Trigger<ChargeShotEvent>,
+    mut commands: Commands,
+    mut spaceship_query: Query<(&mut Spaceship, &Player), With<SelfPlayer>>,
+    time: Res<Time>,
+) {
+    let spaceship = if trigger.target() == Entity::PLACEHOLDER {
+        spaceship_query.single_mut().ok()
+    } else {
+        spaceship_query.get_mut(trigger.target()).ok()
+    };
+    let Some((mut spaceship, player)) = spaceship else {
+        return;
+    };
+    match trigger.event() {
+        ChargeShotEvent::Hold => spaceship.charge(time.delta()),
+        ChargeShotEvent::Release => {
+            if spaceship.take_charge() >= CHARGE_DURATION {
+                commands.spawn_pooled(
+                    Bullet::charged(player.0, spaceship.get_position())
+                        .rotated(spaceship.aim_rotation()),
+                );
+                commands.trigger(RumbleEvent::ChargedShot(player.0));
+            }
+        }
+    }
+}
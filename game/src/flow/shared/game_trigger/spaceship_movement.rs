@@ -1,12 +1,17 @@
+use std::f32::consts::FRAC_PI_4;
+
+use bevy::math::bounding::BoundingVolume;
 use bevy::prelude::*;
-use shooting_game_shared::util::EdgeUtil;
+use shooting_game_shared::util::{EdgeUtil, SPACESHIP_SIZE};
 
-use crate::components::{SelfPlayer, Spaceship, Velocity};
+use crate::components::{rotated_bounds, SelfPlayer, Spaceship, Velocity};
+use crate::res::{EdgeMode, GameSpeed, MovementTuning};
+use crate::states::GameState;
 
 #[derive(Event)]
 pub struct SpaceShipMovementEvent(pub SpaceShipMovement);
 
-#[derive(Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, PartialEq)]
 pub enum SpaceShipMovement {
     Up,
     UpRight,
@@ -19,6 +24,42 @@ pub enum SpaceShipMovement {
     Rest,
 }
 
+impl SpaceShipMovement {
+    // Snap an analog direction to the closest of the eight movements
+    pub fn from_direction(direction: Vec2) -> Self {
+        if direction == Vec2::ZERO {
+            return SpaceShipMovement::Rest;
+        }
+        let sector = (direction.to_angle() / FRAC_PI_4).round() as i32;
+        match sector.rem_euclid(8) {
+            0 => SpaceShipMovement::Right,
+            1 => SpaceShipMovement::UpRight,
+            2 => SpaceShipMovement::Up,
+            3 => SpaceShipMovement::UpLeft,
+            4 => SpaceShipMovement::Left,
+            5 => SpaceShipMovement::DownLeft,
+            6 => SpaceShipMovement::Down,
+            _ => SpaceShipMovement::DownRight,
+        }
+    }
+
+    // Unit length, diagonals included
+    pub fn direction(&self) -> Vec2 {
+        let direction = match self {
+            SpaceShipMovement::Up => Vec2::Y,
+            SpaceShipMovement::UpRight => Vec2::new(1., 1.),
+            SpaceShipMovement::Right => Vec2::X,
+            SpaceShipMovement::DownRight => Vec2::new(1., -1.),
+            SpaceShipMovement::Down => Vec2::NEG_Y,
+            SpaceShipMovement::DownLeft => Vec2::new(-1., -1.),
+            SpaceShipMovement::Left => Vec2::NEG_X,
+            SpaceShipMovement::UpLeft => Vec2::new(-1., 1.),
+            SpaceShipMovement::Rest => Vec2::ZERO,
+        };
+        direction.normalize_or_zero()
+    }
+}
+
 pub struct SpaceshipMovementPlugin;
 
 impl Plugin for SpaceshipMovementPlugin {
@@ -27,38 +68,63 @@ impl Plugin for SpaceshipMovementPlugin {
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn handle_spaceship_movement(
     trigger: Trigger<SpaceShipMovementEvent>,
-    mut spaceship_query: Query<(&mut Velocity, &Transform), (With<Spaceship>, With<SelfPlayer>)>,
+    mut spaceship_query: Query<
+        (&mut Velocity, &mut Transform),
+        (With<Spaceship>, With<SelfPlayer>),
+    >,
+    tuning: Res<MovementTuning>,
+    edge_mode: Res<EdgeMode>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+    game_state: Option<Res<State<GameState>>>,
 ) {
-    let Ok((mut velocity, transform)) = spaceship_query.single_mut() else {
+    // Local co-op targets a spaceship, otherwise there is only one SelfPlayer
+    let spaceship = if trigger.target() == Entity::PLACEHOLDER {
+        spaceship_query.single_mut().ok()
+    } else {
+        spaceship_query.get_mut(trigger.target()).ok()
+    };
+    let Some((mut velocity, mut transform)) = spaceship else {
         return;
     };
     let Vec3 { x, y, z: _ } = transform.translation;
-    let movement = &trigger.event().0;
+    // A turned ship sticks out further, so it stops sooner
+    let bounds = rotated_bounds(&transform, SPACESHIP_SIZE);
+    let edge = EdgeUtil::new(bounds.half_size() * 2.);
+    let target = trigger.event().0.direction() * tuning.max_speed;
+    let delta = game_speed.delta_secs(&time);
 
-    if *movement == SpaceShipMovement::Rest {
+    velocity.x = approach(velocity.x, target.x, &tuning, delta);
+    velocity.y = approach(velocity.y, target.y, &tuning, delta);
+
+    // Online games always clamp so every client sees the same edges
+    if edge_mode.is_wrap() && game_state.is_some() {
+        transform.translation.x = EdgeUtil::wrap_x(x);
+    } else if (velocity.x < 0. && edge.over_left_in(x))
+        || (velocity.x > 0. && edge.over_right_in(x))
+    {
+        // Stop dead at the edges instead of drifting past them
         velocity.x = 0.;
+    }
+    if (velocity.y > 0. && edge.over_top_in(y)) || (velocity.y < 0. && edge.over_bottom_in(y)) {
         velocity.y = 0.;
-        return;
     }
-    let edge = EdgeUtil::spaceship();
-
-    velocity.x = match movement {
-        SpaceShipMovement::Left if !edge.over_left_in(x) => -10.,
-        SpaceShipMovement::UpLeft | SpaceShipMovement::DownLeft if !edge.over_left_in(x) => -7.,
-        SpaceShipMovement::Right if !edge.over_right_in(x) => 10.,
-        SpaceShipMovement::UpRight | SpaceShipMovement::DownRight if !edge.over_right_in(x) => 7.,
-        _ => 0.,
-    };
+}
 
-    velocity.y = match movement {
-        SpaceShipMovement::Up if !edge.over_top_in(y) => 10.,
-        SpaceShipMovement::UpLeft | SpaceShipMovement::UpRight if !edge.over_top_in(y) => 7.,
-        SpaceShipMovement::Down if !edge.over_bottom_in(y) => -10.,
-        SpaceShipMovement::DownLeft | SpaceShipMovement::DownRight if !edge.over_bottom_in(y) => {
-            -7.
-        }
-        _ => 0.,
+// Accelerates toward a held direction, drag takes over once it is released
+fn approach(current: f32, target: f32, tuning: &MovementTuning, delta: f32) -> f32 {
+    let rate = if target == 0. {
+        tuning.drag
+    } else {
+        tuning.acceleration
     };
+    let step = rate * delta;
+    if (target - current).abs() <= step {
+        target
+    } else {
+        current + step * (target - current).signum()
+    }
 }
@@ -1,8 +1,14 @@
 mod cleanup;
 mod control;
 pub mod game_trigger;
+mod offscreen_indicator;
 mod shooting;
 mod stars;
+mod window_mode;
+mod window_resize;
+
+pub use control::controlling_gamepad;
+pub use stars::Backdrop;
 
 use bevy::prelude::{App, Plugin};
 pub struct SharedSystemPlugin;
@@ -15,6 +21,9 @@ impl Plugin for SharedSystemPlugin {
             game_trigger::GameTriggerPlugin,
             control::ControlPlugin,
             shooting::ShootingPlugin,
+            window_resize::WindowResizePlugin,
+            window_mode::WindowModePlugin,
+            offscreen_indicator::OffscreenIndicatorPlugin,
         ));
     }
 }
@@ -0,0 +1,72 @@
+use bevy::color::palettes::css::{AQUA, ORANGE};
+use bevy::prelude::*;
+use shooting_game_shared::util::MOBILE_WINDOW_SIZE;
+
+use crate::components::{SelfPlayer, Spaceship, UFO};
+use crate::states::{GameState, OnlineGameState};
+
+// Kept this far inside the play area so the whole arrow shows
+const INDICATOR_MARGIN: f32 = 16.;
+const INDICATOR_LENGTH: f32 = 14.;
+const INDICATOR_WIDTH: f32 = 12.;
+// Out of sight by more than this shows nothing, closer fades the arrow in
+const FADE_DISTANCE: f32 = 300.;
+// Blue and orange stay apart for color blind players too
+const UFO_INDICATOR_COLOR: Srgba = ORANGE;
+const PLAYER_INDICATOR_COLOR: Srgba = AQUA;
+
+pub struct OffscreenIndicatorPlugin;
+
+impl Plugin for OffscreenIndicatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            draw_offscreen_indicators.run_if(
+                in_state(GameState::InPlay)
+                    .or(in_state(OnlineGameState::Ready))
+                    .or(in_state(OnlineGameState::InPlay)),
+            ),
+        );
+    }
+}
+
+// Spaceships without SelfPlayer are the other players online
+fn draw_offscreen_indicators(
+    mut gizmos: Gizmos,
+    ufo_q: Query<&Transform, With<UFO>>,
+    spaceship_q: Query<&Transform, (With<Spaceship>, Without<SelfPlayer>)>,
+) {
+    let ufos = ufo_q
+        .iter()
+        .map(|transform| (transform, UFO_INDICATOR_COLOR));
+    let spaceships = spaceship_q
+        .iter()
+        .map(|transform| (transform, PLAYER_INDICATOR_COLOR));
+    for (transform, color) in ufos.chain(spaceships) {
+        let position = transform.translation.truncate();
+        let Some((tip, direction, alpha)) = indicator(position) else {
+            continue;
+        };
+        let base = tip - direction * INDICATOR_LENGTH;
+        let side = direction.perp() * INDICATOR_WIDTH / 2.;
+        gizmos.linestrip_2d(
+            [tip, base + side, base - side, tip],
+            color.with_alpha(alpha),
+        );
+    }
+}
+
+// Where the arrow tip goes on the edge, which way it points and how faded it is,
+// None while the position is in sight or too far out
+fn indicator(position: Vec2) -> Option<(Vec2, Vec2, f32)> {
+    let half_size = MOBILE_WINDOW_SIZE / 2.;
+    let outside = (position.abs() - half_size).max(Vec2::ZERO);
+    let distance = outside.length();
+    if distance <= 0. || distance >= FADE_DISTANCE {
+        return None;
+    }
+    let inner = half_size - INDICATOR_MARGIN;
+    let tip = position.clamp(-inner, inner);
+    let direction = (position - tip).try_normalize()?;
+    Some((tip, direction, 1. - distance / FADE_DISTANCE))
+}
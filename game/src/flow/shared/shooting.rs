@@ -2,11 +2,9 @@ use bevy::prelude::*;
 use shooting_game_shared::util::EdgeUtil;
 
 use crate::{
-    components::{Bullet, SelfPlayer, Spaceship},
-    constant::BULLET_SIZE,
-    res::{ControlMode, ControlOption, PlayerTag},
+    components::{Bullet, Missile, PoolCommandsExt},
+    constant::{BULLET_SIZE, MISSILE_SIZE},
     states::{GameState, OnlineGameState},
-    util::Position,
 };
 
 pub struct ShootingPlugin;
@@ -15,37 +13,46 @@ impl Plugin for ShootingPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (shooting_bullet, cleanup_on_out_screen)
-                .run_if(in_state(GameState::InPlay).or(in_state(OnlineGameState::InPlay))),
+            (cleanup_on_out_screen, cleanup_missile_on_out_screen).run_if(
+                in_state(GameState::InPlay)
+                    .or(in_state(GameState::Tutorial))
+                    .or(in_state(OnlineGameState::InPlay)),
+            ),
         );
     }
 }
 
-fn shooting_bullet(
+fn cleanup_on_out_screen(
     mut commands: Commands,
-    keys: Res<ButtonInput<KeyCode>>,
-    control_option: Res<ControlOption>,
-    mut spaceship_query: Query<&mut Spaceship, With<SelfPlayer>>,
-    player_tag: Res<PlayerTag>,
+    bullet_queries: Query<(Entity, &Transform), With<Bullet>>,
 ) {
-    if keys.pressed(KeyCode::Space) || control_option.mode == ControlMode::Button {
-        let Ok(mut spaceship) = spaceship_query.single_mut() else {
-            return;
-        };
-        if spaceship.can_shoot() {
-            commands.spawn(Bullet::by_player(player_tag.0, spaceship.get_position()));
-            spaceship.start_cd();
+    let edge = EdgeUtil::new(BULLET_SIZE);
+    for (entity, transform) in bullet_queries.iter() {
+        let Vec2 { x, y } = transform.translation.truncate();
+        // Drone bullets are aimed, so they can leave through any edge
+        if edge.over_top_out(y)
+            || edge.over_bottom_out(y)
+            || edge.over_left_out(x)
+            || edge.over_right_out(x)
+        {
+            commands.release_pooled::<Bullet>(entity);
         }
     }
 }
 
-fn cleanup_on_out_screen(
+// Missiles can turn around, so any edge counts
+fn cleanup_missile_on_out_screen(
     mut commands: Commands,
-    bullet_queries: Query<(Entity, &Transform), With<Bullet>>,
+    missile_query: Query<(Entity, &Transform), With<Missile>>,
 ) {
-    let edge = EdgeUtil::new(BULLET_SIZE);
-    for (entity, transform) in bullet_queries.iter() {
-        if edge.over_top_out(transform.translation.y) {
+    let edge = EdgeUtil::new(MISSILE_SIZE);
+    for (entity, transform) in missile_query.iter() {
+        let Vec2 { x, y } = transform.translation.truncate();
+        if edge.over_top_out(y)
+            || edge.over_bottom_out(y)
+            || edge.over_left_out(x)
+            || edge.over_right_out(x)
+        {
             if let Ok(mut entity_commands) = commands.get_entity(entity) {
                 entity_commands.despawn();
             }
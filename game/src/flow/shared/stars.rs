@@ -1,70 +1,301 @@
+use std::ops::Range;
+
 use bevy::prelude::*;
-use shooting_game_shared::util::EdgeUtil;
+use rand::Rng;
+use shooting_game_shared::util::{EdgeUtil, MOBILE_WINDOW_SIZE};
 
-use crate::components::Velocity;
+use crate::components::{Boss, SelfPlayer, Spaceship, Velocity};
 use crate::constant::{ZIndex, STAR_SIZE};
-use crate::res::ImageHandles;
+use crate::res::{BackgroundPalette, GameRng, ImageHandles, MovementTuning, RngStream};
 use crate::states::AppState;
 use crate::ui_components::Blink;
 
+// Pixels per second at a scroll of 1, the old single layer moved 2 px per fixed tick
+const BASE_SCROLL_SPEED: f32 = 128.;
+const MENU_SCROLL: f32 = 0.5;
+const PLAY_SCROLL: f32 = 1.;
+const BOSS_SCROLL: f32 = 3.;
+// How quickly the scroll catches up when the target changes
+const SCROLL_EASING: f32 = 2.;
+// Flying at full speed changes the scroll by this much, away from the movement
+const DRIFT_SCROLL: f32 = 0.3;
+// Sideways shift of the nearest layer at full speed, the tiles only overhang the screen a little
+const DRIFT_OFFSET: f32 = 10.;
+const DRIFTER_INTERVAL_SECS: Range<f32> = 8.0..16.0;
+
+struct StarLayer {
+    speed: f32,
+    scale: f32,
+    max_alpha: f32,
+    // Tiles per row, more and smaller tiles read as a denser far field
+    columns: &'static [f32],
+}
+
+const STAR_LAYERS: [StarLayer; 3] = [
+    StarLayer {
+        speed: 0.3,
+        scale: 0.6,
+        max_alpha: 0.06,
+        columns: &[-180., 0., 180.],
+    },
+    StarLayer {
+        speed: 0.6,
+        scale: 1.,
+        max_alpha: 0.1,
+        columns: &[-140., 140.],
+    },
+    StarLayer {
+        speed: 1.,
+        scale: 1.4,
+        max_alpha: 0.16,
+        columns: &[0.],
+    },
+];
+
 pub struct StarsPlugin;
 
 impl Plugin for StarsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (check_stars_number, cleanup_stars).run_if(
-                in_state(AppState::Game)
-                    .or(in_state(AppState::MainMenu))
-                    .or(in_state(AppState::OnlineGame)),
-            ),
-        );
+        app.init_resource::<BackgroundScroll>()
+            .add_systems(
+                Update,
+                (
+                    update_background_scroll,
+                    fill_star_layers,
+                    spawn_drifter,
+                    scroll_background,
+                    cleanup_background,
+                )
+                    .chain()
+                    .run_if(
+                        in_state(AppState::Game)
+                            .or(in_state(AppState::MainMenu))
+                            .or(in_state(AppState::OnlineGame)),
+                    ),
+            )
+            .add_systems(
+                Update,
+                apply_background_palette.run_if(resource_changed::<BackgroundPalette>),
+            );
+    }
+}
+
+// The plain sprite behind everything else
+#[derive(Component)]
+pub struct Backdrop;
+
+#[derive(Resource)]
+struct BackgroundScroll {
+    scroll: f32,
+    // Opposite to the spaceship's velocity, from -1 to 1 at full speed
+    drift: Vec2,
+    // Sideways shift already applied at a speed of 1, layers move by their own share of it
+    drift_offset: f32,
+    drifter_timer: Timer,
+}
+
+impl Default for BackgroundScroll {
+    fn default() -> Self {
+        Self {
+            scroll: MENU_SCROLL,
+            drift: Vec2::ZERO,
+            drift_offset: 0.,
+            drifter_timer: Timer::from_seconds(DRIFTER_INTERVAL_SECS.start, TimerMode::Once),
+        }
     }
 }
 
 #[derive(Component)]
-struct Stars;
+struct StarTile {
+    layer: usize,
+}
+
+// Planets and nebulae passing by between the star layers
+#[derive(Component)]
+struct Drifter {
+    radius: f32,
+}
 
-fn check_stars_number(
+#[derive(Component)]
+struct BackgroundSpeed(f32);
+
+fn update_background_scroll(
+    mut background_scroll: ResMut<BackgroundScroll>,
+    app_state: Res<State<AppState>>,
+    boss_q: Query<(), With<Boss>>,
+    spaceship_q: Query<&Velocity, (With<Spaceship>, With<SelfPlayer>)>,
+    tuning: Res<MovementTuning>,
+    time: Res<Time>,
+) {
+    let target = if !boss_q.is_empty() {
+        BOSS_SCROLL
+    } else if *app_state.get() == AppState::MainMenu {
+        MENU_SCROLL
+    } else {
+        PLAY_SCROLL
+    };
+    let easing = (SCROLL_EASING * time.delta_secs()).min(1.);
+    background_scroll.scroll += (target - background_scroll.scroll) * easing;
+
+    // Local co-op has two spaceships, the stars follow them both
+    let (sum, count) = spaceship_q
+        .iter()
+        .fold((Vec2::ZERO, 0.), |(sum, count), velocity| {
+            (sum + Vec2::new(velocity.x, velocity.y), count + 1.)
+        });
+    let drift_target = if count > 0. {
+        (-sum / count / tuning.max_speed).clamp_length_max(1.)
+    } else {
+        Vec2::ZERO
+    };
+    let drift = background_scroll.drift;
+    background_scroll.drift = drift + (drift_target - drift) * easing;
+}
+
+// Keeps every layer tiled up to the top of the screen
+fn fill_star_layers(
     mut commands: Commands,
-    stars_query: Query<&Transform, With<Stars>>,
+    tile_q: Query<(&StarTile, &Transform)>,
     image_handles: Res<ImageHandles>,
+    palette: Res<BackgroundPalette>,
+    background_scroll: Res<BackgroundScroll>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    let stars_handle = image_handles.stars.clone();
-    let Some(first_star_transform) = stars_query.iter().next() else {
-        spawn_star(&mut commands, stars_handle);
-        return;
-    };
-    if stars_query.iter().len() == 1 && first_star_transform.translation.y < 0. {
-        spawn_star(&mut commands, stars_handle);
+    for (layer_index, layer) in STAR_LAYERS.iter().enumerate() {
+        let size = STAR_SIZE * layer.scale;
+        let highest = tile_q
+            .iter()
+            .filter(|(tile, _)| tile.layer == layer_index)
+            .map(|(_, transform)| transform.translation.y)
+            .reduce(f32::max);
+        // Cover the whole screen straight away the first time
+        let mut y = highest.unwrap_or(-MOBILE_WINDOW_SIZE.y / 2. - size.y);
+        while y + size.y / 2. < MOBILE_WINDOW_SIZE.y / 2. {
+            y += size.y;
+            spawn_star_row(
+                &mut commands,
+                &image_handles,
+                palette.stars,
+                layer_index,
+                Vec2::new(background_scroll.drift_offset * layer.speed, y),
+                game_rng.stream(RngStream::Cosmetic),
+            );
+        }
     }
 }
 
-fn spawn_star(commands: &mut Commands, stars_handle: Handle<Image>) {
-    let edge = EdgeUtil::new(STAR_SIZE);
+fn spawn_star_row(
+    commands: &mut Commands,
+    image_handles: &ImageHandles,
+    color: Color,
+    layer_index: usize,
+    offset: Vec2,
+    rng: &mut impl Rng,
+) {
+    let layer = &STAR_LAYERS[layer_index];
+    let z = ZIndex::STARS.z_value() + layer_index as f32 * 0.1;
+    for column in layer.columns {
+        commands.spawn((
+            StarTile { layer: layer_index },
+            BackgroundSpeed(layer.speed),
+            Blink::new(0.001 * (layer_index + 1) as f32, layer.max_alpha, 0.001),
+            Sprite {
+                image: image_handles.stars.clone(),
+                color,
+                custom_size: Some(STAR_SIZE * layer.scale),
+                flip_x: rng.random_bool(0.5),
+                flip_y: rng.random_bool(0.5),
+                ..default()
+            },
+            Transform::from_xyz(column + offset.x, offset.y, z),
+        ));
+    }
+}
+
+// Blinking only touches the alpha, so the tint is kept across the blink
+fn apply_background_palette(
+    palette: Res<BackgroundPalette>,
+    mut tile_q: Query<&mut Sprite, (With<StarTile>, Without<Backdrop>)>,
+    mut backdrop_q: Query<&mut Sprite, With<Backdrop>>,
+) {
+    for mut sprite in tile_q.iter_mut() {
+        let alpha = sprite.color.alpha();
+        sprite.color = palette.stars.with_alpha(alpha);
+    }
+    for mut sprite in backdrop_q.iter_mut() {
+        sprite.color = palette.backdrop;
+    }
+}
+
+fn spawn_drifter(
+    mut commands: Commands,
+    mut background_scroll: ResMut<BackgroundScroll>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut game_rng: ResMut<GameRng>,
+    time: Res<Time>,
+) {
+    background_scroll.drifter_timer.tick(time.delta());
+    if !background_scroll.drifter_timer.finished() {
+        return;
+    }
+    let rng = game_rng.stream(RngStream::Cosmetic);
+    background_scroll.drifter_timer =
+        Timer::from_seconds(rng.random_range(DRIFTER_INTERVAL_SECS), TimerMode::Once);
+    // Nebulae are huge and faint so they sit furthest back
+    let (radius, color, speed) = if rng.random_bool(0.3) {
+        let color = Color::hsla(rng.random_range(200.0..300.0), 0.6, 0.5, 0.08);
+        (rng.random_range(150.0..250.0), color, 0.15)
+    } else {
+        let color = Color::hsla(rng.random_range(0.0..360.0), 0.4, 0.4, 0.7);
+        (rng.random_range(20.0..60.0), color, 0.45)
+    };
+    let edge = EdgeUtil::new(Vec2::splat(radius * 2.));
     commands.spawn((
-        Stars,
-        Blink::new(0.001, 0.1, 0.001),
-        Velocity { x: 0., y: -2. },
-        Sprite {
-            image: stars_handle,
-            custom_size: Some(STAR_SIZE),
-            ..default()
-        },
-        Transform {
-            translation: Vec3::new(0., edge.top_out(), ZIndex::STARS.z_value()),
-            ..default()
-        },
+        Drifter { radius },
+        BackgroundSpeed(speed),
+        Mesh2d(meshes.add(Circle::new(radius))),
+        MeshMaterial2d(materials.add(color)),
+        Transform::from_xyz(
+            rng.random_range(edge.left_in()..edge.right_in()),
+            edge.top_out(),
+            ZIndex::STARS.z_value() + 0.05,
+        ),
     ));
 }
 
-fn cleanup_stars(mut commands: Commands, stars_query: Query<(Entity, &Transform), With<Stars>>) {
-    let edge = EdgeUtil::new(STAR_SIZE);
-    for (entity, transform) in stars_query.iter() {
+fn scroll_background(
+    mut background_q: Query<(&BackgroundSpeed, &mut Transform)>,
+    mut background_scroll: ResMut<BackgroundScroll>,
+    time: Res<Time>,
+) {
+    let scroll = background_scroll.scroll - background_scroll.drift.y * DRIFT_SCROLL;
+    let distance = BASE_SCROLL_SPEED * scroll * time.delta_secs();
+    // Only the change is applied, so the layers settle back once the spaceship stops
+    let drift_offset = background_scroll.drift.x * DRIFT_OFFSET;
+    let shift = drift_offset - background_scroll.drift_offset;
+    background_scroll.drift_offset = drift_offset;
+    for (speed, mut transform) in background_q.iter_mut() {
+        transform.translation.y -= distance * speed.0;
+        transform.translation.x += shift * speed.0;
+    }
+}
+
+fn cleanup_background(
+    mut commands: Commands,
+    tile_q: Query<(Entity, &StarTile, &Transform)>,
+    drifter_q: Query<(Entity, &Drifter, &Transform)>,
+) {
+    for (entity, tile, transform) in tile_q.iter() {
+        let edge = EdgeUtil::new(STAR_SIZE * STAR_LAYERS[tile.layer].scale);
+        if edge.over_bottom_out(transform.translation.y) {
+            commands.entity(entity).despawn();
+        }
+    }
+    for (entity, drifter, transform) in drifter_q.iter() {
+        let edge = EdgeUtil::new(Vec2::splat(drifter.radius * 2.));
         if edge.over_bottom_out(transform.translation.y) {
-            if let Ok(mut entity_commands) = commands.get_entity(entity) {
-                entity_commands.despawn();
-            }
+            commands.entity(entity).despawn();
         }
     }
 }
@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+use bevy::window::{MonitorSelection, WindowMode};
+
+// F11 is kept by browsers for their own fullscreen
+const FULLSCREEN_KEY: KeyCode = KeyCode::F4;
+
+pub struct WindowModePlugin;
+
+impl Plugin for WindowModePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, toggle_fullscreen);
+        // The browser already paces focused frames, a page left in the background is capped lower
+        #[cfg(target_arch = "wasm32")]
+        app.insert_resource(bevy::winit::WinitSettings {
+            focused_mode: bevy::winit::UpdateMode::Continuous,
+            unfocused_mode: bevy::winit::UpdateMode::reactive_low_power(
+                std::time::Duration::from_secs_f64(1. / 30.),
+            ),
+        });
+    }
+}
+
+fn toggle_fullscreen(keys: Res<ButtonInput<KeyCode>>, mut window_q: Query<&mut Window>) {
+    if !keys.just_pressed(FULLSCREEN_KEY) {
+        return;
+    }
+    let Ok(mut window) = window_q.single_mut() else {
+        warn!("Window not found in toggle_fullscreen");
+        return;
+    };
+    window.mode = match window.mode {
+        WindowMode::Windowed => WindowMode::BorderlessFullscreen(MonitorSelection::Current),
+        _ => WindowMode::Windowed,
+    };
+}
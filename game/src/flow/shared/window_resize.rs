@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+use bevy::render::camera::Viewport;
+use bevy::window::{PrimaryWindow, WindowResized};
+use shooting_game_shared::util::MOBILE_WINDOW_SIZE;
+
+pub struct WindowResizePlugin;
+
+impl Plugin for WindowResizePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, fit_camera_to_window);
+    }
+}
+
+// The play area stays MOBILE_WINDOW_SIZE in world units (the server shares it),
+// so a resized window only changes how big it is drawn. The camera and the UI are
+// letterboxed to it, spawn areas past the edges never come into view.
+fn fit_camera_to_window(
+    mut resized_events: EventReader<WindowResized>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    mut camera_q: Query<(&mut Camera, &mut Projection, Ref<Camera2d>)>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let resized = resized_events.read().last().is_some();
+    if !resized && !camera_q.iter().any(|(_, _, camera)| camera.is_added()) {
+        return;
+    }
+    let Ok(window) = window_q.single() else {
+        return;
+    };
+    let scale = (window.width() / MOBILE_WINDOW_SIZE.x).min(window.height() / MOBILE_WINDOW_SIZE.y);
+    if scale <= 0. {
+        return;
+    }
+    ui_scale.0 = scale;
+    let window_size = UVec2::new(window.physical_width(), window.physical_height());
+    let size = (MOBILE_WINDOW_SIZE * scale * window.scale_factor())
+        .round()
+        .as_uvec2()
+        .clamp(UVec2::ONE, window_size.max(UVec2::ONE));
+    let viewport = Viewport {
+        physical_position: (window_size.saturating_sub(size)) / 2,
+        physical_size: size,
+        ..default()
+    };
+    for (mut camera, mut projection, _) in camera_q.iter_mut() {
+        camera.viewport = Some(viewport.clone());
+        if let Projection::Orthographic(orthographic) = projection.as_mut() {
+            orthographic.scale = 1. / scale;
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use bevy::app::App;
+use bevy::prelude::*;
+
+use crate::cleanup::DespawnOnExit;
+use crate::res::LifetimeStats;
+use crate::states::AppState;
+use crate::ui_components::{FocusBack, InteractionUI, MainContainer};
+
+pub struct StatisticsPlugin;
+
+impl Plugin for StatisticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Statistics), show_statistics)
+            .add_systems(
+                Update,
+                handle_back_button_interaction.run_if(in_state(AppState::Statistics)),
+            );
+    }
+}
+
+#[derive(Component)]
+struct Statistics;
+
+#[derive(Component)]
+struct BackButton;
+
+fn show_statistics(mut commands: Commands, lifetime_stats: Res<LifetimeStats>) {
+    commands
+        .spawn((
+            Statistics,
+            MainContainer,
+            DespawnOnExit(AppState::Statistics),
+        ))
+        .with_children(|statistics_background| {
+            statistics_background.spawn((
+                Text::new("Statistics"),
+                TextFont::from_font_size(40.),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+            statistics_background.spawn((
+                Node {
+                    margin: UiRect::top(Val::Px(50.)),
+                    ..default()
+                },
+                Text::new(statistics_text(&lifetime_stats)),
+            ));
+            statistics_background
+                .spawn(Node {
+                    height: Val::Percent(100.),
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::FlexEnd,
+                    ..default()
+                })
+                .with_children(|back_container| {
+                    back_container
+                        .spawn((
+                            BackButton,
+                            FocusBack,
+                            InteractionUI,
+                            Node {
+                                align_self: AlignSelf::FlexEnd,
+                                width: Val::Px(120.),
+                                height: Val::Px(50.),
+                                border: UiRect::all(Val::Px(2.)),
+                                display: Display::Flex,
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                            BackgroundColor::from(Color::srgba(0.1, 0.1, 0.1, 1.)),
+                            BorderColor::from(Color::BLACK),
+                        ))
+                        .with_child(Text::new("Back"));
+                });
+        });
+}
+
+fn statistics_text(lifetime_stats: &LifetimeStats) -> String {
+    let seconds = lifetime_stats.play_time.as_secs();
+    format!(
+        "Games Played: {}\nUFOs Destroyed: {}\nBullets Fired: {}\nBest Wave: {}\nPlay Time: {:02}:{:02}:{:02}",
+        lifetime_stats.total_games,
+        lifetime_stats.ufos_destroyed,
+        lifetime_stats.bullets_fired,
+        lifetime_stats.best_wave,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+    )
+}
+
+fn handle_back_button_interaction(
+    back_button_query: Query<&Interaction, With<BackButton>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let Ok(interaction) = back_button_query.single() else {
+        warn!("Back button not found in handle_back_button_interaction");
+        return;
+    };
+    if *interaction == Interaction::Pressed {
+        next_state.set(AppState::MainMenu);
+    }
+}
@@ -3,21 +3,29 @@
 use bevy::prelude::*;
 use bevy_embedded_assets::EmbeddedAssetPlugin;
 
+mod cleanup;
 mod components;
 mod constant;
 mod flow;
+mod persistence;
 mod res;
+mod settings;
 mod states;
+mod stats;
 mod ui_components;
 mod util;
 
 fn main() {
     App::new()
         .add_plugins(EmbeddedAssetPlugin::default())
+        .add_plugins(cleanup::CleanupPlugin)
         .add_plugins(components::ComponentPlugin)
         .add_plugins(flow::FlowPlugin)
+        .add_plugins(persistence::PersistencePlugin)
+        .add_plugins(settings::SettingsPlugin)
         .add_plugins(res::ResPlugin)
         .add_plugins(states::StatePlugin)
+        .add_plugins(stats::StatsPlugin)
         .add_plugins(ui_components::UIComponentsPlugin)
         .run();
 }
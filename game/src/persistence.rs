@@ -0,0 +1,71 @@
+use std::fs;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::res::HighScores;
+
+const SAVE_DIR: &str = "shooting_game";
+const HIGH_SCORES_FILE: &str = "high_scores.json";
+
+pub struct PersistencePlugin;
+
+impl Plugin for PersistencePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_high_scores).add_systems(
+            Update,
+            save_high_scores
+                .run_if(resource_changed::<HighScores>.and(not(resource_added::<HighScores>))),
+        );
+    }
+}
+
+pub fn save_path(file_name: &str) -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(SAVE_DIR).join(file_name))
+}
+
+pub fn read_file(file_name: &str) -> Option<String> {
+    save_path(file_name).and_then(|path| fs::read_to_string(path).ok())
+}
+
+pub fn write_file(file_name: &str, content: String) {
+    let Some(path) = save_path(file_name) else {
+        warn!("No data directory to save {file_name}");
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        if let Err(e) = fs::create_dir_all(dir) {
+            warn!("Failed to create save directory: {e}");
+            return;
+        }
+    }
+    if let Err(e) = fs::write(path, content) {
+        warn!("Failed to save {file_name}: {e}");
+    }
+}
+
+pub fn load_json<T: DeserializeOwned>(file_name: &str) -> Option<T> {
+    read_file(file_name).and_then(|content| match serde_json::from_str::<T>(&content) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            warn!("Failed to parse {file_name}: {e}");
+            None
+        }
+    })
+}
+
+pub fn save_json<T: Serialize>(file_name: &str, value: &T) {
+    match serde_json::to_string_pretty(value) {
+        Ok(content) => write_file(file_name, content),
+        Err(e) => warn!("Failed to serialize {file_name}: {e}"),
+    }
+}
+
+fn load_high_scores(mut commands: Commands) {
+    commands.insert_resource(load_json::<HighScores>(HIGH_SCORES_FILE).unwrap_or_default());
+}
+
+fn save_high_scores(high_scores: Res<HighScores>) {
+    save_json(HIGH_SCORES_FILE, high_scores.as_ref());
+}
@@ -0,0 +1,25 @@
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+// Share of particles still spawned with reduce_particles on
+const REDUCED_PARTICLE_DENSITY: f32 = 0.25;
+
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessibilityOption {
+    // Enemies, bullets and pickups use the color blind palette
+    pub color_blind: bool,
+    // Hit flashes and the i-frame blink are replaced by steady tints
+    pub disable_flashing: bool,
+    pub reduce_particles: bool,
+}
+
+impl AccessibilityOption {
+    pub fn particle_density(&self) -> f32 {
+        if self.reduce_particles {
+            REDUCED_PARTICLE_DENSITY
+        } else {
+            1.
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+// The window is this many buckets, the adjustment moves once per bucket
+const BUCKET_DURATION: Duration = Duration::from_secs(5);
+const WINDOW_BUCKETS: usize = 6;
+const STEP: f32 = 0.05;
+const MAX_ADJUSTMENT: f32 = 0.3;
+// Fewer shots than this say nothing about how the player is doing
+const MIN_SHOTS: u32 = 10;
+const GRAZE_WEIGHT: f32 = 0.02;
+const STRONG_PERFORMANCE: f32 = 0.6;
+const WEAK_PERFORMANCE: f32 = 0.25;
+
+// Chosen in settings, off keeps the curve and the difficulty as they are
+#[derive(Resource, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdaptiveDifficultyOption {
+    pub enabled: bool,
+}
+
+impl AdaptiveDifficultyOption {
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct PerformanceWindow {
+    pub deaths: u32,
+    pub shots: u32,
+    pub hits: u32,
+    pub grazes: u32,
+}
+
+impl PerformanceWindow {
+    // Piercing shots can hit more than once, so it is capped at every shot landing
+    pub fn accuracy(&self) -> f32 {
+        if self.shots == 0 {
+            return 0.;
+        }
+        (self.hits as f32 / self.shots as f32).min(1.)
+    }
+
+    fn add(&mut self, other: &PerformanceWindow) {
+        self.deaths += other.deaths;
+        self.shots += other.shots;
+        self.hits += other.hits;
+        self.grazes += other.grazes;
+    }
+
+    // A death always backs off, otherwise accuracy and grazes decide once there are enough shots
+    fn nudge(&self) -> f32 {
+        if self.deaths > 0 {
+            return -STEP;
+        }
+        if self.shots < MIN_SHOTS {
+            return 0.;
+        }
+        let performance = self.accuracy() + self.grazes as f32 * GRAZE_WEIGHT;
+        if performance >= STRONG_PERFORMANCE {
+            STEP
+        } else if performance <= WEAK_PERFORMANCE {
+            -STEP
+        } else {
+            0.
+        }
+    }
+}
+
+// Present during a run with the option on, scales spawning on top of Difficulty
+#[derive(Resource, Default)]
+pub struct AdaptiveDifficulty {
+    window: VecDeque<PerformanceWindow>,
+    current: PerformanceWindow,
+    elapsed: Duration,
+    adjustment: f32,
+}
+
+impl AdaptiveDifficulty {
+    pub fn record_death(&mut self) {
+        self.current.deaths += 1;
+    }
+
+    pub fn record_shot(&mut self) {
+        self.current.shots += 1;
+    }
+
+    pub fn record_hit(&mut self) {
+        self.current.hits += 1;
+    }
+
+    pub fn record_graze(&mut self) {
+        self.current.grazes += 1;
+    }
+
+    // Closes a bucket every BUCKET_DURATION and nudges the adjustment from the whole window
+    pub fn tick(&mut self, delta: Duration) {
+        self.elapsed += delta;
+        if self.elapsed < BUCKET_DURATION {
+            return;
+        }
+        self.elapsed -= BUCKET_DURATION;
+        self.window.push_back(std::mem::take(&mut self.current));
+        if self.window.len() > WINDOW_BUCKETS {
+            self.window.pop_front();
+        }
+        let nudge = self.totals().nudge();
+        self.adjustment = (self.adjustment + nudge).clamp(-MAX_ADJUSTMENT, MAX_ADJUSTMENT);
+    }
+
+    pub fn totals(&self) -> PerformanceWindow {
+        let mut totals = PerformanceWindow::default();
+        for bucket in self.window.iter() {
+            totals.add(bucket);
+        }
+        totals
+    }
+
+    // Negative while the player struggles
+    pub fn adjustment(&self) -> f32 {
+        self.adjustment
+    }
+
+    pub fn ufo_speed_scale(&self) -> f32 {
+        1. + self.adjustment
+    }
+
+    // Above 1 spawns faster, the spawn timer runs this much quicker
+    pub fn spawn_rate_scale(&self) -> f32 {
+        1. + self.adjustment
+    }
+}
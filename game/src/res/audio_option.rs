@@ -0,0 +1,21 @@
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+// Volumes from 0 to 1, kept in the settings file ahead of any audio playback
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioOption {
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub effects_volume: f32,
+}
+
+impl Default for AudioOption {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.,
+            music_volume: 1.,
+            effects_volume: 1.,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+
+// Colors of the backdrop and the star tiles, each stage brings its own
+#[derive(Resource, Clone, Copy)]
+pub struct BackgroundPalette {
+    pub backdrop: Color,
+    pub stars: Color,
+}
+
+impl Default for BackgroundPalette {
+    fn default() -> Self {
+        Self {
+            backdrop: Color::srgb(0.05, 0., 0.05),
+            stars: Color::WHITE,
+        }
+    }
+}
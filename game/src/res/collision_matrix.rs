@@ -0,0 +1,41 @@
+use bevy::prelude::Resource;
+
+use crate::components::CollisionLayer;
+
+// Which layer hits which, a pair is only checked from the first layer so the event says who hit what
+#[derive(Resource)]
+pub struct CollisionMatrix {
+    masks: [u8; CollisionLayer::COUNT],
+}
+
+impl Default for CollisionMatrix {
+    fn default() -> Self {
+        Self::empty()
+            .with_pair(CollisionLayer::Player, CollisionLayer::Enemy)
+            .with_pair(CollisionLayer::Player, CollisionLayer::EnemyBullet)
+            .with_pair(CollisionLayer::PlayerBullet, CollisionLayer::Enemy)
+            .with_pair(CollisionLayer::PlayerBullet, CollisionLayer::Ore)
+    }
+}
+
+impl CollisionMatrix {
+    pub fn empty() -> Self {
+        Self {
+            masks: [0; CollisionLayer::COUNT],
+        }
+    }
+
+    pub fn with_pair(mut self, layer: CollisionLayer, other: CollisionLayer) -> Self {
+        self.masks[layer as usize] |= 1 << other as u8;
+        self
+    }
+
+    pub fn collides(&self, layer: CollisionLayer, other: CollisionLayer) -> bool {
+        self.masks[layer as usize] & (1 << other as u8) != 0
+    }
+
+    // Layers that never hit anything can skip the broadphase query
+    pub fn hits_anything(&self, layer: CollisionLayer) -> bool {
+        self.masks[layer as usize] != 0
+    }
+}
@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+const COMBO_WINDOW: Duration = Duration::from_secs(2);
+
+// Chain of UFO kills, each kill has to land within the window of the last one
+#[derive(Resource)]
+pub struct Combo {
+    kills: u32,
+    timer: Timer,
+}
+
+impl Default for Combo {
+    fn default() -> Self {
+        Self {
+            kills: 0,
+            timer: Timer::new(COMBO_WINDOW, TimerMode::Once),
+        }
+    }
+}
+
+impl Combo {
+    pub fn register_kill(&mut self) {
+        self.kills += 1;
+        self.timer.reset();
+    }
+
+    pub fn tick(&mut self, delta: Duration) {
+        if self.kills == 0 {
+            return;
+        }
+        self.timer.tick(delta);
+        if self.timer.finished() {
+            self.reset();
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.kills = 0;
+        self.timer.reset();
+    }
+
+    pub fn multiplier(&self) -> u32 {
+        match self.kills {
+            0..3 => 1,
+            3..6 => 2,
+            6..10 => 4,
+            _ => 8,
+        }
+    }
+}
@@ -1,18 +1,74 @@
-use bevy::prelude::{Component, Resource};
+use bevy::prelude::{Component, GamepadButton, Resource};
+use serde::{Deserialize, Serialize};
 
-#[derive(Component, Clone, Eq, PartialEq)]
+const DEFAULT_GAMEPAD_DEAD_ZONE: f32 = 0.2;
+const DEFAULT_HOVER_DEAD_ZONE: f32 = 12.;
+const DEFAULT_HOVER_SMOOTHING: f32 = 0.08;
+const FIRE_BUTTON_CHOICES: [GamepadButton; 5] = [
+    GamepadButton::South,
+    GamepadButton::East,
+    GamepadButton::West,
+    GamepadButton::North,
+    GamepadButton::RightTrigger2,
+];
+
+#[derive(Component, Clone, Default, Eq, PartialEq, Debug, Serialize, Deserialize)]
 pub enum ControlMode {
+    #[default]
     Keyboard,
     Button,
+    Gamepad,
+    Touch,
+    Hover,
+    // Moves with the keyboard, aims and fires at the mouse cursor
+    MouseAim,
 }
 
-#[derive(Resource)]
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ControlOption {
     pub mode: ControlMode,
+    pub fire_button: GamepadButton,
+    pub dead_zone: f32,
+    // World units around the cursor where the ship stops chasing it
+    pub hover_dead_zone: f32,
+    // Seconds the followed point takes to catch up with the cursor, 0 follows it exactly
+    pub hover_smoothing: f32,
+}
+
+impl Default for ControlOption {
+    fn default() -> Self {
+        Self {
+            mode: ControlMode::default(),
+            fire_button: GamepadButton::South,
+            dead_zone: DEFAULT_GAMEPAD_DEAD_ZONE,
+            hover_dead_zone: DEFAULT_HOVER_DEAD_ZONE,
+            hover_smoothing: DEFAULT_HOVER_SMOOTHING,
+        }
+    }
+}
+
+impl ControlMode {
+    pub const ALL: [ControlMode; 6] = [
+        ControlMode::Keyboard,
+        ControlMode::Button,
+        ControlMode::Gamepad,
+        ControlMode::Touch,
+        ControlMode::Hover,
+        ControlMode::MouseAim,
+    ];
 }
 
 impl ControlOption {
     pub fn set_mode(&mut self, mode: &ControlMode) {
         self.mode = mode.clone();
     }
+
+    pub fn next_fire_button(&mut self) {
+        let current = FIRE_BUTTON_CHOICES
+            .iter()
+            .position(|button| *button == self.fire_button)
+            .unwrap_or(0);
+        self.fire_button = FIRE_BUTTON_CHOICES[(current + 1) % FIRE_BUTTON_CHOICES.len()];
+    }
 }
@@ -0,0 +1,50 @@
+use bevy::prelude::Resource;
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+// Present while the offline game runs today's challenge instead of a free run
+#[derive(Resource)]
+pub struct DailyChallenge {
+    pub date: NaiveDate,
+}
+
+impl DailyChallenge {
+    pub fn today() -> Self {
+        Self {
+            date: Local::now().date_naive(),
+        }
+    }
+
+    // Everyone playing on the same date gets the same seed
+    pub fn seed(&self) -> u64 {
+        fnv1a(format!("daily:{}", self.date).as_bytes())
+    }
+
+    // Date, score and a checksum that catches typos, anyone can work it out so it proves nothing
+    pub fn share_code(&self, score: u32) -> String {
+        let check = fnv1a(format!("{}:{score}", self.date).as_bytes()) as u16;
+        format!("{}-{score}-{check:04X}", self.date.format("%Y%m%d"))
+    }
+}
+
+// The one attempt allowed per day, kept across sessions
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DailyRecord {
+    pub date: Option<NaiveDate>,
+    // None while the attempt is running or if it was abandoned
+    pub score: Option<u32>,
+}
+
+impl DailyRecord {
+    pub fn played(&self, date: NaiveDate) -> bool {
+        self.date == Some(date)
+    }
+}
+
+// Written out here as std's hasher may change between releases
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
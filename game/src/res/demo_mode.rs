@@ -0,0 +1,5 @@
+use bevy::prelude::Resource;
+
+// Present while the offline game runs the idle demo, driven by the AI instead of the controls
+#[derive(Resource)]
+pub struct DemoMode;
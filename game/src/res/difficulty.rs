@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+// Chosen on the main menu, scales the single player game on top of DifficultyCurve
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn next(&mut self) {
+        *self = match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        };
+    }
+
+    pub fn ufo_speed_scale(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.75,
+            Difficulty::Normal => 1.,
+            Difficulty::Hard => 1.3,
+        }
+    }
+
+    // Above 1 spawns slower
+    pub fn spawn_interval_scale(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 1.3,
+            Difficulty::Normal => 1.,
+            Difficulty::Hard => 0.75,
+        }
+    }
+
+    pub fn bullet_cooldown(&self) -> Duration {
+        match self {
+            Difficulty::Easy => Duration::from_millis(80),
+            Difficulty::Normal => Duration::from_millis(100),
+            Difficulty::Hard => Duration::from_millis(130),
+        }
+    }
+
+    pub fn starting_health(&self) -> u8 {
+        match self {
+            Difficulty::Easy => 5,
+            Difficulty::Normal => 3,
+            Difficulty::Hard => 2,
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use bevy::math::Vec2;
+use bevy::prelude::Resource;
+use rand::Rng;
+
+// Tuning knobs for wave progression, waves start from 1.
+// UFO counts come from the stage scripts, looping through them adds `ufo_count_per_loop`
+#[derive(Resource)]
+pub struct DifficultyCurve {
+    pub ufo_count_per_loop: u32,
+    pub max_ufo_count: u32,
+    pub base_spawn_interval: f32,
+    pub spawn_interval_decay: f32,
+    pub min_spawn_interval: f32,
+    pub base_ufo_speed: f32,
+    pub ufo_speed_per_wave: f32,
+    pub max_ufo_speed: f32,
+    pub ufo_sway_per_wave: f32,
+    pub max_ufo_sway: f32,
+    pub banner_duration: Duration,
+}
+
+impl Default for DifficultyCurve {
+    fn default() -> Self {
+        Self {
+            ufo_count_per_loop: 4,
+            max_ufo_count: 40,
+            base_spawn_interval: 1.2,
+            spawn_interval_decay: 0.9,
+            min_spawn_interval: 0.2,
+            base_ufo_speed: 3.,
+            ufo_speed_per_wave: 0.5,
+            max_ufo_speed: 10.,
+            ufo_sway_per_wave: 0.5,
+            max_ufo_sway: 10.,
+            banner_duration: Duration::from_secs(2),
+        }
+    }
+}
+
+impl DifficultyCurve {
+    pub fn ufo_count(&self, scripted: u32, loops: u32) -> u32 {
+        (scripted + self.ufo_count_per_loop * loops).min(self.max_ufo_count)
+    }
+
+    pub fn spawn_interval(&self, wave: u32) -> Duration {
+        let secs = self.base_spawn_interval * self.spawn_interval_decay.powi(wave as i32 - 1);
+        Duration::from_secs_f32(secs.max(self.min_spawn_interval))
+    }
+
+    pub fn ufo_velocity(&self, wave: u32, rng: &mut impl Rng) -> Vec2 {
+        let progress = (wave - 1) as f32;
+        let speed =
+            (self.base_ufo_speed + self.ufo_speed_per_wave * progress).min(self.max_ufo_speed);
+        let sway = (self.ufo_sway_per_wave * progress).min(self.max_ufo_sway);
+        let x = if sway > 0. {
+            rng.random_range(-sway..sway)
+        } else {
+            0.
+        };
+        Vec2::new(x, -speed)
+    }
+}
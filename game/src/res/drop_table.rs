@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::components::{PowerUpKind, UFOKind};
+
+use super::EnemyCatalog;
+
+#[derive(Deserialize, Clone, Copy)]
+pub struct DropChance {
+    pub kind: PowerUpKind,
+    pub chance: f32,
+}
+
+// Pickups each UFO kind can leave behind, built from the drops in enemies.ron
+#[derive(Resource)]
+pub struct DropTable(Vec<Vec<DropChance>>);
+
+impl DropTable {
+    pub fn from_catalog(catalog: &EnemyCatalog) -> Self {
+        Self(
+            catalog
+                .iter()
+                .map(|definition| definition.drops.clone())
+                .collect(),
+        )
+    }
+
+    // One roll for the whole list so a UFO never drops more than one pickup
+    pub fn roll(&self, kind: UFOKind, rng: &mut impl Rng) -> Option<PowerUpKind> {
+        let drops = &self.0[kind.index() % self.0.len()];
+        let mut roll: f32 = rng.random();
+        for drop in drops {
+            if roll < drop.chance {
+                return Some(drop.kind);
+            }
+            roll -= drop.chance;
+        }
+        None
+    }
+}
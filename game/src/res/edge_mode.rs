@@ -0,0 +1,23 @@
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+// Chosen in settings, Wrap lets the spaceship leave one side and come back on the other
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum EdgeMode {
+    #[default]
+    Clamp,
+    Wrap,
+}
+
+impl EdgeMode {
+    pub fn next(&mut self) {
+        *self = match self {
+            EdgeMode::Clamp => EdgeMode::Wrap,
+            EdgeMode::Wrap => EdgeMode::Clamp,
+        };
+    }
+
+    pub fn is_wrap(&self) -> bool {
+        *self == EdgeMode::Wrap
+    }
+}
@@ -0,0 +1,51 @@
+use bevy::color::{Color, LinearRgba};
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+// How far past white a glowing sprite goes, only the part above 1 blooms
+const GLOW_INTENSITY: f32 = 3.;
+
+// Visual feedback that can be turned off for accessibility
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EffectOption {
+    pub screen_shake: bool,
+    pub hit_flash: bool,
+    pub slow_motion: bool,
+    // Post-processing, both off by default as they cost more on weak devices
+    pub bloom: bool,
+    pub crt: bool,
+}
+
+impl Default for EffectOption {
+    fn default() -> Self {
+        Self {
+            screen_shake: true,
+            hit_flash: true,
+            slow_motion: true,
+            bloom: false,
+            crt: false,
+        }
+    }
+}
+
+impl EffectOption {
+    // Pushes bullets and explosions past white so the bloom picks them up
+    pub fn glow(&self, color: Color) -> Color {
+        if !self.bloom {
+            return color;
+        }
+        let LinearRgba {
+            red,
+            green,
+            blue,
+            alpha,
+        } = color.to_linear();
+        Color::linear_rgba(
+            red * GLOW_INTENSITY,
+            green * GLOW_INTENSITY,
+            blue * GLOW_INTENSITY,
+            alpha,
+        )
+    }
+}
@@ -0,0 +1,151 @@
+use std::ops::Range;
+
+use bevy::prelude::*;
+use rand::seq::IndexedRandom;
+use rand::Rng;
+use serde::Deserialize;
+use shooting_game_shared::util::UFO_SIZE;
+
+use crate::components::UFOKind;
+
+use super::DropChance;
+
+// Built in movement patterns a definition can pick from
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum EnemyMovement {
+    Straight,
+    Zigzag,
+    Kamikaze,
+    // Follows the spaceship sideways from the top and dives at it every so often
+    Stalker,
+}
+
+fn default_scale() -> f32 {
+    1.
+}
+
+// One entry of assets/enemies.ron as written, colors are srgb
+#[derive(Deserialize)]
+pub struct EnemyDefinitionScript {
+    name: String,
+    movement: EnemyMovement,
+    health: u8,
+    score: u32,
+    #[serde(default = "default_scale")]
+    speed: f32,
+    #[serde(default = "default_scale")]
+    size: f32,
+    pub sprite: String,
+    color: (f32, f32, f32),
+    color_blind: (f32, f32, f32),
+    fire_interval: (f32, f32),
+    #[serde(default)]
+    weight: u32,
+    #[serde(default)]
+    from_wave: u32,
+    #[serde(default)]
+    per_wave: u32,
+    max_weight: u32,
+    #[serde(default)]
+    drops: Vec<DropChance>,
+}
+
+impl EnemyDefinitionScript {
+    // Catches entries that would only fail once the UFO spawns
+    pub fn check(&self) -> Result<(), String> {
+        let (min, max) = self.fire_interval;
+        if min <= 0. || min >= max {
+            return Err(format!("{} has an invalid fire_interval", self.name));
+        }
+        if self.health == 0 {
+            return Err(format!("{} has no health", self.name));
+        }
+        let total_chance: f32 = self.drops.iter().map(|drop| drop.chance).sum();
+        if self.drops.iter().any(|drop| drop.chance < 0.) || total_chance > 1. {
+            return Err(format!("{} has invalid drop chances", self.name));
+        }
+        Ok(())
+    }
+
+    pub fn into_definition(self, sprite: Handle<Image>) -> EnemyDefinition {
+        let (r, g, b) = self.color;
+        let color = Color::srgb(r, g, b);
+        let (r, g, b) = self.color_blind;
+        EnemyDefinition {
+            movement: self.movement,
+            health: self.health,
+            score: self.score,
+            speed: self.speed,
+            size: UFO_SIZE * self.size,
+            sprite,
+            color,
+            color_blind_color: Color::srgb(r, g, b),
+            fire_interval: self.fire_interval.0..self.fire_interval.1,
+            weight: self.weight,
+            from_wave: self.from_wave,
+            per_wave: self.per_wave,
+            max_weight: self.max_weight,
+            drops: self.drops,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct EnemyDefinition {
+    pub movement: EnemyMovement,
+    pub health: u8,
+    pub score: u32,
+    // Share of the wave velocity the UFO moves at
+    pub speed: f32,
+    pub size: Vec2,
+    pub sprite: Handle<Image>,
+    color: Color,
+    color_blind_color: Color,
+    pub fire_interval: Range<f32>,
+    weight: u32,
+    from_wave: u32,
+    per_wave: u32,
+    max_weight: u32,
+    pub drops: Vec<DropChance>,
+}
+
+impl EnemyDefinition {
+    pub fn color(&self, color_blind: bool) -> Color {
+        if color_blind {
+            self.color_blind_color
+        } else {
+            self.color
+        }
+    }
+
+    // Tougher kinds show up from later waves and grow more common over time
+    pub fn spawn_weight(&self, wave: u32) -> u32 {
+        (self.weight + wave.saturating_sub(self.from_wave) * self.per_wave).min(self.max_weight)
+    }
+}
+
+// Loaded from assets/enemies.ron, inserted once loading is done
+#[derive(Asset, Resource, TypePath, Clone)]
+pub struct EnemyCatalog(Vec<EnemyDefinition>);
+
+impl EnemyCatalog {
+    pub fn new(definitions: Vec<EnemyDefinition>) -> Self {
+        Self(definitions)
+    }
+
+    pub fn get(&self, kind: UFOKind) -> &EnemyDefinition {
+        &self.0[kind.index() % self.0.len()]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &EnemyDefinition> {
+        self.0.iter()
+    }
+
+    pub fn choose(&self, wave: u32, rng: &mut impl Rng) -> UFOKind {
+        let kinds: Vec<UFOKind> = (0..self.0.len()).map(UFOKind::from_index).collect();
+        kinds
+            .choose_weighted(rng, |kind| self.get(*kind).spawn_weight(wave))
+            .copied()
+            .unwrap_or(UFOKind::BASIC)
+    }
+}
@@ -0,0 +1,15 @@
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+// Chosen in settings, on lets local co-op players shoot each other
+#[derive(Resource, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FriendlyFireOption {
+    pub enabled: bool,
+}
+
+impl FriendlyFireOption {
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+}
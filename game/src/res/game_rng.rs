@@ -0,0 +1,86 @@
+use bevy::prelude::{info, warn, Resource};
+use rand::{rng, rngs::StdRng, Rng, SeedableRng};
+
+const STREAM_COUNT: usize = 8;
+
+// Each consumer draws from its own stream so system ordering can't change the results
+#[derive(Clone, Copy)]
+pub enum RngStream {
+    UfoSpawn,
+    UfoWeapon,
+    PowerUp,
+    Asteroid,
+    // Particles, screen shake and backgrounds, kept apart so effect settings can't shift gameplay
+    Cosmetic,
+    BulletTag,
+    Drop,
+    Ore,
+}
+
+// Gameplay randomness, reseeded every frame so replays can reproduce a run
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    // Hands out the per frame seeds, never reseeded so a whole session follows the launch seed
+    master: StdRng,
+    streams: [StdRng; STREAM_COUNT],
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        let seed = launch_seed().unwrap_or_else(|| rng().random());
+        info!("Game seed: {seed}");
+        Self {
+            seed,
+            master: StdRng::seed_from_u64(seed),
+            streams: streams_from(seed),
+        }
+    }
+}
+
+impl GameRng {
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn next_seed(&mut self) -> u64 {
+        self.master.random()
+    }
+
+    // Restarts the whole session from `seed`, per frame seeds included
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.master = StdRng::seed_from_u64(seed);
+        self.streams = streams_from(seed);
+    }
+
+    pub fn reseed(&mut self, seed: u64) {
+        self.streams = streams_from(seed);
+    }
+
+    pub fn stream(&mut self, stream: RngStream) -> &mut StdRng {
+        &mut self.streams[stream as usize]
+    }
+}
+
+fn streams_from(seed: u64) -> [StdRng; STREAM_COUNT] {
+    std::array::from_fn(|i| StdRng::seed_from_u64(seed.wrapping_add(i as u64)))
+}
+
+// Either `--seed 42` or `--seed=42`, anything else falls back to a random seed
+fn launch_seed() -> Option<u64> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = match arg.strip_prefix("--seed") {
+            Some("") => args.next(),
+            Some(value) => value.strip_prefix('=').map(str::to_owned),
+            None => continue,
+        };
+        let seed = value.as_deref().and_then(|value| value.parse().ok());
+        if seed.is_none() {
+            warn!("Invalid --seed, using a random seed");
+        }
+        return seed;
+    }
+    None
+}
@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Scales gameplay time, movement and timers read their delta through it so they can be slowed down together
+#[derive(Resource)]
+pub struct GameSpeed {
+    // From the GameSpeedOption while an offline game runs, online games always run at 1
+    pub setting: f32,
+    pub slow_motion: f32,
+}
+
+impl Default for GameSpeed {
+    fn default() -> Self {
+        Self {
+            setting: 1.,
+            slow_motion: 1.,
+        }
+    }
+}
+
+impl GameSpeed {
+    pub fn factor(&self) -> f32 {
+        self.setting * self.slow_motion
+    }
+
+    pub fn delta(&self, time: &Time) -> Duration {
+        time.delta().mul_f32(self.factor())
+    }
+
+    pub fn delta_secs(&self, time: &Time) -> f32 {
+        time.delta_secs() * self.factor()
+    }
+}
+
+// Chosen in settings, the whole offline game runs slower or faster, menus and UI don't
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum GameSpeedOption {
+    Relaxed,
+    #[default]
+    Normal,
+    Turbo,
+}
+
+impl GameSpeedOption {
+    pub fn next(&mut self) {
+        *self = match self {
+            GameSpeedOption::Relaxed => GameSpeedOption::Normal,
+            GameSpeedOption::Normal => GameSpeedOption::Turbo,
+            GameSpeedOption::Turbo => GameSpeedOption::Relaxed,
+        };
+    }
+
+    pub fn scale(&self) -> f32 {
+        match self {
+            GameSpeedOption::Relaxed => 0.75,
+            GameSpeedOption::Normal => 1.,
+            GameSpeedOption::Turbo => 1.3,
+        }
+    }
+}
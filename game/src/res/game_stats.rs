@@ -0,0 +1,22 @@
+use std::time::Duration;
+
+use bevy::prelude::Resource;
+
+// Collected over a single run for the game over summary
+#[derive(Resource, Default)]
+pub struct GameStats {
+    pub ufos_destroyed: u32,
+    pub bullets_fired: u32,
+    pub bullets_hit: u32,
+    pub time_survived: Duration,
+    pub wave_reached: u32,
+}
+
+impl GameStats {
+    pub fn accuracy(&self) -> f32 {
+        if self.bullets_fired == 0 {
+            return 0.;
+        }
+        self.bullets_hit as f32 / self.bullets_fired as f32 * 100.
+    }
+}
@@ -0,0 +1,73 @@
+use std::cmp::Reverse;
+
+use bevy::prelude::Resource;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::{ControlMode, ControlOption, Difficulty};
+
+// Per partition
+pub const MAX_HIGH_SCORES: usize = 10;
+
+// Scores only compete with runs played on the same difficulty and control mode,
+// entries saved before the split count as Normal Keyboard runs
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LeaderboardPartition {
+    pub difficulty: Difficulty,
+    pub control: ControlMode,
+}
+
+impl LeaderboardPartition {
+    pub fn current(difficulty: &Difficulty, control_option: &ControlOption) -> Self {
+        Self {
+            difficulty: *difficulty,
+            control: control_option.mode.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HighScoreEntry {
+    pub name: String,
+    pub score: u32,
+    pub date: NaiveDate,
+    #[serde(flatten)]
+    pub partition: LeaderboardPartition,
+}
+
+#[derive(Resource, Default, Serialize, Deserialize)]
+pub struct HighScores(Vec<HighScoreEntry>);
+
+impl HighScores {
+    // Highest first
+    pub fn entries(&self, partition: &LeaderboardPartition) -> Vec<&HighScoreEntry> {
+        self.0
+            .iter()
+            .filter(|entry| entry.partition == *partition)
+            .collect()
+    }
+
+    pub fn qualifies(&self, score: u32, partition: &LeaderboardPartition) -> bool {
+        if score == 0 {
+            return false;
+        }
+        let entries = self.entries(partition);
+        entries.len() < MAX_HIGH_SCORES || entries.iter().any(|entry| entry.score < score)
+    }
+
+    pub fn insert(&mut self, entry: HighScoreEntry) {
+        // Only the new entry's partition can have grown past the limit
+        let partition = entry.partition.clone();
+        self.0.push(entry);
+        self.0.sort_by_key(|entry| Reverse(entry.score));
+        let mut kept = 0;
+        self.0.retain(|entry| {
+            if entry.partition != partition {
+                return true;
+            }
+            kept += 1;
+            kept <= MAX_HIGH_SCORES
+        });
+    }
+}
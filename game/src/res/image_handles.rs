@@ -3,6 +3,7 @@ pub use bevy::prelude::*;
 #[derive(Resource, Default)]
 pub struct ImageHandles {
     pub explosion: Handle<Image>,
+    pub explosion_layout: Handle<TextureAtlasLayout>,
     pub spaceship: Handle<Image>,
     pub ufo: Handle<Image>,
     pub stars: Handle<Image>,
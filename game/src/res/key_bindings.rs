@@ -0,0 +1,121 @@
+use bevy::prelude::{Component, KeyCode, Resource};
+use serde::{Deserialize, Serialize};
+
+#[derive(Component, Clone, Copy, Eq, PartialEq)]
+pub enum KeyAction {
+    Up,
+    Down,
+    Left,
+    Right,
+    Shoot,
+    Missile,
+    Bomb,
+    Dash,
+}
+
+impl KeyAction {
+    pub const ALL: [KeyAction; 8] = [
+        KeyAction::Up,
+        KeyAction::Down,
+        KeyAction::Left,
+        KeyAction::Right,
+        KeyAction::Shoot,
+        KeyAction::Missile,
+        KeyAction::Bomb,
+        KeyAction::Dash,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            KeyAction::Up => "Move Up",
+            KeyAction::Down => "Move Down",
+            KeyAction::Left => "Move Left",
+            KeyAction::Right => "Move Right",
+            KeyAction::Shoot => "Shoot",
+            KeyAction::Missile => "Missile",
+            KeyAction::Bomb => "Bomb",
+            KeyAction::Dash => "Dash",
+        }
+    }
+}
+
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub up: KeyCode,
+    pub down: KeyCode,
+    pub left: KeyCode,
+    pub right: KeyCode,
+    pub shoot: KeyCode,
+    pub missile: KeyCode,
+    pub bomb: KeyCode,
+    pub dash: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            up: KeyCode::ArrowUp,
+            down: KeyCode::ArrowDown,
+            left: KeyCode::ArrowLeft,
+            right: KeyCode::ArrowRight,
+            shoot: KeyCode::Space,
+            missile: KeyCode::KeyX,
+            bomb: KeyCode::KeyB,
+            dash: KeyCode::ShiftLeft,
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn wasd() -> Self {
+        Self {
+            up: KeyCode::KeyW,
+            down: KeyCode::KeyS,
+            left: KeyCode::KeyA,
+            right: KeyCode::KeyD,
+            shoot: KeyCode::KeyF,
+            missile: KeyCode::KeyG,
+            bomb: KeyCode::KeyH,
+            dash: KeyCode::ShiftLeft,
+        }
+    }
+
+    pub fn key(&self, action: KeyAction) -> KeyCode {
+        match action {
+            KeyAction::Up => self.up,
+            KeyAction::Down => self.down,
+            KeyAction::Left => self.left,
+            KeyAction::Right => self.right,
+            KeyAction::Shoot => self.shoot,
+            KeyAction::Missile => self.missile,
+            KeyAction::Bomb => self.bomb,
+            KeyAction::Dash => self.dash,
+        }
+    }
+
+    // A key already used by another action is swapped over so every action stays bound
+    pub fn bind(&mut self, action: KeyAction, key: KeyCode) {
+        let previous = self.key(action);
+        if let Some(other) = KeyAction::ALL
+            .into_iter()
+            .find(|other| *other != action && self.key(*other) == key)
+        {
+            *self.slot(other) = previous;
+        }
+        *self.slot(action) = key;
+    }
+
+    fn slot(&mut self, action: KeyAction) -> &mut KeyCode {
+        match action {
+            KeyAction::Up => &mut self.up,
+            KeyAction::Down => &mut self.down,
+            KeyAction::Left => &mut self.left,
+            KeyAction::Right => &mut self.right,
+            KeyAction::Shoot => &mut self.shoot,
+            KeyAction::Missile => &mut self.missile,
+            KeyAction::Bomb => &mut self.bomb,
+            KeyAction::Dash => &mut self.dash,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+// Set `endpoint` in settings.ron to share scores online, scores stay local without it
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LeaderboardOption {
+    pub endpoint: Option<String>,
+}
+
+impl LeaderboardOption {
+    pub fn is_online(&self) -> bool {
+        self.endpoint.is_some()
+    }
+}
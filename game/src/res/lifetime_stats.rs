@@ -0,0 +1,27 @@
+use std::time::Duration;
+
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+use super::GameStats;
+
+// Totals over every finished single player run, kept across sessions
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LifetimeStats {
+    pub total_games: u32,
+    pub ufos_destroyed: u32,
+    pub bullets_fired: u32,
+    pub best_wave: u32,
+    pub play_time: Duration,
+}
+
+impl LifetimeStats {
+    pub fn add_run(&mut self, game_stats: &GameStats) {
+        self.total_games += 1;
+        self.ufos_destroyed += game_stats.ufos_destroyed;
+        self.bullets_fired += game_stats.bullets_fired;
+        self.best_wave = self.best_wave.max(game_stats.wave_reached);
+        self.play_time += game_stats.time_survived;
+    }
+}
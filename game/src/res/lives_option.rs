@@ -0,0 +1,32 @@
+use bevy::prelude::Resource;
+use serde::{Deserialize, Deserializer, Serialize};
+
+const DEFAULT_LIVES: u8 = 3;
+pub const MAX_LIVES: u8 = 5;
+
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LivesOption {
+    #[serde(deserialize_with = "deserialize_lives")]
+    pub lives: u8,
+}
+
+impl Default for LivesOption {
+    fn default() -> Self {
+        Self {
+            lives: DEFAULT_LIVES,
+        }
+    }
+}
+
+impl LivesOption {
+    // Cycles 1..=MAX_LIVES
+    pub fn next(&mut self) {
+        self.lives = self.lives % MAX_LIVES + 1;
+    }
+}
+
+// A hand edited file could ask for no lives at all or more than the toggle goes up to
+fn deserialize_lives<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u8, D::Error> {
+    u8::deserialize(deserializer).map(|lives| lives.clamp(1, MAX_LIVES))
+}
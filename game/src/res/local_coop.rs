@@ -0,0 +1,24 @@
+use bevy::prelude::{KeyCode, Resource};
+
+use super::KeyBindings;
+
+// Present while two players share one keyboard in the offline game
+#[derive(Resource)]
+pub struct LocalCoop;
+
+impl LocalCoop {
+    pub const PLAYERS: [u8; 2] = [1, 2];
+
+    pub fn key_bindings(player: u8) -> KeyBindings {
+        match player {
+            1 => KeyBindings::wasd(),
+            // The default missile, bomb and dash keys sit on player 1's side of the keyboard
+            _ => KeyBindings {
+                missile: KeyCode::ShiftRight,
+                bomb: KeyCode::Enter,
+                dash: KeyCode::ControlRight,
+                ..KeyBindings::default()
+            },
+        }
+    }
+}
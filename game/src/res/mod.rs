@@ -1,18 +1,96 @@
+mod accessibility_option;
+mod adaptive_difficulty;
+mod audio_option;
+mod background_palette;
+mod collision_matrix;
+mod combo;
 mod control_option;
+mod daily_challenge;
+mod demo_mode;
+mod difficulty;
+mod difficulty_curve;
+mod drop_table;
+mod edge_mode;
+mod effect_option;
+mod enemy_catalog;
+mod friendly_fire_option;
+mod game_rng;
+mod game_speed;
+mod game_stats;
+mod high_scores;
 mod image_handles;
+mod key_bindings;
+mod leaderboard_option;
+mod lifetime_stats;
+mod lives_option;
+mod local_coop;
+mod movement_tuning;
+mod nickname;
+mod player_names;
 mod player_tag;
+mod rumble_option;
+mod run_settings;
+mod save_slot;
+mod session_token;
+mod spectator;
+mod stage_progress;
+mod stage_script;
+mod tutorial_mode;
+mod weapon_mode;
 
+pub use accessibility_option::AccessibilityOption;
+pub use adaptive_difficulty::{AdaptiveDifficulty, AdaptiveDifficultyOption};
+pub use audio_option::AudioOption;
+pub use background_palette::BackgroundPalette;
 use bevy::prelude::{App, Plugin};
+pub use collision_matrix::CollisionMatrix;
+pub use combo::Combo;
 pub use control_option::{ControlMode, ControlOption};
+pub use daily_challenge::{DailyChallenge, DailyRecord};
+pub use demo_mode::DemoMode;
+pub use difficulty::Difficulty;
+pub use difficulty_curve::DifficultyCurve;
+pub use drop_table::{DropChance, DropTable};
+pub use edge_mode::EdgeMode;
+pub use effect_option::EffectOption;
+pub use enemy_catalog::{EnemyCatalog, EnemyDefinitionScript, EnemyMovement};
+pub use friendly_fire_option::FriendlyFireOption;
+pub use game_rng::{GameRng, RngStream};
+pub use game_speed::{GameSpeed, GameSpeedOption};
+pub use game_stats::GameStats;
+pub use high_scores::{HighScoreEntry, HighScores, LeaderboardPartition};
 pub use image_handles::ImageHandles;
+pub use key_bindings::{KeyAction, KeyBindings};
+pub use leaderboard_option::LeaderboardOption;
+pub use lifetime_stats::LifetimeStats;
+pub use lives_option::LivesOption;
+pub use local_coop::LocalCoop;
+pub use movement_tuning::MovementTuning;
+pub use nickname::Nickname;
+pub use player_names::PlayerNames;
 pub use player_tag::PlayerTag;
+pub use rumble_option::RumbleOption;
+pub use run_settings::{PlayerRunSettings, RunSettings, RunSettingsParam};
+pub use save_slot::{ContinueRun, SaveSlot, StageSave};
+pub use session_token::SessionToken;
+pub use spectator::Spectator;
+pub use stage_progress::StageProgress;
+pub use stage_script::{StageScript, StageScripts, WaveScript};
+pub use tutorial_mode::TutorialMode;
+pub use weapon_mode::WeaponMode;
 pub struct ResPlugin;
 impl Plugin for ResPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ImageHandles>()
-            .insert_resource(ControlOption {
-                mode: ControlMode::Keyboard,
-            })
+            .init_resource::<ControlOption>()
+            .init_resource::<DifficultyCurve>()
+            .init_resource::<StageScripts>()
+            .init_resource::<BackgroundPalette>()
+            .init_resource::<GameRng>()
+            .init_resource::<Combo>()
+            .init_resource::<GameSpeed>()
+            .init_resource::<CollisionMatrix>()
+            .init_resource::<PlayerNames>()
             .insert_resource(PlayerTag(1));
     }
 }
@@ -0,0 +1,21 @@
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+// Speeds are per fixed tick like Velocity, acceleration and drag are that speed gained or lost per second
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MovementTuning {
+    pub max_speed: f32,
+    pub acceleration: f32,
+    pub drag: f32,
+}
+
+impl Default for MovementTuning {
+    fn default() -> Self {
+        Self {
+            max_speed: 10.,
+            acceleration: 60.,
+            drag: 40.,
+        }
+    }
+}
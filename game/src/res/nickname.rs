@@ -0,0 +1,22 @@
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+use shooting_game_shared::sanitize_name;
+
+// Typed on the main menu and sent when joining online, empty plays as the player number
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+pub struct Nickname(String);
+
+impl Nickname {
+    pub fn get(&self) -> &str {
+        &self.0
+    }
+
+    // Kept to what the server accepts, so the name shown here is the one the others see
+    pub fn push_str(&mut self, text: &str) {
+        self.0 = sanitize_name(&format!("{}{text}", self.0));
+    }
+
+    pub fn pop(&mut self) {
+        self.0.pop();
+    }
+}
@@ -0,0 +1,17 @@
+use std::collections::HashMap;
+
+use bevy::prelude::Resource;
+
+// What the server last said everyone in the room is called
+#[derive(Resource, Default)]
+pub struct PlayerNames(pub HashMap<u8, String>);
+
+impl PlayerNames {
+    // Players without a nickname go by their number
+    pub fn name(&self, player_tag: u8) -> String {
+        self.0
+            .get(&player_tag)
+            .cloned()
+            .unwrap_or_else(|| format!("Player {player_tag}"))
+    }
+}
@@ -0,0 +1,28 @@
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+const STEP: f32 = 0.1;
+
+// Scales every gamepad rumble, 0 turns rumble off completely
+#[derive(Resource, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RumbleOption {
+    pub intensity: f32,
+}
+
+impl Default for RumbleOption {
+    fn default() -> Self {
+        Self { intensity: 0.8 }
+    }
+}
+
+impl RumbleOption {
+    // Snaps to steps of 10% so the slider lands on round numbers
+    pub fn set(&mut self, intensity: f32) {
+        self.intensity = ((intensity / STEP).round() * STEP).clamp(0., 1.);
+    }
+
+    pub fn is_off(&self) -> bool {
+        self.intensity <= 0.
+    }
+}
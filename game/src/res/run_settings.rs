@@ -0,0 +1,64 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::{ResMut, Resource};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    AdaptiveDifficultyOption, Difficulty, EdgeMode, GameSpeedOption, LivesOption, MovementTuning,
+    WeaponMode,
+};
+
+// The settings that change how a run plays out, a run played again needs the same ones
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunSettings {
+    pub difficulty: Difficulty,
+    pub lives: LivesOption,
+    pub movement: MovementTuning,
+    pub weapon: WeaponMode,
+    pub edges: EdgeMode,
+    pub speed: GameSpeedOption,
+    pub adaptive: AdaptiveDifficultyOption,
+}
+
+// The player's own run settings, put back once a run that swapped in others is left
+#[derive(Resource)]
+pub struct PlayerRunSettings(pub RunSettings);
+
+// The run settings resources read and swapped together
+#[derive(SystemParam)]
+pub struct RunSettingsParam<'w> {
+    difficulty: ResMut<'w, Difficulty>,
+    lives: ResMut<'w, LivesOption>,
+    movement: ResMut<'w, MovementTuning>,
+    weapon: ResMut<'w, WeaponMode>,
+    edges: ResMut<'w, EdgeMode>,
+    speed: ResMut<'w, GameSpeedOption>,
+    adaptive: ResMut<'w, AdaptiveDifficultyOption>,
+}
+
+impl RunSettingsParam<'_> {
+    pub fn get(&self) -> RunSettings {
+        RunSettings {
+            difficulty: *self.difficulty,
+            lives: self.lives.clone(),
+            movement: self.movement.clone(),
+            weapon: *self.weapon,
+            edges: *self.edges,
+            speed: *self.speed,
+            adaptive: *self.adaptive,
+        }
+    }
+
+    // Gives back the settings it replaced
+    pub fn replace(&mut self, settings: RunSettings) -> RunSettings {
+        let replaced = self.get();
+        *self.difficulty = settings.difficulty;
+        *self.lives = settings.lives;
+        *self.movement = settings.movement;
+        *self.weapon = settings.weapon;
+        *self.edges = settings.edges;
+        *self.speed = settings.speed;
+        *self.adaptive = settings.adaptive;
+        replaced
+    }
+}
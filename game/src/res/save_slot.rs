@@ -0,0 +1,49 @@
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+use super::RunSettings;
+
+// Where a solo run stood after its last cleared stage, `stage` is the one to play next
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StageSave {
+    pub stage: usize,
+    pub loops: u32,
+    // Waves played so far, the difficulty curve and bonus stages go by it
+    pub waves: u32,
+    pub weapon_level: u8,
+    pub lives: u8,
+    // A save from before these were kept goes on with the defaults
+    #[serde(default)]
+    pub settings: RunSettings,
+}
+
+impl StageSave {
+    fn is_further_than(&self, other: &StageSave) -> bool {
+        (self.loops, self.stage) >= (other.loops, other.stage)
+    }
+}
+
+// The furthest a run has got, kept across sessions
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SaveSlot {
+    pub save: Option<StageSave>,
+}
+
+impl SaveSlot {
+    // An earlier stage never replaces a further one
+    pub fn record(&mut self, save: StageSave) {
+        if self
+            .save
+            .as_ref()
+            .is_some_and(|saved| !save.is_further_than(saved))
+        {
+            return;
+        }
+        self.save = Some(save);
+    }
+}
+
+// Present while the offline game resumes from the save slot instead of the first stage
+#[derive(Resource)]
+pub struct ContinueRun(pub StageSave);
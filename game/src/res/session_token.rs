@@ -0,0 +1,5 @@
+use bevy::prelude::Resource;
+
+// Given by the server on join, used to resume the same player after a drop
+#[derive(Resource)]
+pub struct SessionToken(pub u64);
@@ -0,0 +1,7 @@
+use bevy::prelude::Resource;
+
+// Present while watching a full room, no spaceship is controlled
+#[derive(Resource)]
+pub struct Spectator {
+    pub joined_mid_game: bool,
+}
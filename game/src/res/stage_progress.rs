@@ -0,0 +1,61 @@
+use bevy::prelude::Resource;
+
+use super::{StageScript, StageScripts, WaveScript};
+
+// Where the run is in the stage scripts, `wave` counts from 0 within the stage
+#[derive(Resource, Default)]
+pub struct StageProgress {
+    stage: usize,
+    wave: usize,
+    loops: u32,
+}
+
+impl StageProgress {
+    // Starts on the first wave of a saved stage
+    pub fn resume(stage: usize, loops: u32) -> Self {
+        Self {
+            stage,
+            wave: 0,
+            loops,
+        }
+    }
+
+    pub fn stage<'a>(&self, scripts: &'a StageScripts) -> &'a StageScript {
+        scripts.get(self.stage)
+    }
+
+    // Counts every stage played from 1, looped ones included
+    pub fn stage_number(&self, scripts: &StageScripts) -> u32 {
+        self.loops * scripts.len() as u32 + self.stage as u32 + 1
+    }
+
+    pub fn stage_index(&self) -> usize {
+        self.stage
+    }
+
+    pub fn loops(&self) -> u32 {
+        self.loops
+    }
+
+    pub fn is_first_wave(&self) -> bool {
+        self.wave == 0
+    }
+
+    // None on the boss wave that follows the scripted ones
+    pub fn wave_script<'a>(&self, scripts: &'a StageScripts) -> Option<&'a WaveScript> {
+        self.stage(scripts).waves.get(self.wave)
+    }
+
+    pub fn next_wave(&mut self) {
+        self.wave += 1;
+    }
+
+    pub fn next_stage(&mut self, scripts: &StageScripts) {
+        self.wave = 0;
+        self.stage += 1;
+        if self.stage >= scripts.len() {
+            self.stage = 0;
+            self.loops += 1;
+        }
+    }
+}
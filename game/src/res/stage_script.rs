@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::BackgroundPalette;
+
+const STAGES: &str = include_str!("../../../assets/stages.ron");
+const DAILY_STAGES: &str = include_str!("../../../assets/daily.ron");
+
+#[derive(Deserialize)]
+pub struct WaveScript {
+    pub ufos: u32,
+    #[serde(default)]
+    pub asteroids: bool,
+}
+
+// One stage of assets/stages.ron, colors are srgb
+#[derive(Deserialize)]
+pub struct StageScript {
+    pub name: String,
+    stars: (f32, f32, f32),
+    backdrop: (f32, f32, f32),
+    pub waves: Vec<WaveScript>,
+}
+
+impl StageScript {
+    pub fn palette(&self) -> BackgroundPalette {
+        let (r, g, b) = self.stars;
+        let stars = Color::srgb(r, g, b);
+        let (r, g, b) = self.backdrop;
+        BackgroundPalette {
+            backdrop: Color::srgb(r, g, b),
+            stars,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct StageScripts(Vec<StageScript>);
+
+impl Default for StageScripts {
+    fn default() -> Self {
+        Self::parse(STAGES, "stages.ron")
+    }
+}
+
+impl StageScripts {
+    pub fn daily() -> Self {
+        Self::parse(DAILY_STAGES, "daily.ron")
+    }
+
+    fn parse(content: &str, file_name: &str) -> Self {
+        let stages: Vec<StageScript> =
+            ron::from_str(content).unwrap_or_else(|e| panic!("Invalid {file_name}: {e}"));
+        if stages.is_empty() {
+            panic!("{file_name} has no stage");
+        }
+        Self(stages)
+    }
+
+    pub fn get(&self, stage: usize) -> &StageScript {
+        &self.0[stage % self.0.len()]
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
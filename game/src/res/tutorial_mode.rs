@@ -0,0 +1,5 @@
+use bevy::prelude::Resource;
+
+// Present while the offline game runs the tutorial instead of a real run
+#[derive(Resource)]
+pub struct TutorialMode;
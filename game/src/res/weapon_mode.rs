@@ -0,0 +1,23 @@
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+// Chosen in settings, Heat swaps the flat bullet cooldown for a heat gauge
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum WeaponMode {
+    #[default]
+    Classic,
+    Heat,
+}
+
+impl WeaponMode {
+    pub fn next(&mut self) {
+        *self = match self {
+            WeaponMode::Classic => WeaponMode::Heat,
+            WeaponMode::Heat => WeaponMode::Classic,
+        };
+    }
+
+    pub fn is_heat(&self) -> bool {
+        *self == WeaponMode::Heat
+    }
+}
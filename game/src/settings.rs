@@ -0,0 +1,157 @@
+use bevy::prelude::*;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+use crate::persistence::{load_json, read_file, write_file};
+use crate::res::{
+    AccessibilityOption, AdaptiveDifficultyOption, AudioOption, ControlOption, Difficulty,
+    EdgeMode, EffectOption, FriendlyFireOption, GameSpeedOption, KeyBindings, LeaderboardOption,
+    LivesOption, MovementTuning, Nickname, PlayerRunSettings, RumbleOption, WeaponMode,
+};
+
+const SETTINGS_FILE: &str = "settings.ron";
+const SETTINGS_BACKUP_FILE: &str = "settings.ron.bak";
+// Written separately before settings.ron existed, only read to migrate
+const LEGACY_KEY_BINDINGS_FILE: &str = "key_bindings.json";
+const LEGACY_EFFECT_OPTION_FILE: &str = "effect_option.json";
+const LEGACY_LIVES_OPTION_FILE: &str = "lives_option.json";
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_settings).add_systems(
+            Update,
+            save_settings.run_if(
+                resource_changed::<ControlOption>
+                    .or(resource_changed::<KeyBindings>)
+                    .or(resource_changed::<EffectOption>)
+                    .or(resource_changed::<LivesOption>)
+                    .or(resource_changed::<Difficulty>)
+                    .or(resource_changed::<AudioOption>)
+                    .or(resource_changed::<MovementTuning>)
+                    .or(resource_changed::<LeaderboardOption>)
+                    .or(resource_changed::<AccessibilityOption>)
+                    .or(resource_changed::<WeaponMode>)
+                    .or(resource_changed::<EdgeMode>)
+                    .or(resource_changed::<RumbleOption>)
+                    .or(resource_changed::<GameSpeedOption>)
+                    .or(resource_changed::<Nickname>)
+                    .or(resource_changed::<AdaptiveDifficultyOption>)
+                    .or(resource_changed::<FriendlyFireOption>)
+                    // The daily challenge swaps in the defaults for the run and a replay the
+                    // ones it was recorded with
+                    .and(not(resource_exists::<PlayerRunSettings>)),
+            ),
+        );
+    }
+}
+
+// Fields missing from an older file fall back to their defaults
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct Settings {
+    control: ControlOption,
+    key_bindings: KeyBindings,
+    effects: EffectOption,
+    lives: LivesOption,
+    difficulty: Difficulty,
+    audio: AudioOption,
+    movement: MovementTuning,
+    leaderboard: LeaderboardOption,
+    accessibility: AccessibilityOption,
+    weapon: WeaponMode,
+    edges: EdgeMode,
+    rumble: RumbleOption,
+    speed: GameSpeedOption,
+    nickname: Nickname,
+    adaptive: AdaptiveDifficultyOption,
+    friendly_fire: FriendlyFireOption,
+}
+
+impl Settings {
+    fn load() -> Self {
+        let Some(content) = read_file(SETTINGS_FILE) else {
+            return Self::from_legacy_files();
+        };
+        ron::from_str(&content).unwrap_or_else(|e| {
+            // The defaults get saved over it, so the player's file is kept aside to fix by hand
+            warn!("Failed to parse {SETTINGS_FILE}, keeping it as {SETTINGS_BACKUP_FILE}: {e}");
+            write_file(SETTINGS_BACKUP_FILE, content);
+            Self::default()
+        })
+    }
+
+    fn from_legacy_files() -> Self {
+        Self {
+            key_bindings: load_json(LEGACY_KEY_BINDINGS_FILE).unwrap_or_default(),
+            effects: load_json(LEGACY_EFFECT_OPTION_FILE).unwrap_or_default(),
+            lives: load_json(LEGACY_LIVES_OPTION_FILE).unwrap_or_default(),
+            ..default()
+        }
+    }
+}
+
+fn load_settings(mut commands: Commands) {
+    let settings = Settings::load();
+    commands.insert_resource(settings.control);
+    commands.insert_resource(settings.key_bindings);
+    commands.insert_resource(settings.effects);
+    commands.insert_resource(settings.lives);
+    commands.insert_resource(settings.difficulty);
+    commands.insert_resource(settings.audio);
+    commands.insert_resource(settings.movement);
+    commands.insert_resource(settings.leaderboard);
+    commands.insert_resource(settings.accessibility);
+    commands.insert_resource(settings.weapon);
+    commands.insert_resource(settings.edges);
+    commands.insert_resource(settings.rumble);
+    commands.insert_resource(settings.speed);
+    commands.insert_resource(settings.nickname);
+    commands.insert_resource(settings.adaptive);
+    commands.insert_resource(settings.friendly_fire);
+}
+
+// Also runs once after loading, which writes out migrated legacy settings
+#[allow(clippy::too_many_arguments)]
+fn save_settings(
+    control: Res<ControlOption>,
+    key_bindings: Res<KeyBindings>,
+    effects: Res<EffectOption>,
+    lives: Res<LivesOption>,
+    difficulty: Res<Difficulty>,
+    audio: Res<AudioOption>,
+    movement: Res<MovementTuning>,
+    leaderboard: Res<LeaderboardOption>,
+    accessibility: Res<AccessibilityOption>,
+    weapon: Res<WeaponMode>,
+    edges: Res<EdgeMode>,
+    rumble: Res<RumbleOption>,
+    speed: Res<GameSpeedOption>,
+    nickname: Res<Nickname>,
+    adaptive: Res<AdaptiveDifficultyOption>,
+    friendly_fire: Res<FriendlyFireOption>,
+) {
+    let settings = Settings {
+        control: control.clone(),
+        key_bindings: key_bindings.clone(),
+        effects: effects.clone(),
+        lives: lives.clone(),
+        difficulty: *difficulty,
+        audio: audio.clone(),
+        movement: movement.clone(),
+        leaderboard: leaderboard.clone(),
+        accessibility: accessibility.clone(),
+        weapon: *weapon,
+        edges: *edges,
+        rumble: *rumble,
+        speed: *speed,
+        nickname: nickname.clone(),
+        adaptive: *adaptive,
+        friendly_fire: *friendly_fire,
+    };
+    match ron::ser::to_string_pretty(&settings, PrettyConfig::default()) {
+        Ok(content) => write_file(SETTINGS_FILE, content),
+        Err(e) => warn!("Failed to serialize {SETTINGS_FILE}: {e}"),
+    }
+}
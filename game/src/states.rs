@@ -5,6 +5,8 @@ impl Plugin for StatePlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<AppState>()
             .add_sub_state::<GameState>()
+            .add_sub_state::<InPlayState>()
+            .add_sub_state::<PauseState>()
             .add_sub_state::<OnlineGameState>();
     }
 }
@@ -14,6 +16,9 @@ pub enum AppState {
     #[default]
     Loading,
     MainMenu,
+    Leaderboard,
+    Statistics,
+    Settings,
     Game,
     OnlineGame,
 }
@@ -24,7 +29,26 @@ pub enum GameState {
     #[default]
     Ready,
     InPlay,
-    Result,
+    GameOver,
+    Tutorial,
+}
+
+// Normal waves pause while the bonus stage runs
+#[derive(Default, SubStates, Debug, Hash, Eq, PartialEq, Clone)]
+#[source(GameState = GameState::InPlay)]
+pub enum InPlayState {
+    #[default]
+    Waves,
+    BonusStage,
+}
+
+// Virtual time stops while Paused, only the offline game can pause as the server keeps going
+#[derive(Default, SubStates, Debug, Hash, Eq, PartialEq, Clone)]
+#[source(AppState = AppState::Game)]
+pub enum PauseState {
+    #[default]
+    Running,
+    Paused,
 }
 
 #[derive(Default, SubStates, Debug, Hash, Eq, PartialEq, Clone)]
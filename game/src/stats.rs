@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+use crate::persistence::{load_json, save_json};
+use crate::res::LifetimeStats;
+
+const STATS_FILE: &str = "stats.json";
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_lifetime_stats).add_systems(
+            Update,
+            save_lifetime_stats.run_if(
+                resource_changed::<LifetimeStats>.and(not(resource_added::<LifetimeStats>)),
+            ),
+        );
+    }
+}
+
+fn load_lifetime_stats(mut commands: Commands) {
+    commands.insert_resource(load_json::<LifetimeStats>(STATS_FILE).unwrap_or_default());
+}
+
+fn save_lifetime_stats(lifetime_stats: Res<LifetimeStats>) {
+    save_json(STATS_FILE, lifetime_stats.as_ref());
+}
@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use bevy::color::palettes::css::{DARK_RED, RED};
+use bevy::prelude::*;
+
+use crate::constant::ZIndex;
+use crate::res::AccessibilityOption;
+
+const BAR_HEIGHT: f32 = 14.;
+const FLASH_DURATION: Duration = Duration::from_millis(600);
+const FLASH_INTERVAL: f32 = 0.1;
+const EMPTY_COLOR: Color = Color::srgba(0.2, 0.2, 0.2, 0.8);
+
+// Wide segmented bar across the top of the screen, fed by whoever owns the boss
+#[derive(Component)]
+pub struct BossHealthBar {
+    name: String,
+    segments: u8,
+    // Health ratios where a new phase starts, drawn as ticks over the bar
+    phase_markers: Vec<f32>,
+    ratio: f32,
+    flash_timer: Option<Timer>,
+}
+
+impl BossHealthBar {
+    pub fn new(name: impl Into<String>, segments: u8) -> Self {
+        Self {
+            name: name.into(),
+            segments: segments.max(1),
+            phase_markers: Vec::new(),
+            ratio: 1.,
+            flash_timer: None,
+        }
+    }
+
+    pub fn with_phase_markers(mut self, phase_markers: Vec<f32>) -> Self {
+        self.phase_markers = phase_markers;
+        self
+    }
+
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = ratio.clamp(0., 1.);
+    }
+
+    pub fn flash(&mut self) {
+        self.flash_timer = Some(Timer::new(FLASH_DURATION, TimerMode::Once));
+    }
+
+    fn filled_segments(&self) -> u8 {
+        (self.ratio * self.segments as f32).ceil() as u8
+    }
+}
+
+#[derive(Component)]
+struct BossHealthSegment(u8);
+
+pub struct BossHealthBarPlugin;
+
+impl Plugin for BossHealthBarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (flash_boss_health_bar, update_boss_health_bar).chain(),
+        )
+        .add_observer(boss_health_bar_on_add);
+    }
+}
+
+fn boss_health_bar_on_add(
+    ev: Trigger<OnAdd, BossHealthBar>,
+    mut commands: Commands,
+    bar_q: Query<&BossHealthBar>,
+) {
+    let Ok(bar) = bar_q.get(ev.target()) else {
+        warn!("BossHealthBar not found in boss_health_bar_on_add");
+        return;
+    };
+    let Ok(mut entity_commands) = commands.get_entity(ev.target()) else {
+        return;
+    };
+    entity_commands
+        .insert((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(5.),
+                left: Val::Percent(10.),
+                width: Val::Percent(80.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(2.),
+                ..default()
+            },
+            ZIndex::TEXT.component(),
+        ))
+        .with_children(|container| {
+            container.spawn((Text::new(bar.name.clone()), TextFont::from_font_size(16.)));
+            container
+                .spawn(Node {
+                    width: Val::Percent(100.),
+                    height: Val::Px(BAR_HEIGHT),
+                    column_gap: Val::Px(1.),
+                    ..default()
+                })
+                .with_children(|segments| {
+                    for index in 0..bar.segments {
+                        segments.spawn((
+                            BossHealthSegment(index),
+                            Node {
+                                flex_grow: 1.,
+                                height: Val::Percent(100.),
+                                ..default()
+                            },
+                            BackgroundColor(RED.into()),
+                        ));
+                    }
+                    for marker in bar.phase_markers.iter() {
+                        segments.spawn((
+                            Node {
+                                position_type: PositionType::Absolute,
+                                left: Val::Percent(marker * 100.),
+                                width: Val::Px(2.),
+                                height: Val::Px(BAR_HEIGHT + 6.),
+                                top: Val::Px(-3.),
+                                ..default()
+                            },
+                            BackgroundColor(Color::WHITE),
+                        ));
+                    }
+                });
+        });
+}
+
+fn flash_boss_health_bar(
+    mut bar_q: Query<&mut BossHealthBar>,
+    time: Res<Time>,
+    accessibility: Res<AccessibilityOption>,
+) {
+    for mut bar in bar_q.iter_mut() {
+        // Read first so idle bars aren't marked changed every frame
+        if bar.flash_timer.is_none() {
+            continue;
+        }
+        let finished = bar
+            .flash_timer
+            .as_mut()
+            .is_some_and(|timer| timer.tick(time.delta()).finished());
+        if finished || accessibility.disable_flashing {
+            bar.flash_timer = None;
+        }
+    }
+}
+
+fn update_boss_health_bar(
+    bar_q: Query<(Entity, &BossHealthBar), Changed<BossHealthBar>>,
+    children_q: Query<&Children>,
+    mut segment_q: Query<(&BossHealthSegment, &mut BackgroundColor)>,
+) {
+    for (entity, bar) in bar_q.iter() {
+        let filled = bar.filled_segments();
+        // Alternates between bright and dark while a phase change flashes the bar
+        let flash_on = bar.flash_timer.as_ref().is_some_and(|timer| {
+            ((timer.elapsed_secs() / FLASH_INTERVAL) as u32).is_multiple_of(2)
+        });
+        for descendant in children_q.iter_descendants(entity) {
+            let Ok((segment, mut background)) = segment_q.get_mut(descendant) else {
+                continue;
+            };
+            background.0 = match (segment.0 < filled, flash_on) {
+                (true, true) => Color::WHITE,
+                (true, false) => RED.into(),
+                (false, true) => DARK_RED.into(),
+                (false, false) => EMPTY_COLOR,
+            };
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+
+const OUTLINE_WIDTH: f32 = 2.;
+const OUTLINE_OFFSET: f32 = 4.;
+const OUTLINE_COLOR: Color = Color::srgb(1., 0.5, 0.);
+// Sideways distance counts this much more than distance ahead when picking the next one
+const CROSS_WEIGHT: f32 = 2.;
+
+// Reachable with the arrow keys or the d-pad, every InteractionUI is one
+#[derive(Component, Default)]
+#[require(Interaction)]
+pub struct Focusable;
+
+// Pressed by Escape or the East button, the way back out of a screen
+#[derive(Component)]
+#[require(Focusable)]
+pub struct FocusBack;
+
+// Present while something else reads the keys (e.g. typing or rebinding),
+// nothing is focused or pressed until it is removed
+#[derive(Resource)]
+pub struct FocusLock;
+
+#[derive(Resource, Default)]
+struct Focus {
+    focused: Option<Entity>,
+    // Pressed through the keys last frame, released again on the next one
+    pressed: Option<Entity>,
+    // Enter has to go down and up while navigating, so a key that ended typing doesn't press
+    armed: bool,
+}
+
+pub struct FocusPlugin;
+
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Focus>().add_systems(
+            PreUpdate,
+            (
+                release_pressed,
+                (move_focus, activate_focus)
+                    .chain()
+                    .run_if(not(resource_exists::<FocusLock>)),
+                disarm_focus.run_if(resource_exists::<FocusLock>),
+                highlight_focus,
+            )
+                .chain()
+                // After the mouse so a keyboard press isn't undone the same frame
+                .after(UiSystem::Focus)
+                .after(InputSystem),
+        );
+    }
+}
+
+fn release_pressed(mut focus: ResMut<Focus>, mut interaction_q: Query<&mut Interaction>) {
+    let Some(pressed) = focus.pressed.take() else {
+        return;
+    };
+    if let Ok(mut interaction) = interaction_q.get_mut(pressed) {
+        if *interaction == Interaction::Pressed {
+            *interaction = Interaction::None;
+        }
+    }
+}
+
+// The Enter that started typing must not press once the lock is gone
+fn disarm_focus(mut focus: ResMut<Focus>) {
+    focus.armed = false;
+}
+
+fn move_focus(
+    mut focus: ResMut<Focus>,
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    focusable_q: Query<(Entity, &GlobalTransform, &InheritedVisibility), With<Focusable>>,
+) {
+    let gamepad_pressed =
+        |button: GamepadButton| gamepads.iter().any(|gamepad| gamepad.just_pressed(button));
+    // UI positions grow downwards
+    let direction = if keys.just_pressed(KeyCode::ArrowUp) || gamepad_pressed(GamepadButton::DPadUp)
+    {
+        Vec2::NEG_Y
+    } else if keys.just_pressed(KeyCode::ArrowDown) || gamepad_pressed(GamepadButton::DPadDown) {
+        Vec2::Y
+    } else if keys.just_pressed(KeyCode::ArrowLeft) || gamepad_pressed(GamepadButton::DPadLeft) {
+        Vec2::NEG_X
+    } else if keys.just_pressed(KeyCode::ArrowRight) || gamepad_pressed(GamepadButton::DPadRight) {
+        Vec2::X
+    } else {
+        return;
+    };
+    let candidates: Vec<(Entity, Vec2)> = focusable_q
+        .iter()
+        .filter(|(_, _, visibility)| visibility.get())
+        .map(|(entity, transform, _)| (entity, transform.translation().truncate()))
+        .collect();
+    let current = focus
+        .focused
+        .and_then(|focused| candidates.iter().find(|(entity, _)| *entity == focused));
+    // Nothing focused yet starts from the top left
+    let Some((current, origin)) = current.copied() else {
+        focus.focused = candidates
+            .iter()
+            .min_by(|(_, a), (_, b)| (a.y, a.x).partial_cmp(&(b.y, b.x)).unwrap())
+            .map(|(entity, _)| *entity);
+        return;
+    };
+    let next = candidates
+        .iter()
+        .filter(|(entity, _)| *entity != current)
+        .filter_map(|(entity, position)| {
+            let offset = *position - origin;
+            let ahead = offset.dot(direction);
+            (ahead > 0.).then(|| {
+                (
+                    *entity,
+                    ahead + offset.perp_dot(direction).abs() * CROSS_WEIGHT,
+                )
+            })
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b));
+    if let Some((next, _)) = next {
+        focus.focused = Some(next);
+    }
+}
+
+fn activate_focus(
+    mut focus: ResMut<Focus>,
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut interaction_q: Query<&mut Interaction, With<Focusable>>,
+    back_q: Query<Entity, With<FocusBack>>,
+) {
+    let gamepad_pressed =
+        |button: GamepadButton| gamepads.iter().any(|gamepad| gamepad.just_pressed(button));
+    if keys.just_pressed(KeyCode::Enter) {
+        focus.armed = true;
+    }
+    // On release, the press itself would still be read by whatever the button opens
+    let enter = focus.armed && keys.just_released(KeyCode::Enter);
+    if enter {
+        focus.armed = false;
+    }
+    let target = if enter || gamepad_pressed(GamepadButton::South) {
+        focus.focused
+    } else if keys.just_pressed(KeyCode::Escape) || gamepad_pressed(GamepadButton::East) {
+        back_q.iter().next()
+    } else {
+        None
+    };
+    let Some(target) = target else {
+        return;
+    };
+    if let Ok(mut interaction) = interaction_q.get_mut(target) {
+        *interaction = Interaction::Pressed;
+        focus.pressed = Some(target);
+    }
+}
+
+// Also forgets focus on anything despawned with its screen
+fn highlight_focus(
+    mut commands: Commands,
+    mut focus: ResMut<Focus>,
+    focusable_q: Query<(Entity, Has<Outline>), With<Focusable>>,
+) {
+    if focus
+        .focused
+        .is_some_and(|focused| !focusable_q.contains(focused))
+    {
+        focus.focused = None;
+    }
+    for (entity, outlined) in focusable_q.iter() {
+        let focused = focus.focused == Some(entity);
+        if focused && !outlined {
+            commands.entity(entity).insert(Outline::new(
+                Val::Px(OUTLINE_WIDTH),
+                Val::Px(OUTLINE_OFFSET),
+                OUTLINE_COLOR,
+            ));
+        } else if !focused && outlined {
+            commands.entity(entity).remove::<Outline>();
+        }
+    }
+}
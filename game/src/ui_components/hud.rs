@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+use shooting_game_shared::util::MOBILE_WINDOW_SIZE;
+
+use crate::constant::{ZIndex, HUD_MARGIN};
+
+// Covers the play area for one state, every HudAnchor element ends up under it,
+// so despawning the root clears the whole HUD
+#[derive(Component)]
+pub struct HudRoot;
+
+// Where an element sits in the HudRoot, elements sharing a corner stack in spawn order
+#[derive(Component, Clone, Copy, PartialEq)]
+pub enum HudAnchor {
+    TopLeft,
+    TopRight,
+    Center,
+    BottomLeft,
+    BottomRight,
+}
+
+impl HudAnchor {
+    const ALL: [HudAnchor; 5] = [
+        HudAnchor::TopLeft,
+        HudAnchor::TopRight,
+        HudAnchor::Center,
+        HudAnchor::BottomLeft,
+        HudAnchor::BottomRight,
+    ];
+
+    fn slot_node(&self) -> Node {
+        let margin_x = Val::Px(HUD_MARGIN.x);
+        let margin_y = Val::Px(HUD_MARGIN.y);
+        let node = Node {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(4.),
+            ..default()
+        };
+        match self {
+            HudAnchor::TopLeft => Node {
+                top: margin_y,
+                left: margin_x,
+                align_items: AlignItems::FlexStart,
+                ..node
+            },
+            HudAnchor::TopRight => Node {
+                top: margin_y,
+                right: margin_x,
+                align_items: AlignItems::FlexEnd,
+                ..node
+            },
+            HudAnchor::Center => Node {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..node
+            },
+            HudAnchor::BottomLeft => Node {
+                bottom: margin_y,
+                left: margin_x,
+                align_items: AlignItems::FlexStart,
+                ..node
+            },
+            HudAnchor::BottomRight => Node {
+                bottom: margin_y,
+                right: margin_x,
+                align_items: AlignItems::FlexEnd,
+                ..node
+            },
+        }
+    }
+}
+
+#[derive(Component)]
+struct HudSlot(HudAnchor);
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(hud_root_on_add)
+            .add_observer(hud_anchor_on_add);
+    }
+}
+
+// Elements spawned before the root are picked up here, later ones find their slot themselves
+fn hud_root_on_add(
+    ev: Trigger<OnAdd, HudRoot>,
+    mut commands: Commands,
+    anchor_q: Query<(Entity, &HudAnchor), Without<ChildOf>>,
+) {
+    let Ok(mut entity_commands) = commands.get_entity(ev.target()) else {
+        return;
+    };
+    entity_commands.insert((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Px(MOBILE_WINDOW_SIZE.x),
+            height: Val::Px(MOBILE_WINDOW_SIZE.y),
+            ..default()
+        },
+        ZIndex::TEXT.component(),
+    ));
+    for anchor in HudAnchor::ALL {
+        let slot = commands
+            .spawn((HudSlot(anchor), anchor.slot_node(), ChildOf(ev.target())))
+            .id();
+        for (entity, _) in anchor_q.iter().filter(|(_, element)| **element == anchor) {
+            commands.entity(slot).add_child(entity);
+        }
+    }
+}
+
+fn hud_anchor_on_add(
+    ev: Trigger<OnAdd, HudAnchor>,
+    mut commands: Commands,
+    anchor_q: Query<&HudAnchor>,
+    slot_q: Query<(Entity, &HudSlot)>,
+) {
+    let Ok(anchor) = anchor_q.get(ev.target()) else {
+        warn!("HudAnchor not found in hud_anchor_on_add");
+        return;
+    };
+    let Some((slot, _)) = slot_q.iter().find(|(_, slot)| slot.0 == *anchor) else {
+        return;
+    };
+    commands.entity(slot).add_child(ev.target());
+}
@@ -1,7 +1,9 @@
 use bevy::{prelude::*, window::SystemCursorIcon, winit::cursor::CursorIcon};
 
+use super::Focusable;
+
 #[derive(Component, Default)]
-#[require(Interaction)]
+#[require(Interaction, Focusable)]
 pub struct InteractionUI;
 
 pub struct InteractionUIPlugin;
@@ -12,6 +14,7 @@ impl Plugin for InteractionUIPlugin {
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn handle_interaction_ui(
     mut commands: Commands,
     mut interaction_ui_query: Query<
@@ -1,14 +1,22 @@
 mod blink;
+mod boss_health_bar;
 mod control_button_panel;
+mod focus;
+mod hud;
 mod interaction_ui;
 mod main_container;
 mod selectable_text;
+mod virtual_joystick;
 
 pub use blink::Blink;
+pub use boss_health_bar::BossHealthBar;
 pub use control_button_panel::{ControlButton, ControlButtonPanel};
+pub use focus::{FocusBack, FocusLock, Focusable};
+pub use hud::{HudAnchor, HudRoot};
 pub use interaction_ui::InteractionUI;
 pub use main_container::MainContainer;
 pub use selectable_text::SelectableText;
+pub use virtual_joystick::VirtualJoystick;
 
 use bevy::prelude::{App, Plugin};
 pub struct UIComponentsPlugin;
@@ -17,10 +25,14 @@ impl Plugin for UIComponentsPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
             blink::BlinkPlugin,
+            boss_health_bar::BossHealthBarPlugin,
             control_button_panel::ControlButtonPlugin,
+            focus::FocusPlugin,
+            hud::HudPlugin,
             main_container::MainContainerPlugin,
             selectable_text::SelectableTextPlugin,
             interaction_ui::InteractionUIPlugin,
+            virtual_joystick::VirtualJoystickPlugin,
         ));
     }
 }
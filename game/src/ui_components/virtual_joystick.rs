@@ -0,0 +1,108 @@
+use bevy::app::App;
+use bevy::prelude::*;
+
+const BASE_RADIUS: f32 = 60.;
+const KNOB_RADIUS: f32 = 25.;
+const DEAD_ZONE: f32 = 10.;
+
+pub struct VirtualJoystickPlugin;
+
+impl Plugin for VirtualJoystickPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, sync_joystick_layout)
+            .add_observer(handle_virtual_joystick_on_add);
+    }
+}
+
+// Floating joystick, it appears wherever the controlling touch starts
+#[derive(Component, Default)]
+pub struct VirtualJoystick {
+    touch_id: Option<u64>,
+    origin: Vec2,
+    offset: Vec2,
+}
+
+impl VirtualJoystick {
+    pub fn touch_id(&self) -> Option<u64> {
+        self.touch_id
+    }
+
+    pub fn press(&mut self, touch_id: u64, position: Vec2) {
+        self.touch_id = Some(touch_id);
+        self.origin = position;
+        self.offset = Vec2::ZERO;
+    }
+
+    pub fn drag(&mut self, position: Vec2) {
+        self.offset = (position - self.origin).clamp_length_max(BASE_RADIUS);
+    }
+
+    pub fn release(&mut self) {
+        self.touch_id = None;
+        self.offset = Vec2::ZERO;
+    }
+
+    // Screen space grows downwards, so y is flipped to match the world
+    pub fn direction(&self) -> Vec2 {
+        if self.offset.length() <= DEAD_ZONE {
+            return Vec2::ZERO;
+        }
+        Vec2::new(self.offset.x, -self.offset.y).normalize_or_zero()
+    }
+}
+
+#[derive(Component)]
+struct JoystickKnob;
+
+fn handle_virtual_joystick_on_add(ev: Trigger<OnAdd, VirtualJoystick>, mut commands: Commands) {
+    commands
+        .entity(ev.target())
+        .insert((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Px(BASE_RADIUS * 2.),
+                height: Val::Px(BASE_RADIUS * 2.),
+                ..default()
+            },
+            BackgroundColor::from(Color::srgba(0.3, 0.3, 0.3, 0.3)),
+            BorderRadius::MAX,
+            Visibility::Hidden,
+        ))
+        .with_child((
+            JoystickKnob,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Px(KNOB_RADIUS * 2.),
+                height: Val::Px(KNOB_RADIUS * 2.),
+                left: Val::Px(BASE_RADIUS - KNOB_RADIUS),
+                top: Val::Px(BASE_RADIUS - KNOB_RADIUS),
+                ..default()
+            },
+            BackgroundColor::from(Color::srgba(0.8, 0.8, 0.8, 0.6)),
+            BorderRadius::MAX,
+        ));
+}
+
+fn sync_joystick_layout(
+    mut joystick_query: Query<
+        (&VirtualJoystick, &mut Node, &mut Visibility, &Children),
+        Changed<VirtualJoystick>,
+    >,
+    mut knob_query: Query<&mut Node, (With<JoystickKnob>, Without<VirtualJoystick>)>,
+) {
+    for (joystick, mut node, mut visibility, children) in joystick_query.iter_mut() {
+        *visibility = match joystick.touch_id {
+            Some(_) => Visibility::Inherited,
+            None => Visibility::Hidden,
+        };
+        node.left = Val::Px(joystick.origin.x - BASE_RADIUS);
+        node.top = Val::Px(joystick.origin.y - BASE_RADIUS);
+        for child in children.iter() {
+            let Ok(mut knob_node) = knob_query.get_mut(child) else {
+                continue;
+            };
+            knob_node.left = Val::Px(BASE_RADIUS - KNOB_RADIUS + joystick.offset.x);
+            knob_node.top = Val::Px(BASE_RADIUS - KNOB_RADIUS + joystick.offset.y);
+        }
+    }
+}
@@ -16,11 +16,41 @@ pub fn cleanup_components<T: Component>(
     }
 }
 
+// Any key, click, touch or gamepad button pressed this frame
+pub fn any_input_just_pressed(
+    keys: &ButtonInput<KeyCode>,
+    mouse: &ButtonInput<MouseButton>,
+    touches: &Touches,
+    gamepad_q: &Query<&Gamepad>,
+) -> bool {
+    keys.get_just_pressed().next().is_some()
+        || mouse.get_just_pressed().next().is_some()
+        || touches.any_just_pressed()
+        || gamepad_q
+            .iter()
+            .any(|gamepad| gamepad.get_just_pressed().next().is_some())
+}
+
 pub trait Position {
     fn get_position(&self) -> Vec2;
     fn set_position(&mut self, position: Vec2);
 }
 
+// Used to pick a target when there may be more than one spaceship
+pub fn closest_position<'a, T: Position + 'a>(
+    origin: Vec2,
+    items: impl IntoIterator<Item = &'a T>,
+) -> Option<Vec2> {
+    items
+        .into_iter()
+        .map(Position::get_position)
+        .min_by(|a, b| {
+            origin
+                .distance_squared(*a)
+                .total_cmp(&origin.distance_squared(*b))
+        })
+}
+
 pub fn listen_position<T: Position + Component<Mutability = Mutable>>(
     mut query: Query<(&Transform, &mut T)>,
 ) {
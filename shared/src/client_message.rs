@@ -1,23 +1,85 @@
+use crate::{Encoding, Payload};
 use serde::{Deserialize, Serialize};
-use tungstenite::{Message, Utf8Bytes};
+
+// Longer chat messages are cut by the server
+pub const CHAT_MAX_LENGTH: usize = 120;
+
+// The movement applied locally since the previous input, the server acknowledges the sequence
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct PlayerInput {
+    pub sequence: u32,
+    pub displacement: (f32, f32),
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum ClientMessage {
     UpdatePlayerInfo {
-        position: Option<(f32, f32)>,
+        input: Option<PlayerInput>,
         bullets: Vec<(f32, f32)>,
     },
+    // `tick` is the last snapshot the client had, the server rewinds enemies to it
     DamagedIntent {
         enemy_tag: u16,
+        tick: u32,
     },
     DestroyEnemyIntent {
         bullet_tag: u16,
         enemy_tag: u16,
+        tick: u32,
+    },
+    // Sent when the spaceship touches a power-up, nothing is applied until the server confirms
+    ClaimPowerUp {
+        power_up_tag: u16,
     },
+    Chat {
+        text: String,
+    },
+    // Sent from the lobby, the game only starts once every player is ready
+    SetReady {
+        ready: bool,
+    },
+    // Sent from the result screen, the room restarts once every player asked
+    Rematch,
+    // Keepalive, `sent_at` is the client clock in milliseconds and comes back untouched
+    Ping {
+        sent_at: u64,
+    },
+    // Sent from the lobby by a lone player, the server fills the empty slot with a bot
+    AddBot,
 }
 
 impl ClientMessage {
-    pub fn text(self) -> Message {
-        Message::Text(Utf8Bytes::from(serde_json::to_string(&self).unwrap()))
+    pub fn text(self) -> Payload {
+        Payload::Text(serde_json::to_string(&self).unwrap())
+    }
+
+    pub fn binary(self) -> Payload {
+        Payload::Binary(postcard::to_allocvec(&self).unwrap())
+    }
+
+    pub fn encode(self, encoding: Encoding) -> Payload {
+        match encoding {
+            Encoding::Json => self.text(),
+            Encoding::Binary => self.binary(),
+        }
+    }
+
+    pub fn from_binary(bytes: &[u8]) -> Option<Self> {
+        postcard::from_bytes(bytes).ok()
+    }
+
+    // Variant name without the payload, used to label server metrics
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ClientMessage::UpdatePlayerInfo { .. } => "UpdatePlayerInfo",
+            ClientMessage::DamagedIntent { .. } => "DamagedIntent",
+            ClientMessage::DestroyEnemyIntent { .. } => "DestroyEnemyIntent",
+            ClientMessage::ClaimPowerUp { .. } => "ClaimPowerUp",
+            ClientMessage::Chat { .. } => "Chat",
+            ClientMessage::SetReady { .. } => "SetReady",
+            ClientMessage::Rematch => "Rematch",
+            ClientMessage::Ping { .. } => "Ping",
+            ClientMessage::AddBot => "AddBot",
+        }
     }
 }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use bevy_math::Vec2;
 use rand::{rng, Rng};
 
@@ -16,7 +18,7 @@ pub enum Stage {
 }
 
 impl Stage {
-    pub fn new(score: u8) -> Stage {
+    pub fn new(score: u32) -> Stage {
         match score {
             0..10 => Stage::Warmup,
             10..50 => Stage::One,
@@ -28,18 +30,16 @@ impl Stage {
         }
     }
 
-    pub fn random_generator(&self, existing_ufo: usize) -> bool {
-        let mut rng = rng();
-        return match self {
+    pub fn random_generator(&self, existing_ufo: usize, rng: &mut impl Rng) -> bool {
+        match self {
             Stage::Warmup => rng.random_bool(0.1),
             Stage::One | Stage::Two => rng.random_bool(1. / (existing_ufo as f64 * 5.)),
             Stage::Three | Stage::Four => rng.random_bool(1. / (existing_ufo as f64 * 3.)),
             Stage::Five | Stage::Six => rng.random_bool(1. / (existing_ufo as f64)),
-        };
+        }
     }
 
-    pub fn get_ufo_velocity(&self) -> Vec2 {
-        let mut rng = rng();
+    pub fn get_ufo_velocity(&self, rng: &mut impl Rng) -> Vec2 {
         match self {
             Stage::Warmup | Stage::One => Vec2::new(0., -3.),
             Stage::Two | Stage::Three => Vec2::new(rng.random_range(-3.0..3.0), -3.),
@@ -50,23 +50,71 @@ impl Stage {
         }
     }
 
-    pub fn get_ufo_velocity_tuple(&self) -> (f32, f32) {
-        let velocity = self.get_ufo_velocity();
+    pub fn get_ufo_velocity_tuple(&self, rng: &mut impl Rng) -> (f32, f32) {
+        let velocity = self.get_ufo_velocity(rng);
         (velocity.x, velocity.y)
     }
 }
 
-pub struct UFORandomGenerator;
+// Matches the client FixedUpdate rate which applies velocity once per tick
+pub const FIXED_TICKS_PER_SECOND: f32 = 64.;
 
-impl UFORandomGenerator {
-    pub fn tag() -> u16 {
-        let mut rng = rng();
-        rng.random_range(u16::MIN..u16::MAX)
+// Replays ufo movement (including bouncing on the side edges) for the given ticks
+pub fn extrapolate_ufo(
+    position: (f32, f32),
+    velocity: (f32, f32),
+    ticks: u32,
+) -> ((f32, f32), (f32, f32)) {
+    let edge = EdgeUtil::ufo();
+    let (mut x, mut y) = position;
+    let (mut velocity_x, velocity_y) = velocity;
+    for _ in 0..ticks {
+        x += velocity_x;
+        y += velocity_y;
+        if edge.over_left_in(x) || edge.over_right_in(x) {
+            velocity_x = -velocity_x;
+        }
+    }
+    ((x, y), (velocity_x, velocity_y))
+}
+
+// A partner this close to a downed player's beacon for the whole duration revives them
+pub const REVIVE_RADIUS: f32 = 40.;
+pub const REVIVE_DURATION: Duration = Duration::from_secs(3);
+
+// Longer jumps, e.g. after a frame hitch, are cut and then corrected on the client
+const MAX_INPUT_DISPLACEMENT: f32 = 60.;
+
+pub fn spaceship_start_position(player_tag: u8) -> (f32, f32) {
+    let x = if player_tag == 1 { -100. } else { 100. };
+    (x, EdgeUtil::spaceship().bottom_out())
+}
+
+// Shared so the client prediction moves exactly like the server does
+pub fn apply_player_input(position: (f32, f32), displacement: (f32, f32)) -> (f32, f32) {
+    let edge = EdgeUtil::spaceship();
+    let displacement =
+        Vec2::new(displacement.0, displacement.1).clamp_length_max(MAX_INPUT_DISPLACEMENT);
+    (
+        (position.0 + displacement.x).clamp(edge.left_in(), edge.right_in()),
+        // The spaceship flies in from below the screen before the game starts
+        (position.1 + displacement.y).clamp(edge.bottom_out(), edge.top_in()),
+    )
+}
+
+pub struct SessionRandomGenerator;
+
+impl SessionRandomGenerator {
+    pub fn token() -> u64 {
+        rng().random()
     }
+}
 
-    pub fn position() -> (f32, f32) {
+pub struct UFORandomGenerator;
+
+impl UFORandomGenerator {
+    pub fn position(rng: &mut impl Rng) -> (f32, f32) {
         let ufo_edge = EdgeUtil::ufo();
-        let mut rng = rng();
         (
             rng.random_range(ufo_edge.left_in()..ufo_edge.right_in()),
             ufo_edge.top_out(),
@@ -1,7 +1,15 @@
 mod client_message;
 pub mod game_related;
+mod protocol;
 mod server_message;
 pub mod util;
 
-pub use client_message::ClientMessage;
-pub use server_message::ServerMessage;
+pub use client_message::{ClientMessage, PlayerInput, CHAT_MAX_LENGTH};
+pub use protocol::{
+    incompatible_version_reason, is_compatible, sanitize_name, server_version_from_reason,
+    Encoding, Payload, INCOMPATIBLE_VERSION_CLOSE_CODE, NAME_MAX_LENGTH, PROTOCOL_VERSION,
+};
+pub use server_message::{
+    BulletSnapshot, DownedSnapshot, EffectKind, EnemySnapshot, LobbyPlayer, OnlinePowerUp,
+    PlayerSnapshot, RoomClosedReason, ServerMessage,
+};
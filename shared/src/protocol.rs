@@ -0,0 +1,59 @@
+// Bumped whenever the wire format changes
+pub const PROTOCOL_VERSION: u8 = 8;
+// Clients from before this version only understand JSON text frames
+const BINARY_SINCE_VERSION: u8 = 2;
+// Longer nicknames are cut by the server
+pub const NAME_MAX_LENGTH: usize = 12;
+
+// An encoded message, each side wraps it in the websocket type it has
+#[derive(Clone, Debug, PartialEq)]
+pub enum Payload {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub enum Encoding {
+    #[default]
+    Json,
+    Binary,
+}
+
+impl Encoding {
+    // The client sends its version on connect, no version means an old JSON only client
+    pub fn negotiate(client_version: Option<u8>) -> Self {
+        match client_version {
+            Some(version) if version.min(PROTOCOL_VERSION) >= BINARY_SINCE_VERSION => {
+                Encoding::Binary
+            }
+            _ => Encoding::Json,
+        }
+    }
+}
+
+// Messages change shape between versions (fields are added, not only variants), so the server
+// only serves clients on its own version
+pub fn is_compatible(client_version: u8) -> bool {
+    client_version == PROTOCOL_VERSION
+}
+
+// Other versions are turned away with a close frame, the one thing every client ever shipped
+// reads without decoding a message (the first ones unwrap any text frame as JSON)
+pub const INCOMPATIBLE_VERSION_CLOSE_CODE: u16 = 4000;
+
+// The close reason carries the server's version, e.g. "protocol 6"
+pub fn incompatible_version_reason() -> String {
+    format!("protocol {PROTOCOL_VERSION}")
+}
+
+pub fn server_version_from_reason(reason: &str) -> Option<u8> {
+    reason.strip_prefix("protocol ")?.parse().ok()
+}
+
+// Nicknames travel in the handshake query, so only what needs no escaping is kept
+pub fn sanitize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+        .take(NAME_MAX_LENGTH)
+        .collect()
+}
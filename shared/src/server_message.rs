@@ -1,25 +1,71 @@
-use rocket_ws::Message;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::{Encoding, Payload};
 
 pub type Position = (f32, f32);
 pub type Velocity = (f32, f32);
+pub type EnemySnapshot = (u16, Position, Velocity);
+// Also carries the last input sequence the server applied for that player
+pub type PlayerSnapshot = (u8, Position, u32);
+// Owned by the player tag
+pub type BulletSnapshot = (u8, Position);
+// A downed player tag and where its beacon is
+pub type DownedSnapshot = (u8, Position);
+// A player tag and whether that player is ready
+pub type LobbyPlayer = (u8, bool);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum RoomClosedReason {
+    // Nobody in the room sent anything for too long
+    Idle,
+    ServerShutdown,
+}
+
+// The power-ups the online game drops, only ones that change the local weapon
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum OnlinePowerUp {
+    SpreadShot,
+    RapidFire,
+}
+
+// Something every screen shows the same way, at the position the server had for it
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum EffectKind {
+    EnemyDestroyed,
+    PlayerDamaged,
+    PlayerDestroyed,
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum ServerMessage {
     Joined {
         player_tag: u8,
+        session_token: u64,
+    },
+    // Sent instead of Joined when the room is full
+    SpectatorJoined {
+        game_started: bool,
+    },
+    // Sent after Joined to the player who opened a new room
+    RoomCreated {
+        room_id: u32,
     },
+    // The room roster, sent while matching whenever someone joins, leaves or readies up
+    LobbyState {
+        players: Vec<LobbyPlayer>,
+    },
+    // Sent once the room is full and every player is ready
     GameReady,
     GameStart,
-    UpdatePosition {
-        player_tag: u8,
-        position: Position,
-        bullets: Vec<Position>,
-    },
-    SpawnEnemy {
-        tag: u16,
-        position: Position,
-        velocity: Velocity,
+    // The whole room state, sent once per server tick
+    Snapshot {
+        tick: u32,
+        players: Vec<PlayerSnapshot>,
+        enemies: Vec<EnemySnapshot>,
+        bullets: Vec<BulletSnapshot>,
+        // The server's scoreboard by player tag, late joiners and reconnects catch up from it
+        scores: HashMap<u8, u32>,
     },
     ConfirmDamaged {
         player_tag: u8,
@@ -32,12 +78,117 @@ pub enum ServerMessage {
         enemy_tag: u16,
         new_score: u8,
     },
-    GameOver,
+    SpawnPowerUp {
+        tag: u16,
+        kind: OnlinePowerUp,
+        position: Position,
+        velocity: Velocity,
+    },
+    // The first valid claim wins, everyone removes the power-up and only `player_tag` gets it
+    ConfirmPowerUp {
+        player_tag: u8,
+        power_up_tag: u16,
+        kind: OnlinePowerUp,
+    },
+    // Sent after Joined when a dropped player reconnects with its session token
+    ResumeState {
+        score: u8,
+        health: u8,
+        enemies: Vec<EnemySnapshot>,
+        // Beacons of the players still down
+        downed: Vec<DownedSnapshot>,
+    },
+    // A player ran out of health and left a beacon where the server had them
+    PlayerDowned {
+        player_tag: u8,
+        position: Position,
+    },
+    // A partner stayed on the beacon long enough, the player is back there
+    PlayerRevived {
+        player_tag: u8,
+        health: u8,
+        position: Position,
+    },
+    Chat {
+        player_tag: u8,
+        text: String,
+    },
+    // Final scores by player tag, no winner on a draw
+    GameOver {
+        scores: HashMap<u8, u32>,
+        winner: Option<u8>,
+    },
     GameInterrupted,
+    // The server is dropping the room, the connection closes right after
+    RoomClosed {
+        reason: RoomClosedReason,
+    },
+    // Answers a Ping, only to the player who sent it
+    Pong {
+        sent_at: u64,
+    },
+    // Never on the wire, the client builds it from the close frame a rejected version gets
+    IncompatibleVersion {
+        server: u8,
+        client: u8,
+    },
+    // Sent alongside the confirmation it belongs to
+    Effect {
+        kind: EffectKind,
+        position: Position,
+    },
+    // Nicknames by player tag, sent ahead of every LobbyState, players without one are left out
+    PlayerNames {
+        names: HashMap<u8, String>,
+    },
 }
 
 impl ServerMessage {
-    pub fn text(self) -> Message {
-        Message::Text(serde_json::to_string(&self).unwrap())
+    pub fn text(self) -> Payload {
+        Payload::Text(serde_json::to_string(&self).unwrap())
+    }
+
+    // Much smaller than text for the Snapshot spam
+    pub fn binary(self) -> Payload {
+        Payload::Binary(postcard::to_allocvec(&self).unwrap())
+    }
+
+    pub fn encode(self, encoding: Encoding) -> Payload {
+        match encoding {
+            Encoding::Json => self.text(),
+            Encoding::Binary => self.binary(),
+        }
+    }
+
+    pub fn from_binary(bytes: &[u8]) -> Option<Self> {
+        postcard::from_bytes(bytes).ok()
+    }
+
+    // Variant name without the payload, used to label server metrics
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ServerMessage::Joined { .. } => "Joined",
+            ServerMessage::SpectatorJoined { .. } => "SpectatorJoined",
+            ServerMessage::RoomCreated { .. } => "RoomCreated",
+            ServerMessage::LobbyState { .. } => "LobbyState",
+            ServerMessage::GameReady => "GameReady",
+            ServerMessage::GameStart => "GameStart",
+            ServerMessage::Snapshot { .. } => "Snapshot",
+            ServerMessage::ConfirmDamaged { .. } => "ConfirmDamaged",
+            ServerMessage::ConfirmDestroyEnemy { .. } => "ConfirmDestroyEnemy",
+            ServerMessage::SpawnPowerUp { .. } => "SpawnPowerUp",
+            ServerMessage::ConfirmPowerUp { .. } => "ConfirmPowerUp",
+            ServerMessage::ResumeState { .. } => "ResumeState",
+            ServerMessage::PlayerDowned { .. } => "PlayerDowned",
+            ServerMessage::PlayerRevived { .. } => "PlayerRevived",
+            ServerMessage::Chat { .. } => "Chat",
+            ServerMessage::GameOver { .. } => "GameOver",
+            ServerMessage::GameInterrupted => "GameInterrupted",
+            ServerMessage::RoomClosed { .. } => "RoomClosed",
+            ServerMessage::Pong { .. } => "Pong",
+            ServerMessage::IncompatibleVersion { .. } => "IncompatibleVersion",
+            ServerMessage::Effect { .. } => "Effect",
+            ServerMessage::PlayerNames { .. } => "PlayerNames",
+        }
     }
 }
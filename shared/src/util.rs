@@ -3,6 +3,7 @@ use bevy_math::Vec2;
 pub const MOBILE_WINDOW_SIZE: Vec2 = Vec2::new(540., 960.);
 pub const UFO_SIZE: Vec2 = Vec2::new(80., 54.);
 pub const SPACESHIP_SIZE: Vec2 = Vec2::new(100., 100.);
+pub const POWER_UP_SIZE: Vec2 = Vec2::new(30., 30.);
 
 pub struct EdgeUtil {
     object_size: Vec2,
@@ -26,6 +27,12 @@ impl EdgeUtil {
     pub fn over_left_in(&self, position: f32) -> bool {
         position < self.left_in()
     }
+    pub fn left_out(&self) -> f32 {
+        -MOBILE_WINDOW_SIZE.x / 2. - self.object_size.x / 2.
+    }
+    pub fn over_left_out(&self, position: f32) -> bool {
+        position < self.left_out()
+    }
 
     pub fn right_in(&self) -> f32 {
         MOBILE_WINDOW_SIZE.x / 2. - self.object_size.x / 2.
@@ -33,6 +40,12 @@ impl EdgeUtil {
     pub fn over_right_in(&self, position: f32) -> bool {
         position > self.right_in()
     }
+    pub fn right_out(&self) -> f32 {
+        MOBILE_WINDOW_SIZE.x / 2. + self.object_size.x / 2.
+    }
+    pub fn over_right_out(&self, position: f32) -> bool {
+        position > self.right_out()
+    }
 
     pub fn top_in(&self) -> f32 {
         MOBILE_WINDOW_SIZE.y / 2. - self.object_size.y / 2.
@@ -59,4 +72,16 @@ impl EdgeUtil {
     pub fn over_bottom_out(&self, position: f32) -> bool {
         position < self.bottom_out()
     }
+
+    // Wrap mode instead of clamping, the center comes back in at the opposite window edge
+    pub fn wrap_x(position: f32) -> f32 {
+        let half_width = MOBILE_WINDOW_SIZE.x / 2.;
+        if position < -half_width {
+            position + MOBILE_WINDOW_SIZE.x
+        } else if position > half_width {
+            position - MOBILE_WINDOW_SIZE.x
+        } else {
+            position
+        }
+    }
 }